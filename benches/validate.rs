@@ -0,0 +1,47 @@
+//! Compares per-character parsing against bulk byte validation.
+//!
+//! Run with and without the `simd` feature to see the lane speedup:
+//!
+//! ```sh
+//! cargo bench --bench validate
+//! cargo bench --bench validate --features simd
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use vds::{validate, VDChar, VDString, VDS_ALLOWED};
+
+const ITERATIONS: u32 = 200;
+
+fn input(len: usize) -> Vec<u8> {
+    (0..len).map(|i| VDS_ALLOWED[i % VDS_ALLOWED.len()] as u8).collect()
+}
+
+fn time<F: FnMut() -> bool>(mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        assert!(black_box(f()));
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    for len in [64, 4 * 1024, 1024 * 1024] {
+        let buf = input(len);
+        let s = std::str::from_utf8(&buf).unwrap();
+
+        let per_char = time(|| s.chars().all(|c| VDChar::new(black_box(c)).is_some()));
+        let bulk = time(|| validate::is_valid(black_box(&buf)));
+        let parse = time(|| black_box(s).parse::<VDString>().is_ok());
+
+        println!(
+            "{:>8} bytes: per-char {:>10.2?}  bulk {:>10.2?}  ({:.1}x)  parse {:>10.2?}",
+            len,
+            per_char,
+            bulk,
+            per_char.as_secs_f64() / bulk.as_secs_f64().max(f64::EPSILON),
+            parse,
+        );
+    }
+}
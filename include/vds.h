@@ -0,0 +1,103 @@
+#ifndef VDS_H
+#define VDS_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * The call succeeded.
+ */
+#define VDS_OK 0
+
+/**
+ * A required pointer was null.
+ */
+#define VDS_ERR_NULL -1
+
+/**
+ * The input contains a byte that is not an allowed character.
+ */
+#define VDS_ERR_INVALID -2
+
+/**
+ * The generator could not satisfy its constraints, e.g. a
+ * [`VDS_NO_REPEATS`] code longer than the alphabet.
+ */
+#define VDS_ERR_GENERATE -3
+
+/**
+ * Flag for [`vds_generate`]: no character appears twice.
+ */
+#define VDS_NO_REPEATS 1
+
+/**
+ * Flag for [`vds_generate`]: no character appears twice in a row.
+ */
+#define VDS_NO_ADJACENT_REPEATS (1 << 1)
+
+/**
+ * Number of characters in [`VDS_ALPHABET`].
+ */
+#define VDS_ALPHABET_LEN 31
+
+/**
+ * Callback that fills `len` bytes at `buf` with random data.
+ *
+ * `ctx` is passed through unchanged from [`vds_generate`]. Use a
+ * cryptographically secure source when codes must be unguessable.
+ */
+typedef void (*VdsFillFn)(void *ctx, uint8_t *buf, size_t len);
+
+/**
+ * The allowed characters as ASCII, in index order.
+ */
+extern const uint8_t VDS_ALPHABET[VDS_ALPHABET_LEN];
+
+/**
+ * Returns `true` if every byte of `code` is an allowed character.
+ *
+ * Returns `false` if `code` is null and `len` is non-zero.
+ *
+ * # Safety
+ * `code` must be null or valid for reads of `len` bytes.
+ */
+bool vds_is_valid(const uint8_t *code, size_t len);
+
+/**
+ * Writes the check character for `payload` to `*out`.
+ *
+ * The check character uses the Damm algorithm, as
+ * [`VDString::with_check_char`](crate::VDString::with_check_char) does.
+ *
+ * # Safety
+ * `payload` must be null or valid for reads of `len` bytes, and `out` must
+ * be null or valid for a write of one byte.
+ */
+int32_t vds_check_char(const uint8_t *payload, size_t len, uint8_t *out);
+
+/**
+ * Returns `true` if the last character of `code` is the correct check
+ * character for the rest, as computed by [`vds_check_char`].
+ *
+ * # Safety
+ * `code` must be null or valid for reads of `len` bytes.
+ */
+bool vds_verify(const uint8_t *code, size_t len);
+
+/**
+ * Generates a random code of `len` characters into `out`.
+ *
+ * `flags` is a combination of [`VDS_NO_REPEATS`] and
+ * [`VDS_NO_ADJACENT_REPEATS`]. Randomness comes from `fill`, called with
+ * `ctx`. Exactly `len` bytes are written; no NUL terminator is added.
+ *
+ * # Safety
+ * `out` must be null or valid for writes of `len` bytes, and `fill` must
+ * write the requested number of bytes to the buffer it is given.
+ */
+int32_t vds_generate(uint8_t *out, size_t len, uint32_t flags, VdsFillFn fill, void *ctx);
+
+#endif /* VDS_H */
@@ -0,0 +1,632 @@
+//! Character sets that [`VDChar`](crate::VDChar) and [`VDString`](crate::VDString)
+//! can be built over.
+//!
+//! Both types take an [`Alphabet`] type parameter that defaults to
+//! [`DefaultAlphabet`]: [`Default31`], the curated [`VDS_ALLOWED`] set, unless
+//! another preset is selected at build time. Parsing, indexing,
+//! display, serde, and [`VDGenerator`](crate::VDGenerator) work over any
+//! alphabet. Features that depend on the default set's size or layout —
+//! checksums and error correction over GF(31), the base-31 codecs,
+//! [`VDGlyph`](crate::VDGlyph), and [`VDCharSet`](crate::VDCharSet) — are only
+//! provided for [`Default31`].
+//!
+//! # Examples
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use vds::alphabet::Alphabet;
+//! use vds::{VDChar, VDString};
+//!
+//! /// Digits and vowels only, no consonants to accidentally spell words.
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+//! struct Vowels;
+//!
+//! impl Alphabet for Vowels {
+//!     const CHARS: &'static [char] = &[
+//!         'A', 'E', 'U', 'Y', '2', '3', '4', '5', '6', '7', '8', '9',
+//!     ];
+//! }
+//!
+//! let code: VDString<Vowels> = "EYE27".parse().unwrap();
+//! assert_eq!(code[1].as_char(), 'Y');
+//! assert!("EBE27".parse::<VDString<Vowels>>().is_err());
+//! assert!(VDChar::<Vowels>::from_char('B').is_none());
+//! # }
+//! ```
+//!
+//! Alphabets only known at runtime, such as per-tenant configuration, are
+//! built with `CustomAlphabetBuilder` instead (requires the `alloc` feature).
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::marker::PhantomData;
+
+#[cfg(feature = "alloc")]
+use crate::VDStringError;
+use crate::VDS_ALLOWED;
+
+/// A fixed, ordered set of characters.
+///
+/// Implementors are zero-sized marker types; a [`VDChar<A>`](crate::VDChar)
+/// stores an index into [`CHARS`](Self::CHARS).
+///
+/// `CHARS` must hold at most 255 characters and no duplicates; a larger
+/// alphabet fails to compile once a `VDChar` of it is used.
+pub trait Alphabet: Copy + Eq + Hash + Debug + Default + 'static {
+    /// The allowed characters, in index order.
+    const CHARS: &'static [char];
+
+    /// Returns the index of `c` in [`CHARS`](Self::CHARS), or `None` if it is
+    /// not allowed.
+    ///
+    /// ASCII characters are looked up in a table built at compile time;
+    /// others are searched for in `CHARS`.
+    fn index_of(c: char) -> Option<u8> {
+        if c.is_ascii() {
+            ascii_lookup(&AsciiIndex::<Self>::TABLE, c as u8)
+        } else {
+            Self::CHARS.iter().position(|&x| x == c).map(|i| i as u8)
+        }
+    }
+
+    /// Returns the index that input character `c` decodes to.
+    ///
+    /// Used when parsing. Override to accept aliases such as lowercase or
+    /// look-alike characters; the default accepts only [`CHARS`](Self::CHARS).
+    /// Overrides must still decode each character of `CHARS` to its own
+    /// index, which parsing assumes when it skips `decode` for them.
+    fn decode(c: char) -> Option<u8> {
+        Self::index_of(c)
+    }
+}
+
+/// Marks ASCII bytes that are not in an alphabet in its lookup table.
+const NOT_ALLOWED: u8 = u8::MAX;
+
+/// Builds a table mapping each ASCII byte to its index in `chars`.
+pub(crate) const fn ascii_table(chars: &[char]) -> [u8; 128] {
+    let mut table = [NOT_ALLOWED; 128];
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].is_ascii() {
+            table[chars[i] as usize] = i as u8;
+        }
+        i += 1;
+    }
+    table
+}
+
+/// Looks up the index of ASCII byte `b` in a table from [`ascii_table`].
+pub(crate) const fn ascii_lookup(table: &[u8; 128], b: u8) -> Option<u8> {
+    match table[(b & 0x7f) as usize] {
+        NOT_ALLOWED => None,
+        index => Some(index),
+    }
+}
+
+/// Builds a bitset with bit `b` set for each ASCII byte `b` in `chars`.
+const fn ascii_mask(chars: &[char]) -> u128 {
+    let mut mask = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].is_ascii() {
+            mask |= 1 << chars[i] as u32;
+        }
+        i += 1;
+    }
+    mask
+}
+
+/// The ASCII lookup tables of alphabet `A`, built once per alphabet.
+pub(crate) struct AsciiIndex<A>(PhantomData<A>);
+
+impl<A: Alphabet> AsciiIndex<A> {
+    /// Index of each ASCII byte in `A::CHARS`.
+    pub(crate) const TABLE: [u8; 128] = ascii_table(A::CHARS);
+    /// Membership bitset of the ASCII bytes in `A::CHARS`.
+    pub(crate) const MASK: u128 = ascii_mask(A::CHARS);
+}
+
+/// The standard alphabet: the 31 characters of [`VDS_ALLOWED`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Default31;
+
+impl Alphabet for Default31 {
+    const CHARS: &'static [char] = VDS_ALLOWED;
+
+    fn index_of(c: char) -> Option<u8> {
+        crate::VDChar::new(c).map(crate::VDChar::index)
+    }
+}
+
+/// Douglas Crockford's Base32 alphabet: `0`–`9` and `A`–`Z` without `I`, `L`, `O`, `U`.
+///
+/// Decoding is case-insensitive and, as the specification requires, reads
+/// `I` and `L` as `1` and `O` as `0`.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::alphabet::Crockford32;
+/// use vds::VDString;
+///
+/// let code: VDString<Crockford32> = "1o4l".parse().unwrap();
+/// assert_eq!(&*code, "1041");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Crockford32;
+
+impl Alphabet for Crockford32 {
+    const CHARS: &'static [char] = &[
+        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+        'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K',
+        'M', 'N', 'P', 'Q', 'R', 'S', 'T', 'V', 'W', 'X', 'Y', 'Z',
+    ];
+
+    fn decode(c: char) -> Option<u8> {
+        match c.to_ascii_uppercase() {
+            'O' => Some(0),
+            'I' | 'L' => Some(1),
+            c => Self::index_of(c),
+        }
+    }
+}
+
+/// The ten decimal digits, for numeric-only systems such as EAN/UPC payloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Digits10;
+
+impl Alphabet for Digits10 {
+    const CHARS: &'static [char] = &['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+}
+
+/// The 23 letters of [`VDS_ALLOWED`], for letters-only codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Letters23;
+
+impl Alphabet for Letters23 {
+    const CHARS: &'static [char] = &[
+        'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K', 'M', 'N',
+        'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+    ];
+}
+
+/// All 36 uppercase letters and digits, for legacy systems that need them.
+///
+/// Includes the ambiguous `0`/`O` and `1`/`I`/`L`, so prefer [`Default31`]
+/// for new codes. Decoding is case-insensitive.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::alphabet::Extended36;
+/// use vds::VDString;
+///
+/// let code: VDString<Extended36> = "lo10ab".parse().unwrap();
+/// assert_eq!(&*code, "LO10AB");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Extended36;
+
+impl Alphabet for Extended36 {
+    const CHARS: &'static [char] = &[
+        'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L',
+        'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X',
+        'Y', 'Z', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+    ];
+
+    fn decode(c: char) -> Option<u8> {
+        Self::index_of(c.to_ascii_uppercase())
+    }
+}
+
+/// A subset of [`VDS_ALLOWED`] with no Cyrillic or Greek look-alikes.
+///
+/// Drops every Latin letter that has an uppercase homoglyph in those
+/// scripts (`A B C E H J K M N P S T X Y Z`), and `3`, which matches
+/// Cyrillic `З`. Use it for international deployments where codes are
+/// retyped from screenshots, so a look-alike is never silently accepted as a
+/// different code. Leaves 15 characters, about 3.9 bits each.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::alphabet::ScriptSafe15;
+/// use vds::VDString;
+///
+/// assert!("DRG48W".parse::<VDString<ScriptSafe15>>().is_ok());
+/// assert!("PAX".parse::<VDString<ScriptSafe15>>().is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ScriptSafe15;
+
+impl Alphabet for ScriptSafe15 {
+    const CHARS: &'static [char] = &[
+        'D', 'F', 'G', 'Q', 'R', 'U', 'V', 'W',
+        '2', '4', '5', '6', '7', '8', '9',
+    ];
+}
+
+/// Defines [`DefaultAlphabet`]: the alphabet named by the
+/// `vds_default_alphabet` cfg, or the first one if it is not set.
+macro_rules! default_alphabet {
+    ($(#[$attr:meta])* $default:ident, $($name:literal => $alphabet:ident,)*) => {
+        $(#[$attr])*
+        #[cfg(not(any($(vds_default_alphabet = $name),*)))]
+        pub type DefaultAlphabet = $default;
+
+        default_alphabet!(@each [$(#[$attr])*] $($name => $alphabet,)*);
+    };
+    (@each [$($attrs:tt)*] $name:literal => $alphabet:ident, $($rest:tt)*) => {
+        $($attrs)*
+        #[cfg(vds_default_alphabet = $name)]
+        pub type DefaultAlphabet = $alphabet;
+
+        default_alphabet!(@each [$($attrs)*] $($rest)*);
+    };
+    (@each [$($attrs:tt)*]) => {};
+}
+
+default_alphabet! {
+    /// The alphabet that [`VDChar`](crate::VDChar), [`VDString`](crate::VDString)
+    /// and the other alphabet-generic types use when none is named.
+    ///
+    /// This is [`Default31`] unless another preset is selected at build time
+    /// with the `vds_default_alphabet` cfg, e.g. for firmware that only ever
+    /// handles numeric codes:
+    ///
+    /// ```text
+    /// RUSTFLAGS='--cfg vds_default_alphabet="digits10"' cargo build
+    /// ```
+    ///
+    /// The accepted values are `default31`, `crockford32`, `digits10`,
+    /// `letters23`, `extended36` and `script_safe15`. It is a cfg rather than
+    /// a cargo feature because only one default can be in effect for the
+    /// whole build, and features must stay additive.
+    ///
+    /// Items that only exist for [`Default31`], such as
+    /// [`VDChar::ALL`](crate::VDChar::ALL), checksums, and the base-31
+    /// codecs, name it explicitly and are unaffected.
+    Default31,
+    "crockford32" => Crockford32,
+    "digits10" => Digits10,
+    "letters23" => Letters23,
+    "extended36" => Extended36,
+    "script_safe15" => ScriptSafe15,
+}
+
+/// Pairs of characters that are easily mistaken for each other in most fonts.
+///
+/// [`CustomAlphabetBuilder`] always rejects an alphabet containing both
+/// characters of any pair.
+pub const CONFUSABLE_PAIRS: &[(char, char)] = &[
+    ('0', 'O'), ('0', 'o'), ('O', 'o'),
+    ('1', 'I'), ('1', 'l'), ('I', 'l'),
+];
+
+/// Pairs that are confusable in some fonts or handwriting.
+///
+/// Rejected only by [`CustomAlphabetBuilder::strict`]. The default alphabet
+/// keeps both characters of each pair.
+pub const STRICT_CONFUSABLE_PAIRS: &[(char, char)] = &[
+    ('5', 'S'), ('2', 'Z'), ('8', 'B'),
+];
+
+/// Error returned by [`CustomAlphabetBuilder::build`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlphabetError {
+    /// No characters were given.
+    Empty,
+    /// More than 255 characters were given; index 255 is reserved as the
+    /// not-in-alphabet marker.
+    TooLarge(usize),
+    /// A character was given more than once.
+    Duplicate(char),
+    /// A whitespace or control character was given.
+    Unprintable(char),
+    /// Both characters of a confusable pair were given.
+    Confusable(char, char),
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            AlphabetError::Empty => f.write_str("alphabet has no characters"),
+            AlphabetError::TooLarge(n) => write!(f, "alphabet has {} characters, more than 255", n),
+            AlphabetError::Duplicate(c) => write!(f, "character {:?} is given more than once", c),
+            AlphabetError::Unprintable(c) => write!(f, "character {:?} is not printable", c),
+            AlphabetError::Confusable(a, b) => {
+                write!(f, "characters {:?} and {:?} are confusable", a, b)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for AlphabetError {}
+
+/// An alphabet chosen at runtime, validated by [`CustomAlphabetBuilder`].
+///
+/// Unlike [`Alphabet`] implementors, a `CustomAlphabet` is a value, so it
+/// validates and generates plain strings rather than typed
+/// [`VDString`](crate::VDString)s.
+///
+/// # Examples
+/// ```
+/// use vds::alphabet::CustomAlphabetBuilder;
+///
+/// let alphabet = CustomAlphabetBuilder::new().chars("ACEHKMRTW3479".chars()).build().unwrap();
+/// assert!(alphabet.validate("KW3R").is_ok());
+/// assert!(!alphabet.is_valid("KB3R"));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CustomAlphabet {
+    chars: Vec<char>,
+}
+
+#[cfg(feature = "alloc")]
+impl CustomAlphabet {
+    /// Returns the characters of a compile-time alphabet as a value.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::alphabet::{CustomAlphabet, Crockford32};
+    ///
+    /// let alphabet = CustomAlphabet::of::<Crockford32>();
+    /// assert_eq!(alphabet.len(), 32);
+    /// assert!(alphabet.is_valid("0Z9"));
+    /// ```
+    pub fn of<A: Alphabet>() -> Self {
+        CustomAlphabet { chars: A::CHARS.to_vec() }
+    }
+
+    /// Returns the characters, in index order.
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    /// Returns the number of characters.
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Always `false`; a built alphabet has at least one character.
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Returns the index of `c`, or `None` if it is not in the alphabet.
+    pub fn index_of(&self, c: char) -> Option<u8> {
+        self.chars.iter().position(|&x| x == c).map(|i| i as u8)
+    }
+
+    /// Returns `true` if `c` is in the alphabet.
+    pub fn contains(&self, c: char) -> bool {
+        self.chars.contains(&c)
+    }
+
+    /// Checks that every character of `s` is in the alphabet.
+    ///
+    /// # Errors
+    /// Returns [`VDStringError::InvalidChar`] for the first character that is not.
+    pub fn validate(&self, s: &str) -> Result<(), VDStringError> {
+        match s.char_indices().find(|&(_, c)| !self.contains(c)) {
+            Some((offset, ch)) => Err(VDStringError::InvalidChar { ch, offset }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `true` if every character of `s` is in the alphabet.
+    pub fn is_valid(&self, s: &str) -> bool {
+        self.validate(s).is_ok()
+    }
+}
+
+/// A builder for [`CustomAlphabet`] that enforces the same safety rules as
+/// the built-in set.
+///
+/// # Examples
+/// ```
+/// use vds::alphabet::{AlphabetError, CustomAlphabetBuilder};
+///
+/// let err = CustomAlphabetBuilder::new().chars("ABCO0".chars()).build();
+/// assert_eq!(err, Err(AlphabetError::Confusable('0', 'O')));
+///
+/// let err = CustomAlphabetBuilder::new().chars("ABS5".chars()).strict().build();
+/// assert_eq!(err, Err(AlphabetError::Confusable('5', 'S')));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct CustomAlphabetBuilder {
+    chars: Vec<char>,
+    strict: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl CustomAlphabetBuilder {
+    /// Creates a builder with no characters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends characters, in index order.
+    pub fn chars<I: IntoIterator<Item = char>>(mut self, chars: I) -> Self {
+        self.chars.extend(chars);
+        self
+    }
+
+    /// Also rejects the pairs in [`STRICT_CONFUSABLE_PAIRS`].
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Validates the characters and builds the alphabet.
+    ///
+    /// # Errors
+    /// - [`AlphabetError::Empty`] if no characters were given
+    /// - [`AlphabetError::TooLarge`] if more than 255 were given
+    /// - [`AlphabetError::Duplicate`] for the first repeated character
+    /// - [`AlphabetError::Unprintable`] for whitespace or control characters
+    /// - [`AlphabetError::Confusable`] for the first confusable pair present
+    pub fn build(self) -> Result<CustomAlphabet, AlphabetError> {
+        let chars = self.chars;
+        if chars.is_empty() {
+            return Err(AlphabetError::Empty);
+        }
+        if chars.len() > 255 {
+            return Err(AlphabetError::TooLarge(chars.len()));
+        }
+        for (i, &c) in chars.iter().enumerate() {
+            if c.is_whitespace() || c.is_control() {
+                return Err(AlphabetError::Unprintable(c));
+            }
+            if chars[..i].contains(&c) {
+                return Err(AlphabetError::Duplicate(c));
+            }
+        }
+
+        let strict: &[(char, char)] = if self.strict { STRICT_CONFUSABLE_PAIRS } else { &[] };
+        let conflict = CONFUSABLE_PAIRS
+            .iter()
+            .chain(strict)
+            .find(|(a, b)| chars.contains(a) && chars.contains(b));
+        if let Some(&(a, b)) = conflict {
+            return Err(AlphabetError::Confusable(a, b));
+        }
+
+        Ok(CustomAlphabet { chars })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_indices_match_allowed() {
+        for (i, &c) in VDS_ALLOWED.iter().enumerate() {
+            assert_eq!(Default31::index_of(c), Some(i as u8));
+        }
+        assert_eq!(Default31::index_of('O'), None);
+    }
+
+    #[test]
+    fn index_of_matches_linear_search() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        struct Mixed;
+        impl Alphabet for Mixed {
+            const CHARS: &'static [char] = &['\u{3b1}', 'Z', '7', '\u{3b2}', 'a'];
+        }
+
+        fn check<A: Alphabet>() {
+            let chars = (0..0x400).filter_map(char::from_u32);
+            for c in chars {
+                let expected = A::CHARS.iter().position(|&x| x == c).map(|i| i as u8);
+                assert_eq!(A::index_of(c), expected, "{:?}", c);
+            }
+        }
+        check::<Default31>();
+        check::<Crockford32>();
+        check::<Digits10>();
+        check::<Mixed>();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn default_set_passes_builder_checks() {
+        let built = CustomAlphabetBuilder::new().chars(VDS_ALLOWED.iter().copied()).build();
+        assert_eq!(built.unwrap().chars(), VDS_ALLOWED);
+
+        let strict = CustomAlphabetBuilder::new().chars(VDS_ALLOWED.iter().copied()).strict().build();
+        assert_eq!(strict, Err(AlphabetError::Confusable('5', 'S')));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn builder_rejects_unsafe_sets() {
+        let build = |s: &str| CustomAlphabetBuilder::new().chars(s.chars()).build();
+        assert_eq!(build(""), Err(AlphabetError::Empty));
+        assert_eq!(build("ABCA"), Err(AlphabetError::Duplicate('A')));
+        assert_eq!(build("AB C"), Err(AlphabetError::Unprintable(' ')));
+        assert_eq!(build("1Il"), Err(AlphabetError::Confusable('1', 'I')));
+        assert_eq!(build("Il"), Err(AlphabetError::Confusable('I', 'l')));
+        assert!(build("S5Z2B8").is_ok());
+
+        let sized = |n: u32| CustomAlphabetBuilder::new().chars((0x100..0x100 + n).filter_map(char::from_u32)).build();
+        assert_eq!(sized(255).map(|a| a.len()), Ok(255));
+        assert_eq!(sized(256), Err(AlphabetError::TooLarge(256)));
+        assert_eq!(alloc::format!("{}", AlphabetError::TooLarge(256)), "alphabet has 256 characters, more than 255");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn custom_alphabet_validates_strings() {
+        let alphabet = CustomAlphabetBuilder::new().chars("XYZ789".chars()).build().unwrap();
+        assert_eq!(alphabet.len(), 6);
+        assert_eq!(alphabet.index_of('7'), Some(3));
+        assert_eq!(alphabet.validate("X7Z9"), Ok(()));
+        assert_eq!(alphabet.validate("X7A9"), Err(VDStringError::InvalidChar { ch: 'A', offset: 2 }));
+        assert!(alphabet.is_valid(""));
+    }
+
+    fn check_presets<A: Alphabet>(len: usize) {
+        assert_eq!(A::CHARS.len(), len);
+        #[cfg(feature = "alloc")]
+        match CustomAlphabetBuilder::new().chars(A::CHARS.iter().copied()).build() {
+            Ok(_) => {}
+            Err(AlphabetError::Confusable(..)) => {}
+            Err(e) => panic!("{:?}", e),
+        }
+        for (i, &c) in A::CHARS.iter().enumerate() {
+            assert_eq!(A::decode(c), Some(i as u8));
+        }
+    }
+
+    #[test]
+    fn presets_are_well_formed() {
+        check_presets::<Default31>(31);
+        check_presets::<Crockford32>(32);
+        check_presets::<Digits10>(10);
+        check_presets::<Letters23>(23);
+        check_presets::<Extended36>(36);
+        check_presets::<ScriptSafe15>(15);
+        assert!(Letters23::CHARS.iter().all(|c| VDS_ALLOWED.contains(c)));
+    }
+
+    #[test]
+    fn presets_alias_on_decode() {
+        assert_eq!(Crockford32::decode('o'), Crockford32::index_of('0'));
+        assert_eq!(Crockford32::decode('L'), Crockford32::index_of('1'));
+        assert_eq!(Crockford32::decode('i'), Crockford32::index_of('1'));
+        assert_eq!(Crockford32::decode('U'), None);
+        assert_eq!(Extended36::decode('q'), Extended36::index_of('Q'));
+        assert_eq!(Default31::decode('a'), None);
+        assert_eq!(Digits10::decode('O'), None);
+    }
+
+    #[test]
+    fn script_safe_excludes_homoglyphs() {
+        // Latin characters and their uppercase Cyrillic or Greek twins.
+        let twins = [
+            ('A', 'А'), ('B', 'В'), ('C', 'С'), ('E', 'Е'), ('H', 'Н'), ('J', 'Ј'),
+            ('K', 'К'), ('M', 'М'), ('N', 'Ν'), ('P', 'Р'), ('S', 'Ѕ'), ('T', 'Т'),
+            ('X', 'Х'), ('Y', 'Υ'), ('Z', 'Ζ'), ('3', 'З'),
+        ];
+        for (latin, twin) in twins {
+            assert!(VDS_ALLOWED.contains(&latin));
+            assert_eq!(ScriptSafe15::decode(latin), None, "{} ({})", latin, twin);
+        }
+        assert!(ScriptSafe15::CHARS.iter().all(|c| VDS_ALLOWED.contains(c)));
+    }
+}
@@ -0,0 +1,283 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, Index};
+use core::str::FromStr;
+
+use crate::alphabet::Default31;
+use crate::vdchar::ASCII_INDEX;
+use crate::{validate, VDChar, VDStr};
+
+/// Error returned when building or parsing a [`VDArrayString`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VDArrayStringError {
+    /// A character in the input was not part of the allowed set.
+    InvalidChar(char),
+    /// The input has more characters than the string's capacity.
+    TooLong {
+        /// The fixed capacity that was exceeded.
+        capacity: usize,
+    },
+}
+
+impl fmt::Display for VDArrayStringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VDArrayStringError::InvalidChar(c) => write!(f, "invalid character {:?}", c),
+            VDArrayStringError::TooLong { capacity } => {
+                write!(f, "input exceeds the capacity of {} characters", capacity)
+            }
+        }
+    }
+}
+
+impl core::error::Error for VDArrayStringError {}
+
+/// A fixed-capacity string of up to `N` [`VDChar`]s that never allocates.
+///
+/// Offers the same parsing and formatting surface as
+/// [`VDString`](crate::VDString) — `FromStr`, `Deref<Target = str>`,
+/// `Display`, indexing, iteration, and serde — but lives entirely on the
+/// stack, so it is available without the `alloc` feature (e.g. on
+/// microcontrollers with no heap).
+///
+/// # Examples
+/// ```
+/// use vds::VDArrayString;
+///
+/// let code: VDArrayString<8> = "AB29XY".parse().unwrap();
+/// assert_eq!(&*code, "AB29XY");
+/// assert_eq!(code.capacity(), 8);
+///
+/// assert!("AB29XY7KM".parse::<VDArrayString<8>>().is_err());
+/// ```
+#[derive(Clone, Copy)]
+pub struct VDArrayString<const N: usize> {
+    chars: [VDChar<Default31>; N],
+    // ASCII rendering of `chars`, kept in step so the string can be borrowed as `&str`.
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> VDArrayString<N> {
+    /// Creates an empty string.
+    pub const fn new() -> Self {
+        Self { chars: [VDChar::from_index_unchecked(0); N], bytes: [0; N], len: 0 }
+    }
+
+    /// Creates a string holding a copy of `chars`.
+    ///
+    /// # Errors
+    /// Returns [`VDArrayStringError::TooLong`] if `chars` has more than `N` characters.
+    pub fn from_vdchars(chars: &[VDChar<Default31>]) -> Result<Self, VDArrayStringError> {
+        let mut s = Self::new();
+        for &ch in chars {
+            s.try_push(ch)?;
+        }
+        Ok(s)
+    }
+
+    /// Appends a character.
+    ///
+    /// # Errors
+    /// Returns [`VDArrayStringError::TooLong`] if the string is full.
+    pub fn try_push(&mut self, ch: VDChar<Default31>) -> Result<(), VDArrayStringError> {
+        if self.len == N {
+            return Err(VDArrayStringError::TooLong { capacity: N });
+        }
+        self.chars[self.len] = ch;
+        self.bytes[self.len] = ch.as_char() as u8;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Returns the maximum number of characters, `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if no more characters fit.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns a slice of the stored [`VDChar`]s.
+    pub fn as_vdchars(&self) -> &[VDChar<Default31>] {
+        &self.chars[..self.len]
+    }
+
+    /// Borrows the stored characters as a [`VDStr`].
+    pub fn as_vdstr(&self) -> &VDStr<Default31> {
+        VDStr::from_vdchars(self.as_vdchars())
+    }
+
+    /// Returns the string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        // Every stored byte is an ASCII character from `VDS_ALLOWED`.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+
+    /// Returns the character at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<VDChar<Default31>> {
+        self.as_vdchars().get(index).copied()
+    }
+}
+
+impl<const N: usize> Default for VDArrayString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for VDArrayString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Display for VDArrayString<N> {
+    /// Honors width, fill, alignment, and precision like a `str`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for VDArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if cfg!(feature = "redact-debug") {
+            write!(f, "VDArrayString(REDACTED, len={})", self.len)
+        } else {
+            f.debug_tuple("VDArrayString").field(&self.as_str()).finish()
+        }
+    }
+}
+
+impl<const N: usize> PartialEq for VDArrayString<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_vdchars() == other.as_vdchars()
+    }
+}
+
+impl<const N: usize> Eq for VDArrayString<N> {}
+
+impl<const N: usize> Hash for VDArrayString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_vdchars().hash(state);
+    }
+}
+
+impl<const N: usize> Index<usize> for VDArrayString<N> {
+    type Output = VDChar<Default31>;
+
+    /// # Panics
+    /// Panics if the index is out of bounds.
+    fn index(&self, index: usize) -> &VDChar<Default31> {
+        &self.as_vdchars()[index]
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a VDArrayString<N> {
+    type Item = VDChar<Default31>;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, VDChar<Default31>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_vdchars().iter().copied()
+    }
+}
+
+impl<const N: usize> FromStr for VDArrayString<N> {
+    type Err = VDArrayStringError;
+
+    /// Parses a `&str`, validating each character.
+    ///
+    /// Returns the first invalid character, or [`VDArrayStringError::TooLong`]
+    /// once more than `N` valid characters have been read.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut out = Self::new();
+        let valid = validate::find_invalid::<Default31>(s.as_bytes()).unwrap_or(s.len());
+        for &b in &s.as_bytes()[..valid] {
+            out.try_push(VDChar::from_index_unchecked(ASCII_INDEX[usize::from(b)]))?;
+        }
+        for c in s[valid..].chars() {
+            let ch = VDChar::new(c).ok_or(VDArrayStringError::InvalidChar(c))?;
+            out.try_push(ch)?;
+        }
+        Ok(out)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for VDArrayString<N> {
+    type Error = VDArrayStringError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> From<VDArrayString<N>> for crate::VDString<Default31> {
+    fn from(s: VDArrayString<N>) -> Self {
+        crate::VDString::new(s.as_vdchars().to_vec())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> TryFrom<&crate::VDString<Default31>> for VDArrayString<N> {
+    type Error = VDArrayStringError;
+
+    fn try_from(s: &crate::VDString<Default31>) -> Result<Self, Self::Error> {
+        Self::from_vdchars(s.as_vdchars())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vd(c: char) -> VDChar<Default31> {
+        VDChar::<Default31>::new(c).unwrap()
+    }
+
+    #[test]
+    fn parses_up_to_capacity() {
+        let s: VDArrayString<4> = "M29W".parse().unwrap();
+        assert_eq!(s.as_str(), "M29W");
+        assert_eq!(s.len(), 4);
+        assert!(s.is_full());
+        assert_eq!(s[0], vd('M'));
+        assert_eq!(s.get(4), None);
+
+        assert_eq!(
+            "M29WX".parse::<VDArrayString<4>>(),
+            Err(VDArrayStringError::TooLong { capacity: 4 })
+        );
+        assert_eq!("M2O".parse::<VDArrayString<4>>(), Err(VDArrayStringError::InvalidChar('O')));
+    }
+
+    #[test]
+    fn push_and_compare() {
+        let mut a = VDArrayString::<3>::new();
+        assert!(a.is_empty());
+        a.try_push(vd('A')).unwrap();
+        a.try_push(vd('7')).unwrap();
+
+        let b = VDArrayString::<3>::from_vdchars(&[vd('A'), vd('7')]).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.into_iter().count(), 2);
+        assert_eq!(a.as_vdstr(), "A7");
+
+        a.try_push(vd('K')).unwrap();
+        assert_eq!(a.try_push(vd('K')), Err(VDArrayStringError::TooLong { capacity: 3 }));
+        assert_ne!(a, b);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn converts_to_and_from_vdstring() {
+        let owned: crate::VDString<Default31> = "AB29".parse().unwrap();
+        let array = VDArrayString::<6>::try_from(&owned).unwrap();
+        assert_eq!(crate::VDString::<Default31>::from(array), owned);
+        assert!(VDArrayString::<3>::try_from(&owned).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+extern crate alloc;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::alphabet::Default31;
+use crate::homoglyph::canonicalize_char;
+use crate::{Ambiguity, ParseHint, VDChar, VDString};
+
+/// Checks every character of `text` against the VDS rules.
+///
+/// Intended for vetting existing identifiers, such as legacy SKUs, before
+/// migrating them: the report says which characters are already allowed,
+/// which can be replaced without guessing, and which are excluded as
+/// confusable, with the reason.
+///
+/// # Examples
+/// ```
+/// use vds::{audit, Ambiguity, CharVerdict, VDChar};
+///
+/// let report = audit("sku-10B");
+/// assert!(!report.is_valid());
+/// assert_eq!(report.entries()[0].verdict, CharVerdict::Substitute(VDChar::new('S').unwrap()));
+/// assert_eq!(report.entries()[3].verdict, CharVerdict::Separator);
+/// assert_eq!(report.entries()[4].verdict, CharVerdict::Excluded(Ambiguity::LooksLikeLetterI));
+/// assert_eq!(report.suggestion(), None);
+///
+/// assert_eq!(audit("sku-29B").suggestion().unwrap(), "SKU29B");
+/// ```
+pub fn audit(text: &str) -> AmbiguityReport {
+    let entries = text
+        .char_indices()
+        .enumerate()
+        .map(|(char_index, (byte_index, ch))| CharAudit { ch, char_index, byte_index, verdict: CharVerdict::of(ch) })
+        .collect();
+    AmbiguityReport { entries }
+}
+
+/// How a single character fares against the VDS rules.
+///
+/// Part of a [`CharAudit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CharVerdict {
+    /// The character is in [`VDS_ALLOWED`](crate::VDS_ALLOWED).
+    Allowed,
+    /// A lowercase, fullwidth, or look-alike form of exactly one allowed
+    /// character, which can replace it without changing its meaning.
+    Substitute(VDChar<Default31>),
+    /// A character, or a form of one, deliberately excluded as confusable.
+    /// It has no safe substitute, since it could stand for either side of
+    /// the confusable pair.
+    Excluded(Ambiguity),
+    /// A space, tab, or hyphen, as skipped by
+    /// [`ParseOptions::ignore_separators`](crate::ParseOptions::ignore_separators).
+    Separator,
+    /// Any other character, such as punctuation or a letter from another
+    /// script.
+    Unsupported,
+}
+
+impl CharVerdict {
+    fn of(ch: char) -> Self {
+        if VDChar::new(ch).is_some() {
+            return CharVerdict::Allowed;
+        }
+        if matches!(ch, ' ' | '\t' | '-') {
+            return CharVerdict::Separator;
+        }
+        let folded = canonicalize_char(ch);
+        if let Some(ParseHint::ExcludedAmbiguous { reason, .. }) = ParseHint::for_char(folded) {
+            return CharVerdict::Excluded(reason);
+        }
+        VDChar::new(folded).map_or(CharVerdict::Unsupported, CharVerdict::Substitute)
+    }
+}
+
+/// One character of an audited string and its [`CharVerdict`].
+///
+/// Part of an [`AmbiguityReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharAudit {
+    /// The audited character.
+    pub ch: char,
+    /// Position of the character, counted in `char`s.
+    pub char_index: usize,
+    /// Byte offset of the character in the audited `str`.
+    pub byte_index: usize,
+    /// How the character fares against the VDS rules.
+    pub verdict: CharVerdict,
+}
+
+impl fmt::Display for CharAudit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} at position {}: ", self.ch, self.char_index)?;
+        match self.verdict {
+            CharVerdict::Allowed => f.write_str("allowed"),
+            CharVerdict::Substitute(c) => write!(f, "use {:?} instead", c.as_char()),
+            CharVerdict::Excluded(reason) => f.write_str(reason.explanation()),
+            CharVerdict::Separator => f.write_str("separator, dropped"),
+            CharVerdict::Unsupported => f.write_str("not supported, no substitute"),
+        }
+    }
+}
+
+/// The result of [`audit`]: a [`CharVerdict`] for every character of a
+/// string.
+///
+/// Its `Display` lists one line per character that is not already allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmbiguityReport {
+    entries: Vec<CharAudit>,
+}
+
+impl AmbiguityReport {
+    /// Returns the audit of every character, in input order.
+    pub fn entries(&self) -> &[CharAudit] {
+        &self.entries
+    }
+
+    /// Returns the characters that are not already allowed.
+    pub fn issues(&self) -> impl Iterator<Item = &CharAudit> {
+        self.entries.iter().filter(|e| e.verdict != CharVerdict::Allowed)
+    }
+
+    /// Returns `true` if the string is a valid code as it stands.
+    pub fn is_valid(&self) -> bool {
+        self.issues().next().is_none()
+    }
+
+    /// Returns the code the string migrates to, with substitutes applied and
+    /// separators dropped, or `None` if any character is
+    /// [`Excluded`](CharVerdict::Excluded) or
+    /// [`Unsupported`](CharVerdict::Unsupported) and needs a human decision.
+    pub fn suggestion(&self) -> Option<VDString<Default31>> {
+        self.entries
+            .iter()
+            .filter(|e| e.verdict != CharVerdict::Separator)
+            .map(|e| match e.verdict {
+                CharVerdict::Allowed => VDChar::new(e.ch),
+                CharVerdict::Substitute(c) => Some(c),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(VDString::new)
+    }
+}
+
+impl fmt::Display for AmbiguityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.issues().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn classifies_each_kind_of_character() {
+        let report = audit("K7ｋ0o1l Ж.");
+        let verdicts: Vec<_> = report.entries().iter().map(|e| e.verdict).collect();
+        assert_eq!(
+            verdicts,
+            [
+                CharVerdict::Allowed,
+                CharVerdict::Allowed,
+                CharVerdict::Substitute(VDChar::new('K').unwrap()),
+                CharVerdict::Excluded(Ambiguity::LooksLikeLetterO),
+                CharVerdict::Excluded(Ambiguity::LooksLikeZero),
+                CharVerdict::Excluded(Ambiguity::LooksLikeLetterI),
+                CharVerdict::Excluded(Ambiguity::LooksLikeOne),
+                CharVerdict::Separator,
+                CharVerdict::Unsupported,
+                CharVerdict::Unsupported,
+            ]
+        );
+        assert_eq!(report.entries()[3].byte_index, 5);
+        assert_eq!(report.issues().count(), 8);
+    }
+
+    #[test]
+    fn valid_codes_have_no_issues() {
+        let report = audit("AB29XY");
+        assert!(report.is_valid());
+        assert_eq!(report.suggestion().unwrap(), "AB29XY");
+        assert_eq!(report.to_string(), "");
+        assert!(audit("").is_valid());
+    }
+
+    #[test]
+    fn display_lists_issues() {
+        assert_eq!(
+            audit("a0").to_string(),
+            "'a' at position 0: use 'A' instead\n\
+             '0' at position 1: the digit 0 is not used because it looks like the letter O"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! `vds` command-line tool: generate, check, and format codes.
+//!
+//! Built only with the `cli` feature. Run `vds help` for usage.
+
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process::ExitCode;
+
+use vds::{ParseOptions, VDGenerator, VDString};
+
+const USAGE: &str = "\
+usage:
+  vds gen [--length N] [--count N] [--no-repeats] [--no-adjacent-repeats]
+      Print N distinct random codes, one per line (defaults: length 8, count 1).
+  vds check [--lenient] [CODE...]
+      Report whether each code is valid. Reads one code per line from stdin
+      when no codes are given. Exits with status 1 if any code is invalid.
+  vds fmt [--group N] [--sep S] [CODE...]
+      Print each code in groups of N characters joined by S (defaults: 4, \"-\").
+      Input is parsed leniently, so \"ab29 xy7k\" prints as \"AB29-XY7K\".
+      Reads one code per line from stdin when no codes are given.
+  vds help
+      Print this message.";
+
+/// A command-line usage error, reported with exit status 2.
+struct UsageError(String);
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(code) => code,
+        Err(UsageError(msg)) => {
+            eprintln!("vds: {}\n\n{}", msg, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode, UsageError> {
+    let (command, rest) = args.split_first().ok_or_else(|| UsageError("missing command".into()))?;
+    let mut opts = Options::new(rest);
+    match command.as_str() {
+        "gen" => gen(&mut opts),
+        "check" => check(&mut opts),
+        "fmt" => fmt(&mut opts),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(ExitCode::SUCCESS)
+        }
+        other => Err(UsageError(format!("unknown command `{}`", other))),
+    }
+}
+
+/// Flags and positional arguments of one command.
+struct Options<'a> {
+    args: std::slice::Iter<'a, String>,
+    positional: Vec<&'a str>,
+}
+
+impl<'a> Options<'a> {
+    fn new(args: &'a [String]) -> Self {
+        Self { args: args.iter(), positional: Vec::new() }
+    }
+
+    /// Returns the next flag, collecting positional arguments on the way.
+    fn next_flag(&mut self) -> Option<&'a str> {
+        for arg in self.args.by_ref() {
+            if arg.starts_with("--") {
+                return Some(arg);
+            }
+            self.positional.push(arg);
+        }
+        None
+    }
+
+    fn value(&mut self, flag: &str) -> Result<&'a str, UsageError> {
+        self.args.next().map(String::as_str).ok_or_else(|| UsageError(format!("`{}` needs a value", flag)))
+    }
+
+    fn number(&mut self, flag: &str) -> Result<usize, UsageError> {
+        let value = self.value(flag)?;
+        value.parse().map_err(|_| UsageError(format!("`{}` expects a number, got `{}`", flag, value)))
+    }
+}
+
+fn unknown(flag: &str) -> UsageError {
+    UsageError(format!("unknown option `{}`", flag))
+}
+
+/// Returns the codes given on the command line, or else the lines of stdin.
+fn inputs(positional: Vec<&str>) -> io::Result<Vec<String>> {
+    if !positional.is_empty() {
+        return Ok(positional.into_iter().map(String::from).collect());
+    }
+    io::stdin().lock().lines().filter(|line| line.as_ref().map_or(true, |l| !l.trim().is_empty())).collect()
+}
+
+fn io_failure(err: io::Error) -> ExitCode {
+    eprintln!("vds: {}", err);
+    ExitCode::FAILURE
+}
+
+fn gen(opts: &mut Options<'_>) -> Result<ExitCode, UsageError> {
+    let mut generator = VDGenerator::new().length(8);
+    let mut count = 1;
+    while let Some(flag) = opts.next_flag() {
+        match flag {
+            "--length" => generator = generator.length(opts.number(flag)?),
+            "--count" => count = opts.number(flag)?,
+            "--no-repeats" => generator = generator.no_repeats(),
+            "--no-adjacent-repeats" => generator = generator.no_adjacent_repeats(),
+            _ => return Err(unknown(flag)),
+        }
+    }
+    if let Some(extra) = opts.positional.first() {
+        return Err(UsageError(format!("unexpected argument `{}`", extra)));
+    }
+
+    let codes = match generator.generate_many(count, &mut rand::rng()) {
+        Ok(codes) => codes,
+        Err(err) => {
+            eprintln!("vds: {}", err);
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    let mut out = BufWriter::new(io::stdout().lock());
+    let written = codes.iter().try_for_each(|code| writeln!(out, "{}", code)).and_then(|()| out.flush());
+    Ok(written.map_or_else(io_failure, |()| ExitCode::SUCCESS))
+}
+
+fn check(opts: &mut Options<'_>) -> Result<ExitCode, UsageError> {
+    let mut options = ParseOptions::default();
+    while let Some(flag) = opts.next_flag() {
+        match flag {
+            "--lenient" => options = ParseOptions::lenient(),
+            _ => return Err(unknown(flag)),
+        }
+    }
+
+    let codes = match inputs(std::mem::take(&mut opts.positional)) {
+        Ok(codes) => codes,
+        Err(err) => return Ok(io_failure(err)),
+    };
+    let mut all_valid = true;
+    let mut out = BufWriter::new(io::stdout().lock());
+    let written = codes.iter().try_for_each(|code| match VDString::parse_with(code, options) {
+        Ok(_) => writeln!(out, "{}: ok", code),
+        Err(err) => {
+            all_valid = false;
+            writeln!(out, "{}: {}", code, err)
+        }
+    });
+    if let Err(err) = written.and_then(|()| out.flush()) {
+        return Ok(io_failure(err));
+    }
+    Ok(if all_valid { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn fmt(opts: &mut Options<'_>) -> Result<ExitCode, UsageError> {
+    let mut group = 4;
+    let mut separator = "-";
+    while let Some(flag) = opts.next_flag() {
+        match flag {
+            "--group" => group = opts.number(flag)?,
+            "--sep" => separator = opts.value(flag)?,
+            _ => return Err(unknown(flag)),
+        }
+    }
+
+    let codes = match inputs(std::mem::take(&mut opts.positional)) {
+        Ok(codes) => codes,
+        Err(err) => return Ok(io_failure(err)),
+    };
+    let mut status = ExitCode::SUCCESS;
+    let mut out = BufWriter::new(io::stdout().lock());
+    for input in &codes {
+        let written = match VDString::parse_with(input, ParseOptions::lenient()) {
+            Ok(code) => writeln!(out, "{}", code.display_grouped(group, separator)),
+            Err(err) => {
+                eprintln!("vds: {}: {}", input, err);
+                status = ExitCode::FAILURE;
+                Ok(())
+            }
+        };
+        if let Err(err) = written {
+            return Ok(io_failure(err));
+        }
+    }
+    Ok(out.flush().map_or_else(io_failure, |()| status))
+}
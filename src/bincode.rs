@@ -0,0 +1,131 @@
+//! Native [`bincode`] 2 support for [`VDChar`] and [`VDString`].
+//!
+//! This module is only available when the `bincode` feature is enabled.
+//!
+//! These impls talk to bincode directly rather than through serde:
+//!
+//! - a [`VDChar`] is its alphabet index as one byte
+//! - a [`VDString`] is its [packed form](VDString::to_packed_bytes): a
+//!   varint character count, then 5 bits per character
+//!
+//! Decoding validates every index (and the padding of packed codes), so a
+//! corrupted cache entry is an error rather than an invalid code.
+//!
+//! # Examples
+//! ```
+//! use vds::VDString;
+//!
+//! let code: VDString = "K7M3PQ".parse().unwrap();
+//! let bytes = bincode::encode_to_vec(&code, bincode::config::standard()).unwrap();
+//! assert_eq!(bytes.len(), 1 + 4);
+//!
+//! let (decoded, _): (VDString, _) = bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+//! assert_eq!(decoded, code);
+//! ```
+
+extern crate alloc;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use bincode::de::read::Reader;
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::enc::write::Writer;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{BorrowDecode, Decode, Encode};
+
+use crate::alphabet::{Alphabet, Default31};
+use crate::codec::{packed_body_len, PackedError};
+use crate::{VDChar, VDString};
+
+impl<A: Alphabet> Encode for VDChar<A> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.index().encode(encoder)
+    }
+}
+
+impl<A: Alphabet, Context> Decode<Context> for VDChar<A> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let index = u8::decode(decoder)?;
+        VDChar::try_from_index(index).map_err(|err| DecodeError::OtherString(err.to_string()))
+    }
+}
+
+impl<'de, A: Alphabet, Context> BorrowDecode<'de, Context> for VDChar<A> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Self::decode(decoder)
+    }
+}
+
+impl Encode for VDString<Default31> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        encoder.writer().write(&self.to_packed_bytes())
+    }
+}
+
+impl<Context> Decode<Context> for VDString<Default31> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        // Read the varint length as `from_packed_bytes` expects it, then the
+        // body, and let it validate both.
+        let mut packed = Vec::new();
+        let (mut len, mut shift) = (0usize, 0);
+        loop {
+            let byte = u8::decode(decoder)?;
+            packed.push(byte);
+            let part = usize::from(byte & 0x7f);
+            if shift >= usize::BITS || (part << shift) >> shift != part {
+                return Err(DecodeError::OtherString(PackedError::LengthOverflow.to_string()));
+            }
+            len |= part << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        let body_len = packed_body_len(len);
+        decoder.claim_bytes_read(body_len)?;
+        let start = packed.len();
+        packed.resize(start + body_len, 0);
+        decoder.reader().read(&mut packed[start..])?;
+        VDString::from_packed_bytes(&packed).map_err(|err| DecodeError::OtherString(err.to_string()))
+    }
+}
+
+impl<'de, Context> BorrowDecode<'de, Context> for VDString<Default31> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Self::decode(decoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bincode::config;
+
+    #[test]
+    fn round_trips_chars_and_codes() {
+        let code: VDString<Default31> = "XK29M5TQ7W".repeat(20).parse().unwrap();
+        let bytes = bincode::encode_to_vec((&code, code[3]), config::standard()).unwrap();
+        assert_eq!(bytes.len(), 2 + 125 + 1);
+        let (decoded, read): ((VDString<Default31>, VDChar<Default31>), _) = bincode::decode_from_slice(&bytes, config::standard()).unwrap();
+        assert_eq!(decoded, (code, VDChar::<Default31>::new('9').unwrap()));
+        assert_eq!(read, bytes.len());
+    }
+
+    #[test]
+    fn rejects_invalid_and_truncated_input() {
+        let decode = |bytes: &[u8]| bincode::decode_from_slice::<VDString<Default31>, _>(bytes, config::standard()).map(|(s, _)| s);
+        // One character with index 31.
+        assert!(matches!(decode(&[1, 31 << 3]), Err(DecodeError::OtherString(_))));
+        assert!(matches!(decode(&[1, 0b0000_0100]), Err(DecodeError::OtherString(_))));
+        assert!(matches!(decode(&[2, 0]), Err(DecodeError::UnexpectedEnd { .. })));
+        assert_eq!(decode(&[0]).unwrap(), "");
+
+        let limited = config::standard().with_limit::<8>();
+        assert!(matches!(
+            bincode::decode_from_slice::<VDString<Default31>, _>(&[0xff, 0x7f], limited),
+            Err(DecodeError::LimitExceeded)
+        ));
+        assert!(bincode::decode_from_slice::<VDChar<Default31>, _>(&[31], config::standard()).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use core::f64::consts::LN_2;
+use core::marker::PhantomData;
+
+use crate::alphabet::{Alphabet, DefaultAlphabet};
+use crate::siphash::SipHasher;
+use crate::VDStr;
+
+/// Key of the second hash; the first is [`VDStr::fingerprint`].
+const SECOND_KEY: [u8; 16] = *b"vds-bloom-second";
+
+/// A Bloom filter over codes, for screening out duplicates in far less
+/// memory than a set of the codes themselves.
+///
+/// [`maybe_contains`](Self::maybe_contains) never misses a code that was
+/// inserted, but may claim a code is present when it is not, at roughly
+/// the false-positive rate the filter was sized for. Screen new codes with
+/// it and confirm the rare hits against authoritative storage.
+///
+/// The filter holds about `1.44 × log2(1 / rate)` bits per expected code,
+/// e.g. 36 MB for 10 million codes at a one-in-a-million rate. Hashing is
+/// based on [`VDStr::fingerprint`], so it is stable across platforms and
+/// releases.
+///
+/// This type is only available when the `bloom` feature is enabled.
+///
+/// # Examples
+/// ```
+/// use vds::{VDBloom, VDString};
+///
+/// let mut seen = VDBloom::new(1_000_000, 0.000_001);
+/// let code: VDString = "K7M3PQ".parse().unwrap();
+///
+/// assert!(!seen.maybe_contains(&code));
+/// assert!(seen.insert(&code));
+/// assert!(seen.maybe_contains(&code));
+/// assert!(!seen.insert(&code));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VDBloom<A: Alphabet = DefaultAlphabet> {
+    words: Vec<u64>,
+    hashes: u32,
+    alphabet: PhantomData<A>,
+}
+
+impl<A: Alphabet> VDBloom<A> {
+    /// Creates an empty filter sized for `expected` codes at a false-positive
+    /// rate of at most `false_positive_rate` once they are all inserted.
+    ///
+    /// The number of hash functions is `log2(1 / rate)` rounded up, and the
+    /// bit count is chosen to match, so the actual rate at capacity is at
+    /// or slightly below the one requested.
+    ///
+    /// # Panics
+    /// Panics if `false_positive_rate` is not strictly between `0` and `1`.
+    pub fn new(expected: usize, false_positive_rate: f64) -> Self {
+        assert!(
+            false_positive_rate > 0.0 && false_positive_rate < 1.0,
+            "false-positive rate must be between 0 and 1, got {}",
+            false_positive_rate
+        );
+        let mut hashes = 1;
+        let mut rate = 0.5;
+        while rate > false_positive_rate && hashes < 64 {
+            rate /= 2.0;
+            hashes += 1;
+        }
+        // Optimal bit count for `hashes` hash functions: m = n·k / ln 2.
+        let bits = (expected as f64 * f64::from(hashes) / LN_2) as u64 + 1;
+        let words = usize::try_from(bits.div_ceil(64)).unwrap_or(usize::MAX);
+        Self { words: vec![0; words], hashes, alphabet: PhantomData }
+    }
+
+    /// Returns the number of bits in the filter, a multiple of 64.
+    pub fn bit_len(&self) -> u64 {
+        self.words.len() as u64 * 64
+    }
+
+    /// Returns the number of bits set per code.
+    pub fn hash_count(&self) -> u32 {
+        self.hashes
+    }
+
+    /// Adds `code` to the filter.
+    ///
+    /// Returns `true` if the code was certainly not present before, and
+    /// `false` if it may have been (it was inserted, or is a false
+    /// positive).
+    pub fn insert(&mut self, code: &VDStr<A>) -> bool {
+        let mut added = false;
+        for bit in self.bits(code) {
+            let (word, mask) = ((bit / 64) as usize, 1 << (bit % 64));
+            added |= self.words[word] & mask == 0;
+            self.words[word] |= mask;
+        }
+        added
+    }
+
+    /// Returns `false` if `code` was certainly never inserted, and `true`
+    /// if it probably was.
+    pub fn maybe_contains(&self, code: &VDStr<A>) -> bool {
+        self.bits(code).all(|bit| self.words[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Removes every code, keeping the filter's size.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Returns the bit positions of `code`, by double hashing.
+    fn bits(&self, code: &VDStr<A>) -> impl Iterator<Item = u64> {
+        let h1 = code.fingerprint();
+        let mut hasher = SipHasher::new(&SECOND_KEY);
+        for ch in code {
+            hasher.write(&[ch.index()]);
+        }
+        // Odd, so the probe sequence never collapses onto one bit.
+        let h2 = hasher.finish() | 1;
+        let len = self.bit_len();
+        (0..u64::from(self.hashes)).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % len)
+    }
+}
+
+impl<'a, A: Alphabet> Extend<&'a VDStr<A>> for VDBloom<A> {
+    fn extend<I: IntoIterator<Item = &'a VDStr<A>>>(&mut self, iter: I) {
+        for code in iter {
+            self.insert(code);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Default31;
+    use crate::VDString;
+
+    #[test]
+    fn sizing_follows_requested_rate() {
+        let bloom = VDBloom::<Default31>::new(1000, 0.01);
+        assert_eq!(bloom.hash_count(), 7);
+        assert!((9_000..11_000).contains(&bloom.bit_len()));
+        assert_eq!(VDBloom::<Default31>::new(0, 0.5).bit_len(), 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "false-positive rate")]
+    fn rejects_rate_out_of_range() {
+        VDBloom::<Default31>::new(10, 1.0);
+    }
+
+    #[test]
+    fn has_no_false_negatives_and_few_false_positives() {
+        let mut bloom = VDBloom::new(2000, 0.01);
+        let inserted: Vec<VDString<Default31>> = (0..2000).map(|n| VDString::<Default31>::encode_u64_padded(n, 6)).collect();
+        bloom.extend(inserted.iter().map(|code| &**code));
+        assert!(inserted.iter().all(|code| bloom.maybe_contains(code)));
+
+        let false_positives = (2000..12_000)
+            .filter(|&n| bloom.maybe_contains(&VDString::<Default31>::encode_u64_padded(n, 6)))
+            .count();
+        assert!(false_positives < 200, "{} false positives in 10000", false_positives);
+
+        bloom.clear();
+        assert!(!bloom.maybe_contains(&inserted[0]));
+    }
+}
@@ -0,0 +1,365 @@
+extern crate alloc;
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::alphabet::{Alphabet, DefaultAlphabet};
+use crate::{VDChar, VDString, VDStringError};
+
+/// Error returned when constructing or parsing a length-checked code such
+/// as [`NonEmptyVDString`] or [`VDStringBounded`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VDLengthError {
+    /// The input contained a character outside the alphabet.
+    Invalid(VDStringError),
+    /// The code is shorter than the minimum length.
+    TooShort {
+        /// The length of the code.
+        len: usize,
+        /// The minimum length.
+        min: usize,
+    },
+    /// The code is longer than the maximum length.
+    TooLong {
+        /// The length of the code.
+        len: usize,
+        /// The maximum length.
+        max: usize,
+    },
+}
+
+impl fmt::Display for VDLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VDLengthError::Invalid(_) => f.write_str("invalid code"),
+            VDLengthError::TooShort { len: 0, .. } => f.write_str("code is empty"),
+            VDLengthError::TooShort { len, min } => {
+                write!(f, "code of {} characters is shorter than {}", len, min)
+            }
+            VDLengthError::TooLong { len, max } => {
+                write!(f, "code of {} characters is longer than {}", len, max)
+            }
+        }
+    }
+}
+
+impl core::error::Error for VDLengthError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            VDLengthError::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<VDStringError> for VDLengthError {
+    fn from(err: VDStringError) -> Self {
+        VDLengthError::Invalid(err)
+    }
+}
+
+/// A [`VDString`] with at least one character.
+///
+/// Parsing, `TryFrom`, and deserialization reject empty input, so code that
+/// receives a `NonEmptyVDString` never has to handle an empty code.
+/// Dereferences to the [`VDString`], and [`first`](Self::first) and
+/// [`last`](Self::last) need no `Option`.
+///
+/// # Examples
+/// ```
+/// use vds::{NonEmptyVDString, VDLengthError};
+///
+/// let code: NonEmptyVDString = "AB29".parse().unwrap();
+/// assert_eq!(code.first().as_char(), 'A');
+/// assert_eq!(code.len(), 4);
+///
+/// assert_eq!("".parse::<NonEmptyVDString>(), Err(VDLengthError::TooShort { len: 0, min: 1 }));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct NonEmptyVDString<A: Alphabet = DefaultAlphabet> {
+    code: VDString<A>,
+}
+
+impl<A: Alphabet> NonEmptyVDString<A> {
+    /// Wraps `code` if it is not empty.
+    ///
+    /// # Errors
+    /// Returns [`VDLengthError::TooShort`] if `code` is empty.
+    pub fn from_vdstring(code: VDString<A>) -> Result<Self, VDLengthError> {
+        if code.is_empty() {
+            return Err(VDLengthError::TooShort { len: 0, min: 1 });
+        }
+        Ok(Self { code })
+    }
+
+    /// Returns the first character.
+    pub fn first(&self) -> VDChar<A> {
+        self.code.as_vdchars()[0]
+    }
+
+    /// Returns the last character.
+    pub fn last(&self) -> VDChar<A> {
+        self.code.as_vdchars()[self.code.len() - 1]
+    }
+
+    /// Returns the code as a [`VDString`].
+    pub fn as_vdstring(&self) -> &VDString<A> {
+        &self.code
+    }
+
+    /// Consumes the wrapper, returning the [`VDString`].
+    pub fn into_vdstring(self) -> VDString<A> {
+        self.code
+    }
+}
+
+impl<A: Alphabet> FromStr for NonEmptyVDString<A> {
+    type Err = VDLengthError;
+
+    /// Parses a non-empty code, validating each character.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_vdstring(s.parse()?)
+    }
+}
+
+impl<A: Alphabet> TryFrom<&str> for NonEmptyVDString<A> {
+    type Error = VDLengthError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<A: Alphabet> TryFrom<VDString<A>> for NonEmptyVDString<A> {
+    type Error = VDLengthError;
+
+    fn try_from(code: VDString<A>) -> Result<Self, Self::Error> {
+        Self::from_vdstring(code)
+    }
+}
+
+impl<A: Alphabet> From<NonEmptyVDString<A>> for VDString<A> {
+    fn from(code: NonEmptyVDString<A>) -> Self {
+        code.code
+    }
+}
+
+impl<A: Alphabet> Deref for NonEmptyVDString<A> {
+    type Target = VDString<A>;
+
+    fn deref(&self) -> &VDString<A> {
+        &self.code
+    }
+}
+
+impl<A: Alphabet> PartialOrd for NonEmptyVDString<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Alphabet> Ord for NonEmptyVDString<A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.code.cmp(&other.code)
+    }
+}
+
+impl<A: Alphabet> PartialEq<str> for NonEmptyVDString<A> {
+    fn eq(&self, other: &str) -> bool {
+        self.code == *other
+    }
+}
+
+impl<A: Alphabet> PartialEq<&str> for NonEmptyVDString<A> {
+    fn eq(&self, other: &&str) -> bool {
+        self.code == **other
+    }
+}
+
+impl<A: Alphabet> fmt::Display for NonEmptyVDString<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.code, f)
+    }
+}
+
+impl<A: Alphabet> fmt::Debug for NonEmptyVDString<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NonEmptyVDString").field(&self.code).finish()
+    }
+}
+
+/// A [`VDString`] of `MIN` to `MAX` characters, inclusive.
+///
+/// The bounds are part of the type, so a request field can declare
+/// "redemption code: 8–12 characters" as `VDStringBounded<8, 12>` and have
+/// parsing, `TryFrom`, and deserialization enforce it. `MIN` must not
+/// exceed `MAX`; a type that breaks this fails to compile when used.
+///
+/// # Examples
+/// ```
+/// use vds::{VDLengthError, VDStringBounded};
+///
+/// type RedemptionCode = VDStringBounded<8, 12>;
+///
+/// let code: RedemptionCode = "AB29XY7K".parse().unwrap();
+/// assert_eq!(code, "AB29XY7K");
+/// assert_eq!(RedemptionCode::MAX, 12);
+///
+/// assert_eq!("AB29".parse::<RedemptionCode>(), Err(VDLengthError::TooShort { len: 4, min: 8 }));
+/// assert_eq!("AB29XY7KAB29X".parse::<RedemptionCode>(), Err(VDLengthError::TooLong { len: 13, max: 12 }));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct VDStringBounded<const MIN: usize, const MAX: usize, A: Alphabet = DefaultAlphabet> {
+    code: VDString<A>,
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> VDStringBounded<MIN, MAX, A> {
+    /// The minimum length, inclusive.
+    pub const MIN: usize = MIN;
+
+    /// The maximum length, inclusive.
+    pub const MAX: usize = MAX;
+
+    const OK: () = assert!(MIN <= MAX, "VDStringBounded requires MIN <= MAX");
+
+    /// Wraps `code` if its length is within the bounds.
+    ///
+    /// # Errors
+    /// Returns [`VDLengthError::TooShort`] or [`VDLengthError::TooLong`] if
+    /// it is not.
+    pub fn from_vdstring(code: VDString<A>) -> Result<Self, VDLengthError> {
+        let () = Self::OK;
+        match code.len() {
+            len if len < MIN => Err(VDLengthError::TooShort { len, min: MIN }),
+            len if len > MAX => Err(VDLengthError::TooLong { len, max: MAX }),
+            _ => Ok(Self { code }),
+        }
+    }
+
+    /// Returns the code as a [`VDString`].
+    pub fn as_vdstring(&self) -> &VDString<A> {
+        &self.code
+    }
+
+    /// Consumes the wrapper, returning the [`VDString`].
+    pub fn into_vdstring(self) -> VDString<A> {
+        self.code
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> FromStr for VDStringBounded<MIN, MAX, A> {
+    type Err = VDLengthError;
+
+    /// Parses a code, validating each character and the length.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_vdstring(s.parse()?)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> TryFrom<&str> for VDStringBounded<MIN, MAX, A> {
+    type Error = VDLengthError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> TryFrom<VDString<A>> for VDStringBounded<MIN, MAX, A> {
+    type Error = VDLengthError;
+
+    fn try_from(code: VDString<A>) -> Result<Self, Self::Error> {
+        Self::from_vdstring(code)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> From<VDStringBounded<MIN, MAX, A>> for VDString<A> {
+    fn from(code: VDStringBounded<MIN, MAX, A>) -> Self {
+        code.code
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> Deref for VDStringBounded<MIN, MAX, A> {
+    type Target = VDString<A>;
+
+    fn deref(&self) -> &VDString<A> {
+        &self.code
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> PartialOrd for VDStringBounded<MIN, MAX, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> Ord for VDStringBounded<MIN, MAX, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.code.cmp(&other.code)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> PartialEq<str> for VDStringBounded<MIN, MAX, A> {
+    fn eq(&self, other: &str) -> bool {
+        self.code == *other
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> PartialEq<&str> for VDStringBounded<MIN, MAX, A> {
+    fn eq(&self, other: &&str) -> bool {
+        self.code == **other
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> fmt::Display for VDStringBounded<MIN, MAX, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.code, f)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize, A: Alphabet> fmt::Debug for VDStringBounded<MIN, MAX, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VDStringBounded").field(&self.code).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Default31;
+    use alloc::string::ToString;
+
+    #[test]
+    fn rejects_empty_and_invalid() {
+        let code: NonEmptyVDString<Default31> = "K7M3".parse().unwrap();
+        assert_eq!((code.first().as_char(), code.last().as_char()), ('K', '3'));
+        assert_eq!(code, "K7M3");
+        assert_eq!(code.to_string(), "K7M3");
+
+        let single: NonEmptyVDString<Default31> = NonEmptyVDString::<Default31>::try_from("X").unwrap();
+        assert_eq!(single.first(), single.last());
+
+        let empty = "".parse::<NonEmptyVDString<Default31>>().unwrap_err();
+        assert_eq!(empty.to_string(), "code is empty");
+        assert_eq!("K7O".parse::<NonEmptyVDString<Default31>>(), Err(VDLengthError::Invalid(VDStringError::InvalidChar { ch: 'O', offset: 2 })));
+        assert!(NonEmptyVDString::<Default31>::try_from(VDString::<Default31>::new(alloc::vec::Vec::new())).is_err());
+        assert_eq!(VDString::from(code.clone()), *code);
+    }
+
+    #[test]
+    fn enforces_both_bounds() {
+        type Code = VDStringBounded<2, 4, Default31>;
+        for s in ["AB", "AB2", "AB29"] {
+            assert_eq!(s.parse::<Code>().unwrap(), s);
+        }
+        assert_eq!("A".parse::<Code>(), Err(VDLengthError::TooShort { len: 1, min: 2 }));
+        assert_eq!("AB29X".parse::<Code>(), Err(VDLengthError::TooLong { len: 5, max: 4 }));
+        assert_eq!("AO".parse::<Code>(), Err(VDLengthError::Invalid(VDStringError::InvalidChar { ch: 'O', offset: 1 })));
+        assert_eq!(Code::try_from("AB29X").unwrap_err().to_string(), "code of 5 characters is longer than 4");
+
+        let exact: VDStringBounded<3, 3, Default31> = "K7M".parse().unwrap();
+        assert_eq!(exact.into_vdstring(), "K7M");
+        assert!("".parse::<VDStringBounded<0, 0, Default31>>().is_ok());
+    }
+}
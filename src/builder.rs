@@ -0,0 +1,210 @@
+extern crate alloc;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::alphabet::{Alphabet, DefaultAlphabet};
+use crate::{VDChar, VDStr, VDString, VDStringError};
+
+/// A growable, mutable buffer of [`VDChar`]s that builds a [`VDString`].
+///
+/// Every edit takes or yields whole `VDChar`s, so the contents are always
+/// valid. Unlike `VDString`, the builder keeps no cached `String`, so edits
+/// are as cheap as on a `Vec`; the cache is built once by
+/// [`build`](Self::build).
+///
+/// # Examples
+/// ```
+/// use vds::{VDChar, VDStringBuilder};
+///
+/// let mut b = VDStringBuilder::new();
+/// b.push_char('A').unwrap();
+/// b.push(VDChar::new('9').unwrap());
+/// b.insert(1, VDChar::new('B').unwrap());
+/// assert!(b.push_char('0').is_err());
+///
+/// assert_eq!(&*b.build(), "AB9");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct VDStringBuilder<A: Alphabet = DefaultAlphabet> {
+    chars: Vec<VDChar<A>>,
+}
+
+impl VDStringBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty builder with room for `capacity` characters.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { chars: Vec::with_capacity(capacity) }
+    }
+}
+
+impl<A: Alphabet> Default for VDStringBuilder<A> {
+    fn default() -> Self {
+        Self { chars: Vec::new() }
+    }
+}
+
+impl<A: Alphabet> VDStringBuilder<A> {
+    /// Appends a character.
+    pub fn push(&mut self, ch: VDChar<A>) {
+        self.chars.push(ch);
+    }
+
+    /// Validates `c` and appends it.
+    ///
+    /// # Errors
+    /// Returns [`VDStringError::InvalidChar`] if `c` is not in the alphabet,
+    /// leaving the builder unchanged. Its offset is the byte length of the
+    /// string built so far.
+    pub fn push_char(&mut self, c: char) -> Result<(), VDStringError> {
+        let ch = VDChar::from_char(c).ok_or_else(|| VDStringError::InvalidChar {
+            ch: c,
+            offset: self.chars.iter().map(|ch| ch.as_char().len_utf8()).sum(),
+        })?;
+        self.chars.push(ch);
+        Ok(())
+    }
+
+    /// Removes and returns the last character, or `None` if empty.
+    pub fn pop(&mut self) -> Option<VDChar<A>> {
+        self.chars.pop()
+    }
+
+    /// Inserts a character at `index`, shifting later characters right.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, ch: VDChar<A>) {
+        self.chars.insert(index, ch);
+    }
+
+    /// Removes and returns the character at `index`, shifting later
+    /// characters left.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> VDChar<A> {
+        self.chars.remove(index)
+    }
+
+    /// Replaces the character at `index`, returning the old one.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn replace(&mut self, index: usize, ch: VDChar<A>) -> VDChar<A> {
+        core::mem::replace(&mut self.chars[index], ch)
+    }
+
+    /// Shortens the builder to `len` characters. Has no effect if `len` is
+    /// greater than the current length.
+    pub fn truncate(&mut self, len: usize) {
+        self.chars.truncate(len);
+    }
+
+    /// Removes all characters.
+    pub fn clear(&mut self) {
+        self.chars.clear();
+    }
+
+    /// Borrows the current contents as a [`VDStr`].
+    pub fn as_vdstr(&self) -> &VDStr<A> {
+        VDStr::from_vdchars(&self.chars)
+    }
+
+    /// Returns the number of characters.
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Returns `true` if the builder holds no characters.
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Consumes the builder and returns the finished [`VDString`].
+    pub fn build(self) -> VDString<A> {
+        VDString::new(self.chars)
+    }
+}
+
+impl<A: Alphabet> fmt::Debug for VDStringBuilder<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VDStringBuilder").field(&self.as_vdstr()).finish()
+    }
+}
+
+impl<A: Alphabet> FromIterator<VDChar<A>> for VDStringBuilder<A> {
+    fn from_iter<I: IntoIterator<Item = VDChar<A>>>(iter: I) -> Self {
+        Self { chars: iter.into_iter().collect() }
+    }
+}
+
+impl<A: Alphabet> Extend<VDChar<A>> for VDStringBuilder<A> {
+    fn extend<I: IntoIterator<Item = VDChar<A>>>(&mut self, iter: I) {
+        self.chars.extend(iter);
+    }
+}
+
+impl<A: Alphabet> From<VDString<A>> for VDStringBuilder<A> {
+    /// Reuses the string's character buffer, so editing an existing code
+    /// does not copy it.
+    fn from(s: VDString<A>) -> Self {
+        Self { chars: s.into_vdchars() }
+    }
+}
+
+impl<A: Alphabet> From<VDStringBuilder<A>> for VDString<A> {
+    fn from(b: VDStringBuilder<A>) -> Self {
+        b.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Default31;
+
+    fn vd(c: char) -> VDChar<Default31> {
+        VDChar::<Default31>::new(c).unwrap()
+    }
+
+    #[test]
+    fn edits_in_place() {
+        let mut b = VDStringBuilder::<Default31>::default();
+        for c in "AB29".chars() {
+            b.push_char(c).unwrap();
+        }
+        assert_eq!(b.replace(0, vd('Z')), vd('A'));
+        assert_eq!(b.remove(1), vd('B'));
+        b.insert(0, vd('K'));
+        assert_eq!(b.as_vdstr(), "KZ29");
+        assert_eq!(b.pop(), Some(vd('9')));
+        b.truncate(1);
+        assert_eq!(&*b.clone().build(), "K");
+
+        b.clear();
+        assert!(b.is_empty());
+        assert_eq!(b.pop(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_chars_without_change() {
+        let mut b = VDStringBuilder::<Default31>::default();
+        b.push_char('M').unwrap();
+        assert_eq!(b.push_char('O'), Err(VDStringError::InvalidChar { ch: 'O', offset: 1 }));
+        assert_eq!(b.len(), 1);
+    }
+
+    #[test]
+    fn round_trips_through_vdstring() {
+        let code: VDString<Default31> = "XY7K".parse().unwrap();
+        let mut b = VDStringBuilder::from(code.clone());
+        assert_eq!(b.as_vdstr(), &*code);
+        b.push(vd('3'));
+        assert_eq!(VDString::<Default31>::from(b), "XY7K3".parse::<VDString<Default31>>().unwrap());
+    }
+}
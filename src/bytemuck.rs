@@ -0,0 +1,81 @@
+//! `bytemuck` support for [`VDChar`](crate::VDChar).
+//!
+//! This module is only available when the `bytemuck` feature is enabled.
+//!
+//! [`VDChar`] is `#[repr(transparent)]` over a byte holding its index into its alphabet,
+//! by default [`VDS_ALLOWED`](crate::VDS_ALLOWED). Not every byte is a valid index, so
+//! `VDChar` cannot be [`Pod`](bytemuck::Pod); instead it implements:
+//!
+//! - [`NoUninit`]: `&[VDChar]` can be cast to `&[u8]` for free
+//! - [`CheckedBitPattern`]: `&[u8]` can be cast to `&[VDChar]` after every
+//!   byte is checked to be an in-range index
+//!
+//! This lets index tables stored on disk or in memory-mapped files be viewed
+//! as `VDChar`s without copying.
+//!
+//! # Examples
+//! ```
+//! use vds::{VDChar, VDString};
+//!
+//! let code: VDString = "AB29".parse().unwrap();
+//! let bytes: &[u8] = bytemuck::cast_slice(code.as_vdchars());
+//!
+//! let chars: &[VDChar] = bytemuck::checked::try_cast_slice(bytes).unwrap();
+//! assert_eq!(chars, code.as_vdchars());
+//!
+//! assert!(bytemuck::checked::try_cast_slice::<u8, VDChar>(&[200]).is_err());
+//! ```
+
+#![allow(unsafe_code)]
+
+use bytemuck::{CheckedBitPattern, NoUninit};
+
+use crate::alphabet::Alphabet;
+use crate::vdchar::VDChar;
+
+// SAFETY: `VDChar` is `#[repr(transparent)]` over a `#[repr(u8)]` index plus a
+// zero-sized marker, so it has no padding and every value is fully initialized.
+unsafe impl<A: Alphabet> NoUninit for VDChar<A> {}
+
+// SAFETY: `VDChar` is `#[repr(transparent)]` over a `#[repr(u8)]` index, so
+// `Bits = u8` has the same size and alignment. The index enum is valid for
+// every byte up to 254, and alphabets hold at most 255 characters, so checking
+// that the index is in range for the alphabet, as `is_valid_bit_pattern`
+// does, is sufficient.
+unsafe impl<A: Alphabet> CheckedBitPattern for VDChar<A> {
+    type Bits = u8;
+
+    fn is_valid_bit_pattern(bits: &u8) -> bool {
+        (*bits as usize) < A::CHARS.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Default31;
+    use crate::VDS_ALLOWED;
+    use bytemuck::checked;
+
+    #[test]
+    fn cast_to_bytes_exposes_indices() {
+        let chars = [VDChar::<Default31>::new('A').unwrap(), VDChar::<Default31>::new('9').unwrap()];
+        let bytes: &[u8] = bytemuck::cast_slice(&chars);
+        assert_eq!(bytes, &[0, (VDS_ALLOWED.len() - 1) as u8]);
+    }
+
+    #[test]
+    fn checked_cast_accepts_every_valid_index() {
+        let bytes: [u8; 31] = core::array::from_fn(|i| i as u8);
+        let chars: &[VDChar<Default31>] = checked::try_cast_slice(&bytes).unwrap();
+        for (i, ch) in chars.iter().enumerate() {
+            assert_eq!(ch.as_char(), VDS_ALLOWED[i]);
+        }
+    }
+
+    #[test]
+    fn checked_cast_rejects_out_of_range_index() {
+        let bytes = [0u8, 1, VDS_ALLOWED.len() as u8];
+        assert!(checked::try_cast_slice::<u8, VDChar<Default31>>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use core::fmt;
+
+use crate::alphabet::Default31;
+use crate::vdchar::{VDChar, VDS_ALLOWED};
+
+/// A set of [`VDChar`]s, stored as a bitmask over [`VDS_ALLOWED`] indices.
+///
+/// The whole alphabet fits in a single `u32`, so the set is `Copy` and all
+/// operations are constant time. Iteration yields characters in alphabet
+/// order.
+///
+/// # Examples
+/// ```
+/// use vds::{VDChar, VDCharSet};
+///
+/// let mut set = VDCharSet::new();
+/// set.insert(VDChar::new('B').unwrap());
+/// set.insert(VDChar::new('A').unwrap());
+///
+/// assert_eq!(set.len(), 2);
+/// assert!(set.contains(VDChar::new('A').unwrap()));
+/// let chars: String = set.iter().map(|c| c.as_char()).collect();
+/// assert_eq!(chars, "AB");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct VDCharSet(u32);
+
+impl VDCharSet {
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Creates a set containing every character in [`VDS_ALLOWED`].
+    pub const fn full() -> Self {
+        Self((1 << VDS_ALLOWED.len()) - 1)
+    }
+
+    /// Adds `ch` to the set, returning `true` if it was not already present.
+    pub fn insert(&mut self, ch: VDChar<Default31>) -> bool {
+        let bit = 1 << ch.index();
+        let added = self.0 & bit == 0;
+        self.0 |= bit;
+        added
+    }
+
+    /// Removes `ch` from the set, returning `true` if it was present.
+    pub fn remove(&mut self, ch: VDChar<Default31>) -> bool {
+        let bit = 1 << ch.index();
+        let present = self.0 & bit != 0;
+        self.0 &= !bit;
+        present
+    }
+
+    /// Returns `true` if `ch` is in the set.
+    pub fn contains(&self, ch: VDChar<Default31>) -> bool {
+        self.0 & (1 << ch.index()) != 0
+    }
+
+    /// Returns the number of characters in the set.
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns `true` if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns an iterator over the characters in the set, in alphabet order.
+    pub fn iter(&self) -> VDCharSetIter {
+        VDCharSetIter(self.0)
+    }
+}
+
+impl fmt::Debug for VDCharSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter().map(|c| c.as_char())).finish()
+    }
+}
+
+impl FromIterator<VDChar<Default31>> for VDCharSet {
+    fn from_iter<I: IntoIterator<Item = VDChar<Default31>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<VDChar<Default31>> for VDCharSet {
+    fn extend<I: IntoIterator<Item = VDChar<Default31>>>(&mut self, iter: I) {
+        for ch in iter {
+            self.insert(ch);
+        }
+    }
+}
+
+impl IntoIterator for VDCharSet {
+    type Item = VDChar<Default31>;
+    type IntoIter = VDCharSetIter;
+
+    fn into_iter(self) -> VDCharSetIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the characters in a [`VDCharSet`], in alphabet order.
+#[derive(Debug, Clone)]
+pub struct VDCharSetIter(u32);
+
+impl Iterator for VDCharSetIter {
+    type Item = VDChar<Default31>;
+
+    fn next(&mut self) -> Option<VDChar<Default31>> {
+        if self.0 == 0 {
+            return None;
+        }
+        let i = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(VDChar::from_index_unchecked(i as u8))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.0.count_ones() as usize;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for VDCharSetIter {
+    fn next_back(&mut self) -> Option<VDChar<Default31>> {
+        if self.0 == 0 {
+            return None;
+        }
+        let i = 31 - self.0.leading_zeros();
+        self.0 &= !(1 << i);
+        Some(VDChar::from_index_unchecked(i as u8))
+    }
+}
+
+impl ExactSizeIterator for VDCharSetIter {}
+
+impl core::iter::FusedIterator for VDCharSetIter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vd(c: char) -> VDChar<Default31> {
+        VDChar::<Default31>::new(c).unwrap()
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut set = VDCharSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(vd('Z')));
+        assert!(!set.insert(vd('Z')));
+        assert!(set.contains(vd('Z')));
+        assert!(set.remove(vd('Z')));
+        assert!(!set.remove(vd('Z')));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn full_contains_every_char() {
+        let set = VDCharSet::full();
+        assert_eq!(set.len(), VDS_ALLOWED.len());
+        assert!(VDChar::<Default31>::all().all(|c| set.contains(c)));
+        assert!(set.iter().eq(VDChar::<Default31>::all()));
+    }
+
+    #[test]
+    fn iterates_in_alphabet_order() {
+        let set: VDCharSet = [vd('9'), vd('A'), vd('M')].into_iter().collect();
+        let chars: [char; 3] = [vd('A'), vd('M'), vd('9')].map(|c| c.as_char());
+        assert!(set.iter().map(|c| c.as_char()).eq(chars));
+        assert_eq!(set.iter().len(), 3);
+        assert!(set.iter().rev().map(|c| c.as_char()).eq(chars.into_iter().rev()));
+
+        let mut iter = set.iter();
+        assert_eq!(iter.next_back(), Some(vd('9')));
+        assert_eq!(iter.next(), Some(vd('A')));
+        assert_eq!(iter.next_back(), Some(vd('M')));
+        assert_eq!((iter.next(), iter.next_back(), iter.len()), (None, None, 0));
+    }
+}
@@ -0,0 +1,541 @@
+//! Check characters for [`VDString`]s.
+//!
+//! A check character is appended to a payload so that common entry errors
+//! can be caught without a server round-trip. Algorithms implement
+//! [`ChecksumAlgorithm`]; three are built in:
+//!
+//! | Algorithm    | Single substitutions | Adjacent transpositions |
+//! |--------------|----------------------|-------------------------|
+//! | [`Damm`]     | all                  | all                     |
+//! | [`Iso7064`]  | all                  | all                     |
+//! | [`LuhnModN`] | all                  | all                     |
+//!
+//! [`Damm`] is the default used by [`VDCode`] and
+//! [`VDString::with_check_char`].
+//!
+//! All algorithms work directly on raw `&str` / `&[u8]` input through
+//! [`ChecksumAlgorithm::verify_str`] and [`ChecksumAlgorithm::verify_ascii`],
+//! which reject bad input before anything is allocated. They are available
+//! without the `alloc` feature; [`VDCode`] and the `VDString` helpers are not.
+
+#[cfg(feature = "alloc")]
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use core::ops::Deref;
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+
+use crate::alphabet::Default31;
+use crate::validate;
+use crate::vdchar::{VDChar, VDS_ALLOWED};
+#[cfg(feature = "alloc")]
+use crate::{VDString, VDStringError};
+
+const N: u32 = VDS_ALLOWED.len() as u32;
+
+/// A check-character scheme over the [`VDS_ALLOWED`] alphabet.
+///
+/// Implementors only need [`compute`](Self::compute); verification is
+/// derived from it. Input is given as an iterator of [`VDChar`]s in reading
+/// order, which lets the same implementation serve `VDString`s, slices, and
+/// raw validated bytes.
+///
+/// # Examples
+///
+/// A (weak) custom scheme: the sum of indices modulo 31.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::{ChecksumAlgorithm, VDChar, VDString};
+///
+/// struct SumMod31;
+///
+/// impl ChecksumAlgorithm for SumMod31 {
+///     fn compute<I>(&self, data: I) -> VDChar
+///     where
+///         I: DoubleEndedIterator<Item = VDChar> + ExactSizeIterator,
+///     {
+///         let sum: usize = data.map(|c| VDChar::ALL.iter().position(|&a| a == c).unwrap()).sum();
+///         VDChar::ALL[sum % VDChar::ALL.len()]
+///     }
+/// }
+///
+/// let code: VDString = "BC".parse().unwrap();
+/// assert_eq!(&*code.with_check_char_using(&SumMod31), "BCD");
+/// assert!(SumMod31.verify_str("BCD"));
+/// # }
+/// ```
+pub trait ChecksumAlgorithm {
+    /// Computes the check character for `data`.
+    fn compute<I>(&self, data: I) -> VDChar<Default31>
+    where
+        I: DoubleEndedIterator<Item = VDChar<Default31>> + ExactSizeIterator;
+
+    /// Returns `true` if the last character of `code` is the correct check
+    /// character for the characters before it.
+    ///
+    /// Returns `false` for empty input.
+    fn verify<I>(&self, code: I) -> bool
+    where
+        I: DoubleEndedIterator<Item = VDChar<Default31>> + ExactSizeIterator,
+    {
+        let mut code = code;
+        match code.next_back() {
+            Some(check) => self.compute(code) == check,
+            None => false,
+        }
+    }
+
+    /// Verifies a raw ASCII byte slice, including its trailing check character.
+    ///
+    /// Returns `false` if any byte is outside [`VDS_ALLOWED`]. Does not allocate.
+    fn verify_ascii(&self, bytes: &[u8]) -> bool
+    where
+        Self: Sized,
+    {
+        validate::is_valid(bytes) && self.verify(bytes.iter().map(|&b| ascii_to_vdchar(b)))
+    }
+
+    /// Verifies a raw string, including its trailing check character.
+    ///
+    /// Returns `false` if any character is outside [`VDS_ALLOWED`]. Does not allocate.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::checksum::{ChecksumAlgorithm, Damm};
+    ///
+    /// assert!(Damm.verify_str("AB29XYQ"));
+    /// assert!(!Damm.verify_str("AB29YXQ"));
+    /// assert!(!Damm.verify_str("ab29xyq"));
+    /// ```
+    fn verify_str(&self, s: &str) -> bool
+    where
+        Self: Sized,
+    {
+        self.verify_ascii(s.as_bytes())
+    }
+}
+
+/// Maps an already-validated ASCII byte to its [`VDChar`].
+fn ascii_to_vdchar(b: u8) -> VDChar<Default31> {
+    // Callers validate first, so the fallback is never taken.
+    VDChar::new(b as char).unwrap_or(VDChar::ALL[0])
+}
+
+/// The Damm algorithm over a totally anti-symmetric quasigroup of order 31.
+///
+/// Uses the operation `x ∘ y = 2(x − y) mod 31` on alphabet indices, which
+/// has a zero diagonal, so the check character is simply the final interim
+/// value. Detects every single-character substitution and every adjacent
+/// transposition.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Damm;
+
+impl ChecksumAlgorithm for Damm {
+    fn compute<I>(&self, data: I) -> VDChar<Default31>
+    where
+        I: DoubleEndedIterator<Item = VDChar<Default31>> + ExactSizeIterator,
+    {
+        let interim = data.fold(0, |x, c| 2 * (x + N - u32::from(c.index())) % N);
+        VDChar::from_index_unchecked(interim as u8)
+    }
+}
+
+/// An ISO/IEC 7064 pure system adapted to the 31-character alphabet
+/// ("MOD 31-3").
+///
+/// Follows the structure of MOD 37-2 with modulus 31 and radix 3. Because 31
+/// is prime and 3 is a primitive root modulo 31, the scheme detects every
+/// single-character substitution and every adjacent transposition.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Iso7064;
+
+impl ChecksumAlgorithm for Iso7064 {
+    fn compute<I>(&self, data: I) -> VDChar<Default31>
+    where
+        I: DoubleEndedIterator<Item = VDChar<Default31>> + ExactSizeIterator,
+    {
+        let p = data.fold(0, |p, c| (p + u32::from(c.index())) * 3 % N);
+        VDChar::from_index_unchecked(((N + 1 - p) % N) as u8)
+    }
+}
+
+/// The Luhn mod N algorithm with N = 31.
+///
+/// Doubles every second index from the right, as in the decimal Luhn
+/// algorithm. The classic fold of a doubled value (`q + r` for `2x = qN + r`)
+/// only permutes the alphabet when N is even, so with N = 31 the doubled
+/// value is reduced modulo 31 instead. This keeps the doubling a
+/// permutation, so every single-character substitution and every adjacent
+/// transposition is detected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LuhnModN;
+
+impl ChecksumAlgorithm for LuhnModN {
+    fn compute<I>(&self, data: I) -> VDChar<Default31>
+    where
+        I: DoubleEndedIterator<Item = VDChar<Default31>> + ExactSizeIterator,
+    {
+        let sum = data.rev().enumerate().fold(0, |sum, (i, c)| {
+            let factor = if i % 2 == 0 { 2 } else { 1 };
+            (sum + u32::from(c.index()) * factor) % N
+        });
+        VDChar::from_index_unchecked(((N - sum) % N) as u8)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VDString<Default31> {
+    /// Returns a copy of this string with a [`Damm`] check character appended.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let payload: VDString = "AB29XY".parse().unwrap();
+    /// let code = payload.with_check_char();
+    /// assert_eq!(code.len(), 7);
+    /// assert!(code.verify_check_char());
+    /// ```
+    pub fn with_check_char(&self) -> VDString<Default31> {
+        self.with_check_char_using(&Damm)
+    }
+
+    /// Returns a copy of this string with a check character computed by `alg` appended.
+    pub fn with_check_char_using<C: ChecksumAlgorithm>(&self, alg: &C) -> VDString<Default31> {
+        let mut chars = self.as_vdchars().to_vec();
+        chars.push(alg.compute(self.as_vdchars().iter().copied()));
+        VDString::new(chars)
+    }
+
+    /// Returns `true` if the last character is a valid [`Damm`] check character.
+    pub fn verify_check_char(&self) -> bool {
+        self.verify_check_char_using(&Damm)
+    }
+
+    /// Returns `true` if the last character is a valid check character under `alg`.
+    pub fn verify_check_char_using<C: ChecksumAlgorithm>(&self, alg: &C) -> bool {
+        alg.verify(self.as_vdchars().iter().copied())
+    }
+}
+
+#[cfg(all(feature = "generate", feature = "alloc"))]
+impl crate::VDGenerator<Default31> {
+    /// Generates a random payload and appends a check character, returning a [`VDCode`].
+    ///
+    /// The configured length applies to the payload; the resulting code is
+    /// one character longer. Constraints such as `no_repeats` apply to the
+    /// payload only, since the check character is determined by it.
+    ///
+    /// This method is only available when the `generate` feature is enabled.
+    ///
+    /// # Errors
+    /// Same as [`VDGenerator::generate`](crate::VDGenerator::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{StepRng, VDCode, VDGenerator};
+    ///
+    /// let mut rng = StepRng::new(0, 5);
+    /// let code: VDCode = VDGenerator::new().length(8).generate_code(&mut rng).unwrap();
+    /// assert_eq!(code.len(), 9);
+    /// assert!(code.verify_check_char());
+    /// ```
+    pub fn generate_code<C, R>(&self, rng: &mut R) -> Result<VDCode<C>, crate::VDGeneratorError>
+    where
+        C: ChecksumAlgorithm + Default,
+        R: crate::VDRng + ?Sized,
+    {
+        self.generate(rng).map(|payload| VDCode::new(&payload))
+    }
+}
+
+/// Error returned when parsing a [`VDCode`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VDCodeError {
+    /// The input contained a character outside the allowed set.
+    Invalid(VDStringError),
+    /// The input was empty, so there is no check character.
+    Empty,
+    /// The check character did not match the payload.
+    CheckMismatch,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for VDCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VDCodeError::Invalid(_) => f.write_str("invalid code"),
+            VDCodeError::Empty => f.write_str("code is empty"),
+            VDCodeError::CheckMismatch => f.write_str("check character does not match"),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for VDCodeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            VDCodeError::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<VDStringError> for VDCodeError {
+    fn from(err: VDStringError) -> Self {
+        VDCodeError::Invalid(err)
+    }
+}
+
+/// A [`VDString`] whose last character is a verified check character.
+///
+/// The algorithm is part of the type (defaulting to [`Damm`]), so a
+/// `VDCode` can only be constructed with a matching check character.
+/// Dereferences to the full code, including the check character.
+///
+/// # Examples
+/// ```
+/// use vds::checksum::LuhnModN;
+/// use vds::{VDCode, VDCodeError, VDString};
+///
+/// let payload: VDString = "AB29XY".parse().unwrap();
+/// let code: VDCode = VDCode::new(&payload);
+/// assert_eq!(code.payload(), payload.as_vdchars());
+///
+/// let parsed: VDCode = code.to_string().parse().unwrap();
+/// assert_eq!(parsed, code);
+///
+/// assert_eq!("AB29YXQ".parse::<VDCode>(), Err(VDCodeError::CheckMismatch));
+///
+/// let luhn: VDCode<LuhnModN> = VDCode::new(&payload);
+/// assert!(luhn.to_string().parse::<VDCode<LuhnModN>>().is_ok());
+/// ```
+#[cfg(feature = "alloc")]
+pub struct VDCode<C = Damm> {
+    code: VDString<Default31>,
+    alg: PhantomData<C>,
+}
+
+#[cfg(feature = "alloc")]
+impl<C: ChecksumAlgorithm + Default> VDCode<C> {
+    /// Creates a code by appending a check character to `payload`.
+    pub fn new(payload: &VDString<Default31>) -> Self {
+        Self { code: payload.with_check_char_using(&C::default()), alg: PhantomData }
+    }
+
+    /// Wraps `code` if its last character is a valid check character.
+    ///
+    /// # Errors
+    /// - [`VDCodeError::Empty`] if `code` is empty
+    /// - [`VDCodeError::CheckMismatch`] if the check character is wrong
+    pub fn from_vdstring(code: VDString<Default31>) -> Result<Self, VDCodeError> {
+        if code.is_empty() {
+            return Err(VDCodeError::Empty);
+        }
+        if !code.verify_check_char_using(&C::default()) {
+            return Err(VDCodeError::CheckMismatch);
+        }
+        Ok(Self { code, alg: PhantomData })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> VDCode<C> {
+    /// Returns the payload, i.e. every character except the check character.
+    pub fn payload(&self) -> &[VDChar<Default31>] {
+        let chars = self.code.as_vdchars();
+        &chars[..chars.len() - 1]
+    }
+
+    /// Returns the check character.
+    pub fn check_char(&self) -> VDChar<Default31> {
+        self.code.as_vdchars()[self.code.len() - 1]
+    }
+
+    /// Returns the full code, including the check character.
+    pub fn as_vdstring(&self) -> &VDString<Default31> {
+        &self.code
+    }
+
+    /// Consumes the code, returning the full [`VDString`].
+    pub fn into_vdstring(self) -> VDString<Default31> {
+        self.code
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: ChecksumAlgorithm + Default> FromStr for VDCode<C> {
+    type Err = VDCodeError;
+
+    /// Parses and verifies a code, including its trailing check character.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_vdstring(s.parse()?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: ChecksumAlgorithm + Default> TryFrom<&str> for VDCode<C> {
+    type Error = VDCodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> Deref for VDCode<C> {
+    type Target = VDString<Default31>;
+
+    fn deref(&self) -> &VDString<Default31> {
+        &self.code
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> fmt::Display for VDCode<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.code, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> fmt::Debug for VDCode<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VDCode").field(&self.code).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> Clone for VDCode<C> {
+    fn clone(&self) -> Self {
+        Self { code: self.code.clone(), alg: PhantomData }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> PartialEq for VDCode<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> Eq for VDCode<C> {}
+
+#[cfg(feature = "alloc")]
+impl<C> core::hash::Hash for VDCode<C> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.code.hash(state)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use alloc::{string::ToString, vec::Vec};
+
+    fn samples() -> Vec<VDString<Default31>> {
+        ["A", "AB29XY", "ZZZZ", "9876543", "M3PQKXW7V2"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect()
+    }
+
+    fn assert_detects_substitutions<C: ChecksumAlgorithm>(alg: &C) {
+        for payload in samples() {
+            let code = payload.with_check_char_using(alg);
+            assert!(code.verify_check_char_using(alg));
+            for pos in 0..code.len() {
+                for ch in VDChar::<Default31>::all().filter(|&c| c != code[pos]) {
+                    let mut chars = code.as_vdchars().to_vec();
+                    chars[pos] = ch;
+                    assert!(!alg.verify(chars.iter().copied()), "{} at {}", code, pos);
+                }
+            }
+        }
+    }
+
+    fn assert_detects_transpositions<C: ChecksumAlgorithm>(alg: &C) {
+        for payload in samples() {
+            let code = payload.with_check_char_using(alg);
+            for pos in 1..code.len() {
+                let mut chars = code.as_vdchars().to_vec();
+                if chars[pos - 1] == chars[pos] {
+                    continue;
+                }
+                chars.swap(pos - 1, pos);
+                assert!(!alg.verify(chars.iter().copied()), "{} swap {}", code, pos);
+            }
+        }
+    }
+
+    #[test]
+    fn damm_detects_single_errors_and_transpositions() {
+        assert_detects_substitutions(&Damm);
+        assert_detects_transpositions(&Damm);
+    }
+
+    #[test]
+    fn iso7064_detects_single_errors_and_transpositions() {
+        assert_detects_substitutions(&Iso7064);
+        assert_detects_transpositions(&Iso7064);
+    }
+
+    #[test]
+    fn luhn_detects_single_errors_and_transpositions() {
+        assert_detects_substitutions(&LuhnModN);
+        assert_detects_transpositions(&LuhnModN);
+    }
+
+    #[test]
+    fn raw_verification_matches_vdstring() {
+        for payload in samples() {
+            let code = payload.with_check_char();
+            assert!(Damm.verify_str(code.as_str()));
+            assert!(Damm.verify_ascii(code.as_str().as_bytes()));
+        }
+        assert!(!Damm.verify_str(""));
+        assert!(!Damm.verify_str("AB0"));
+        assert!(!Iso7064.verify_ascii(&[0xff]));
+    }
+
+    #[test]
+    fn vdcode_parse_errors() {
+        assert_eq!("".parse::<VDCode>(), Err(VDCodeError::Empty));
+        assert_eq!(
+            "AB0".parse::<VDCode>(),
+            Err(VDCodeError::Invalid(VDStringError::InvalidChar { ch: '0', offset: 2 }))
+        );
+        let code: VDCode = VDCode::new(&"AB29".parse().unwrap());
+        let mut s = code.to_string();
+        s.pop();
+        s.push(if code.check_char().as_char() == 'A' { 'B' } else { 'A' });
+        assert_eq!(s.parse::<VDCode>(), Err(VDCodeError::CheckMismatch));
+    }
+
+    #[test]
+    fn invalid_code_error_has_source() {
+        use core::error::Error;
+
+        let err = "AB0".parse::<VDCode>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid code");
+        assert_eq!(err.source().unwrap().to_string(), "invalid character '0' at byte 2");
+        assert!(VDCodeError::Empty.source().is_none());
+    }
+
+    #[test]
+    fn vdcode_splits_payload_and_check() {
+        let payload: VDString<Default31> = "XK29".parse().unwrap();
+        let code: VDCode<Iso7064> = VDCode::new(&payload);
+        assert_eq!(code.payload(), payload.as_vdchars());
+        assert_eq!(code.len(), 5);
+        assert_eq!(code.check_char(), code[4]);
+        assert_eq!(code.clone().into_vdstring(), *code.as_vdstring());
+    }
+}
@@ -0,0 +1,160 @@
+//! [`clap`] argument parsing for codes and option enums.
+//!
+//! This module is only available when the `clap` feature is enabled.
+//!
+//! [`VDString`] implements [`ValueParserFactory`], so
+//! `value_parser!(VDString)` (and a `VDString` field of a derived parser)
+//! validates the argument. Rejected input is reported with the offending
+//! character, a hint where there is one, and the allowed alphabet.
+//! [`VDStringValueParser::lenient`] accepts codes as typed by people.
+//! [`Collation`] and [`VDCharClass`] implement [`ValueEnum`], so they can be
+//! chosen by name with help text.
+//!
+//! # Examples
+//! ```
+//! use clap::{value_parser, Arg, Command};
+//! use vds::VDString;
+//!
+//! let cmd = Command::new("redeem").arg(Arg::new("code").value_parser(value_parser!(VDString)));
+//!
+//! let matches = cmd.clone().try_get_matches_from(["redeem", "AB29"]).unwrap();
+//! assert_eq!(matches.get_one::<VDString>("code").unwrap(), "AB29");
+//!
+//! let err = cmd.try_get_matches_from(["redeem", "AB20"]).unwrap_err().to_string();
+//! assert!(err.contains("invalid character '0' at byte 3 in 'AB20'"));
+//! assert!(err.contains("[allowed characters: ABCDEFGHJKMNPQRSTUVWXYZ23456789]"));
+//! ```
+
+use std::ffi::OsStr;
+use std::string::{String, ToString};
+
+use clap::builder::{PossibleValue, TypedValueParser, ValueParserFactory};
+use clap::error::ErrorKind;
+use clap::{Arg, Command, Error, ValueEnum};
+
+use crate::alphabet::Default31;
+use crate::{Collation, ParseOptions, VDCharClass, VDString, VDS_ALLOWED};
+
+/// Parses a command-line argument into a [`VDString`].
+///
+/// The parser returned by `value_parser!(VDString)` is strict, like
+/// `FromStr`; use [`lenient`](Self::lenient) for codes typed by people.
+///
+/// # Examples
+/// ```
+/// use clap::{Arg, Command};
+/// use vds::clap::VDStringValueParser;
+/// use vds::VDString;
+///
+/// let cmd = Command::new("redeem").arg(Arg::new("code").value_parser(VDStringValueParser::lenient()));
+/// let matches = cmd.try_get_matches_from(["redeem", "ab2-9xy"]).unwrap();
+/// assert_eq!(matches.get_one::<VDString>("code").unwrap(), "AB29XY");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VDStringValueParser {
+    options: ParseOptions,
+}
+
+impl VDStringValueParser {
+    /// Creates a strict parser.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a parser that accepts input as [`ParseOptions::lenient`] does.
+    pub fn lenient() -> Self {
+        Self::with_options(ParseOptions::lenient())
+    }
+
+    /// Creates a parser with the given leniencies.
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl TypedValueParser for VDStringValueParser {
+    type Value = VDString<Default31>;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<VDString<Default31>, Error> {
+        let Some(value) = value.to_str() else {
+            return Err(Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd));
+        };
+        VDString::parse_with(value, self.options).map_err(|err| {
+            let arg = arg.map_or_else(|| String::from("..."), ToString::to_string);
+            let hint = err.hint().map(|hint| std::format!(": {}", hint)).unwrap_or_default();
+            let allowed: String = VDS_ALLOWED.iter().collect();
+            cmd.clone().error(
+                ErrorKind::ValueValidation,
+                std::format!("{} in '{}' for '{}'{}\n\n  [allowed characters: {}]", err, value, arg, hint, allowed),
+            )
+        })
+    }
+}
+
+impl ValueParserFactory for VDString<Default31> {
+    type Parser = VDStringValueParser;
+
+    fn value_parser() -> VDStringValueParser {
+        VDStringValueParser::new()
+    }
+}
+
+impl ValueEnum for Collation {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Collation::Index, Collation::Ascii]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Collation::Index => PossibleValue::new("index").help("Alphabet order; letters before digits"),
+            Collation::Ascii => PossibleValue::new("ascii").help("Character order; digits before letters"),
+        })
+    }
+}
+
+impl ValueEnum for VDCharClass {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[VDCharClass::Letter, VDCharClass::Digit]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            VDCharClass::Letter => PossibleValue::new("letter").help("Uppercase letters"),
+            VDCharClass::Digit => PossibleValue::new("digit").help("Digits"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::value_parser;
+
+    fn cmd() -> Command {
+        Command::new("t")
+            .arg(Arg::new("code").long("code").value_parser(value_parser!(VDString<Default31>)))
+            .arg(Arg::new("lenient").long("lenient").value_parser(VDStringValueParser::lenient()))
+            .arg(Arg::new("order").long("order").value_parser(value_parser!(Collation)))
+    }
+
+    #[test]
+    fn parses_codes_and_enums() {
+        let m = cmd().try_get_matches_from(["t", "--code", "K7M3", "--lenient", " k7 m3 ", "--order", "ascii"]).unwrap();
+        assert_eq!(m.get_one::<VDString<Default31>>("code").unwrap(), "K7M3");
+        assert_eq!(m.get_one::<VDString<Default31>>("lenient").unwrap(), "K7M3");
+        assert_eq!(m.get_one::<Collation>("order"), Some(&Collation::Ascii));
+        assert_eq!(VDCharClass::from_str("digit", false), Ok(VDCharClass::Digit));
+    }
+
+    #[test]
+    fn explains_rejected_values() {
+        let err = cmd().try_get_matches_from(["t", "--code", "k7m3"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        let msg = err.to_string();
+        assert!(msg.contains("invalid character 'k' at byte 0 in 'k7m3' for '--code <code>': use uppercase 'K' instead of 'k'"), "{}", msg);
+        assert!(msg.contains("[allowed characters: ABCDEFGHJKMNPQRSTUVWXYZ23456789]"), "{}", msg);
+
+        let err = cmd().try_get_matches_from(["t", "--order", "random"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
+}
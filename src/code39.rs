@@ -0,0 +1,199 @@
+//! Code 39 barcode patterns for codes.
+//!
+//! Every character of [`VDS_ALLOWED`](crate::VDS_ALLOWED) is in the Code 39
+//! character set, so any [`VDString`](crate::VDString) can be printed as a
+//! Code 39 barcode next to its human-readable form.
+//!
+//! Each Code 39 character is nine elements, alternating bar and space and
+//! starting with a bar; three of them are wide. [`pattern`] gives these as
+//! a compact 9-bit value, and [`VDStr::code39_modules`] renders a whole
+//! barcode, with its `*` start and stop characters, as a sequence of
+//! modules ready to print.
+//!
+//! # Examples
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use vds::code39;
+//! use vds::VDString;
+//!
+//! let code: VDString = "AB29".parse().unwrap();
+//! let bars: String = code.code39_modules().unwrap().map(|bar| if bar { '█' } else { ' ' }).collect();
+//! assert_eq!(bars.chars().count(), code39::symbol_len(code.len()));
+//! # }
+//! ```
+
+use crate::alphabet::Alphabet;
+use crate::{VDChar, VDStr};
+
+/// Width of a wide element, in modules (narrow elements are one module).
+pub const WIDE: u8 = 3;
+
+/// Pattern of the `*` start/stop character.
+pub const START_STOP: u16 = 0b010_010_100;
+
+/// Characters of Code 39 other than `*`, with their patterns.
+const PATTERNS: [(char, u16); 43] = [
+    ('0', 0b000_110_100), ('1', 0b100_100_001), ('2', 0b001_100_001), ('3', 0b101_100_000),
+    ('4', 0b000_110_001), ('5', 0b100_110_000), ('6', 0b001_110_000), ('7', 0b000_100_101),
+    ('8', 0b100_100_100), ('9', 0b001_100_100), ('A', 0b100_001_001), ('B', 0b001_001_001),
+    ('C', 0b101_001_000), ('D', 0b000_011_001), ('E', 0b100_011_000), ('F', 0b001_011_000),
+    ('G', 0b000_001_101), ('H', 0b100_001_100), ('I', 0b001_001_100), ('J', 0b000_011_100),
+    ('K', 0b100_000_011), ('L', 0b001_000_011), ('M', 0b101_000_010), ('N', 0b000_010_011),
+    ('O', 0b100_010_010), ('P', 0b001_010_010), ('Q', 0b000_000_111), ('R', 0b100_000_110),
+    ('S', 0b001_000_110), ('T', 0b000_010_110), ('U', 0b110_000_001), ('V', 0b011_000_001),
+    ('W', 0b111_000_000), ('X', 0b010_010_001), ('Y', 0b110_010_000), ('Z', 0b011_010_000),
+    ('-', 0b010_000_101), ('.', 0b110_000_100), (' ', 0b011_000_100), ('$', 0b010_101_000),
+    ('/', 0b010_100_010), ('+', 0b010_001_010), ('%', 0b000_101_010),
+];
+
+/// Returns the Code 39 pattern of `c`, or `None` if Code 39 cannot encode
+/// it (or `c` is the reserved `*`).
+///
+/// Bit 8 is the first element and bit 0 the last; a set bit marks a wide
+/// element. Elements alternate bar and space, starting with a bar.
+///
+/// # Examples
+/// ```
+/// use vds::code39::pattern;
+///
+/// assert_eq!(pattern('A'), Some(0b100_001_001));
+/// assert_eq!(pattern('a'), None);
+/// ```
+pub fn pattern(c: char) -> Option<u16> {
+    PATTERNS.iter().find(|&&(x, _)| x == c).map(|&(_, p)| p)
+}
+
+/// Returns the width in modules of a barcode for a code of `len`
+/// characters, including start/stop characters and the one-module gaps
+/// between characters.
+pub fn symbol_len(len: usize) -> usize {
+    let char_len = 6 + 3 * usize::from(WIDE);
+    (len + 2) * char_len + (len + 1)
+}
+
+/// Returns the width in modules of element `element` (`0`–`8`) of `pattern`.
+fn element_width(pattern: u16, element: u8) -> u8 {
+    if (pattern >> (8 - element)) & 1 == 1 { WIDE } else { 1 }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns `true` if every character can be written in Code 39.
+    ///
+    /// Always `true` for [`VDS_ALLOWED`](crate::VDS_ALLOWED).
+    pub fn is_code39(&self) -> bool {
+        self.iter().all(|c| pattern(c.as_char()).is_some())
+    }
+
+    /// Returns the barcode's modules, `true` for bar and `false` for space,
+    /// or `None` if [`is_code39`](Self::is_code39) is `false`.
+    ///
+    /// The barcode is the code between `*` start and stop characters, with
+    /// a narrow space between characters. Wide elements are [`WIDE`]
+    /// modules. Quiet zones are left to the caller.
+    pub fn code39_modules(&self) -> Option<Code39Modules<'_, A>> {
+        self.is_code39().then(|| Code39Modules {
+            chars: self.as_vdchars(),
+            pos: 0,
+            element: 0,
+            left: element_width(START_STOP, 0),
+        })
+    }
+}
+
+/// Iterator over the modules of a Code 39 barcode.
+///
+/// Returned by [`VDStr::code39_modules`].
+#[derive(Debug, Clone)]
+pub struct Code39Modules<'a, A: Alphabet> {
+    chars: &'a [VDChar<A>],
+    /// `0` for the start character, `1..=len` for the code, then the stop.
+    pos: usize,
+    /// Element within the character, or `9` for the gap after it.
+    element: u8,
+    /// Modules left in the current element.
+    left: u8,
+}
+
+impl<A: Alphabet> Code39Modules<'_, A> {
+    fn pattern_at(&self, pos: usize) -> u16 {
+        match pos.checked_sub(1).and_then(|i| self.chars.get(i)) {
+            Some(c) => pattern(c.as_char()).unwrap_or(START_STOP),
+            None => START_STOP,
+        }
+    }
+}
+
+impl<A: Alphabet> Iterator for Code39Modules<'_, A> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        let stop = self.chars.len() + 1;
+        while self.pos <= stop {
+            if self.left > 0 {
+                self.left -= 1;
+                return Some(self.element < 9 && self.element.is_multiple_of(2));
+            }
+            self.element += 1;
+            if self.element == 9 && self.pos < stop {
+                self.left = 1;
+                continue;
+            }
+            if self.element >= 9 {
+                self.pos += 1;
+                self.element = 0;
+            }
+            self.left = element_width(self.pattern_at(self.pos), self.element);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use crate::{VDArrayString, VDS_ALLOWED};
+    use alloc::string::String;
+
+    fn render<A: Alphabet>(code: &VDStr<A>) -> String {
+        code.code39_modules().unwrap().map(|bar| if bar { '1' } else { '0' }).collect()
+    }
+
+    #[test]
+    fn patterns_have_three_wide_elements() {
+        for &(c, p) in PATTERNS.iter().chain(&[('*', START_STOP)]) {
+            assert_eq!(p.count_ones(), 3, "{}", c);
+            let wide_bars = (p & 0b101_010_101).count_ones();
+            assert!(wide_bars == 2 || (wide_bars == 0 && "$/+%".contains(c)), "{}", c);
+        }
+        for (i, &(_, p)) in PATTERNS.iter().enumerate() {
+            assert!(PATTERNS[i + 1..].iter().all(|&(_, q)| q != p));
+            assert_ne!(p, START_STOP);
+        }
+    }
+
+    #[test]
+    fn allowed_set_is_code39() {
+        assert!(VDS_ALLOWED.iter().all(|&c| pattern(c).is_some()));
+        assert_eq!(pattern('*'), None);
+    }
+
+    #[test]
+    fn renders_start_code_and_stop() {
+        let star = "100010111011101";
+        let a = "111010100010111";
+        let code: VDArrayString<4> = "A".parse().unwrap();
+        let expected = [star, "0", a, "0", star].concat();
+        assert_eq!(render(code.as_vdstr()), expected);
+    }
+
+    #[test]
+    fn length_matches_symbol_len() {
+        for s in ["", "K", "AB29XY7K"] {
+            let code: VDArrayString<8> = s.parse().unwrap();
+            let modules = render(code.as_vdstr());
+            assert_eq!(modules.len(), symbol_len(s.len()), "{:?}", s);
+            assert!(modules.starts_with('1') && modules.ends_with('1'));
+        }
+    }
+}
@@ -0,0 +1,709 @@
+//! Numeric codecs between integers or byte strings and [`VDString`]s.
+//!
+//! Values are written in base 31, most significant character first, using
+//! alphabet indices as digits (`A` = 0, `B` = 1, …, `9` = 30). Since `A` is
+//! the zero digit, padded codes are left-filled with `A`.
+//!
+//! Byte payloads are treated as one big-endian integer, as in Base58, with
+//! each leading zero byte kept as a leading `A` so that the exact byte
+//! length round-trips.
+//!
+//! The same value-preserving conversion transcodes codes to and from
+//! Crockford Base32 and Base58 strings, for systems that only accept those
+//! standard alphabets.
+//!
+//! Codes can also be packed at 5 bits per character for compact storage,
+//! with a length prefix so packed codes can be stored back to back.
+
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::vdchar::{VDChar, VDS_ALLOWED};
+use crate::alphabet::{Alphabet, Crockford32, Default31};
+use crate::{VDString, VDStringError};
+
+const BASE: u64 = VDS_ALLOWED.len() as u64;
+
+/// Number of characters needed to encode any `u64` (`31^13 > 2^64`).
+pub const U64_WIDTH: usize = 13;
+
+/// Number of characters needed to encode any `u128` (`31^26 > 2^128`).
+pub const U128_WIDTH: usize = 26;
+
+/// Error returned when decoding a [`VDString`] into a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The decoded value does not fit in the target type.
+    Overflow,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DecodeError::Overflow => f.write_str("decoded value overflows the target type"),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+/// Error returned by [`VDString::increment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IncrementError {
+    /// Every character is already `9`, the largest digit, so the successor
+    /// needs one more character than the code has.
+    Overflow,
+}
+
+impl fmt::Display for IncrementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IncrementError::Overflow => f.write_str("code is already the largest value of its length"),
+        }
+    }
+}
+
+impl core::error::Error for IncrementError {}
+
+/// Error returned when unpacking bytes produced by
+/// [`VDString::to_packed_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackedError {
+    /// The input ends inside the length prefix or the packed characters.
+    Truncated,
+    /// The length prefix does not fit in a `usize`.
+    LengthOverflow,
+    /// A 5-bit group is `31`, which is not an alphabet index.
+    InvalidIndex(u8),
+    /// The unused bits of the final byte are not zero.
+    NonZeroPadding,
+    /// Bytes remain after the packed code.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for PackedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PackedError::Truncated => f.write_str("packed code is truncated"),
+            PackedError::LengthOverflow => f.write_str("packed code length overflows usize"),
+            PackedError::InvalidIndex(i) => write!(f, "invalid character index {} in packed code", i),
+            PackedError::NonZeroPadding => f.write_str("packed code has non-zero padding bits"),
+            PackedError::TrailingBytes(n) => write!(f, "{} trailing bytes after packed code", n),
+        }
+    }
+}
+
+impl core::error::Error for PackedError {}
+
+impl VDString<Default31> {
+    /// Encodes `n` in base 31, using as few characters as possible.
+    ///
+    /// `0` encodes as `"A"`.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// assert_eq!(&*VDString::encode_u64(0), "A");
+    /// assert_eq!(&*VDString::encode_u64(31), "BA");
+    /// assert_eq!(VDString::encode_u64(123_456_789).decode_u64(), Ok(123_456_789));
+    /// ```
+    pub fn encode_u64(n: u64) -> VDString<Default31> {
+        Self::encode_u64_padded(n, 1)
+    }
+
+    /// Encodes `n` in base 31, left-padded with `A` to at least `width` characters.
+    ///
+    /// Values needing more than `width` characters are not truncated.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// assert_eq!(&*VDString::encode_u64_padded(31, 6), "AAAABA");
+    /// assert_eq!(VDString::encode_u64_padded(u64::MAX, 4).len(), vds::codec::U64_WIDTH);
+    /// ```
+    pub fn encode_u64_padded(mut n: u64, width: usize) -> VDString<Default31> {
+        let mut chars = Vec::with_capacity(width.max(U64_WIDTH));
+        while n > 0 {
+            chars.push(VDChar::from_index_unchecked((n % BASE) as u8));
+            n /= BASE;
+        }
+        while chars.len() < width {
+            chars.push(VDChar::from_index_unchecked(0));
+        }
+        chars.reverse();
+        VDString::new(chars)
+    }
+
+    /// Decodes this string as a base-31 number.
+    ///
+    /// Leading `A`s are ignored, and the empty string decodes to `0`.
+    ///
+    /// # Errors
+    /// Returns [`DecodeError::Overflow`] if the value exceeds `u64::MAX`.
+    pub fn decode_u64(&self) -> Result<u64, DecodeError> {
+        self.into_iter().try_fold(0u64, |acc, ch| {
+            acc.checked_mul(BASE)
+                .and_then(|v| v.checked_add(u64::from(ch.index())))
+                .ok_or(DecodeError::Overflow)
+        })
+    }
+}
+
+impl VDString<Default31> {
+    /// Encodes `n` in base 31, left-padded with `A` to at least `width` characters.
+    ///
+    /// The 128-bit counterpart of [`encode_u64_padded`](Self::encode_u64_padded).
+    pub fn encode_u128_padded(mut n: u128, width: usize) -> VDString<Default31> {
+        let mut chars = Vec::with_capacity(width.max(U128_WIDTH));
+        while n > 0 {
+            chars.push(VDChar::from_index_unchecked((n % u128::from(BASE)) as u8));
+            n /= u128::from(BASE);
+        }
+        while chars.len() < width {
+            chars.push(VDChar::from_index_unchecked(0));
+        }
+        chars.reverse();
+        VDString::new(chars)
+    }
+
+    /// Decodes this string as a base-31 number.
+    ///
+    /// # Errors
+    /// Returns [`DecodeError::Overflow`] if the value exceeds `u128::MAX`.
+    pub fn decode_u128(&self) -> Result<u128, DecodeError> {
+        self.into_iter().try_fold(0u128, |acc, ch| {
+            acc.checked_mul(u128::from(BASE))
+                .and_then(|v| v.checked_add(u128::from(ch.index())))
+                .ok_or(DecodeError::Overflow)
+        })
+    }
+}
+
+impl VDString<Default31> {
+    /// Encodes arbitrary bytes as a base-31 big integer.
+    ///
+    /// Each leading zero byte becomes a leading `A`, so
+    /// [`decode_bytes`](Self::decode_bytes) restores the exact input.
+    /// A 16-byte payload encodes to at most 26 characters.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let token = [0x00, 0x12, 0xab, 0xff];
+    /// let code = VDString::encode_bytes(&token);
+    /// assert_eq!(code.decode_bytes(), token);
+    /// ```
+    pub fn encode_bytes(bytes: &[u8]) -> VDString<Default31> {
+        let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+
+        // Little-endian base-31 digits of the non-zero tail.
+        // Each byte adds fewer than two base-31 digits.
+        let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 2);
+        for &byte in &bytes[zeros..] {
+            let mut carry = u32::from(byte);
+            for d in digits.iter_mut() {
+                carry += u32::from(*d) << 8;
+                *d = (carry % BASE as u32) as u8;
+                carry /= BASE as u32;
+            }
+            while carry > 0 {
+                digits.push((carry % BASE as u32) as u8);
+                carry /= BASE as u32;
+            }
+        }
+
+        let chars = core::iter::repeat_n(VDChar::from_index_unchecked(0), zeros)
+            .chain(digits.iter().rev().map(|&d| VDChar::from_index_unchecked(d)))
+            .collect();
+        VDString::new(chars)
+    }
+
+    /// Decodes a string produced by [`encode_bytes`](Self::encode_bytes).
+    ///
+    /// Every `VDString` decodes to some byte string; each leading `A`
+    /// becomes a leading zero byte.
+    pub fn decode_bytes(&self) -> Vec<u8> {
+        let chars = self.as_vdchars();
+        let zeros = chars.iter().take_while(|c| c.index() == 0).count();
+
+        // Little-endian bytes of the non-zero tail.
+        let mut bytes: Vec<u8> = Vec::with_capacity(chars.len());
+        for ch in &chars[zeros..] {
+            let mut carry = u32::from(ch.index());
+            for b in bytes.iter_mut() {
+                carry += u32::from(*b) * BASE as u32;
+                *b = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+
+        let mut out = alloc::vec![0; zeros];
+        out.extend(bytes.iter().rev());
+        out
+    }
+}
+
+/// Bitcoin's Base58 alphabet, in digit order.
+const BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Converts big-endian digits from base `from` to base `to`, keeping each
+/// leading zero digit as one leading zero digit.
+fn convert_base(digits: &[u8], from: u32, to: u32) -> Vec<u8> {
+    let zeros = digits.iter().take_while(|&&d| d == 0).count();
+
+    // Little-endian digits of the non-zero tail, as in `encode_bytes`.
+    let mut out: Vec<u8> = Vec::with_capacity(digits.len() * 2);
+    for &digit in &digits[zeros..] {
+        let mut carry = u32::from(digit);
+        for d in out.iter_mut() {
+            carry += u32::from(*d) * from;
+            *d = (carry % to) as u8;
+            carry /= to;
+        }
+        while carry > 0 {
+            out.push((carry % to) as u8);
+            carry /= to;
+        }
+    }
+    out.extend(core::iter::repeat_n(0, zeros));
+    out.reverse();
+    out
+}
+
+/// Parses `s` as big-endian digits of another encoding, and transcodes them
+/// to base 31.
+fn transcode_from(
+    s: &str,
+    base: u32,
+    digit: impl Fn(char) -> Option<u8>,
+) -> Result<VDString<Default31>, VDStringError> {
+    let digits = s
+        .char_indices()
+        .map(|(offset, ch)| digit(ch).ok_or(VDStringError::InvalidChar { ch, offset }))
+        .collect::<Result<Vec<_>, _>>()?;
+    let chars = convert_base(&digits, base, BASE as u32).into_iter().map(VDChar::from_index_unchecked).collect();
+    Ok(VDString::new(chars))
+}
+
+impl VDString<Default31> {
+    /// Transcodes this code to Crockford Base32 by its value.
+    ///
+    /// The code is read as a base-31 number, as in
+    /// [`decode_bytes`](Self::decode_bytes), and written in base 32 with
+    /// the [`Crockford32`] digits. Each leading `A` becomes a leading `0`,
+    /// so [`from_base32_crockford`](Self::from_base32_crockford) restores
+    /// the exact code. The result is never longer than the code.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AK7M3PQ".parse().unwrap();
+    /// let base32 = code.to_base32_crockford();
+    /// assert_eq!(base32, "08EP5R2");
+    /// assert_eq!(VDString::from_base32_crockford(&base32).unwrap(), code);
+    /// ```
+    pub fn to_base32_crockford(&self) -> String {
+        let digits: Vec<u8> = self.iter().map(|c| c.index()).collect();
+        convert_base(&digits, BASE as u32, 32).into_iter().map(|d| Crockford32::CHARS[usize::from(d)]).collect()
+    }
+
+    /// Transcodes a Crockford Base32 string to a code by its value.
+    ///
+    /// Decoding follows the Crockford specification: it is case-insensitive,
+    /// reads `I` and `L` as `1` and `O` as `0`, and skips hyphens. Each
+    /// leading `0` becomes a leading `A`.
+    ///
+    /// # Errors
+    /// Returns [`VDStringError::InvalidChar`] with the first character that
+    /// is not a Crockford Base32 digit.
+    pub fn from_base32_crockford(s: &str) -> Result<VDString<Default31>, VDStringError> {
+        let digits: String = s.chars().filter(|&c| c != '-').collect();
+        transcode_from(&digits, 32, Crockford32::decode)
+    }
+
+    /// Transcodes this code to Base58 (Bitcoin alphabet) by its value.
+    ///
+    /// As with [`to_base32_crockford`](Self::to_base32_crockford), each
+    /// leading `A` becomes a leading `1`, Base58's zero digit, so
+    /// [`from_base58`](Self::from_base58) restores the exact code.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AK7M3PQ".parse().unwrap();
+    /// let base58 = code.to_base58();
+    /// assert_eq!(base58, "1S5mRs");
+    /// assert_eq!(VDString::from_base58(&base58).unwrap(), code);
+    /// ```
+    pub fn to_base58(&self) -> String {
+        let digits: Vec<u8> = self.iter().map(|c| c.index()).collect();
+        convert_base(&digits, BASE as u32, 58).into_iter().map(|d| char::from(BASE58[usize::from(d)])).collect()
+    }
+
+    /// Transcodes a Base58 (Bitcoin alphabet) string to a code by its value.
+    ///
+    /// Base58 is case-sensitive, so no folding is done. Each leading `1`
+    /// becomes a leading `A`.
+    ///
+    /// # Errors
+    /// Returns [`VDStringError::InvalidChar`] with the first character that
+    /// is not a Base58 digit.
+    pub fn from_base58(s: &str) -> Result<VDString<Default31>, VDStringError> {
+        transcode_from(s, 58, |c| {
+            let c = u8::try_from(c).ok()?;
+            BASE58.iter().position(|&b| b == c).map(|i| i as u8)
+        })
+    }
+}
+
+impl VDString<Default31> {
+    /// Returns the next code of the same length, counting in base 31.
+    ///
+    /// The successor is the smallest code of this length that orders after
+    /// `self`, so consecutive successors walk every code in [`Ord`] order:
+    /// the last character advances, and a `9` rolls over to `A` and carries
+    /// into the character before it. Returns `None` if every character is
+    /// `9` (including the empty code), as the length is never changed.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AB9".parse().unwrap();
+    /// assert_eq!(code.successor().unwrap(), "ACA");
+    ///
+    /// let last: VDString = "99".parse().unwrap();
+    /// assert_eq!(last.successor(), None);
+    /// ```
+    pub fn successor(&self) -> Option<VDString<Default31>> {
+        let max = VDS_ALLOWED.len() as u8 - 1;
+        let mut chars = self.as_vdchars().to_vec();
+        let pos = chars.iter().rposition(|ch| ch.index() < max)?;
+        chars[pos] = VDChar::from_index_unchecked(chars[pos].index() + 1);
+        chars[pos + 1..].fill(VDChar::from_index_unchecked(0));
+        Some(VDString::new(chars))
+    }
+
+    /// Advances this code to its [`successor`](Self::successor) in place.
+    ///
+    /// # Errors
+    /// Returns [`IncrementError::Overflow`] if every character is `9`, in
+    /// which case the code is left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let mut code: VDString = "K7M9".parse().unwrap();
+    /// code.increment().unwrap();
+    /// assert_eq!(code, "K7NA");
+    /// ```
+    pub fn increment(&mut self) -> Result<(), IncrementError> {
+        *self = self.successor().ok_or(IncrementError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// Number of bytes holding `len` packed characters, `⌈5·len / 8⌉`.
+pub(crate) const fn packed_body_len(len: usize) -> usize {
+    len / 8 * 5 + (len % 8 * 5).div_ceil(8)
+}
+
+impl VDString<Default31> {
+    /// Packs this code into 5 bits per character, prefixed by its length.
+    ///
+    /// The output is the character count as an unsigned LEB128 varint
+    /// (one byte up to 127 characters), followed by the alphabet indices as
+    /// 5-bit groups, most significant bit first, in `⌈5n/8⌉` bytes with the
+    /// final byte zero-padded. A 16-character code takes 11 bytes instead
+    /// of 16. The length prefix makes packed codes self-delimiting, so many
+    /// can be stored back to back and read with
+    /// [`from_packed_prefix`](Self::from_packed_prefix).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "K7M3PQ".parse().unwrap();
+    /// let packed = code.to_packed_bytes();
+    /// assert_eq!(packed.len(), 1 + 4);
+    /// assert_eq!(VDString::from_packed_bytes(&packed), Ok(code));
+    /// ```
+    pub fn to_packed_bytes(&self) -> Vec<u8> {
+        let mut n = self.len();
+        let mut out = Vec::with_capacity(packed_body_len(n) + 2);
+        while n >= 0x80 {
+            out.push(n as u8 | 0x80);
+            n >>= 7;
+        }
+        out.push(n as u8);
+
+        let (mut acc, mut bits) = (0u16, 0);
+        for ch in self.iter() {
+            acc = acc << 5 | u16::from(ch.index());
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                out.push((acc >> bits) as u8);
+            }
+        }
+        if bits > 0 {
+            out.push((acc << (8 - bits)) as u8);
+        }
+        out
+    }
+
+    /// Unpacks a code produced by [`to_packed_bytes`](Self::to_packed_bytes).
+    ///
+    /// # Errors
+    /// Returns a [`PackedError`] if the input is truncated, holds an index
+    /// outside the alphabet or non-zero padding, or continues past the code.
+    pub fn from_packed_bytes(bytes: &[u8]) -> Result<VDString<Default31>, PackedError> {
+        match Self::from_packed_prefix(bytes)? {
+            (code, []) => Ok(code),
+            (_, rest) => Err(PackedError::TrailingBytes(rest.len())),
+        }
+    }
+
+    /// Unpacks the first of several codes stored back to back, returning it
+    /// with the bytes that follow it.
+    ///
+    /// # Errors
+    /// As [`from_packed_bytes`](Self::from_packed_bytes), except that
+    /// trailing bytes are returned rather than rejected.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let mut buf = Vec::new();
+    /// for code in ["K7M3", "XK29M5TQ7W"] {
+    ///     buf.extend(code.parse::<VDString>().unwrap().to_packed_bytes());
+    /// }
+    ///
+    /// let (first, rest) = VDString::from_packed_prefix(&buf).unwrap();
+    /// let (second, rest) = VDString::from_packed_prefix(rest).unwrap();
+    /// assert_eq!((first.as_str(), second.as_str()), ("K7M3", "XK29M5TQ7W"));
+    /// assert!(rest.is_empty());
+    /// ```
+    pub fn from_packed_prefix(bytes: &[u8]) -> Result<(VDString<Default31>, &[u8]), PackedError> {
+        let mut len = 0usize;
+        let mut shift = 0;
+        let mut rest = bytes;
+        loop {
+            let (&byte, tail) = rest.split_first().ok_or(PackedError::Truncated)?;
+            rest = tail;
+            let part = usize::from(byte & 0x7f);
+            if shift >= usize::BITS || (part << shift) >> shift != part {
+                return Err(PackedError::LengthOverflow);
+            }
+            len |= part << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        let body_len = packed_body_len(len);
+        if rest.len() < body_len {
+            return Err(PackedError::Truncated);
+        }
+        let (body, rest) = rest.split_at(body_len);
+
+        let mut chars = Vec::with_capacity(len);
+        let (mut acc, mut bits) = (0u16, 0);
+        for &byte in body {
+            acc = acc << 8 | u16::from(byte);
+            bits += 8;
+            while bits >= 5 && chars.len() < len {
+                bits -= 5;
+                let index = (acc >> bits) as u8 & 0x1f;
+                if usize::from(index) >= VDS_ALLOWED.len() {
+                    return Err(PackedError::InvalidIndex(index));
+                }
+                chars.push(VDChar::from_index_unchecked(index));
+            }
+            acc &= (1 << bits) - 1;
+        }
+        if acc != 0 {
+            return Err(PackedError::NonZeroPadding);
+        }
+        Ok((VDString::new(chars), rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn u64_roundtrips_edge_values() {
+        for n in [0, 1, 30, 31, 32, 961, u64::MAX / 31, u64::MAX - 1, u64::MAX] {
+            assert_eq!(VDString::<Default31>::encode_u64(n).decode_u64(), Ok(n), "{}", n);
+        }
+        assert_eq!(VDString::<Default31>::encode_u64(u64::MAX).len(), U64_WIDTH);
+    }
+
+    #[test]
+    fn padding_preserves_value() {
+        let s = VDString::<Default31>::encode_u64_padded(42, 8);
+        assert_eq!(s.len(), 8);
+        assert!(s.as_str().starts_with("AAAAAA"));
+        assert_eq!(s.decode_u64(), Ok(42));
+    }
+
+    #[test]
+    fn encoding_preserves_order_at_fixed_width() {
+        let a = VDString::<Default31>::encode_u64_padded(1000, 6);
+        let b = VDString::<Default31>::encode_u64_padded(1001, 6);
+        let ia: alloc::vec::Vec<u8> = a.into_iter().map(|c| c.index()).collect();
+        let ib: alloc::vec::Vec<u8> = b.into_iter().map(|c| c.index()).collect();
+        assert!(ia < ib);
+    }
+
+    #[test]
+    fn u128_roundtrips_at_fixed_width() {
+        for n in [0, 1, u128::from(u64::MAX) + 1, u128::MAX] {
+            let s = VDString::<Default31>::encode_u128_padded(n, U128_WIDTH);
+            assert_eq!(s.len(), U128_WIDTH);
+            assert_eq!(s.decode_u128(), Ok(n));
+        }
+        let too_big = VDString::<Default31>::new(alloc::vec![VDChar::<Default31>::from_index_unchecked(30); U128_WIDTH]);
+        assert_eq!(too_big.decode_u128(), Err(DecodeError::Overflow));
+    }
+
+    #[test]
+    fn bytes_roundtrip_with_leading_zeros() {
+        let cases: [&[u8]; 6] = [&[], &[0], &[0, 0, 1], &[255], &[1, 0, 0], &[0xde, 0xad, 0xbe, 0xef]];
+        for bytes in cases {
+            let code = VDString::<Default31>::encode_bytes(bytes);
+            assert_eq!(code.decode_bytes(), bytes, "{:?} -> {}", bytes, code);
+        }
+    }
+
+    #[test]
+    fn bytes_encode_as_big_integer() {
+        assert_eq!(&*VDString::<Default31>::encode_bytes(&[31]), "BA");
+        assert_eq!(VDString::<Default31>::encode_bytes(&42u64.to_be_bytes()[7..]).decode_u64(), Ok(42));
+        assert!(VDString::<Default31>::encode_bytes(&[0xff; 16]).len() <= 26);
+        assert!(VDString::<Default31>::encode_bytes(&[0xff; 32]).len() <= 52);
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let s: VDString<Default31> = "99999999999999".parse().unwrap();
+        assert_eq!(s.decode_u64(), Err(DecodeError::Overflow));
+        assert_eq!(VDString::<Default31>::new(Vec::new()).decode_u64(), Ok(0));
+    }
+
+    #[test]
+    fn successor_carries_and_counts() {
+        let code: VDString<Default31> = "AB9".parse().unwrap();
+        assert_eq!(code.successor().unwrap(), "ACA");
+        for n in [0, 30, 31, 960, 123_456] {
+            let code = VDString::<Default31>::encode_u64_padded(n, 5);
+            assert_eq!(code.successor().unwrap().decode_u64(), Ok(n + 1), "{}", code);
+            assert!(code.successor().unwrap() > code);
+        }
+    }
+
+    #[test]
+    fn increment_reports_overflow_and_keeps_code() {
+        let mut code: VDString<Default31> = "9Z".parse().unwrap();
+        assert_eq!(code.increment(), Ok(()));
+        assert_eq!(code, "92");
+        for _ in 0..7 {
+            code.increment().unwrap();
+        }
+        assert_eq!(code, "99");
+        assert_eq!(code.increment(), Err(IncrementError::Overflow));
+        assert_eq!(code, "99");
+
+        let mut empty = VDString::<Default31>::new(Vec::new());
+        assert_eq!(empty.increment(), Err(IncrementError::Overflow));
+    }
+
+    #[test]
+    fn transcodes_by_value() {
+        for n in [0, 1, 30, 31, 123_456_789, u64::MAX] {
+            let mut expected = alloc::vec::Vec::new();
+            let mut v = n;
+            while v > 0 || expected.is_empty() {
+                expected.push(Crockford32::CHARS[(v % 32) as usize]);
+                v /= 32;
+            }
+            let expected: String = expected.into_iter().rev().collect();
+            assert_eq!(VDString::<Default31>::encode_u64(n).to_base32_crockford(), expected, "{}", n);
+        }
+        assert_eq!(VDString::<Default31>::encode_u64(57).to_base58(), "z");
+        assert_eq!(VDString::<Default31>::encode_u64(58).to_base58(), "21");
+    }
+
+    #[test]
+    fn transcoding_round_trips_leading_zeros() {
+        for s in ["", "A", "AAA", "AB", "AAK7M3PQ", "999999999999999999999999999999"] {
+            let code: VDString<Default31> = s.parse().unwrap();
+            let base32 = code.to_base32_crockford();
+            let base58 = code.to_base58();
+            assert!(base32.len() <= code.len());
+            assert_eq!(VDString::<Default31>::from_base32_crockford(&base32).unwrap(), code, "{}", base32);
+            assert_eq!(VDString::<Default31>::from_base58(&base58).unwrap(), code, "{}", base58);
+        }
+        assert_eq!(VDString::<Default31>::encode_u64_padded(31, 3).to_base32_crockford(), "0Z");
+        assert_eq!(VDString::<Default31>::encode_u64_padded(31, 3).to_base58(), "1Y");
+    }
+
+    #[test]
+    fn parses_crockford_leniently_and_base58_strictly() {
+        let code = VDString::<Default31>::from_base32_crockford("8EP5R2").unwrap();
+        assert_eq!(VDString::<Default31>::from_base32_crockford("8ep-5r2").unwrap(), code);
+        assert_eq!(VDString::<Default31>::from_base32_crockford("oI").unwrap(), VDString::<Default31>::from_base32_crockford("01").unwrap());
+        assert_eq!(VDString::<Default31>::from_base32_crockford("8EU"), Err(VDStringError::InvalidChar { ch: 'U', offset: 2 }));
+        assert_eq!(VDString::<Default31>::from_base58("S5m0"), Err(VDStringError::InvalidChar { ch: '0', offset: 3 }));
+        assert_eq!(VDString::<Default31>::from_base58("S5mI"), Err(VDStringError::InvalidChar { ch: 'I', offset: 3 }));
+    }
+
+    #[test]
+    fn packed_bytes_roundtrip_every_length() {
+        for len in 0..=40usize {
+            let code = VDString::<Default31>::new((0..len).map(|i| VDChar::<Default31>::from_index_unchecked((i * 7 % 31) as u8)).collect());
+            let packed = code.to_packed_bytes();
+            assert_eq!(packed.len(), 1 + (5 * len).div_ceil(8), "{}", len);
+            assert_eq!(VDString::<Default31>::from_packed_bytes(&packed), Ok(code));
+        }
+        let long = VDString::<Default31>::encode_u64_padded(7, 300);
+        let packed = long.to_packed_bytes();
+        assert_eq!(&packed[..2], &[0xac, 0x02]);
+        assert_eq!(VDString::<Default31>::from_packed_bytes(&packed), Ok(long));
+    }
+
+    #[test]
+    fn packed_bytes_layout_is_msb_first() {
+        // "B9" is indices 1 and 30: 00001 11110, padded with six zero bits.
+        let code: VDString<Default31> = "B9".parse().unwrap();
+        assert_eq!(code.to_packed_bytes(), [2, 0b0000_1111, 0b1000_0000]);
+    }
+
+    #[test]
+    fn packed_bytes_reject_malformed_input() {
+        assert_eq!(VDString::<Default31>::from_packed_bytes(&[]), Err(PackedError::Truncated));
+        assert_eq!(VDString::<Default31>::from_packed_bytes(&[0x80]), Err(PackedError::Truncated));
+        assert_eq!(VDString::<Default31>::from_packed_bytes(&[2, 0x0f]), Err(PackedError::Truncated));
+        assert_eq!(VDString::<Default31>::from_packed_bytes(&[1, 0xf8]), Err(PackedError::InvalidIndex(31)));
+        assert_eq!(VDString::<Default31>::from_packed_bytes(&[1, 0x0c]), Err(PackedError::NonZeroPadding));
+        assert_eq!(VDString::<Default31>::from_packed_bytes(&[0, 0]), Err(PackedError::TrailingBytes(1)));
+        assert_eq!(VDString::<Default31>::from_packed_bytes(&[0xff; 11]), Err(PackedError::LengthOverflow));
+    }
+}
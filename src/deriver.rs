@@ -0,0 +1,184 @@
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::alphabet::Default31;
+#[cfg(feature = "alloc")]
+use crate::siphash::siphash;
+use crate::{VDArrayString, VDChar, VDObfuscator};
+
+/// Derives fixed-length codes from a secret key and a `u64` counter.
+///
+/// Every counter maps to a distinct [`LEN`](Self::LEN)-character code, and
+/// without the key the code for one counter says nothing about the code for
+/// any other. Issuers that share a key can therefore mint codes
+/// independently, without a shared RNG or database sequence, as long as
+/// they never use the same counter: give each issuer its own counter range,
+/// e.g. the issuer ID in the high bits.
+///
+/// Derivation is the keyed permutation of [`VDObfuscator`] at length
+/// [`LEN`](Self::LEN), so a key holder can recover the counter with
+/// [`VDObfuscator::reveal`]. It needs no allocation; [`derive`](Self::derive)
+/// returns a [`VDString`](crate::VDString) when the `alloc` feature is
+/// enabled, and [`derive_array`](Self::derive_array) works everywhere.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::{VDDeriver, VDObfuscator};
+///
+/// let key = [7; 16];
+/// let deriver = VDDeriver::new(key);
+/// let code = deriver.derive(1);
+///
+/// assert_eq!(code.len(), VDDeriver::LEN);
+/// assert_ne!(code, deriver.derive(2));
+/// assert_eq!(&*deriver.derive_array(1), code.as_str());
+///
+/// let ob = VDObfuscator::new(key, VDDeriver::LEN).unwrap();
+/// assert_eq!(ob.reveal(&code), Ok(1));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct VDDeriver {
+    inner: VDObfuscator,
+}
+
+impl VDDeriver {
+    /// Length of every derived code, the fewest characters that give each
+    /// `u64` counter its own code (`31^13 > 2^64`).
+    pub const LEN: usize = 13;
+
+    /// Creates a deriver for `key`.
+    pub const fn new(key: [u8; 16]) -> Self {
+        Self { inner: VDObfuscator::new_unchecked(key, Self::LEN) }
+    }
+
+    /// Returns the code for `counter`, without allocating.
+    pub fn derive_array(&self, counter: u64) -> VDArrayString<{ Self::LEN }> {
+        let mut x = self.inner.permute(u128::from(counter));
+        let mut chars = [VDChar::from_index_unchecked(0); Self::LEN];
+        for ch in chars.iter_mut().rev() {
+            *ch = VDChar::from_index_unchecked((x % 31) as u8);
+            x /= 31;
+        }
+        // `chars` has exactly `LEN` characters, so it always fits.
+        VDArrayString::from_vdchars(&chars).unwrap_or_default()
+    }
+
+    /// Returns the code for `counter`.
+    ///
+    /// This method is only available when the `alloc` feature is enabled.
+    #[cfg(feature = "alloc")]
+    pub fn derive(&self, counter: u64) -> crate::VDString<Default31> {
+        crate::VDString::new(self.derive_array(counter).as_vdchars().to_vec())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl crate::VDString<Default31> {
+    /// Derives a `len`-character code from arbitrary bytes, e.g. a
+    /// human-readable fingerprint of a document, public key, or device
+    /// serial.
+    ///
+    /// The same input always gives the same code, on every platform and in
+    /// every version of this crate. The algorithm is fixed:
+    ///
+    /// 1. Block `i` (from 0) is SipHash-2-4 of `data` under a key of `i` as
+    ///    8 little-endian bytes followed by 8 zero bytes.
+    /// 2. Each block yields 8 characters: its 8 lowest base-31 digits,
+    ///    least significant first, as alphabet indices. The remaining high
+    ///    bits are discarded, which keeps the bias below one part in 10^7.
+    /// 3. The code is the first `len` characters of blocks 0, 1, 2, ….
+    ///
+    /// A shorter code is therefore a prefix of a longer one for the same
+    /// input. The key is public, so this is not a cryptographic hash: to
+    /// resist deliberately crafted collisions, pass a cryptographic digest
+    /// (e.g. SHA-256) of the input instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code = VDString::derive_from(b"device-0042", 10);
+    /// assert_eq!(code.len(), 10);
+    /// assert_eq!(code, "6NEX79UQPU");
+    /// assert!(VDString::derive_from(b"device-0042", 16).starts_with(&code));
+    /// assert_ne!(code, VDString::derive_from(b"device-0043", 10));
+    /// ```
+    pub fn derive_from(data: &[u8], len: usize) -> crate::VDString<Default31> {
+        let mut chars = alloc::vec::Vec::with_capacity(len);
+        for block in 0u64.. {
+            if chars.len() == len {
+                break;
+            }
+            let mut key = [0; 16];
+            key[..8].copy_from_slice(&block.to_le_bytes());
+            let mut x = siphash(&key, data);
+            for _ in 0..8.min(len - chars.len()) {
+                chars.push(VDChar::from_index_unchecked((x % 31) as u8));
+                x /= 31;
+            }
+        }
+        crate::VDString::new(chars)
+    }
+}
+
+impl fmt::Debug for VDDeriver {
+    /// Omits the key.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VDDeriver").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_obfuscator_at_fixed_length() {
+        let deriver = VDDeriver::new([3; 16]);
+        for n in [0, 1, 31, u64::MAX / 2, u64::MAX] {
+            let code = deriver.derive_array(n);
+            assert_eq!(code.len(), VDDeriver::LEN);
+            #[cfg(feature = "alloc")]
+            {
+                let ob = VDObfuscator::new([3; 16], VDDeriver::LEN).unwrap();
+                assert_eq!(ob.obfuscate(n).unwrap(), code.as_str());
+                assert_eq!(deriver.derive(n), code.as_str());
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn derive_from_follows_documented_algorithm() {
+        let data = b"public key bytes";
+        let mut expected = alloc::vec::Vec::new();
+        for block in 0u64..3 {
+            let mut key = [0; 16];
+            key[..8].copy_from_slice(&block.to_le_bytes());
+            let mut x = siphash(&key, data);
+            for _ in 0..8 {
+                expected.push(VDChar::from_index_unchecked((x % 31) as u8));
+                x /= 31;
+            }
+        }
+        assert_eq!(crate::VDString::derive_from(data, 20).as_vdchars(), &expected[..20]);
+        assert_eq!(crate::VDString::derive_from(data, 0).len(), 0);
+        assert_eq!(crate::VDString::derive_from(b"", 4), "JEMG");
+    }
+
+    #[test]
+    fn counters_and_keys_give_distinct_codes() {
+        let a = VDDeriver::new([1; 16]);
+        let b = VDDeriver::new([2; 16]);
+        let codes: [_; 64] = core::array::from_fn(|n| a.derive_array(n as u64));
+        for (i, code) in codes.iter().enumerate() {
+            assert!(codes[i + 1..].iter().all(|other| other != code));
+        }
+        assert_ne!(a.derive_array(5), b.derive_array(5));
+    }
+}
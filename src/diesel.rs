@@ -0,0 +1,106 @@
+//! [`diesel`] support for [`VDString`].
+//!
+//! This module is only available when the `diesel` feature is enabled.
+//!
+//! A [`VDString`] maps to the [`Text`] SQL type on any backend that stores
+//! `String`s, and can be used in queries and `Queryable` structs like one.
+//! Loading validates the text: a column holding characters outside the
+//! alphabet is a deserialization error, not a panic or an invalid code.
+//!
+//! # Examples
+//! ```
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Text;
+//! use vds::VDString;
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! let code: VDString = "K7M3PQ".parse().unwrap();
+//!
+//! let fetched: VDString = diesel::select(code.clone().into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(fetched, code);
+//! ```
+
+extern crate alloc;
+use alloc::string::String;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+
+use crate::alphabet::Alphabet;
+use crate::VDString;
+
+/// Derives `AsExpression<Text>` and `Queryable` for [`VDString`], which
+/// is defined outside of a `diesel` derive.
+#[derive(AsExpression, FromSqlRow)]
+#[diesel(foreign_derive)]
+#[diesel(sql_type = Text)]
+#[allow(dead_code)]
+struct VDStringProxy<A: Alphabet>(VDString<A>);
+
+impl<A: Alphabet, DB: Backend> ToSql<Text, DB> for VDString<A>
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.as_str().to_sql(out)
+    }
+}
+
+impl<A: Alphabet, DB: Backend> FromSql<Text, DB> for VDString<A>
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let text = String::from_sql(bytes)?;
+        Ok(text.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::{Default31, Digits10};
+    use crate::VDStringError;
+    use diesel::prelude::*;
+    use diesel::result::{DeserializeFieldError, Error};
+
+    diesel::table! {
+        codes (id) {
+            id -> Integer,
+            code -> Text,
+        }
+    }
+
+    #[derive(Queryable, Insertable)]
+    #[diesel(table_name = codes)]
+    struct Row {
+        id: i32,
+        code: VDString<Default31>,
+    }
+
+    #[test]
+    fn round_trip_and_invalid_text() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        diesel::sql_query("CREATE TABLE codes (id INTEGER PRIMARY KEY, code TEXT NOT NULL)").execute(&mut conn).unwrap();
+
+        let code: VDString<Default31> = "K7M3PQ".parse().unwrap();
+        diesel::insert_into(codes::table).values(Row { id: 1, code: code.clone() }).execute(&mut conn).unwrap();
+        let stored: String = codes::table.select(codes::code).first(&mut conn).unwrap();
+        assert_eq!(stored, "K7M3PQ");
+
+        let row: Row = codes::table.filter(codes::code.eq(&code)).first(&mut conn).unwrap();
+        assert_eq!((row.id, row.code), (1, code));
+
+        let err = codes::table.select(codes::code).first::<VDString<Digits10>>(&mut conn).unwrap_err();
+        match err {
+            Error::DeserializationError(source) => {
+                let field = source.downcast_ref::<DeserializeFieldError>().unwrap();
+                assert!(field.error.downcast_ref::<VDStringError>().is_some());
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,727 @@
+//! Display adapters for [`VDString`](crate::VDString) and [`VDStr`](crate::VDStr).
+//!
+//! Adapters borrow the string and render on demand, so none of them
+//! allocate, and they are available without the `alloc` feature, e.g. via
+//! [`VDArrayString::as_vdstr`](crate::VDArrayString::as_vdstr).
+
+use core::fmt::{self, Write};
+
+use crate::alphabet::{Alphabet, DefaultAlphabet};
+use crate::{VDChar, VDStr};
+
+/// Default continuation marker used by [`FitDisplay`].
+///
+/// Plain ASCII so it renders on character LCDs without custom glyphs.
+pub const CONTINUATION: char = '>';
+
+/// Default mask character used by [`MaskedDisplay`].
+pub const MASK: char = '•';
+
+/// Default marker used by [`DiffDisplay`] under differing characters.
+pub const DIFF_MARKER: char = '^';
+
+/// Writes `chars` in groups of `group_size` (`0` for no grouping) joined by
+/// `separator`.
+fn write_grouped(
+    f: &mut fmt::Formatter<'_>,
+    chars: impl Iterator<Item = char>,
+    group_size: usize,
+    separator: &str,
+) -> fmt::Result {
+    let group = if group_size == 0 { usize::MAX } else { group_size };
+    for (i, c) in chars.enumerate() {
+        if i > 0 && i % group == 0 {
+            f.write_str(separator)?;
+        }
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns a [`FitDisplay`] showing as many whole groups as fit within
+    /// `max_chars` columns.
+    ///
+    /// Groups of `group_size` characters are separated by a space. If any
+    /// characters don't fit, a [`CONTINUATION`] marker is appended (and
+    /// counted against `max_chars`). Call [`FitDisplay::remainder`] to get
+    /// the next page.
+    ///
+    /// A `group_size` of `0` disables grouping. If not even one group fits,
+    /// the first group is split so that each page still makes progress.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AB29XY7KM3PQ".parse().unwrap();
+    ///
+    /// let page = code.fit_display(10, 4);
+    /// assert_eq!(page.to_string(), "AB29 XY7K>");
+    ///
+    /// let next = page.remainder().unwrap();
+    /// assert_eq!(next.to_string(), "M3PQ");
+    /// assert!(next.remainder().is_none());
+    /// # }
+    /// ```
+    pub fn fit_display(&self, max_chars: usize, group_size: usize) -> FitDisplay<'_, A> {
+        FitDisplay::new(self.as_vdchars(), max_chars, group_size, CONTINUATION)
+    }
+
+    /// Returns a [`GroupedDisplay`] that renders the characters in groups of
+    /// `group_size`, joined by `separator`.
+    ///
+    /// A `group_size` of `0` disables grouping.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AB29XY7KM3PQ".parse().unwrap();
+    /// assert_eq!(code.display_grouped(4, "-").to_string(), "AB29-XY7K-M3PQ");
+    /// assert_eq!(format!("{}", code.display_grouped(5, " ")), "AB29X Y7KM3 PQ");
+    /// # }
+    /// ```
+    pub fn display_grouped<'a>(&'a self, group_size: usize, separator: &'a str) -> GroupedDisplay<'a, A> {
+        GroupedDisplay { chars: self.as_vdchars(), group_size, separator }
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns a [`MaskedDisplay`] that shows only the last
+    /// `visible_suffix_len` characters, masking the rest with [`MASK`].
+    ///
+    /// Suitable for logs and support screens where the full code must not
+    /// appear.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AB29XY7KM".parse().unwrap();
+    /// assert_eq!(code.display_masked(3).to_string(), "••••••7KM");
+    /// assert_eq!(code.display_masked(4).with_mask('*').grouped(3, "-").to_string(), "***-**Y-7KM");
+    /// # }
+    /// ```
+    pub fn display_masked(&self, visible_suffix_len: usize) -> MaskedDisplay<'_, A> {
+        MaskedDisplay {
+            chars: self.as_vdchars(),
+            visible: visible_suffix_len,
+            mask: MASK,
+            group_size: 0,
+            separator: "",
+        }
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns a [`SpellOut`] that renders each character as its NATO
+    /// phonetic word, separated by spaces.
+    ///
+    /// Intended for reading codes aloud, e.g. in call-center scripts or IVR
+    /// prompts. Characters without a word (see [`VDChar::phonetic`]) are
+    /// written as themselves. Use [`SpellOut::words`] to get the words one
+    /// at a time, e.g. to queue audio clips.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "A7K".parse().unwrap();
+    /// assert_eq!(code.spell_out().to_string(), "Alfa Seven Kilo");
+    /// assert_eq!(code.spell_out().with_separator(", ").to_string(), "Alfa, Seven, Kilo");
+    /// assert!(code.spell_out().words().eq([Some("Alfa"), Some("Seven"), Some("Kilo")]));
+    /// # }
+    /// ```
+    pub fn spell_out(&self) -> SpellOut<'_, A> {
+        SpellOut { chars: self.as_vdchars(), separator: " " }
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns an [`SsmlDisplay`] that renders the code as SSML for
+    /// text-to-speech, one character at a time with a pause after each.
+    ///
+    /// Every character is wrapped in `<say-as interpret-as="characters">`
+    /// so engines do not read runs like `2345` as numbers, and the whole
+    /// document is wrapped in `<speak>`. Use [`SsmlDisplay::phonetic`] to
+    /// read the NATO words instead of the bare characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "K7".parse().unwrap();
+    /// assert_eq!(
+    ///     code.display_ssml().to_string(),
+    ///     r#"<speak><say-as interpret-as="characters">K</say-as><break time="250ms"/><say-as interpret-as="characters">7</say-as></speak>"#
+    /// );
+    /// assert_eq!(
+    ///     code.display_ssml().phonetic().with_pause_ms(400).fragment().to_string(),
+    ///     r#"Kilo<break time="400ms"/>Seven"#
+    /// );
+    /// # }
+    /// ```
+    pub fn display_ssml(&self) -> SsmlDisplay<'_, A> {
+        SsmlDisplay { chars: self.as_vdchars(), pause_ms: 250, phonetic: false, fragment: false }
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns a [`LowercaseDisplay`] that renders the characters in
+    /// lowercase, e.g. `ab29xy`.
+    ///
+    /// For design systems that forbid all-caps text. Only the rendering
+    /// changes: the code itself stays canonical uppercase, and parsing the
+    /// output back needs [`ParseOptions::fold_case`](crate::ParseOptions).
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AB29XY7KM".parse().unwrap();
+    /// assert_eq!(code.display_lowercase().to_string(), "ab29xy7km");
+    /// assert_eq!(code.display_lowercase().grouped(3, "-").to_string(), "ab2-9xy-7km");
+    /// # }
+    /// ```
+    pub fn display_lowercase(&self) -> LowercaseDisplay<'_, A> {
+        LowercaseDisplay { chars: self.as_vdchars(), group_size: 0, separator: "" }
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns a [`PrefixedDisplay`] that renders the characters after the
+    /// literal `prefix`, e.g. `VD-AB29XY`.
+    ///
+    /// The inverse of [`VDString::parse_with_prefix`](crate::VDString::parse_with_prefix).
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AB29XY".parse().unwrap();
+    /// assert_eq!(code.display_with_prefix("VD-").to_string(), "VD-AB29XY");
+    /// assert_eq!(code.display_with_prefix("VD-").grouped(3, " ").to_string(), "VD-AB2 9XY");
+    /// # }
+    /// ```
+    pub fn display_with_prefix<'a>(&'a self, prefix: &'a str) -> PrefixedDisplay<'a, A> {
+        PrefixedDisplay { prefix, chars: self.as_vdchars(), group_size: 0, separator: "" }
+    }
+}
+
+/// A [`VDStr`] rendered after a literal prefix.
+///
+/// Returned by [`VDStr::display_with_prefix`].
+#[derive(Debug, Clone, Copy)]
+pub struct PrefixedDisplay<'a, A: Alphabet = DefaultAlphabet> {
+    prefix: &'a str,
+    chars: &'a [VDChar<A>],
+    group_size: usize,
+    separator: &'a str,
+}
+
+impl<'a, A: Alphabet> PrefixedDisplay<'a, A> {
+    /// Renders the code (not the prefix) in groups of `group_size` joined
+    /// by `separator`, as with [`VDStr::display_grouped`].
+    pub fn grouped(mut self, group_size: usize, separator: &'a str) -> Self {
+        self.group_size = group_size;
+        self.separator = separator;
+        self
+    }
+}
+
+impl<A: Alphabet> fmt::Display for PrefixedDisplay<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.prefix)?;
+        write_grouped(f, self.chars.iter().map(|c| c.as_char()), self.group_size, self.separator)
+    }
+}
+
+/// A [`VDStr`] rendered in lowercase.
+///
+/// Returned by [`VDStr::display_lowercase`].
+#[derive(Debug, Clone, Copy)]
+pub struct LowercaseDisplay<'a, A: Alphabet = DefaultAlphabet> {
+    chars: &'a [VDChar<A>],
+    group_size: usize,
+    separator: &'a str,
+}
+
+impl<'a, A: Alphabet> LowercaseDisplay<'a, A> {
+    /// Renders in groups of `group_size` joined by `separator`, as with
+    /// [`VDStr::display_grouped`].
+    pub fn grouped(mut self, group_size: usize, separator: &'a str) -> Self {
+        self.group_size = group_size;
+        self.separator = separator;
+        self
+    }
+}
+
+impl<A: Alphabet> fmt::Display for LowercaseDisplay<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let group = if self.group_size == 0 { usize::MAX } else { self.group_size };
+        for (i, ch) in self.chars.iter().enumerate() {
+            if i > 0 && i % group == 0 {
+                f.write_str(self.separator)?;
+            }
+            // Non-ASCII alphabets may lowercase to more than one char.
+            for c in ch.as_char().to_lowercase() {
+                f.write_char(c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns a [`DiffDisplay`] that shows `self` above `other`, with a
+    /// third line marking with [`DIFF_MARKER`] each position where they
+    /// disagree (see [`VDStr::diff`]).
+    ///
+    /// If one code is longer, its extra characters are marked too. The
+    /// marker line has no trailing spaces, and is left out when the codes
+    /// are equal.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let issued: VDString = "AB29XY".parse().unwrap();
+    /// let read: VDString = "AB39XK".parse().unwrap();
+    /// assert_eq!(issued.display_diff(&read).to_string(), "AB29XY\nAB39XK\n  ^  ^");
+    /// # }
+    /// ```
+    pub fn display_diff<'a>(&'a self, other: &'a Self) -> DiffDisplay<'a, A> {
+        DiffDisplay { left: self.as_vdchars(), right: other.as_vdchars(), marker: DIFF_MARKER }
+    }
+}
+
+/// Two [`VDStr`]s one above the other, with their differences marked below.
+///
+/// Returned by [`VDStr::display_diff`].
+#[derive(Debug, Clone, Copy)]
+pub struct DiffDisplay<'a, A: Alphabet = DefaultAlphabet> {
+    left: &'a [VDChar<A>],
+    right: &'a [VDChar<A>],
+    marker: char,
+}
+
+impl<A: Alphabet> DiffDisplay<'_, A> {
+    /// Replaces the marker character (default [`DIFF_MARKER`]).
+    pub fn with_marker(mut self, marker: char) -> Self {
+        self.marker = marker;
+        self
+    }
+}
+
+impl<A: Alphabet> fmt::Display for DiffDisplay<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, line) in [self.left, self.right].into_iter().enumerate() {
+            if i > 0 {
+                f.write_char('\n')?;
+            }
+            for ch in line {
+                f.write_char(ch.as_char())?;
+            }
+        }
+        let len = self.left.len().max(self.right.len());
+        let differs = |i: usize| self.left.get(i) != self.right.get(i);
+        let Some(last) = (0..len).rposition(differs) else {
+            return Ok(());
+        };
+        f.write_char('\n')?;
+        for i in 0..=last {
+            f.write_char(if differs(i) { self.marker } else { ' ' })?;
+        }
+        Ok(())
+    }
+}
+
+/// A [`VDStr`] spelled out as NATO phonetic words.
+///
+/// Returned by [`VDStr::spell_out`].
+#[derive(Debug, Clone, Copy)]
+pub struct SpellOut<'a, A: Alphabet = DefaultAlphabet> {
+    chars: &'a [VDChar<A>],
+    separator: &'a str,
+}
+
+impl<'a, A: Alphabet> SpellOut<'a, A> {
+    /// Replaces the separator written between words (default `" "`).
+    pub fn with_separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Returns an iterator over the word for each character, as given by
+    /// [`VDChar::phonetic`].
+    pub fn words(&self) -> impl Iterator<Item = Option<&'static str>> + 'a {
+        self.chars.iter().map(|ch| ch.phonetic())
+    }
+}
+
+impl<A: Alphabet> fmt::Display for SpellOut<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, ch) in self.chars.iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.separator)?;
+            }
+            match ch.phonetic() {
+                Some(word) => f.write_str(word)?,
+                None => f.write_char(ch.as_char())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [`VDStr`] rendered as SSML for voice output.
+///
+/// Returned by [`VDStr::display_ssml`].
+#[derive(Debug, Clone, Copy)]
+pub struct SsmlDisplay<'a, A: Alphabet = DefaultAlphabet> {
+    chars: &'a [VDChar<A>],
+    pause_ms: u32,
+    phonetic: bool,
+    fragment: bool,
+}
+
+impl<A: Alphabet> SsmlDisplay<'_, A> {
+    /// Sets the pause between characters (default 250 ms); `0` for none.
+    pub fn with_pause_ms(mut self, pause_ms: u32) -> Self {
+        self.pause_ms = pause_ms;
+        self
+    }
+
+    /// Reads each character as its NATO word, as given by
+    /// [`VDChar::phonetic`]. Characters without a word are still read as
+    /// characters.
+    pub fn phonetic(mut self) -> Self {
+        self.phonetic = true;
+        self
+    }
+
+    /// Leaves out the `<speak>` root, for embedding in a larger document.
+    pub fn fragment(mut self) -> Self {
+        self.fragment = true;
+        self
+    }
+}
+
+impl<A: Alphabet> fmt::Display for SsmlDisplay<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.fragment {
+            f.write_str("<speak>")?;
+        }
+        for (i, ch) in self.chars.iter().enumerate() {
+            if i > 0 && self.pause_ms > 0 {
+                write!(f, "<break time=\"{}ms\"/>", self.pause_ms)?;
+            }
+            match ch.phonetic().filter(|_| self.phonetic) {
+                Some(word) => f.write_str(word)?,
+                None => {
+                    f.write_str("<say-as interpret-as=\"characters\">")?;
+                    // Custom alphabets may contain XML metacharacters.
+                    match ch.as_char() {
+                        '&' => f.write_str("&amp;")?,
+                        '<' => f.write_str("&lt;")?,
+                        '>' => f.write_str("&gt;")?,
+                        c => f.write_char(c)?,
+                    }
+                    f.write_str("</say-as>")?;
+                }
+            }
+        }
+        if !self.fragment {
+            f.write_str("</speak>")?;
+        }
+        Ok(())
+    }
+}
+
+/// A [`VDStr`] rendered in fixed-size groups with a separator.
+///
+/// Returned by [`VDStr::display_grouped`].
+#[derive(Debug, Clone, Copy)]
+pub struct GroupedDisplay<'a, A: Alphabet = DefaultAlphabet> {
+    chars: &'a [VDChar<A>],
+    group_size: usize,
+    separator: &'a str,
+}
+
+impl<A: Alphabet> fmt::Display for GroupedDisplay<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_grouped(f, self.chars.iter().map(|c| c.as_char()), self.group_size, self.separator)
+    }
+}
+
+/// A [`VDStr`] with all but its last few characters replaced by a mask.
+///
+/// Returned by [`VDStr::display_masked`]. The output has the same length
+/// as the code, so it can still be grouped like the real one.
+#[derive(Debug, Clone, Copy)]
+pub struct MaskedDisplay<'a, A: Alphabet = DefaultAlphabet> {
+    chars: &'a [VDChar<A>],
+    visible: usize,
+    mask: char,
+    group_size: usize,
+    separator: &'a str,
+}
+
+impl<'a, A: Alphabet> MaskedDisplay<'a, A> {
+    /// Replaces the mask character (default [`MASK`]).
+    pub fn with_mask(mut self, mask: char) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Renders in groups of `group_size` joined by `separator`, as with
+    /// [`VDStr::display_grouped`].
+    pub fn grouped(mut self, group_size: usize, separator: &'a str) -> Self {
+        self.group_size = group_size;
+        self.separator = separator;
+        self
+    }
+}
+
+impl<A: Alphabet> fmt::Display for MaskedDisplay<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hidden = self.chars.len().saturating_sub(self.visible);
+        let chars = self
+            .chars
+            .iter()
+            .enumerate()
+            .map(|(i, c)| if i < hidden { self.mask } else { c.as_char() });
+        write_grouped(f, chars, self.group_size, self.separator)
+    }
+}
+
+/// One page of a [`VDStr`] sized for a fixed-width display.
+///
+/// Returned by [`VDStr::fit_display`].
+#[derive(Debug, Clone, Copy)]
+pub struct FitDisplay<'a, A: Alphabet = DefaultAlphabet> {
+    chars: &'a [VDChar<A>],
+    shown: usize,
+    max_chars: usize,
+    group_size: usize,
+    marker: char,
+}
+
+impl<'a, A: Alphabet> FitDisplay<'a, A> {
+    fn new(chars: &'a [VDChar<A>], max_chars: usize, group_size: usize, marker: char) -> Self {
+        let group = if group_size == 0 { usize::MAX } else { group_size };
+        let mut used = 0;
+        let mut shown = 0;
+
+        while shown < chars.len() {
+            let len = group.min(chars.len() - shown);
+            let sep = usize::from(shown > 0);
+            let more = usize::from(shown + len < chars.len());
+            if used + sep + len + more > max_chars {
+                break;
+            }
+            used += sep + len;
+            shown += len;
+        }
+
+        if shown == 0 && !chars.is_empty() {
+            // Not even one group fits: split it, leaving room for the marker.
+            shown = group.min(max_chars.saturating_sub(1)).min(chars.len());
+        }
+
+        Self { chars, shown, max_chars, group_size, marker }
+    }
+
+    /// Replaces the continuation marker (default [`CONTINUATION`]).
+    pub fn with_marker(mut self, marker: char) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Returns the number of code characters shown on this page.
+    pub fn shown(&self) -> usize {
+        self.shown
+    }
+
+    /// Returns `true` if characters remain after this page.
+    pub fn has_more(&self) -> bool {
+        self.shown < self.chars.len()
+    }
+
+    /// Returns the next page with the same width and grouping, or `None` if
+    /// this page shows the rest of the code.
+    ///
+    /// Also returns `None` if this page cannot show any characters (e.g.
+    /// `max_chars` is `0`), so paging loops always terminate.
+    pub fn remainder(&self) -> Option<Self> {
+        if !self.has_more() || self.shown == 0 {
+            return None;
+        }
+        let rest = &self.chars[self.shown..];
+        Some(Self::new(rest, self.max_chars, self.group_size, self.marker))
+    }
+}
+
+impl<A: Alphabet> fmt::Display for FitDisplay<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let group = if self.group_size == 0 { usize::MAX } else { self.group_size };
+        for (i, ch) in self.chars[..self.shown].iter().enumerate() {
+            if i > 0 && i % group == 0 {
+                f.write_char(' ')?;
+            }
+            f.write_char(ch.as_char())?;
+        }
+        if self.has_more() && self.max_chars > 0 {
+            f.write_char(self.marker)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use crate::alphabet::Default31;
+    use crate::VDString;
+    use alloc::{string::{String, ToString}, vec::Vec};
+
+    fn pages(code: &VDString<Default31>, max: usize, group: usize) -> Vec<String> {
+        let mut out = Vec::new();
+        let mut page = Some(code.fit_display(max, group));
+        while let Some(p) = page {
+            out.push(p.to_string());
+            page = p.remainder();
+        }
+        out
+    }
+
+    #[test]
+    fn whole_code_fits_without_marker() {
+        let code: VDString<Default31> = "AB29XY7KM3PQ".parse().unwrap();
+        assert_eq!(pages(&code, 16, 4), ["AB29 XY7K M3PQ"]);
+    }
+
+    #[test]
+    fn pages_never_exceed_width() {
+        let code: VDString<Default31> = "AB29XY7KM3PQRSTU".parse().unwrap();
+        for max in 1..20 {
+            for group in 0..6 {
+                let all = pages(&code, max, group);
+                for page in &all {
+                    assert!(page.chars().count() <= max, "{:?} > {}", page, max);
+                }
+                if max >= 2 {
+                    let joined: String = all
+                        .iter()
+                        .flat_map(|p| p.chars())
+                        .filter(|&c| c != ' ' && c != CONTINUATION)
+                        .collect();
+                    assert_eq!(joined, code.as_str());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn oversized_group_is_split() {
+        let code: VDString<Default31> = "AB29XY".parse().unwrap();
+        assert_eq!(pages(&code, 4, 6), ["AB2>", "9XY"]);
+    }
+
+    #[test]
+    fn zero_width_terminates() {
+        let code: VDString<Default31> = "AB".parse().unwrap();
+        let page = code.fit_display(0, 2);
+        assert_eq!(page.to_string(), "");
+        assert!(page.remainder().is_none());
+    }
+
+    #[test]
+    fn grouped_display_separates_groups() {
+        let code: VDString<Default31> = "AB29XY7K".parse().unwrap();
+        assert_eq!(code.display_grouped(4, "-").to_string(), "AB29-XY7K");
+        assert_eq!(code.display_grouped(3, " / ").to_string(), "AB2 / 9XY / 7K");
+        assert_eq!(code.display_grouped(0, "-").to_string(), "AB29XY7K");
+        assert_eq!(code[..2].display_grouped(1, ".").to_string(), "A.B");
+        assert_eq!(code[..0].display_grouped(2, "-").to_string(), "");
+    }
+
+    #[test]
+    fn masked_display_hides_prefix() {
+        let code: VDString<Default31> = "AB29XY7K".parse().unwrap();
+        assert_eq!(code.display_masked(3).to_string(), "•••••Y7K");
+        assert_eq!(code.display_masked(0).to_string(), "••••••••");
+        assert_eq!(code.display_masked(20).to_string(), "AB29XY7K");
+        assert_eq!(code.display_masked(2).grouped(4, " ").to_string(), "•••• ••7K");
+    }
+
+    #[test]
+    fn spell_out_uses_phonetic_words() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        struct Marks;
+        impl Alphabet for Marks {
+            const CHARS: &'static [char] = &['A', '#'];
+        }
+
+        let code: VDString<Default31> = "X9MZ".parse().unwrap();
+        assert_eq!(code.spell_out().to_string(), "X-ray Nine Mike Zulu");
+        assert_eq!(code.spell_out().with_separator("-").to_string(), "X-ray-Nine-Mike-Zulu");
+        assert_eq!(code[..0].spell_out().to_string(), "");
+
+        let custom: VDString<Marks> = "A#A".parse().unwrap();
+        assert_eq!(custom.spell_out().to_string(), "Alfa # Alfa");
+        assert!(custom.spell_out().words().eq([Some("Alfa"), None, Some("Alfa")]));
+    }
+
+    #[test]
+    fn ssml_escapes_and_falls_back_from_phonetic() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        struct Marks;
+        impl Alphabet for Marks {
+            const CHARS: &'static [char] = &['A', '&', '<'];
+        }
+
+        let custom: VDString<Marks> = "A&<".parse().unwrap();
+        assert_eq!(
+            custom.display_ssml().phonetic().with_pause_ms(0).fragment().to_string(),
+            r#"Alfa<say-as interpret-as="characters">&amp;</say-as><say-as interpret-as="characters">&lt;</say-as>"#
+        );
+        let code: VDString<Default31> = "".parse().unwrap();
+        assert_eq!(code.display_ssml().to_string(), "<speak></speak>");
+    }
+
+    #[test]
+    fn custom_marker() {
+        let code: VDString<Default31> = "AB29XY".parse().unwrap();
+        assert_eq!(code.fit_display(6, 2).with_marker('~').to_string(), "AB 29~");
+    }
+
+    #[test]
+    fn lowercase_display_parses_back_with_fold_case() {
+        let code: VDString<Default31> = "AB29XY7KM3PQ".parse().unwrap();
+        let shown = code.display_lowercase().grouped(4, " ").to_string();
+        assert_eq!(shown, "ab29 xy7k m3pq");
+        let options = crate::ParseOptions { ignore_separators: true, fold_case: true, ..Default::default() };
+        assert_eq!(VDString::<Default31>::parse_with(&shown, options), Ok(code));
+    }
+
+    #[test]
+    fn diff_display_marks_extra_characters() {
+        let a: VDString<Default31> = "AB29".parse().unwrap();
+        let b: VDString<Default31> = "AB2".parse().unwrap();
+        assert_eq!(a.display_diff(&b).to_string(), "AB29\nAB2\n   ^");
+        assert_eq!(b.display_diff(&a).with_marker('*').to_string(), "AB2\nAB29\n   *");
+        assert_eq!(a.display_diff(&a).to_string(), "AB29\nAB29");
+    }
+}
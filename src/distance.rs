@@ -0,0 +1,539 @@
+//! Distances between codes, for duplicate screening and typo correction.
+//!
+//! [`VDStr::hamming_distance`] compares [`VDChar`] indices,
+//! so it works the same for every [`Alphabet`].
+//! [`VDStr::confusable_distance`] is an edit distance in which swapping two
+//! easily confused characters costs less than an unrelated substitution, as
+//! configured by an [`ErrorModel`].
+//!
+//! Which characters are easily confused depends on how a code was entered,
+//! so there is a model per intake channel: [`VisualModel`] for codes read
+//! off a screen or label, [`KeyboardModel`] for codes typed on a QWERTY
+//! keyboard, and [`PhoneticModel`] for codes read out over the phone.
+//! [`ConfusionMatrix`] builds custom models.
+//!
+//! # Examples
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use vds::distance::ConfusionMatrix;
+//! use vds::VDString;
+//!
+//! let issued: VDString = "AB29".parse().unwrap();
+//! let typed: VDString = "A829".parse().unwrap();
+//! let other: VDString = "AK29".parse().unwrap();
+//!
+//! let m = ConfusionMatrix::visual();
+//! assert!(issued.confusable_distance(&typed, &m) < issued.confusable_distance(&other, &m));
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::alphabet::Alphabet;
+use crate::{VDChar, VDStr};
+
+/// Built-in confusions between characters of
+/// [`VDS_ALLOWED`](crate::VDS_ALLOWED), with their substitution cost.
+///
+/// Costs are relative to `1.0` for an unrelated substitution: the lower the
+/// cost, the more easily the two characters are mistaken for each other,
+/// visually (`B`/`8`) or when read aloud (`M`/`N`).
+pub const VISUAL_CONFUSIONS: &[(char, char, f64)] = &[
+    ('B', '8', 0.3), ('S', '5', 0.3), ('Z', '2', 0.3), ('G', '6', 0.4),
+    ('M', 'N', 0.5), ('U', 'V', 0.5), ('E', 'F', 0.6), ('P', 'R', 0.6),
+    ('C', 'G', 0.6), ('V', 'Y', 0.6), ('T', '7', 0.6), ('A', '4', 0.7),
+    ('B', 'P', 0.7), ('D', 'T', 0.7), ('K', 'X', 0.7), ('3', '8', 0.7),
+];
+
+/// Adjacent pairs of characters of [`VDS_ALLOWED`](crate::VDS_ALLOWED)
+/// that read as a single other character when handwritten or set in a
+/// tight font, with the character they are taken for.
+///
+/// Used by [`VDGenerator::no_confusable_digraphs`](crate::VDGenerator::no_confusable_digraphs).
+pub const CONFUSABLE_DIGRAPHS: &[(&str, char)] = &[
+    ("VV", 'W'), ("UU", 'W'), ("RN", 'M'), ("NN", 'M'), ("CJ", 'G'),
+];
+
+/// Letters and digits that sound alike when spelled aloud, with the
+/// substitution cost between any two members of a group.
+///
+/// Used by [`PhoneticModel`]. Costs are on the same scale as
+/// [`VISUAL_CONFUSIONS`].
+pub const PHONETIC_GROUPS: &[(&str, f64)] = &[
+    // The "E-set", the classic source of errors in spelled-out letters.
+    ("BCDEGPTVZ3", 0.5),
+    ("MN", 0.4),
+    ("FSX", 0.6),
+    ("AJK8", 0.6),
+    ("QUW2", 0.7),
+    ("59Y", 0.7),
+];
+
+/// Costs of the edits that turn one code into another.
+///
+/// Consumed by [`VDStr::confusable_distance`] and the [`fuzzy`](crate::fuzzy)
+/// lookups. Costs are relative to `1.0` for an unrelated substitution, and
+/// the substitution cost should be symmetric and `0.0` for equal
+/// characters.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::distance::ErrorModel;
+/// use vds::VDString;
+///
+/// /// Treats every pair of digits as half an error.
+/// struct DigitSlips;
+///
+/// impl ErrorModel for DigitSlips {
+///     fn substitution_cost(&self, a: char, b: char) -> f64 {
+///         match (a == b, a.is_ascii_digit() && b.is_ascii_digit()) {
+///             (true, _) => 0.0,
+///             (false, true) => 0.5,
+///             (false, false) => 1.0,
+///         }
+///     }
+/// }
+///
+/// let a: VDString = "AB29".parse().unwrap();
+/// let b: VDString = "AB39".parse().unwrap();
+/// assert_eq!(a.confusable_distance(&b, &DigitSlips), 0.5);
+/// # }
+/// ```
+pub trait ErrorModel {
+    /// Returns the cost of substituting `a` with `b`.
+    fn substitution_cost(&self, a: char, b: char) -> f64;
+
+    /// Returns the cost of inserting or deleting a character (default `1.0`).
+    fn indel_cost(&self) -> f64 {
+        1.0
+    }
+}
+
+/// Confusions between characters that look alike, from
+/// [`VISUAL_CONFUSIONS`].
+///
+/// The same costs as [`ConfusionMatrix::visual`], without allocating.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct VisualModel;
+
+impl ErrorModel for VisualModel {
+    fn substitution_cost(&self, a: char, b: char) -> f64 {
+        if a == b {
+            return 0.0;
+        }
+        VISUAL_CONFUSIONS
+            .iter()
+            .find(|&&(x, y, _)| (x, y) == (a, b) || (x, y) == (b, a))
+            .map_or(1.0, |&(_, _, cost)| cost)
+    }
+}
+
+/// Confusions between neighbouring keys on a US QWERTY keyboard.
+///
+/// Characters on adjacent keys, including the diagonal neighbours of the
+/// staggered rows (`G` neighbours `T`, `Y`, `F`, `H`, `V`, and `B`), cost
+/// [`ADJACENT_COST`](Self::ADJACENT_COST) to substitute. Case is ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct KeyboardModel;
+
+impl KeyboardModel {
+    /// Cost of substituting a character with one on an adjacent key.
+    pub const ADJACENT_COST: f64 = 0.5;
+
+    const ROWS: [(&'static str, i32); 4] = [("1234567890", 0), ("QWERTYUIOP", 2), ("ASDFGHJKL", 3), ("ZXCVBNM", 5)];
+
+    /// Returns the key's row and horizontal position in quarter-key units.
+    fn position(c: char) -> Option<(i32, i32)> {
+        let c = c.to_ascii_uppercase();
+        Self::ROWS.iter().zip(0..).find_map(|(&(keys, offset), row)| {
+            let col = keys.chars().position(|k| k == c)?;
+            Some((row, offset + 4 * col as i32))
+        })
+    }
+}
+
+impl ErrorModel for KeyboardModel {
+    fn substitution_cost(&self, a: char, b: char) -> f64 {
+        if a.eq_ignore_ascii_case(&b) {
+            return 0.0;
+        }
+        let adjacent = match (Self::position(a), Self::position(b)) {
+            (Some((ra, xa)), Some((rb, xb))) => match (ra - rb).abs() {
+                0 => (xa - xb).abs() == 4,
+                1 => (xa - xb).abs() < 4,
+                _ => false,
+            },
+            _ => false,
+        };
+        if adjacent { Self::ADJACENT_COST } else { 1.0 }
+    }
+}
+
+/// Confusions between characters that sound alike when spelled aloud,
+/// from [`PHONETIC_GROUPS`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PhoneticModel;
+
+impl ErrorModel for PhoneticModel {
+    fn substitution_cost(&self, a: char, b: char) -> f64 {
+        if a == b {
+            return 0.0;
+        }
+        PHONETIC_GROUPS
+            .iter()
+            .filter(|(group, _)| group.contains(a) && group.contains(b))
+            .map(|&(_, cost)| cost)
+            .fold(1.0, f64::min)
+    }
+}
+
+/// Substitution, insertion, and deletion costs for
+/// [`VDStr::confusable_distance`], as a custom [`ErrorModel`].
+///
+/// Pairs are symmetric and keyed by character, so one matrix can serve any
+/// alphabet. Characters without a configured pair cost `1.0` to substitute.
+///
+/// # Examples
+/// ```
+/// use vds::distance::ConfusionMatrix;
+///
+/// let m = ConfusionMatrix::new().pair('Q', '9', 0.4).indel_cost(1.5);
+/// assert_eq!(m.substitution_cost('9', 'Q'), 0.4);
+/// assert_eq!(m.substitution_cost('Q', 'Q'), 0.0);
+/// assert_eq!(m.substitution_cost('Q', 'A'), 1.0);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfusionMatrix {
+    pairs: Vec<(char, char, f64)>,
+    indel: f64,
+}
+
+#[cfg(feature = "alloc")]
+impl ConfusionMatrix {
+    /// Creates a matrix with no confusable pairs: every substitution,
+    /// insertion, and deletion costs `1.0` (plain Levenshtein distance).
+    pub fn new() -> Self {
+        Self { pairs: Vec::new(), indel: 1.0 }
+    }
+
+    /// Creates a matrix preloaded with [`VISUAL_CONFUSIONS`].
+    pub fn visual() -> Self {
+        VISUAL_CONFUSIONS.iter().fold(Self::new(), |m, &(a, b, cost)| m.pair(a, b, cost))
+    }
+
+    /// Sets the cost of substituting `a` for `b` (and `b` for `a`),
+    /// replacing any earlier cost for the pair.
+    pub fn pair(mut self, a: char, b: char, cost: f64) -> Self {
+        self.pairs.retain(|&(x, y, _)| !((x, y) == (a, b) || (x, y) == (b, a)));
+        self.pairs.push((a, b, cost));
+        self
+    }
+
+    /// Sets the cost of inserting or deleting a character.
+    pub fn indel_cost(mut self, cost: f64) -> Self {
+        self.indel = cost;
+        self
+    }
+
+    /// Returns the cost of substituting `a` with `b`.
+    pub fn substitution_cost(&self, a: char, b: char) -> f64 {
+        if a == b {
+            return 0.0;
+        }
+        self.pairs
+            .iter()
+            .find(|&&(x, y, _)| (x, y) == (a, b) || (x, y) == (b, a))
+            .map_or(1.0, |&(_, _, cost)| cost)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ErrorModel for ConfusionMatrix {
+    fn substitution_cost(&self, a: char, b: char) -> f64 {
+        ConfusionMatrix::substitution_cost(self, a, b)
+    }
+
+    fn indel_cost(&self) -> f64 {
+        self.indel
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for ConfusionMatrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns the number of positions at which `self` and `other` differ,
+    /// or `None` if their lengths differ.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let a: VDString = "AB29".parse().unwrap();
+    /// let b: VDString = "AB39".parse().unwrap();
+    /// assert_eq!(a.hamming_distance(&b), Some(1));
+    /// assert_eq!(a.hamming_distance(&b[..3]), None);
+    /// # }
+    /// ```
+    pub fn hamming_distance(&self, other: &Self) -> Option<usize> {
+        if self.len() != other.len() {
+            return None;
+        }
+        Some(self.iter().zip(other).filter(|(a, b)| a != b).count())
+    }
+
+    /// Returns the positions at which `self` and `other` disagree, with the
+    /// character each has there.
+    ///
+    /// Positions are compared up to the shorter length; check the lengths
+    /// separately, or use [`display_diff`](Self::display_diff), which also
+    /// marks the extra characters of the longer code.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let issued: VDString = "AB29XY".parse().unwrap();
+    /// let read: VDString = "AB39XK".parse().unwrap();
+    /// let diff: Vec<_> = issued.diff(&read).map(|(i, a, b)| (i, a.as_char(), b.as_char())).collect();
+    /// assert_eq!(diff, [(2, '2', '3'), (5, 'Y', 'K')]);
+    /// # }
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = (usize, VDChar<A>, VDChar<A>)> + 'a {
+        self.iter().zip(other).enumerate().filter(|(_, (a, b))| a != b).map(|(i, (a, b))| (i, a, b))
+    }
+
+    /// Returns a heuristic score for how likely the code is to be misread
+    /// when copied by hand; higher is worse.
+    ///
+    /// Each character scores the closeness (`1.0` minus the cost) of all of
+    /// its [`VISUAL_CONFUSIONS`], and each adjacent pair that is itself a
+    /// confusion, such as `B8` or `NM`, scores its closeness again: side
+    /// by side, the reader has no clear reference for either form. The
+    /// score is the total divided by the length, so codes of different
+    /// lengths can share a threshold; an empty code scores `0.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let clear: VDString = "HJW9".parse().unwrap();
+    /// let murky: VDString = "B8S5".parse().unwrap();
+    /// assert_eq!(clear.transcription_risk(), 0.0);
+    /// assert!(murky.transcription_risk() > 0.5);
+    ///
+    /// let apart: VDString = "B4X8".parse().unwrap();
+    /// let adjacent: VDString = "B84X".parse().unwrap();
+    /// assert!(adjacent.transcription_risk() > apart.transcription_risk());
+    /// # }
+    /// ```
+    pub fn transcription_risk(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let closeness = |a: char, b: char| {
+            VISUAL_CONFUSIONS
+                .iter()
+                .filter(|&&(x, y, _)| (x, y) == (a, b) || (x, y) == (b, a))
+                .map(|&(_, _, cost)| 1.0 - cost)
+                .sum::<f64>()
+        };
+        let chars = self.iter().map(|c| {
+            let c = c.as_char();
+            VISUAL_CONFUSIONS
+                .iter()
+                .filter(|&&(x, y, _)| x == c || y == c)
+                .map(|&(_, _, cost)| 1.0 - cost)
+                .sum::<f64>()
+        });
+        let digraphs = self.as_vdchars().windows(2).map(|w| closeness(w[0].as_char(), w[1].as_char()));
+        (chars.sum::<f64>() + digraphs.sum::<f64>()) / self.len() as f64
+    }
+
+    /// Returns the weighted edit distance from `self` to `other`.
+    ///
+    /// Like Levenshtein distance, but each substitution and each insertion
+    /// or deletion costs what `model` says. With [`ConfusionMatrix::new`]
+    /// this is exactly the Levenshtein distance.
+    ///
+    /// Uses `O(min(len))` memory.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::distance::ConfusionMatrix;
+    /// use vds::VDString;
+    ///
+    /// let a: VDString = "MB2".parse().unwrap();
+    /// let b: VDString = "N82".parse().unwrap();
+    /// assert_eq!(a.confusable_distance(&b, &ConfusionMatrix::new()), 2.0);
+    /// assert!((a.confusable_distance(&b, &ConfusionMatrix::visual()) - 0.8).abs() < 1e-9);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn confusable_distance<M: ErrorModel + ?Sized>(&self, other: &Self, model: &M) -> f64 {
+        let (long, short) = if self.len() >= other.len() { (self, other) } else { (other, self) };
+        weighted_edit_distance(long.iter().map(VDChar::as_char), short.iter().map(VDChar::as_char), model)
+    }
+}
+
+/// Weighted edit distance between two character sequences, keeping one row
+/// of the table sized by `short`.
+#[cfg(feature = "alloc")]
+pub(crate) fn weighted_edit_distance<L, S, M>(long: L, short: S, model: &M) -> f64
+where
+    L: Iterator<Item = char>,
+    S: Iterator<Item = char> + Clone,
+    M: ErrorModel + ?Sized,
+{
+    let indel = model.indel_cost();
+    let mut prev: Vec<f64> = (0..=short.clone().count()).map(|j| j as f64 * indel).collect();
+    let mut row = Vec::with_capacity(prev.len());
+
+    for (i, a) in long.enumerate() {
+        row.clear();
+        row.push((i + 1) as f64 * indel);
+        for (j, b) in short.clone().enumerate() {
+            let substitute = prev[j] + model.substitution_cost(a, b);
+            let delete = prev[j + 1] + indel;
+            let insert = row[j] + indel;
+            row.push(substitute.min(delete).min(insert));
+        }
+        core::mem::swap(&mut prev, &mut row);
+    }
+    prev[prev.len() - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Default31;
+
+    fn vds(s: &str) -> [VDChar<Default31>; 4] {
+        let mut out = [VDChar::<Default31>::from_index_unchecked(0); 4];
+        for (slot, c) in out.iter_mut().zip(s.chars()) {
+            *slot = VDChar::<Default31>::new(c).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn hamming_counts_differing_positions() {
+        let (a, b) = (vds("AB29"), vds("BA29"));
+        let (a, b) = (VDStr::<Default31>::from_vdchars(&a), VDStr::<Default31>::from_vdchars(&b));
+        assert_eq!(a.hamming_distance(a), Some(0));
+        assert_eq!(a.hamming_distance(b), Some(2));
+        assert_eq!(b.hamming_distance(a), Some(2));
+        assert_eq!(a.hamming_distance(&b[..2]), None);
+        assert_eq!(a[..0].hamming_distance(&b[..0]), Some(0));
+    }
+
+    #[test]
+    fn diff_lists_mismatches_up_to_shorter_length() {
+        let (a, b) = (vds("AB29"), vds("BB27"));
+        let (a, b) = (VDStr::<Default31>::from_vdchars(&a), VDStr::<Default31>::from_vdchars(&b));
+        let diff = a.diff(b).map(|(i, x, y)| (i, x.as_char(), y.as_char()));
+        assert!(diff.eq([(0, 'A', 'B'), (3, '9', '7')]));
+        assert_eq!(a.diff(&b[..2]).count(), 1);
+        assert_eq!(a.diff(a).count(), 0);
+    }
+
+    #[test]
+    fn transcription_risk_sums_chars_and_confusable_digraphs() {
+        let risk = |s: &str| VDStr::<Default31>::from_vdchars(&vds(s)).transcription_risk();
+        assert_eq!(risk("HJW9"), 0.0);
+        // B: 0.7 (8) + 0.3 (P); 8: 0.7 (B) + 0.3 (3); the pair B8: 0.7.
+        assert!((risk("B8HJ") - (1.0 + 1.0 + 0.7) / 4.0).abs() < 1e-9);
+        assert_eq!(risk("B8HJ"), risk("8BHJ"));
+        assert!(risk("B8HJ") > risk("BH8J"));
+        assert_eq!(<&VDStr<Default31>>::default().transcription_risk(), 0.0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn plain_matrix_is_levenshtein() {
+        let m = ConfusionMatrix::new();
+        let d = |a: &str, b: &str| {
+            let a: crate::VDString<Default31> = a.parse().unwrap();
+            let b: crate::VDString<Default31> = b.parse().unwrap();
+            a.confusable_distance(&b, &m)
+        };
+        assert_eq!(d("KATTEN", "SATTEN"), 1.0);
+        assert_eq!(d("AB29", "AB29"), 0.0);
+        assert_eq!(d("", "ABC"), 3.0);
+        assert_eq!(d("ABC", ""), 3.0);
+        assert_eq!(d("ABCD", "ACD"), 1.0);
+        assert_eq!(d("XYZW", "WXYZ"), 2.0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn confusable_substitutions_cost_less() {
+        let m = ConfusionMatrix::visual().indel_cost(2.0);
+        let a: crate::VDString<Default31> = "S2B".parse().unwrap();
+        let b: crate::VDString<Default31> = "5Z8".parse().unwrap();
+        assert!((a.confusable_distance(&b, &m) - 0.9).abs() < 1e-9);
+        assert_eq!(b.confusable_distance(&a, &m), a.confusable_distance(&b, &m));
+
+        let short: crate::VDString<Default31> = "S2".parse().unwrap();
+        assert!((a.confusable_distance(&short, &m) - 2.0).abs() < 1e-9);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn visual_pairs_are_allowed_chars() {
+        for &(a, b, cost) in VISUAL_CONFUSIONS {
+            assert!(VDChar::<Default31>::new(a).is_some() && VDChar::<Default31>::new(b).is_some(), "{}{}", a, b);
+            assert!(cost > 0.0 && cost < 1.0);
+        }
+        assert_eq!(ConfusionMatrix::visual().pair('8', 'B', 0.1).substitution_cost('B', '8'), 0.1);
+    }
+
+    #[test]
+    fn keyboard_model_follows_qwerty_stagger() {
+        let m = KeyboardModel;
+        for (a, b) in [('G', 'T'), ('G', 'Y'), ('G', 'B'), ('G', 'V'), ('Q', '2'), ('A', 'Q'), ('A', 'Z'), ('M', 'N')] {
+            assert_eq!(m.substitution_cost(a, b), KeyboardModel::ADJACENT_COST, "{}{}", a, b);
+            assert_eq!(m.substitution_cost(b, a), KeyboardModel::ADJACENT_COST, "{}{}", b, a);
+        }
+        for (a, b) in [('G', 'N'), ('G', 'R'), ('A', 'X'), ('Q', 'S'), ('P', '-')] {
+            assert_eq!(m.substitution_cost(a, b), 1.0, "{}{}", a, b);
+        }
+        assert_eq!(m.substitution_cost('k', 'K'), 0.0);
+    }
+
+    #[test]
+    fn phonetic_model_uses_cheapest_shared_group() {
+        let m = PhoneticModel;
+        assert_eq!(m.substitution_cost('B', 'D'), 0.5);
+        assert_eq!(m.substitution_cost('3', 'E'), 0.5);
+        assert_eq!(m.substitution_cost('N', 'M'), 0.4);
+        assert_eq!(m.substitution_cost('B', 'M'), 1.0);
+        assert_eq!(m.substitution_cost('B', 'B'), 0.0);
+        for &(group, cost) in PHONETIC_GROUPS {
+            assert!(group.chars().all(|c| VDChar::<Default31>::new(c).is_some()), "{}", group);
+            assert!(cost > 0.0 && cost < 1.0);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn visual_model_matches_visual_matrix() {
+        let matrix = ConfusionMatrix::visual();
+        for a in crate::VDS_ALLOWED {
+            for b in crate::VDS_ALLOWED {
+                assert_eq!(VisualModel.substitution_cost(*a, *b), matrix.substitution_cost(*a, *b));
+            }
+        }
+    }
+}
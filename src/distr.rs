@@ -0,0 +1,90 @@
+use rand::distr::{Distribution, StandardUniform};
+use rand::Rng;
+
+use crate::alphabet::Alphabet;
+use crate::{uniform_index, VDChar};
+#[cfg(feature = "alloc")]
+use crate::VDString;
+
+/// A [`Distribution`] sampling uniformly from an alphabet's characters.
+///
+/// Samples [`VDChar<A>`](VDChar) for any [`Alphabet`] `A`, inferred from
+/// the annotated type, so `rand`'s `sample` and `sample_iter` work without
+/// configuring a [`VDGenerator`](crate::VDGenerator). `StandardUniform` is
+/// implemented the same way, so `rng.random::<VDChar>()` works too.
+///
+/// This type is only available when the `generate` feature is enabled.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use rand::Rng;
+/// use vds::{StepRng, VDAlphabet, VDChar, VDString};
+///
+/// let mut rng = StepRng::new(0, 1);
+/// let ch: VDChar = rng.sample(VDAlphabet);
+/// assert_eq!(ch.as_char(), 'A');
+///
+/// let code: VDString = (&mut rng).sample_iter(VDAlphabet).take(4).collect();
+/// assert_eq!(&*code, "BCDE");
+///
+/// let code: VDString = VDAlphabet.sample_vdstring(&mut rng, 3);
+/// assert_eq!(&*code, "FGH");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct VDAlphabet;
+
+#[cfg(feature = "alloc")]
+impl VDAlphabet {
+    /// Samples a [`VDString`] of `len` characters.
+    ///
+    /// The counterpart of `rand`'s `SampleString`, which can only produce a
+    /// `String`. For repeat constraints and the like, use
+    /// [`VDGenerator`](crate::VDGenerator).
+    pub fn sample_vdstring<A: Alphabet, R: Rng + ?Sized>(&self, rng: &mut R, len: usize) -> VDString<A> {
+        (0..len).map(|_| self.sample(rng)).collect()
+    }
+}
+
+impl<A: Alphabet> Distribution<VDChar<A>> for VDAlphabet {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> VDChar<A> {
+        VDChar::from_index_unchecked(uniform_index(rng, A::CHARS.len() as u32) as u8)
+    }
+}
+
+impl<A: Alphabet> Distribution<VDChar<A>> for StandardUniform {
+    /// Samples uniformly from the alphabet, as [`VDAlphabet`] does.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> VDChar<A> {
+        VDAlphabet.sample(rng)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod tests {
+    use super::*;
+    use crate::alphabet::{Default31, Digits10};
+    use rand::SeedableRng;
+
+    #[test]
+    fn samples_every_character() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+        let mut seen = [false; 31];
+        for ch in (&mut rng).sample_iter::<VDChar<Default31>, _>(VDAlphabet).take(2000) {
+            seen[usize::from(ch.index())] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        let ch: VDChar<Default31> = rng.random();
+        assert!(ch.is_letter() || ch.is_digit());
+    }
+
+    #[test]
+    fn samples_other_alphabets() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+        let code: VDString<Digits10> = VDAlphabet.sample_vdstring(&mut rng, 12);
+        assert_eq!(code.len(), 12);
+        assert!(code.as_str().chars().all(|c| c.is_ascii_digit()));
+    }
+}
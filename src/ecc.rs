@@ -0,0 +1,414 @@
+//! Reed–Solomon error correction over GF(31).
+//!
+//! The alphabet has a prime number of characters, so alphabet indices form
+//! the finite field GF(31) and [`VDString`]s can be protected with a
+//! Reed–Solomon code. Appending `2t` parity characters lets up to `t`
+//! mistyped characters be located and corrected, with no server lookup.
+//!
+//! [`VDString::with_ecc`] and [`VDString::correct`] use [`DEFAULT_PARITY`]
+//! parity characters, correcting up to two errors. Use [`ReedSolomon`] for
+//! other parity lengths.
+//!
+//! A codeword (payload plus parity) holds at most [`MAX_LEN`] characters.
+//!
+//! # Examples
+//! ```
+//! use vds::VDString;
+//!
+//! let payload: VDString = "AB29XY7K".parse().unwrap();
+//! let code = payload.with_ecc().unwrap();
+//!
+//! // Two characters misread over the phone.
+//! let mut heard = code.to_string();
+//! heard.replace_range(1..2, "8");
+//! heard.replace_range(5..6, "Q");
+//! let heard: VDString = heard.parse().unwrap();
+//!
+//! assert_eq!(heard.correct().unwrap(), code);
+//! ```
+
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::alphabet::Default31;
+use crate::vdchar::{VDChar, VDS_ALLOWED};
+use crate::VDString;
+
+/// Field order.
+const Q: u32 = VDS_ALLOWED.len() as u32;
+
+/// Primitive element of GF(31).
+const ALPHA: u32 = 3;
+
+/// Maximum codeword length (the multiplicative order of the primitive
+/// element of GF(31)).
+pub const MAX_LEN: usize = Q as usize - 1;
+
+/// Number of parity characters used by [`VDString::with_ecc`] and
+/// [`VDString::correct`]. Corrects up to two errors.
+pub const DEFAULT_PARITY: usize = 4;
+
+/// Error returned by Reed–Solomon encoding and correction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EccError {
+    /// The codeword would be longer than [`MAX_LEN`] characters.
+    TooLong {
+        /// Length of the codeword, including parity, or `usize::MAX` if
+        /// that overflows.
+        len: usize,
+        /// Maximum supported codeword length.
+        max: usize,
+    },
+    /// The parity length is zero, or the input is too short to contain it.
+    InvalidParity,
+    /// The input has more errors than the parity can correct.
+    Uncorrectable,
+}
+
+impl fmt::Display for EccError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            EccError::TooLong { len, max } => {
+                write!(f, "codeword of {} characters exceeds the maximum of {}", len, max)
+            }
+            EccError::InvalidParity => f.write_str("invalid parity length"),
+            EccError::Uncorrectable => f.write_str("too many errors to correct"),
+        }
+    }
+}
+
+impl core::error::Error for EccError {}
+
+fn mul(a: u32, b: u32) -> u32 {
+    a * b % Q
+}
+
+fn sub(a: u32, b: u32) -> u32 {
+    (a + Q - b) % Q
+}
+
+fn pow(mut base: u32, mut exp: u32) -> u32 {
+    let mut acc = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul(acc, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    acc
+}
+
+fn inv(a: u32) -> u32 {
+    pow(a, Q - 2)
+}
+
+/// Evaluates a lowest-degree-first polynomial at `x`.
+fn eval(poly: &[u32], x: u32) -> u32 {
+    poly.iter().rev().fold(0, |acc, &c| (mul(acc, x) + c) % Q)
+}
+
+/// A systematic Reed–Solomon code over GF(31) with a fixed parity length.
+///
+/// Corrects up to `parity / 2` errors anywhere in the codeword, including
+/// the parity characters themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReedSolomon {
+    parity: usize,
+}
+
+impl ReedSolomon {
+    /// Creates a code that appends `parity` check characters.
+    pub const fn new(parity: usize) -> Self {
+        Self { parity }
+    }
+
+    /// Returns the number of parity characters.
+    pub const fn parity(&self) -> usize {
+        self.parity
+    }
+
+    /// Returns the maximum number of errors this code can correct.
+    pub const fn max_errors(&self) -> usize {
+        self.parity / 2
+    }
+
+    /// Returns `payload` with parity characters appended.
+    ///
+    /// # Errors
+    /// - [`EccError::InvalidParity`] if the parity length is zero
+    /// - [`EccError::TooLong`] if the codeword would exceed [`MAX_LEN`]
+    pub fn encode(&self, payload: &VDString<Default31>) -> Result<VDString<Default31>, EccError> {
+        if self.parity == 0 {
+            return Err(EccError::InvalidParity);
+        }
+        let len = payload.len().checked_add(self.parity).ok_or(EccError::TooLong { len: usize::MAX, max: MAX_LEN })?;
+        if len > MAX_LEN {
+            return Err(EccError::TooLong { len, max: MAX_LEN });
+        }
+
+        // Generator g(x) = (x - α)(x - α²)…(x - α^parity), highest degree first.
+        let mut generator = vec![1];
+        for j in 1..=self.parity as u32 {
+            let root = pow(ALPHA, j);
+            generator.push(0);
+            for i in (1..generator.len()).rev() {
+                generator[i] = sub(generator[i], mul(root, generator[i - 1]));
+            }
+        }
+
+        // Remainder of payload(x)·x^parity divided by g(x), via LFSR division.
+        let mut rem = vec![0; self.parity];
+        for ch in payload {
+            let feedback = (u32::from(ch.index()) + rem[0]) % Q;
+            rem.rotate_left(1);
+            rem[self.parity - 1] = 0;
+            for (r, &g) in rem.iter_mut().zip(&generator[1..]) {
+                *r = sub(*r, mul(feedback, g));
+            }
+        }
+
+        // Codeword c(x) = payload(x)·x^parity − remainder, divisible by g(x).
+        let mut chars = payload.as_vdchars().to_vec();
+        chars.extend(rem.iter().map(|&r| VDChar::from_index_unchecked(sub(0, r) as u8)));
+        Ok(VDString::new(chars))
+    }
+
+    /// Returns `code` with up to [`max_errors`](Self::max_errors) errors corrected.
+    ///
+    /// The result includes the parity characters; use
+    /// [`decode`](Self::decode) to get just the payload.
+    ///
+    /// # Errors
+    /// - [`EccError::InvalidParity`] if the parity length is zero or longer than `code`
+    /// - [`EccError::TooLong`] if `code` is longer than [`MAX_LEN`]
+    /// - [`EccError::Uncorrectable`] if `code` has too many errors to correct
+    pub fn correct(&self, code: &VDString<Default31>) -> Result<VDString<Default31>, EccError> {
+        let n = code.len();
+        if self.parity == 0 || self.parity > n {
+            return Err(EccError::InvalidParity);
+        }
+        if n > MAX_LEN {
+            return Err(EccError::TooLong { len: n, max: MAX_LEN });
+        }
+
+        let mut word: Vec<u32> = code.into_iter().map(|c| u32::from(c.index())).collect();
+        // Symbol i is the coefficient of x^(n-1-i).
+        let syndromes = |word: &[u32]| -> Vec<u32> {
+            (1..=self.parity as u32)
+                .map(|j| {
+                    let x = pow(ALPHA, j);
+                    word.iter().fold(0, |acc, &c| (mul(acc, x) + c) % Q)
+                })
+                .collect()
+        };
+
+        let s = syndromes(&word);
+        if s.iter().all(|&v| v == 0) {
+            return Ok(code.clone());
+        }
+
+        // Berlekamp–Massey: error locator Λ(x), lowest degree first.
+        let mut lambda = vec![1];
+        let mut prev = vec![1];
+        let mut l = 0;
+        let mut shift = 1;
+        let mut prev_d = 1;
+        for k in 0..self.parity {
+            let d = (1..=l.min(lambda.len() - 1))
+                .fold(s[k], |acc, i| (acc + mul(lambda[i], s[k - i])) % Q);
+            if d == 0 {
+                shift += 1;
+                continue;
+            }
+            let coef = mul(d, inv(prev_d));
+            let mut next = lambda.clone();
+            if next.len() < prev.len() + shift {
+                next.resize(prev.len() + shift, 0);
+            }
+            for (i, &p) in prev.iter().enumerate() {
+                next[i + shift] = sub(next[i + shift], mul(coef, p));
+            }
+            if 2 * l <= k {
+                l = k + 1 - l;
+                prev = core::mem::replace(&mut lambda, next);
+                prev_d = d;
+                shift = 1;
+            } else {
+                lambda = next;
+                shift += 1;
+            }
+        }
+        if l > self.max_errors() {
+            return Err(EccError::Uncorrectable);
+        }
+
+        // Ω(x) = S(x)·Λ(x) mod x^parity.
+        let omega: Vec<u32> = (0..self.parity)
+            .map(|i| {
+                (0..=i)
+                    .filter(|&j| j < lambda.len())
+                    .fold(0, |acc, j| (acc + mul(lambda[j], s[i - j])) % Q)
+            })
+            .collect();
+        let lambda_prime: Vec<u32> = lambda
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| mul(i as u32, c))
+            .collect();
+
+        // Chien search and Forney's formula.
+        let mut found = 0;
+        for (i, symbol) in word.iter_mut().enumerate() {
+            let x_inv = inv(pow(ALPHA, (n - 1 - i) as u32));
+            if eval(&lambda, x_inv) != 0 {
+                continue;
+            }
+            let denom = eval(&lambda_prime, x_inv);
+            if denom == 0 {
+                return Err(EccError::Uncorrectable);
+            }
+            let magnitude = mul(eval(&omega, x_inv), inv(denom));
+            *symbol = (*symbol + magnitude) % Q;
+            found += 1;
+        }
+        if found != l || syndromes(&word).iter().any(|&v| v != 0) {
+            return Err(EccError::Uncorrectable);
+        }
+
+        Ok(VDString::new(word.into_iter().map(|c| VDChar::from_index_unchecked(c as u8)).collect()))
+    }
+
+    /// Corrects `code` and returns only the payload (parity removed).
+    ///
+    /// # Errors
+    /// Same as [`correct`](Self::correct).
+    pub fn decode(&self, code: &VDString<Default31>) -> Result<VDString<Default31>, EccError> {
+        let corrected = self.correct(code)?;
+        let payload = corrected.len() - self.parity;
+        Ok(VDString::new(corrected.as_vdchars()[..payload].to_vec()))
+    }
+}
+
+impl VDString<Default31> {
+    /// Returns this string with [`DEFAULT_PARITY`] Reed–Solomon parity
+    /// characters appended.
+    ///
+    /// # Errors
+    /// Returns [`EccError::TooLong`] if the result would exceed [`MAX_LEN`].
+    pub fn with_ecc(&self) -> Result<VDString<Default31>, EccError> {
+        ReedSolomon::new(DEFAULT_PARITY).encode(self)
+    }
+
+    /// Corrects up to two errors in a string produced by [`with_ecc`](Self::with_ecc).
+    ///
+    /// Returns the corrected string, parity included.
+    ///
+    /// # Errors
+    /// See [`ReedSolomon::correct`].
+    pub fn correct(&self) -> Result<VDString<Default31>, EccError> {
+        ReedSolomon::new(DEFAULT_PARITY).correct(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vs(s: &str) -> VDString<Default31> {
+        s.parse().unwrap()
+    }
+
+    fn with_errors(code: &VDString<Default31>, errors: &[(usize, u8)]) -> VDString<Default31> {
+        let mut chars = code.as_vdchars().to_vec();
+        for &(pos, delta) in errors {
+            chars[pos] = VDChar::<Default31>::from_index_unchecked((chars[pos].index() + delta) % Q as u8);
+        }
+        VDString::<Default31>::new(chars)
+    }
+
+    #[test]
+    fn field_has_primitive_alpha() {
+        let order = (1..Q).find(|&k| pow(ALPHA, k) == 1).unwrap();
+        assert_eq!(order as usize, MAX_LEN);
+        for a in 1..Q {
+            assert_eq!(mul(a, inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn encoded_words_have_zero_syndromes() {
+        let code = vs("AB29XY7K").with_ecc().unwrap();
+        assert_eq!(code.len(), 12);
+        assert_eq!(code.correct().unwrap(), code);
+    }
+
+    #[test]
+    fn corrects_every_single_error() {
+        let code = vs("M3PQKXW7").with_ecc().unwrap();
+        for pos in 0..code.len() {
+            for delta in 1..Q as u8 {
+                let bad = with_errors(&code, &[(pos, delta)]);
+                assert_eq!(bad.correct().unwrap(), code, "pos {} delta {}", pos, delta);
+            }
+        }
+    }
+
+    #[test]
+    fn corrects_every_double_error_position_pair() {
+        let code = vs("Z9Z9Z9").with_ecc().unwrap();
+        for a in 0..code.len() {
+            for b in a + 1..code.len() {
+                for delta in [1, 7, 30] {
+                    let bad = with_errors(&code, &[(a, delta), (b, 31 - delta)]);
+                    assert_eq!(bad.correct().unwrap(), code, "{} {} {}", a, b, delta);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn larger_parity_corrects_more() {
+        let rs = ReedSolomon::new(6);
+        let payload = vs("HEMP2WXYZ");
+        let code = rs.encode(&payload).unwrap();
+        let bad = with_errors(&code, &[(0, 3), (4, 9), (12, 1)]);
+        assert_eq!(rs.decode(&bad).unwrap(), payload);
+    }
+
+    #[test]
+    fn reports_too_many_errors() {
+        let code = vs("AB29XY7K").with_ecc().unwrap();
+        let mut uncorrectable = 0;
+        for a in 0..code.len() - 2 {
+            let bad = with_errors(&code, &[(a, 1), (a + 1, 2), (a + 2, 3)]);
+            match bad.correct() {
+                Ok(fixed) => assert_ne!(fixed, code),
+                Err(e) => {
+                    assert_eq!(e, EccError::Uncorrectable);
+                    uncorrectable += 1;
+                }
+            }
+        }
+        assert!(uncorrectable > 0);
+    }
+
+    #[test]
+    fn length_and_parity_limits() {
+        let long = VDString::<Default31>::new(alloc::vec![VDChar::<Default31>::from_index_unchecked(0); MAX_LEN - DEFAULT_PARITY + 1]);
+        assert_eq!(
+            long.with_ecc(),
+            Err(EccError::TooLong { len: MAX_LEN + 1, max: MAX_LEN })
+        );
+        assert_eq!(ReedSolomon::new(0).encode(&vs("AB")), Err(EccError::InvalidParity));
+        assert_eq!(
+            ReedSolomon::new(usize::MAX).encode(&vs("AB")),
+            Err(EccError::TooLong { len: usize::MAX, max: MAX_LEN })
+        );
+        assert_eq!(vs("ABC").correct(), Err(EccError::InvalidParity));
+    }
+}
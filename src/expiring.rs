@@ -0,0 +1,272 @@
+extern crate alloc;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::alphabet::Default31;
+use crate::checksum::{ChecksumAlgorithm, Damm};
+use crate::vdchar::{VDChar, VDS_ALLOWED};
+use crate::{uniform_index, VDRng, VDString, VDStringError};
+
+const BASE: u64 = VDS_ALLOWED.len() as u64;
+
+/// Error returned when creating, parsing, or verifying a [`VDExpiringCode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpiringCodeError {
+    /// The input contained a character outside the allowed set.
+    Invalid(VDStringError),
+    /// The input is too short to hold an expiry and a check character.
+    TooShort {
+        /// The number of characters in the input.
+        len: usize,
+    },
+    /// The check character did not match the rest of the code.
+    CheckMismatch,
+    /// The expiry is before [`VDExpiringCode::EPOCH`] or too far after it
+    /// to fit in [`VDExpiringCode::EXPIRY_LEN`] characters.
+    ExpiryOutOfRange(u64),
+    /// The code is well-formed but has expired.
+    Expired {
+        /// When the code expired, in Unix seconds.
+        expires_at: u64,
+    },
+}
+
+impl fmt::Display for ExpiringCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ExpiringCodeError::Invalid(_) => f.write_str("invalid code"),
+            ExpiringCodeError::TooShort { len } => write!(f, "code of {} characters is too short", len),
+            ExpiringCodeError::CheckMismatch => f.write_str("check character does not match"),
+            ExpiringCodeError::ExpiryOutOfRange(t) => write!(f, "expiry {} is out of range", t),
+            ExpiringCodeError::Expired { expires_at } => write!(f, "code expired at {}", expires_at),
+        }
+    }
+}
+
+impl core::error::Error for ExpiringCodeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ExpiringCodeError::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<VDStringError> for ExpiringCodeError {
+    fn from(err: VDStringError) -> Self {
+        ExpiringCodeError::Invalid(err)
+    }
+}
+
+/// A code that carries its own expiry, so it can be checked offline.
+///
+/// Each code is the expiry hour, written in [`EXPIRY_LEN`](Self::EXPIRY_LEN)
+/// base-31 characters, then random characters, then a [`Damm`] check
+/// character over everything before it. [`verify`](Self::verify) checks the
+/// format, the check character, and the expiry without any lookup, which
+/// suits vouchers redeemed at the edge.
+///
+/// The expiry is not authenticated: anyone who knows the layout can mint a
+/// code with any expiry. Where forgery matters, still look the code up (or
+/// sign it) before honoring it; the offline check then only screens out
+/// typos and stale codes early.
+///
+/// Times are Unix seconds supplied by the caller, which keeps this type
+/// usable without `std`. Expiries are rounded up to the next whole hour.
+///
+/// This type is only available when the `generate` feature is enabled.
+///
+/// # Examples
+/// ```
+/// use vds::{ExpiringCodeError, StepRng, VDExpiringCode};
+///
+/// let issued_at = 1_700_000_000;
+/// let code = VDExpiringCode::generate(issued_at + 86_400, 8, &mut StepRng::new(0, 7)).unwrap();
+/// assert_eq!(code.len(), 13);
+///
+/// let checked = VDExpiringCode::verify(&code.to_string(), issued_at + 3_600).unwrap();
+/// assert_eq!(checked, code);
+/// assert_eq!(
+///     VDExpiringCode::verify(&code.to_string(), issued_at + 172_800),
+///     Err(ExpiringCodeError::Expired { expires_at: code.expires_at() })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VDExpiringCode {
+    code: VDString<Default31>,
+    expires_at: u64,
+}
+
+impl VDExpiringCode {
+    /// Number of characters used for the expiry.
+    ///
+    /// Four base-31 characters count hours for about 105 years from
+    /// [`EPOCH`](Self::EPOCH).
+    pub const EXPIRY_LEN: usize = 4;
+
+    /// Granularity of the expiry, in seconds.
+    pub const GRANULARITY_SECS: u64 = 3_600;
+
+    /// The earliest expiry that can be encoded: 2020-01-01T00:00:00Z, in
+    /// Unix seconds.
+    pub const EPOCH: u64 = 1_577_836_800;
+
+    /// Generates a code expiring at `expires_at` (Unix seconds, rounded up
+    /// to the hour), with `random_len` random characters drawn from `rng`.
+    ///
+    /// # Errors
+    /// Returns [`ExpiringCodeError::ExpiryOutOfRange`] if `expires_at` is
+    /// before [`EPOCH`](Self::EPOCH) or does not fit in
+    /// [`EXPIRY_LEN`](Self::EXPIRY_LEN) characters.
+    pub fn generate<R: VDRng + ?Sized>(
+        expires_at: u64,
+        random_len: usize,
+        rng: &mut R,
+    ) -> Result<Self, ExpiringCodeError> {
+        let Some(elapsed) = expires_at.checked_sub(Self::EPOCH) else {
+            return Err(ExpiringCodeError::ExpiryOutOfRange(expires_at));
+        };
+        let mut hours = elapsed.div_ceil(Self::GRANULARITY_SECS);
+        let mut chars = Vec::with_capacity(Self::EXPIRY_LEN + random_len + 1);
+        for _ in 0..Self::EXPIRY_LEN {
+            chars.push(VDChar::from_index_unchecked((hours % BASE) as u8));
+            hours /= BASE;
+        }
+        if hours > 0 {
+            return Err(ExpiringCodeError::ExpiryOutOfRange(expires_at));
+        }
+        chars.reverse();
+
+        for _ in 0..random_len {
+            chars.push(VDChar::from_index_unchecked(uniform_index(rng, BASE as u32) as u8));
+        }
+        chars.push(Damm.compute(chars.iter().copied()));
+
+        Self::from_vdstring(VDString::new(chars))
+    }
+
+    /// Wraps `code` if it is well-formed, without checking the expiry.
+    ///
+    /// # Errors
+    /// - [`ExpiringCodeError::TooShort`] if `code` cannot hold an expiry
+    ///   and a check character
+    /// - [`ExpiringCodeError::CheckMismatch`] if the check character is wrong
+    pub fn from_vdstring(code: VDString<Default31>) -> Result<Self, ExpiringCodeError> {
+        if code.len() <= Self::EXPIRY_LEN {
+            return Err(ExpiringCodeError::TooShort { len: code.len() });
+        }
+        if !code.verify_check_char() {
+            return Err(ExpiringCodeError::CheckMismatch);
+        }
+        let hours = code.as_vdchars()[..Self::EXPIRY_LEN].iter().fold(0, |acc, ch| acc * BASE + u64::from(ch.index()));
+        Ok(Self { expires_at: Self::EPOCH + hours * Self::GRANULARITY_SECS, code })
+    }
+
+    /// Parses `code` and checks that it has not expired at `now` (Unix
+    /// seconds).
+    ///
+    /// # Errors
+    /// - [`ExpiringCodeError::Invalid`] if `code` contains a character
+    ///   outside the allowed set
+    /// - the errors of [`from_vdstring`](Self::from_vdstring) if it is
+    ///   malformed
+    /// - [`ExpiringCodeError::Expired`] if `now` is at or after its expiry
+    pub fn verify(code: &str, now: u64) -> Result<Self, ExpiringCodeError> {
+        let code: Self = code.parse()?;
+        if code.is_expired(now) {
+            return Err(ExpiringCodeError::Expired { expires_at: code.expires_at });
+        }
+        Ok(code)
+    }
+
+    /// Returns when the code expires, in Unix seconds.
+    pub fn expires_at(&self) -> u64 {
+        self.expires_at
+    }
+
+    /// Returns `true` if the code has expired at `now` (Unix seconds).
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Returns the full code, including the expiry and check character.
+    pub fn as_vdstring(&self) -> &VDString<Default31> {
+        &self.code
+    }
+
+    /// Consumes the wrapper, returning the full code.
+    pub fn into_vdstring(self) -> VDString<Default31> {
+        self.code
+    }
+}
+
+impl FromStr for VDExpiringCode {
+    type Err = ExpiringCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_vdstring(s.parse()?)
+    }
+}
+
+impl TryFrom<&str> for VDExpiringCode {
+    type Error = ExpiringCodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Deref for VDExpiringCode {
+    type Target = VDString<Default31>;
+
+    fn deref(&self) -> &VDString<Default31> {
+        &self.code
+    }
+}
+
+impl fmt::Display for VDExpiringCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.code, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn round_trips_expiry_rounded_up_to_the_hour() {
+        let mut rng = crate::StepRng::new(0, 3);
+        for expires_at in [VDExpiringCode::EPOCH, VDExpiringCode::EPOCH + 1, 1_700_000_000, 1_700_003_600] {
+            let code = VDExpiringCode::generate(expires_at, 6, &mut rng).unwrap();
+            assert_eq!(code.len(), VDExpiringCode::EXPIRY_LEN + 7);
+            assert!(code.expires_at() >= expires_at && code.expires_at() - expires_at < 3_600);
+            assert_eq!(VDExpiringCode::verify(code.as_str(), expires_at - 1), Ok(code.clone()));
+            assert!(code.is_expired(code.expires_at()));
+        }
+
+        let last = VDExpiringCode::EPOCH + (BASE.pow(4) - 1) * 3_600;
+        assert!(VDExpiringCode::generate(last, 0, &mut rng).is_ok());
+        for t in [0, VDExpiringCode::EPOCH - 1, last + 1] {
+            assert_eq!(VDExpiringCode::generate(t, 0, &mut rng), Err(ExpiringCodeError::ExpiryOutOfRange(t)));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_codes() {
+        let code = VDExpiringCode::from_vdstring("CDEFXK29".parse::<VDString<Default31>>().unwrap().with_check_char()).unwrap();
+        let s = code.to_string();
+        let mut typo = s.clone().into_bytes();
+        typo.swap(4, 5);
+        let typo = core::str::from_utf8(&typo).unwrap();
+
+        assert_eq!(VDExpiringCode::verify(typo, 0), Err(ExpiringCodeError::CheckMismatch));
+        assert_eq!(VDExpiringCode::verify("CDEF", 0), Err(ExpiringCodeError::TooShort { len: 4 }));
+        assert_eq!(VDExpiringCode::verify("CDEO5", 0), Err(ExpiringCodeError::Invalid(VDStringError::InvalidChar { ch: 'O', offset: 3 })));
+        assert_eq!(VDExpiringCode::verify(&s, 0), Ok(code));
+    }
+}
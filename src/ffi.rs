@@ -0,0 +1,271 @@
+//! C-compatible functions for validating, generating, and checksumming codes.
+//!
+//! This module is only available when the `ffi` feature is enabled. The
+//! matching C declarations are in `include/vds.h`, which can be regenerated
+//! with `cbindgen --config cbindgen.toml --output include/vds.h`.
+//!
+//! Codes cross the boundary as ASCII byte buffers with an explicit length;
+//! nothing is NUL-terminated and nothing is allocated for the caller.
+//! Functions returning `i32` return [`VDS_OK`] or a negative `VDS_ERR_*`
+//! code.
+//!
+//! The crate is `no_std`, so it is linked into C programs through a small
+//! `staticlib` or `cdylib` crate that depends on `vds` with this feature and
+//! provides the panic handler. The exported symbols need no re-export.
+
+#![allow(unsafe_code)]
+
+use core::ffi::c_void;
+use core::slice;
+
+use rand_core::RngCore;
+
+use crate::alphabet::Default31;
+use crate::checksum::{ChecksumAlgorithm, Damm};
+use crate::{validate, VDChar, VDGenerator};
+
+/// The call succeeded.
+pub const VDS_OK: i32 = 0;
+/// A required pointer was null.
+pub const VDS_ERR_NULL: i32 = -1;
+/// The input contains a byte that is not an allowed character.
+pub const VDS_ERR_INVALID: i32 = -2;
+/// The generator could not satisfy its constraints, e.g. a
+/// [`VDS_NO_REPEATS`] code longer than the alphabet.
+pub const VDS_ERR_GENERATE: i32 = -3;
+
+/// Flag for [`vds_generate`]: no character appears twice.
+pub const VDS_NO_REPEATS: u32 = 1;
+/// Flag for [`vds_generate`]: no character appears twice in a row.
+pub const VDS_NO_ADJACENT_REPEATS: u32 = 1 << 1;
+
+/// Number of characters in [`VDS_ALPHABET`].
+pub const VDS_ALPHABET_LEN: usize = 31;
+
+/// The allowed characters as ASCII, in index order.
+#[no_mangle]
+pub static VDS_ALPHABET: [u8; VDS_ALPHABET_LEN] = *b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+
+/// Callback that fills `len` bytes at `buf` with random data.
+///
+/// `ctx` is passed through unchanged from [`vds_generate`]. Use a
+/// cryptographically secure source when codes must be unguessable.
+pub type VdsFillFn = extern "C" fn(ctx: *mut c_void, buf: *mut u8, len: usize);
+
+/// Adapts a C fill callback to [`RngCore`].
+struct CallbackRng {
+    fill: VdsFillFn,
+    ctx: *mut c_void,
+}
+
+impl RngCore for CallbackRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        (self.fill)(self.ctx, dest.as_mut_ptr(), dest.len());
+    }
+}
+
+/// Borrows `len` bytes at `ptr`, allowing a null `ptr` when `len` is `0`.
+///
+/// # Safety
+/// If `len` is non-zero, `ptr` must be null or valid for reads of `len` bytes.
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (ptr.is_null(), len) {
+        (true, 0) => Some(&[]),
+        (true, _) => None,
+        // SAFETY: `ptr` is non-null and the caller guarantees it is valid
+        // for `len` bytes.
+        (false, _) => Some(unsafe { slice::from_raw_parts(ptr, len) }),
+    }
+}
+
+/// Returns `true` if every byte of `code` is an allowed character.
+///
+/// Returns `false` if `code` is null and `len` is non-zero.
+///
+/// # Safety
+/// `code` must be null or valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn vds_is_valid(code: *const u8, len: usize) -> bool {
+    // SAFETY: forwarded from the caller.
+    unsafe { input(code, len) }.is_some_and(validate::is_valid)
+}
+
+/// Writes the check character for `payload` to `*out`.
+///
+/// The check character uses the Damm algorithm, as
+/// [`VDString::with_check_char`](crate::VDString::with_check_char) does.
+///
+/// # Safety
+/// `payload` must be null or valid for reads of `len` bytes, and `out` must
+/// be null or valid for a write of one byte.
+#[no_mangle]
+pub unsafe extern "C" fn vds_check_char(payload: *const u8, len: usize, out: *mut u8) -> i32 {
+    // SAFETY: forwarded from the caller.
+    let Some(payload) = (unsafe { input(payload, len) }) else {
+        return VDS_ERR_NULL;
+    };
+    if out.is_null() {
+        return VDS_ERR_NULL;
+    }
+    if !validate::is_valid(payload) {
+        return VDS_ERR_INVALID;
+    }
+    // Validated above, so the fallback is never taken.
+    let chars = payload.iter().map(|&b| VDChar::new(char::from(b)).unwrap_or(VDChar::ALL[0]));
+    let check = Damm.compute(chars);
+    // SAFETY: `out` is non-null and the caller guarantees it is writable.
+    unsafe { out.write(check.as_char() as u8) };
+    VDS_OK
+}
+
+/// Returns `true` if the last character of `code` is the correct check
+/// character for the rest, as computed by [`vds_check_char`].
+///
+/// # Safety
+/// `code` must be null or valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn vds_verify(code: *const u8, len: usize) -> bool {
+    // SAFETY: forwarded from the caller.
+    unsafe { input(code, len) }.is_some_and(|code| Damm.verify_ascii(code))
+}
+
+/// Generates a random code of `len` characters into `out`.
+///
+/// `flags` is a combination of [`VDS_NO_REPEATS`] and
+/// [`VDS_NO_ADJACENT_REPEATS`]. Randomness comes from `fill`, called with
+/// `ctx`. Exactly `len` bytes are written; no NUL terminator is added.
+///
+/// # Safety
+/// `out` must be null or valid for writes of `len` bytes, and `fill` must
+/// write the requested number of bytes to the buffer it is given.
+#[no_mangle]
+pub unsafe extern "C" fn vds_generate(
+    out: *mut u8,
+    len: usize,
+    flags: u32,
+    fill: Option<VdsFillFn>,
+    ctx: *mut c_void,
+) -> i32 {
+    let Some(fill) = fill else {
+        return VDS_ERR_NULL;
+    };
+    if out.is_null() && len > 0 {
+        return VDS_ERR_NULL;
+    }
+
+    let mut generator = VDGenerator::<Default31>::default().length(len);
+    if flags & VDS_NO_REPEATS != 0 {
+        generator = generator.no_repeats();
+    }
+    if flags & VDS_NO_ADJACENT_REPEATS != 0 {
+        generator = generator.no_adjacent_repeats();
+    }
+
+    let buf: &mut [VDChar<Default31>] = if len == 0 {
+        &mut []
+    } else {
+        // SAFETY: `out` is non-null (as `len > 0` here) and the caller
+        // guarantees it is writable for `len` bytes.
+        unsafe { out.write_bytes(0, len) };
+        // SAFETY: `VDChar` is `repr(transparent)` over its `repr(u8)` index,
+        // and every byte was just set to `0`, a valid index.
+        unsafe { slice::from_raw_parts_mut(out.cast::<VDChar<Default31>>(), len) }
+    };
+    let mut rng = CallbackRng { fill, ctx };
+    let Ok(code) = generator.generate_into(buf, &mut rng) else {
+        return VDS_ERR_GENERATE;
+    };
+    if !code.is_empty() {
+        // SAFETY: as above; the `VDChar` view of the buffer is no longer used.
+        let bytes = unsafe { slice::from_raw_parts_mut(out, len) };
+        // Each byte holds a character's index; replace it with the character.
+        for b in bytes {
+            *b = VDS_ALPHABET[usize::from(*b)];
+        }
+    }
+    VDS_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VDS_ALLOWED;
+    use core::ptr;
+
+    extern "C" fn counter(ctx: *mut c_void, buf: *mut u8, len: usize) {
+        // SAFETY: tests pass a `u8` counter as `ctx` and a valid buffer.
+        unsafe {
+            let next = &mut *ctx.cast::<u8>();
+            for i in 0..len {
+                *buf.add(i) = *next;
+                *next = next.wrapping_add(37);
+            }
+        }
+    }
+
+    #[test]
+    fn alphabet_matches_allowed_set() {
+        assert_eq!(VDS_ALPHABET.len(), VDS_ALLOWED.len());
+        assert!(VDS_ALPHABET.iter().zip(VDS_ALLOWED).all(|(&b, &c)| char::from(b) == c));
+    }
+
+    #[test]
+    fn validates_buffers() {
+        unsafe {
+            assert!(vds_is_valid(b"AB29".as_ptr(), 4));
+            assert!(!vds_is_valid(b"AB20".as_ptr(), 4));
+            assert!(vds_is_valid(ptr::null(), 0));
+            assert!(!vds_is_valid(ptr::null(), 3));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn check_char_matches_vdcode() {
+        let payload: crate::VDString<Default31> = "K7M3PQ".parse().unwrap();
+        let expected = payload.with_check_char();
+        let mut out = 0u8;
+        unsafe {
+            assert_eq!(vds_check_char(b"K7M3PQ".as_ptr(), 6, &mut out), VDS_OK);
+            assert_eq!(char::from(out), expected[6].as_char());
+            assert!(vds_verify(b"K7M3PQ".as_ptr(), 6) == payload.verify_check_char());
+            assert_eq!(vds_check_char(b"K7M0".as_ptr(), 4, &mut out), VDS_ERR_INVALID);
+            assert_eq!(vds_check_char(b"K7M".as_ptr(), 3, ptr::null_mut()), VDS_ERR_NULL);
+        }
+        let mut full = [0u8; 7];
+        for (b, c) in full.iter_mut().zip(expected.iter()) {
+            *b = c.as_char() as u8;
+        }
+        assert!(unsafe { vds_verify(full.as_ptr(), 7) });
+    }
+
+    #[test]
+    fn generates_with_flags() {
+        let mut state = 0u8;
+        let ctx = ptr::from_mut(&mut state).cast::<c_void>();
+        let mut out = [0u8; 32];
+        unsafe {
+            assert_eq!(vds_generate(out.as_mut_ptr(), 32, VDS_NO_REPEATS, Some(counter), ctx), VDS_ERR_GENERATE);
+            assert_eq!(vds_generate(out.as_mut_ptr(), 12, VDS_NO_REPEATS, Some(counter), ctx), VDS_OK);
+            assert!(vds_is_valid(out.as_ptr(), 12));
+            assert_eq!(vds_generate(out.as_mut_ptr(), 12, 0, None, ctx), VDS_ERR_NULL);
+            assert_eq!(vds_generate(ptr::null_mut(), 0, 0, Some(counter), ctx), VDS_OK);
+        }
+        let code = &out[..12];
+        for (i, b) in code.iter().enumerate() {
+            assert!(!code[i + 1..].contains(b));
+        }
+    }
+}
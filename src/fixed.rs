@@ -0,0 +1,255 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::alphabet::Default31;
+use crate::vdchar::ASCII_INDEX;
+use crate::{validate, VDChar, VDStr};
+
+/// Error returned when building or parsing a [`VDStringFixed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VDStringFixedError {
+    /// A character in the input was not part of the allowed set.
+    InvalidChar(char),
+    /// The input does not have exactly the required number of characters.
+    WrongLength {
+        /// The number of characters in the input.
+        len: usize,
+        /// The required number of characters.
+        expected: usize,
+    },
+}
+
+impl fmt::Display for VDStringFixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VDStringFixedError::InvalidChar(c) => write!(f, "invalid character {:?}", c),
+            VDStringFixedError::WrongLength { len, expected } => {
+                write!(f, "expected {} characters, found {}", expected, len)
+            }
+        }
+    }
+}
+
+impl core::error::Error for VDStringFixedError {}
+
+/// A string of exactly `N` [`VDChar`]s, stored inline.
+///
+/// Where [`VDArrayString`](crate::VDArrayString) holds up to `N`
+/// characters, every `VDStringFixed<N>` holds exactly `N`, so a field typed
+/// `VDStringFixed<8>` can only ever hold an 8-character code. Parsing,
+/// `TryFrom`, and serde reject any other length. Dereferences to [`VDStr`],
+/// is `Copy`, and needs no allocator.
+///
+/// # Examples
+/// ```
+/// use vds::{VDStringFixed, VDStringFixedError};
+///
+/// let code: VDStringFixed<8> = "AB29XY7K".parse().unwrap();
+/// assert_eq!(code, "AB29XY7K");
+/// assert_eq!(code.to_string(), "AB29XY7K");
+///
+/// assert_eq!(
+///     "AB29".parse::<VDStringFixed<8>>(),
+///     Err(VDStringFixedError::WrongLength { len: 4, expected: 8 })
+/// );
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct VDStringFixed<const N: usize> {
+    chars: [VDChar<Default31>; N],
+}
+
+impl<const N: usize> VDStringFixed<N> {
+    /// Creates a string from exactly `N` characters.
+    pub const fn new(chars: [VDChar<Default31>; N]) -> Self {
+        Self { chars }
+    }
+
+    /// Creates a string holding a copy of `chars`.
+    ///
+    /// # Errors
+    /// Returns [`VDStringFixedError::WrongLength`] unless `chars` has
+    /// exactly `N` characters.
+    pub fn from_vdchars(chars: &[VDChar<Default31>]) -> Result<Self, VDStringFixedError> {
+        chars
+            .try_into()
+            .map(Self::new)
+            .map_err(|_| VDStringFixedError::WrongLength { len: chars.len(), expected: N })
+    }
+
+    /// Returns the characters as an array.
+    pub const fn as_array(&self) -> &[VDChar<Default31>; N] {
+        &self.chars
+    }
+
+    /// Consumes the string, returning its characters.
+    pub const fn into_array(self) -> [VDChar<Default31>; N] {
+        self.chars
+    }
+
+    /// Borrows the characters as a [`VDStr`].
+    pub fn as_vdstr(&self) -> &VDStr<Default31> {
+        VDStr::from_vdchars(&self.chars)
+    }
+
+    /// Renders the characters as ASCII bytes.
+    pub(crate) fn to_ascii(self) -> [u8; N] {
+        self.chars.map(|ch| ch.as_char() as u8)
+    }
+}
+
+impl<const N: usize> Deref for VDStringFixed<N> {
+    type Target = VDStr<Default31>;
+
+    fn deref(&self) -> &VDStr<Default31> {
+        self.as_vdstr()
+    }
+}
+
+impl<const N: usize> fmt::Display for VDStringFixed<N> {
+    /// Honors width, fill, alignment, and precision like a `str`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_vdstr(), f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for VDStringFixed<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if cfg!(feature = "redact-debug") {
+            write!(f, "VDStringFixed(REDACTED, len={})", N)
+        } else {
+            let bytes = self.to_ascii();
+            f.debug_tuple("VDStringFixed").field(&core::str::from_utf8(&bytes).unwrap_or_default()).finish()
+        }
+    }
+}
+
+impl<const N: usize> Hash for VDStringFixed<N> {
+    /// Hashes like the borrowed [`VDStr`].
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_vdstr().hash(state);
+    }
+}
+
+impl<const N: usize> PartialOrd for VDStringFixed<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for VDStringFixed<N> {
+    /// Orders like the borrowed [`VDStr`], i.e. by alphabet index.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_vdstr().cmp(other.as_vdstr())
+    }
+}
+
+impl<const N: usize> PartialEq<str> for VDStringFixed<N> {
+    fn eq(&self, other: &str) -> bool {
+        *self.as_vdstr() == *other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for VDStringFixed<N> {
+    fn eq(&self, other: &&str) -> bool {
+        *self.as_vdstr() == **other
+    }
+}
+
+impl<const N: usize> FromStr for VDStringFixed<N> {
+    type Err = VDStringFixedError;
+
+    /// Parses a `&str`, validating each character.
+    ///
+    /// Returns the first invalid character, or
+    /// [`VDStringFixedError::WrongLength`] if every character is valid but
+    /// there are not exactly `N` of them.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = [VDChar::from_index_unchecked(0); N];
+        if s.len() == N && validate::find_invalid::<Default31>(s.as_bytes()).is_none() {
+            for (ch, &b) in chars.iter_mut().zip(s.as_bytes()) {
+                *ch = VDChar::from_index_unchecked(ASCII_INDEX[usize::from(b)]);
+            }
+            return Ok(Self::new(chars));
+        }
+        let mut len = 0;
+        for c in s.chars() {
+            let ch = VDChar::new(c).ok_or(VDStringFixedError::InvalidChar(c))?;
+            if let Some(slot) = chars.get_mut(len) {
+                *slot = ch;
+            }
+            len += 1;
+        }
+        if len != N {
+            return Err(VDStringFixedError::WrongLength { len, expected: N });
+        }
+        Ok(Self::new(chars))
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for VDStringFixed<N> {
+    type Error = VDStringFixedError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<const N: usize> From<[VDChar<Default31>; N]> for VDStringFixed<N> {
+    fn from(chars: [VDChar<Default31>; N]) -> Self {
+        Self::new(chars)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> From<VDStringFixed<N>> for crate::VDString<Default31> {
+    fn from(s: VDStringFixed<N>) -> Self {
+        crate::VDString::new(s.chars.to_vec())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> TryFrom<&crate::VDString<Default31>> for VDStringFixed<N> {
+    type Error = VDStringFixedError;
+
+    fn try_from(s: &crate::VDString<Default31>) -> Result<Self, Self::Error> {
+        Self::from_vdchars(s.as_vdchars())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_exactly_n() {
+        let s: VDStringFixed<4> = "M29W".parse().unwrap();
+        assert_eq!(s, "M29W");
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.as_array()[0], VDChar::<Default31>::new('M').unwrap());
+
+        for (input, len) in [("", 0), ("M29", 3), ("M29WX", 5)] {
+            assert_eq!(input.parse::<VDStringFixed<4>>(), Err(VDStringFixedError::WrongLength { len, expected: 4 }));
+        }
+        assert_eq!("Ｍ29W".parse::<VDStringFixed<4>>(), Err(VDStringFixedError::InvalidChar('Ｍ')));
+        assert_eq!("M2O".parse::<VDStringFixed<4>>(), Err(VDStringFixedError::InvalidChar('O')));
+        assert_eq!("M2OWX".parse::<VDStringFixed<4>>(), Err(VDStringFixedError::InvalidChar('O')));
+        assert!("".parse::<VDStringFixed<0>>().is_ok());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn converts_to_and_from_vdstring() {
+        extern crate alloc;
+        let code: crate::VDString<Default31> = "K7M3".parse().unwrap();
+        let fixed = VDStringFixed::<4>::try_from(&code).unwrap();
+        assert_eq!(crate::VDString::<Default31>::from(fixed), code);
+        assert_eq!(
+            VDStringFixed::<5>::try_from(&code),
+            Err(VDStringFixedError::WrongLength { len: 4, expected: 5 })
+        );
+        assert_eq!(alloc::format!("{:>6}", fixed), "  K7M3");
+    }
+}
@@ -0,0 +1,132 @@
+//! Fuzzy lookup of noisy input among issued codes.
+//!
+//! For "we couldn't find that code — did you mean …?" flows: when typed or
+//! read-back input matches no code exactly, [`best_match`] finds the
+//! closest issued code by [confusable distance](crate::distance), so that
+//! swapping `B` for `8` counts for less than an unrelated typo.
+//!
+//! Input is canonicalized first: spaces, tabs, and hyphens are dropped, and
+//! lowercase, fullwidth, and look-alike characters are folded as by
+//! [`canonicalize_char`]. Characters outside the alphabet, such as `0` or
+//! `O`, are kept and simply never match, so the input need not be a valid
+//! code.
+//!
+//! # Examples
+//! ```
+//! use vds::{fuzzy, VDString};
+//!
+//! let issued: Vec<VDString> = ["AB29XY", "K7M3PQ", "ZZ42RT"].iter().map(|s| s.parse().unwrap()).collect();
+//!
+//! let found = fuzzy::best_match("k7m-3pg", issued.iter(), 1.0).unwrap();
+//! assert_eq!(found.code, "K7M3PQ");
+//! assert_eq!(found.distance, 1.0);
+//!
+//! assert!(fuzzy::best_match("QQQQQQ", issued.iter(), 1.0).is_none());
+//! ```
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::alphabet::Default31;
+use crate::distance::{weighted_edit_distance, ErrorModel, VisualModel};
+use crate::homoglyph::canonicalize_char;
+use crate::{VDChar, VDString};
+
+/// The closest candidate found by [`best_match`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuzzyMatch<'a> {
+    /// The matched candidate.
+    pub code: &'a VDString<Default31>,
+    /// Weighted edit distance from the canonicalized input to `code`;
+    /// `0.0` for an exact match.
+    pub distance: f64,
+}
+
+/// Returns the candidate closest to `input` by [`VisualModel`] distance, if
+/// it is within `max_distance`.
+///
+/// Ties go to the earliest candidate. See the [module docs](self) for how
+/// `input` is canonicalized.
+pub fn best_match<'a, I>(input: &str, candidates: I, max_distance: f64) -> Option<FuzzyMatch<'a>>
+where
+    I: IntoIterator<Item = &'a VDString<Default31>>,
+{
+    best_match_with(input, candidates, max_distance, &VisualModel)
+}
+
+/// Like [`best_match`], with the costs given by `model`.
+///
+/// Pick the model for the channel the input came through, e.g.
+/// [`KeyboardModel`](crate::distance::KeyboardModel) for a web form or
+/// [`PhoneticModel`](crate::distance::PhoneticModel) for a call center.
+///
+/// # Examples
+/// ```
+/// use vds::distance::{KeyboardModel, PhoneticModel};
+/// use vds::{fuzzy, VDString};
+///
+/// let issued: Vec<VDString> = ["AB29", "AM29"].iter().map(|s| s.parse().unwrap()).collect();
+///
+/// // `N` is next to `B` on a keyboard, and sounds like `M` over the phone.
+/// let typed = fuzzy::best_match_with("AN29", issued.iter(), 1.0, &KeyboardModel).unwrap();
+/// assert_eq!((typed.code.as_str(), typed.distance), ("AB29", 0.5));
+/// let heard = fuzzy::best_match_with("AN29", issued.iter(), 1.0, &PhoneticModel).unwrap();
+/// assert_eq!((heard.code.as_str(), heard.distance), ("AM29", 0.4));
+/// ```
+pub fn best_match_with<'a, I, M>(input: &str, candidates: I, max_distance: f64, model: &M) -> Option<FuzzyMatch<'a>>
+where
+    I: IntoIterator<Item = &'a VDString<Default31>>,
+    M: ErrorModel + ?Sized,
+{
+    let input: Vec<char> = input
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '\t' | '-'))
+        .map(canonicalize_char)
+        .collect();
+
+    let mut best: Option<FuzzyMatch<'a>> = None;
+    for code in candidates {
+        let chars = code.iter().map(VDChar::as_char);
+        let distance = weighted_edit_distance(input.iter().copied(), chars, model);
+        if distance <= max_distance && best.is_none_or(|b| distance < b.distance) {
+            best = Some(FuzzyMatch { code, distance });
+            if distance == 0.0 {
+                break;
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(list: &[&str]) -> Vec<VDString<Default31>> {
+        list.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn exact_match_after_canonicalization() {
+        let issued = codes(&["AB29XY", "K7M3PQ"]);
+        let found = best_match(" k7ｍ-3pq ", &issued, 0.0).unwrap();
+        assert_eq!(found.code, "K7M3PQ");
+        assert_eq!(found.distance, 0.0);
+    }
+
+    #[test]
+    fn excluded_characters_cost_a_substitution() {
+        let issued = codes(&["AB29XY"]);
+        let found = best_match("AB2OXY", &issued, 1.0).unwrap();
+        assert_eq!(found.distance, 1.0);
+        assert!(best_match("AB2OXY", &issued, 0.9).is_none());
+    }
+
+    #[test]
+    fn ties_go_to_earliest_candidate() {
+        let issued = codes(&["AB2", "AB3", "AB4"]);
+        assert_eq!(best_match("AB", &issued, 2.0).unwrap().code, "AB2");
+        assert_eq!(best_match("AB4", &issued, 2.0).unwrap().code, "AB4");
+        assert!(best_match("AB", &[], 2.0).is_none());
+    }
+}
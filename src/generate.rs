@@ -1,26 +1,211 @@
+#[cfg(feature = "alloc")]
 extern crate alloc;
-use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeSet, string::String, vec, vec::Vec};
 
-use rand_core::RngCore;
-use crate::{VDChar, VDString};
-use crate::vdchar::VDS_ALLOWED;
+use core::fmt;
+use core::marker::PhantomData;
+
+#[cfg(feature = "alloc")]
+use rand_core::CryptoRng;
+use crate::alphabet::{Alphabet, DefaultAlphabet};
+#[cfg(feature = "alloc")]
+use crate::alphabet::CustomAlphabet;
+use crate::{uniform_index, VDChar, VDRng, VDStr};
+#[cfg(feature = "alloc")]
+use crate::VDString;
 
 /// Error returned when [`VDGenerator::generate`] is called with an invalid configuration.
 ///
 /// This type is only available when the `generate` feature is enabled.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum VDGeneratorError {
     /// The requested output length exceeds the number of unique characters available.
     ///
-    /// This error occurs when `no_repeats` is enabled and `length > VDS_ALLOWED.len()`.
+    /// This error occurs when `no_repeats` is enabled and the length exceeds
+    /// the alphabet size.
     LengthExceedsUniqueSet {
         /// The requested number of characters.
         requested: usize,
         /// The number of distinct characters available.
         available: usize,
     },
+    /// More distinct codes were requested than the configuration can produce.
+    ///
+    /// Returned by [`VDGenerator::generate_many`].
+    NotEnoughDistinctCodes {
+        /// The requested number of codes.
+        requested: usize,
+        /// The number of distinct codes the configuration can produce.
+        available: u128,
+    },
+    /// [`VDGenerator::generate_many`] could not find enough codes at the
+    /// configured [`min_distance`](VDGenerator::min_distance) from each other.
+    MinDistanceUnreachable {
+        /// The requested number of codes.
+        requested: usize,
+        /// The number of codes found before giving up.
+        found: usize,
+    },
+    /// [`max_attempts`](VDGenerator::max_attempts) candidates in a row
+    /// contained a denied substring.
+    ///
+    /// This error occurs when the deny list covers (almost) every code of
+    /// the configured length.
+    DeniedSubstringsUnavoidable {
+        /// The number of candidates generated before giving up.
+        attempts: usize,
+    },
+    /// [`max_attempts`](VDGenerator::max_attempts) candidates in a row were
+    /// too close to an [avoided](VDGenerator::avoid) code.
+    AvoidedCodesUnavoidable {
+        /// The number of candidates generated before giving up.
+        attempts: usize,
+    },
+    /// The [`prefix`](VDGenerator::prefix) and [`suffix`](VDGenerator::suffix)
+    /// together are longer than the configured length.
+    AffixesExceedLength {
+        /// The configured total length.
+        length: usize,
+        /// The combined length of the prefix and suffix.
+        affixes: usize,
+    },
+    /// The prefix and suffix break the repeat constraints on their own, or
+    /// leave no random part that satisfies them.
+    ///
+    /// For example, a prefix `"AA"` with `no_adjacent_repeats`, or a prefix
+    /// and suffix sharing a character with `no_repeats`.
+    AffixesViolateConstraints,
+    /// [`max_attempts`](VDGenerator::max_attempts) candidates in a row
+    /// contained a sequential run.
+    ///
+    /// This error occurs when [`no_sequential_runs`](VDGenerator::no_sequential_runs)
+    /// is set so short that (almost) every code contains a run.
+    SequentialRunsUnavoidable {
+        /// The number of candidates generated before giving up.
+        attempts: usize,
+    },
+    /// [`min_digits`](VDGenerator::min_digits) and
+    /// [`min_letters`](VDGenerator::min_letters) cannot be met.
+    ///
+    /// Checked up front against the length and the characters available;
+    /// also returned if [`max_attempts`](VDGenerator::max_attempts)
+    /// candidates in a row fell short, e.g. because the repeat constraints
+    /// leave too few digits.
+    CompositionInfeasible,
+    /// The buffer passed to [`generate_into`](VDGenerator::generate_into)
+    /// is shorter than the configured length.
+    ///
+    /// Without the `alloc` feature, also returned by
+    /// [`generate_to`](VDGenerator::generate_to) for codes longer than its
+    /// [`SCRATCH_LEN`](VDGenerator::SCRATCH_LEN) stack buffer.
+    BufferTooSmall {
+        /// The configured length.
+        required: usize,
+        /// The length of the buffer.
+        available: usize,
+    },
+    /// [`exclude_chars`](VDGenerator::exclude_chars) removed every
+    /// character of the alphabet, so nothing is left to draw.
+    AllCharsExcluded,
+    /// The repeat constraints could not be met.
+    ///
+    /// Returned up front if no code satisfies them, e.g. `no_adjacent_repeats`
+    /// over a one-character alphabet, and otherwise once a generation has
+    /// redrawn [`max_attempts`](VDGenerator::max_attempts) characters, which
+    /// only a broken or constant RNG should cause.
+    ConstraintUnsatisfiable {
+        /// The number of redraws made before giving up (`0` if the
+        /// configuration was rejected up front).
+        attempts: usize,
+    },
+    /// The sink passed to [`generate_to`](VDGenerator::generate_to)
+    /// returned an error.
+    WriteFailed,
+    /// The operating system's RNG could not supply entropy.
+    ///
+    /// Returned by [`generate_with_os_rng`](VDGenerator::generate_with_os_rng).
+    EntropyUnavailable,
+    /// The bytes passed to
+    /// [`generate_from_entropy`](VDGenerator::generate_from_entropy) ran out
+    /// before the code was complete.
+    EntropyExhausted {
+        /// The number of bytes supplied.
+        provided: usize,
+    },
+    /// Every candidate drawn by
+    /// [`generate_unique`](VDGenerator::generate_unique) already existed.
+    NoUniqueCode {
+        /// The number of candidates drawn before giving up.
+        attempts: usize,
+    },
+}
+
+impl fmt::Display for VDGeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VDGeneratorError::LengthExceedsUniqueSet { requested, available } => write!(
+                f,
+                "length {} exceeds the {} distinct characters available",
+                requested, available
+            ),
+            VDGeneratorError::NotEnoughDistinctCodes { requested, available } => write!(
+                f,
+                "requested {} distinct codes, but the configuration can produce only {}",
+                requested, available
+            ),
+            VDGeneratorError::MinDistanceUnreachable { requested, found } => write!(
+                f,
+                "found only {} of {} codes at the minimum distance",
+                found, requested
+            ),
+            VDGeneratorError::DeniedSubstringsUnavoidable { attempts } => {
+                write!(f, "all {} candidates contained a denied substring", attempts)
+            }
+            VDGeneratorError::AvoidedCodesUnavoidable { attempts } => {
+                write!(f, "all {} candidates were too close to an avoided code", attempts)
+            }
+            VDGeneratorError::AffixesExceedLength { length, affixes } => write!(
+                f,
+                "prefix and suffix ({} characters) exceed the length {}",
+                affixes, length
+            ),
+            VDGeneratorError::AffixesViolateConstraints => {
+                f.write_str("prefix and suffix violate the repeat constraints")
+            }
+            VDGeneratorError::SequentialRunsUnavoidable { attempts } => {
+                write!(f, "all {} candidates contained a sequential run", attempts)
+            }
+            VDGeneratorError::CompositionInfeasible => {
+                f.write_str("minimum digit and letter counts cannot be met")
+            }
+            VDGeneratorError::BufferTooSmall { required, available } => write!(
+                f,
+                "buffer of {} characters is too small for length {}",
+                available, required
+            ),
+            VDGeneratorError::AllCharsExcluded => f.write_str("every character of the alphabet is excluded"),
+            VDGeneratorError::ConstraintUnsatisfiable { attempts: 0 } => {
+                f.write_str("no code satisfies the repeat constraints")
+            }
+            VDGeneratorError::ConstraintUnsatisfiable { attempts } => {
+                write!(f, "repeat constraints still unmet after {} redraws", attempts)
+            }
+            VDGeneratorError::WriteFailed => f.write_str("writing the generated code failed"),
+            VDGeneratorError::EntropyUnavailable => f.write_str("the operating system RNG is unavailable"),
+            VDGeneratorError::NoUniqueCode { attempts } => {
+                write!(f, "all {} candidates already existed", attempts)
+            }
+            VDGeneratorError::EntropyExhausted { provided } => {
+                write!(f, "{} bytes of entropy ran out before the code was complete", provided)
+            }
+        }
+    }
 }
 
+impl core::error::Error for VDGeneratorError {}
+
 /// A builder-style configuration for generating random [`VDString`]s.
 ///
 /// This generator creates strings made up of [`VDChar`]s — characters from a curated
@@ -29,16 +214,18 @@ pub enum VDGeneratorError {
 /// You can customize the output length and control whether repeated or adjacent characters
 /// are allowed.
 ///
+/// Generates over [`DefaultAlphabet`] unless another [`Alphabet`] is given, e.g.
+/// `VDGenerator::<MyAlphabet>::default()`.
+///
 /// This type is only available when the `generate` feature is enabled.
 ///
 /// # Examples
 ///
 /// ```
-/// use rand::rngs::SmallRng;
-/// use rand::SeedableRng;
-/// use vds::{VDGenerator, VDString};
+/// # #[cfg(feature = "alloc")] {
+/// use vds::{StepRng, VDGenerator, VDString};
 ///
-/// let mut rng = SmallRng::seed_from_u64(123);
+/// let mut rng = StepRng::new(0, 7);
 ///
 /// let result: VDString = VDGenerator::new()
 ///     .length(8)
@@ -48,13 +235,245 @@ pub enum VDGeneratorError {
 ///     .unwrap();
 ///
 /// assert_eq!(result.len(), 8);
+/// # }
 /// ```
-pub struct VDGenerator {
+#[derive(Debug, Clone)]
+pub struct VDGenerator<A: Alphabet = DefaultAlphabet> {
+    pub(crate) len: usize,
+    pub(crate) no_adjacent_repeats: bool,
+    pub(crate) no_repeats: bool,
+    pub(crate) min_distance: usize,
+    #[cfg(feature = "alloc")]
+    pub(crate) denied: Vec<Vec<char>>,
+    #[cfg(feature = "alloc")]
+    pub(crate) avoided: Vec<Vec<VDChar<A>>>,
+    #[cfg(feature = "alloc")]
+    pub(crate) avoid_distance: usize,
+    #[cfg(feature = "alloc")]
+    pub(crate) prefix: Vec<VDChar<A>>,
+    #[cfg(feature = "alloc")]
+    pub(crate) suffix: Vec<VDChar<A>>,
+    #[cfg(feature = "alloc")]
+    pub(crate) excluded: Vec<VDChar<A>>,
+    pub(crate) max_run: Option<usize>,
+    pub(crate) min_digits: usize,
+    pub(crate) min_letters: usize,
+    pub(crate) max_attempts: usize,
+    pub(crate) alphabet: PhantomData<A>,
+}
+
+/// The random part of a code: how long it is, which alphabet indices it may
+/// use, and the fixed characters on either side of it.
+#[derive(Debug, Clone)]
+struct Layout {
     len: usize,
-    no_adjacent_repeats: bool,
-    no_repeats: bool,
+    // A fixed array, since alphabet indices are `u8`s, so that generation
+    // works without `alloc`.
+    pool: [u8; 256],
+    pool_len: usize,
+    before: Option<u8>,
+    after: Option<u8>,
+}
+
+/// A validated [`VDGenerator`] with its character pool precomputed.
+///
+/// Returned by [`VDGenerator::build`]. Configuration errors are reported
+/// once, by `build`, so generating only fails if a filter such as a deny
+/// list exhausts [`max_attempts`](VDGenerator::max_attempts). Prefer this over calling
+/// [`VDGenerator::generate`] repeatedly, which revalidates every time.
+///
+/// This type is only available when the `generate` feature is enabled.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::{StepRng, VDGenerator};
+///
+/// let gen = VDGenerator::new().length(8).no_adjacent_repeats().build().unwrap();
+/// let mut rng = StepRng::new(0, 7);
+/// for _ in 0..3 {
+///     assert_eq!(gen.generate(&mut rng).unwrap().len(), 8);
+/// }
+///
+/// assert!(VDGenerator::new().length(32).no_repeats().build().is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompiledGenerator<A: Alphabet = DefaultAlphabet> {
+    generator: VDGenerator<A>,
+    layout: Layout,
+    entropy_bits: f64,
+}
+
+impl<A: Alphabet> CompiledGenerator<A> {
+    /// Generates a [`VDString`], as [`VDGenerator::generate`] does.
+    ///
+    /// # Errors
+    /// Only the candidate-limit errors, such as
+    /// [`VDGeneratorError::DeniedSubstringsUnavoidable`].
+    #[cfg(feature = "alloc")]
+    pub fn generate<R: VDRng + ?Sized>(&self, rng: &mut R) -> Result<VDString<A>, VDGeneratorError> {
+        self.generate_audited(rng).map(|generated| generated.code)
+    }
+
+    /// Generates a code with metadata, as [`VDGenerator::generate_audited`] does.
+    ///
+    /// # Errors
+    /// Same as [`generate`](Self::generate).
+    #[cfg(feature = "alloc")]
+    pub fn generate_audited<R: VDRng + ?Sized>(&self, rng: &mut R) -> Result<GeneratedCode<A>, VDGeneratorError> {
+        self.generator.generate_audited_in(&self.layout, rng)
+    }
+
+    /// Generates a [`VDString`], reporting how it went to `observer`, as
+    /// [`VDGenerator::generate_observed`] does.
+    ///
+    /// # Errors
+    /// Same as [`generate`](Self::generate).
+    #[cfg(feature = "alloc")]
+    pub fn generate_observed<R, O>(&self, rng: &mut R, observer: &mut O) -> Result<VDString<A>, VDGeneratorError>
+    where
+        R: VDRng + ?Sized,
+        O: GenerationObserver + ?Sized,
+    {
+        self.generator.generate_observed_in(&self.layout, rng, observer)
+    }
+
+    /// Generates a code into `buf` without allocating, as
+    /// [`VDGenerator::generate_into`] does.
+    ///
+    /// # Errors
+    /// [`VDGeneratorError::BufferTooSmall`], or the candidate-limit errors
+    /// of [`generate`](VDGenerator::generate).
+    pub fn generate_into<'b, R: VDRng + ?Sized>(
+        &self,
+        buf: &'b mut [VDChar<A>],
+        rng: &mut R,
+    ) -> Result<&'b VDStr<A>, VDGeneratorError> {
+        self.generator.generate_into_in(&self.layout, buf, rng)
+    }
+
+    /// Generates a code into a [`heapless::Vec`] without allocating, as
+    /// [`VDGenerator::generate_into_heapless`] does.
+    ///
+    /// # Errors
+    /// [`VDGeneratorError::BufferTooSmall`], or the candidate-limit errors
+    /// of [`generate`](VDGenerator::generate).
+    #[cfg(feature = "heapless")]
+    pub fn generate_into_heapless<const N: usize, R: VDRng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<heapless::Vec<VDChar<A>, N>, VDGeneratorError> {
+        let mut buf = heapless::Vec::new();
+        let len = self.generator.generate_into_in(&self.layout, heapless_buf(&mut buf), rng)?.len();
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    /// Writes a generated code to `out`, as [`VDGenerator::generate_to`] does.
+    ///
+    /// # Errors
+    /// [`VDGeneratorError::WriteFailed`], or the candidate-limit errors of
+    /// [`generate`](VDGenerator::generate).
+    pub fn generate_to<W: fmt::Write + ?Sized, R: VDRng + ?Sized>(
+        &self,
+        out: &mut W,
+        rng: &mut R,
+    ) -> Result<(), VDGeneratorError> {
+        self.generator.generate_to_in(&self.layout, out, rng)
+    }
+
+    /// Writes a generated code to `out`, as
+    /// [`VDGenerator::generate_to_writer`] does.
+    ///
+    /// This method is only available when the `std` feature is enabled.
+    ///
+    /// # Errors
+    /// Same as [`VDGenerator::generate_to_writer`].
+    #[cfg(feature = "std")]
+    pub fn generate_to_writer<W: std::io::Write + ?Sized, R: VDRng + ?Sized>(
+        &self,
+        out: &mut W,
+        rng: &mut R,
+    ) -> std::io::Result<()> {
+        let mut out = IoAdapter { inner: out, error: None };
+        self.generate_to(&mut out, rng).map_err(|err| out.into_error(err))
+    }
+
+    /// Returns the length of generated codes, including any prefix and suffix.
+    pub fn code_len(&self) -> usize {
+        self.generator.len
+    }
+
+    /// Returns the bits of entropy of generated codes, as
+    /// [`VDGenerator::entropy_bits`] does, computed once by `build`.
+    pub fn entropy_bits(&self) -> f64 {
+        self.entropy_bits
+    }
+}
+
+/// Storage for one drawn character: a bare alphabet index, or a [`VDChar`].
+trait Slot: Copy + PartialEq {
+    fn from_index(index: u8) -> Self;
+    fn index(self) -> u8;
+}
+
+impl Slot for u8 {
+    fn from_index(index: u8) -> Self {
+        index
+    }
+
+    fn index(self) -> u8 {
+        self
+    }
+}
+
+impl<A: Alphabet> Slot for VDChar<A> {
+    fn from_index(index: u8) -> Self {
+        VDChar::from_index_unchecked(index)
+    }
+
+    fn index(self) -> u8 {
+        VDChar::index(self)
+    }
+}
+
+/// Length of the stack buffer behind [`VDGenerator::SCRATCH_LEN`].
+const SCRATCH_LEN: usize = 64;
+
+/// Adapts an `io::Write` to `fmt::Write`, keeping the first I/O error.
+#[cfg(feature = "std")]
+struct IoAdapter<'a, W: std::io::Write + ?Sized> {
+    inner: &'a mut W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> IoAdapter<'_, W> {
+    /// Returns the I/O error behind `err`, or `err` wrapped as one.
+    fn into_error(self, err: VDGeneratorError) -> std::io::Error {
+        match (err, self.error) {
+            (VDGeneratorError::WriteFailed, Some(io)) => io,
+            (err, _) => std::io::Error::other(err),
+        }
+    }
 }
 
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Collisions [`VDGenerator::generate_unique`] allows at one length before
+/// lengthening the code by one character.
+#[cfg(feature = "alloc")]
+const COLLISIONS_PER_LENGTH: usize = 3;
+
 impl VDGenerator {
     /// Creates a new generator with default settings.
     ///
@@ -62,15 +481,88 @@ impl VDGenerator {
     /// - Length: 6
     /// - Adjacent repeats: allowed
     /// - Any repeats: allowed
+    /// - Minimum distance between batch codes: 1
+    /// - Prefix and suffix: none
+    /// - Excluded characters: none
+    /// - Sequential runs: allowed
+    /// - Minimum digits and letters: 0
+    /// - Max attempts: 10,000
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generates a string over a runtime [`CustomAlphabet`], honoring the
+    /// same length and repeat settings as [`generate`](Self::generate).
+    ///
+    /// The prefix and suffix are copied as is, even if they contain
+    /// characters outside `alphabet`.
+    ///
+    /// # Errors
+    /// Same as [`generate`](Self::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::alphabet::CustomAlphabetBuilder;
+    /// use vds::{StepRng, VDGenerator};
+    ///
+    /// let alphabet = CustomAlphabetBuilder::new().chars("ACEHKMRTW3479".chars()).build().unwrap();
+    /// let mut rng = StepRng::new(0, 7);
+    ///
+    /// let code = VDGenerator::new().length(8).generate_in(&alphabet, &mut rng).unwrap();
+    /// assert!(alphabet.is_valid(&code));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn generate_in<R: VDRng + ?Sized>(
+        &self,
+        alphabet: &CustomAlphabet,
+        rng: &mut R,
+    ) -> Result<String, VDGeneratorError> {
+        let layout = self.layout(alphabet.chars())?;
+        let mut indices = vec![0; layout.len];
+        self.draw(&layout, alphabet.chars(), &mut indices, rng)?;
+        let random = indices.into_iter().map(|i| alphabet.chars()[usize::from(i)]);
+        Ok(self
+            .prefix
+            .iter()
+            .map(|c| c.as_char())
+            .chain(random)
+            .chain(self.suffix.iter().map(|c| c.as_char()))
+            .collect())
+    }
+}
+
+impl<A: Alphabet> Default for VDGenerator<A> {
+    /// Same settings as [`VDGenerator::new`], for any alphabet.
+    fn default() -> Self {
         Self {
             len: 6,
             no_adjacent_repeats: false,
             no_repeats: false,
+            min_distance: 1,
+            #[cfg(feature = "alloc")]
+            denied: Vec::new(),
+            #[cfg(feature = "alloc")]
+            avoided: Vec::new(),
+            #[cfg(feature = "alloc")]
+            avoid_distance: 0,
+            #[cfg(feature = "alloc")]
+            prefix: Vec::new(),
+            #[cfg(feature = "alloc")]
+            suffix: Vec::new(),
+            #[cfg(feature = "alloc")]
+            excluded: Vec::new(),
+            max_run: None,
+            min_digits: 0,
+            min_letters: 0,
+            max_attempts: 10_000,
+            alphabet: PhantomData,
         }
     }
+}
 
-    /// Sets the length of the generated string.
+impl<A: Alphabet> VDGenerator<A> {
+    /// Sets the length of the generated string, including any
+    /// [`prefix`](Self::prefix) and [`suffix`](Self::suffix).
     pub fn length(mut self, len: usize) -> Self {
         self.len = len;
         self
@@ -85,117 +577,2097 @@ impl VDGenerator {
     /// Forbids any repeated characters in the output.
     ///
     /// Useful when aiming for high visual uniqueness in short codes.
-    /// Has an upper bound of `VDS_ALLOWED.len()` characters.
+    /// Has an upper bound of one character per alphabet entry.
     pub fn no_repeats(mut self) -> Self {
         self.no_repeats = true;
         self
     }
 
-    /// Generates a [`VDString`] based on the current configuration and RNG.
+    /// Starts every code with `prefix`, e.g. a campaign or region identifier.
     ///
-    /// Returns a [`VDGeneratorError`] if the configuration is invalid.
+    /// The prefix counts toward the [`length`](Self::length), so only the
+    /// characters between it and the [`suffix`](Self::suffix) are random.
+    /// The repeat constraints and deny list apply to the whole code: with
+    /// [`no_repeats`](Self::no_repeats), the random characters avoid those
+    /// of the prefix and suffix. Replaces any previous prefix.
     ///
-    /// # Errors
-    /// - [`VDGeneratorError::LengthExceedsUniqueSet`] if `no_repeats` is enabled and
-    ///   `length > VDS_ALLOWED.len()`.
-    pub fn generate<R: RngCore + ?Sized>(
-        &self,
-        rng: &mut R,
-    ) -> Result<VDString, VDGeneratorError> {
-        if self.no_repeats && self.len > VDS_ALLOWED.len() {
-            return Err(VDGeneratorError::LengthExceedsUniqueSet {
-                requested: self.len,
-                available: VDS_ALLOWED.len(),
-            });
-        }
+    /// # Examples
+    /// ```
+    /// use vds::{vdstr, StepRng, VDGenerator};
+    ///
+    /// let mut rng = StepRng::new(0, 1);
+    /// let gen = VDGenerator::new().length(8).prefix(&vdstr!("EU")).suffix(&vdstr!("9"));
+    ///
+    /// assert_eq!(&*gen.generate(&mut rng).unwrap(), "EUABCDE9");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn prefix(mut self, prefix: &VDStr<A>) -> Self {
+        self.prefix = prefix.as_vdchars().to_vec();
+        self
+    }
 
-        let mut result = Vec::with_capacity(self.len);
+    /// Ends every code with `suffix`, as with [`prefix`](Self::prefix).
+    /// Replaces any previous suffix.
+    #[cfg(feature = "alloc")]
+    pub fn suffix(mut self, suffix: &VDStr<A>) -> Self {
+        self.suffix = suffix.as_vdchars().to_vec();
+        self
+    }
 
-        if self.no_repeats {
-            // Sample without replacement by shuffling
-            let mut pool: Vec<VDChar> = (0..VDS_ALLOWED.len())
-                .map(|i| VDChar(i as u8))
-                .collect();
+    /// Limits how many candidate codes a single generation may discard, and
+    /// how many characters it may redraw, before giving up with an error.
+    ///
+    /// Bounds the time spent on over-constrained configurations or a
+    /// pathological RNG. A limit of `0` still allows one candidate.
+    pub fn max_attempts(mut self, attempts: usize) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
 
-            // Fisher-Yates shuffle (partial)
-            for i in 0..self.len {
-                let j = i + (rng.next_u32() as usize % (pool.len() - i));
-                pool.swap(i, j);
-            }
+    /// Rejects generated codes containing `len` or more consecutive
+    /// characters that step up or down by one, either in alphabet order or
+    /// in ASCII, e.g. `ABC`, `HJK`, or `987` with `len = 3`.
+    ///
+    /// Such runs look "not random" to users and are among the first codes
+    /// an attacker tries. The prefix and suffix are checked as part of the
+    /// code. As with [`deny_substrings`](Self::deny_substrings), rejected
+    /// codes are redrawn. Values below `2` are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::{StepRng, VDGenerator};
+    ///
+    /// // StepRng(0, 1) draws ABCD first, then EFGH, ...
+    /// let gen = VDGenerator::new().length(4).no_sequential_runs(3);
+    /// let generated = gen.generate_audited(&mut StepRng::new(0, 1)).unwrap();
+    /// assert!(generated.rejected_by.sequential_runs > 0);
+    /// # }
+    /// ```
+    pub fn no_sequential_runs(mut self, len: usize) -> Self {
+        self.max_run = Some(len).filter(|&len| len >= 2);
+        self
+    }
+
+    /// Requires every generated code to contain at least `k` digits.
+    ///
+    /// Digits in the prefix and suffix count. Codes falling short are
+    /// redrawn, so every code meeting the requirement stays equally likely;
+    /// minimums far above the expected share of digits make redraws slow,
+    /// and may exhaust the retry limit.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::{StepRng, VDGenerator, VDGeneratorError};
+    ///
+    /// let gen = VDGenerator::new().length(6).min_digits(2).min_letters(2);
+    /// let code = gen.generate(&mut StepRng::new(0, 7)).unwrap();
+    /// assert!(code.iter().filter(|c| c.is_digit()).count() >= 2);
+    ///
+    /// let gen = VDGenerator::new().length(3).min_digits(2).min_letters(2);
+    /// assert_eq!(gen.generate(&mut StepRng::new(0, 7)), Err(VDGeneratorError::CompositionInfeasible));
+    /// # }
+    /// ```
+    pub fn min_digits(mut self, k: usize) -> Self {
+        self.min_digits = k;
+        self
+    }
 
-            result.extend_from_slice(&pool[..self.len]);
+    /// Requires every generated code to contain at least `k` letters, as
+    /// with [`min_digits`](Self::min_digits).
+    pub fn min_letters(mut self, k: usize) -> Self {
+        self.min_letters = k;
+        self
+    }
 
-            if self.no_adjacent_repeats {
-                // Rotate until no adjacent duplicates, up to `len` tries
-                for _ in 0..self.len {
-                    if result.windows(2).any(|w| w[0] == w[1]) {
-                        result.rotate_left(1);
-                    } else {
-                        break;
-                    }
-                }
-            }
+    /// Never draws any of `chars`, e.g. to drop `B` and `8` for one campaign
+    /// without defining a whole new alphabet.
+    ///
+    /// The `no_repeats` bound and [`entropy_bits`](Self::entropy_bits) use
+    /// the remaining characters. The prefix and suffix are not affected. May
+    /// be called repeatedly to extend the list.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{StepRng, VDChar, VDGenerator};
+    ///
+    /// let excluded = [VDChar::new('B').unwrap(), VDChar::new('8').unwrap()];
+    /// let gen = VDGenerator::new().length(29).no_repeats().exclude_chars(&excluded);
+    ///
+    /// let code = gen.generate(&mut StepRng::new(0, 1)).unwrap();
+    /// assert!(!code.as_str().contains(['B', '8']));
+    /// assert!(VDGenerator::new().length(30).no_repeats().exclude_chars(&excluded).generate(&mut StepRng::new(0, 1)).is_err());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn exclude_chars(mut self, chars: &[VDChar<A>]) -> Self {
+        self.excluded.extend_from_slice(chars);
+        self
+    }
 
-            return Ok(VDString::new(result));
+    /// Returns the bits of entropy of codes from this configuration, i.e.
+    /// `log2` of the number of distinct codes it can produce.
+    ///
+    /// Accounts for the reduced keyspace of [`no_repeats`](Self::no_repeats)
+    /// and [`no_adjacent_repeats`](Self::no_adjacent_repeats); the prefix
+    /// and suffix add none. Returns `f64::NEG_INFINITY` if the
+    /// configuration cannot produce any code, so a minimum-entropy check
+    /// rejects it too.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDGenerator;
+    ///
+    /// let gen = VDGenerator::new().length(8).no_adjacent_repeats();
+    /// assert!(gen.entropy_bits() >= 39.0);
+    /// assert!(gen.entropy_bits() < VDGenerator::new().length(8).entropy_bits());
+    ///
+    /// assert_eq!(VDGenerator::new().length(32).no_repeats().entropy_bits(), f64::NEG_INFINITY);
+    /// ```
+    pub fn entropy_bits(&self) -> f64 {
+        match self.layout(A::CHARS) {
+            Ok(layout) => self.keyspace_bits(&layout),
+            Err(_) => f64::NEG_INFINITY,
         }
+    }
 
-        // With replacement sampling
-        let mut last: Option<VDChar> = None;
-
-        while result.len() < self.len {
-            let idx = (rng.next_u32() as usize) % VDS_ALLOWED.len();
-            let ch = VDChar(idx as u8);
+    /// Requires every pair of codes from [`generate_many`](Self::generate_many)
+    /// to differ in at least `d` positions.
+    ///
+    /// With `d = 2`, no single typo can turn one issued code into another;
+    /// with `d = 3`, a single typo can also be corrected to the nearest code.
+    /// Values below `2` only require codes to be distinct. Does not affect
+    /// single-code generation.
+    pub fn min_distance(mut self, d: usize) -> Self {
+        self.min_distance = d;
+        self
+    }
 
-            if self.no_adjacent_repeats && last == Some(ch) {
-                continue;
-            }
+    /// Rejects generated codes that contain any of `substrings`.
+    ///
+    /// Matching is exact and case-sensitive on characters; may be called
+    /// repeatedly to extend the list. Rejected codes are redrawn, which
+    /// slightly reduces the effective keyspace below
+    /// [`entropy_bits`](Self::entropy_bits).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{vdstr, StepRng, VDGenerator};
+    ///
+    /// let mut rng = StepRng::new(0, 1);
+    /// let gen = VDGenerator::new().length(4).deny_substrings(&[vdstr!("BC")]);
+    /// let generated = gen.generate_audited(&mut rng).unwrap();
+    ///
+    /// assert_eq!(&*generated.code, "EFGH");
+    /// assert_eq!(generated.attempts, 2);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn deny_substrings(mut self, substrings: &[VDString<A>]) -> Self {
+        self.denied.extend(
+            substrings
+                .iter()
+                .filter(|s| !s.is_empty())
+                .map(|s| s.iter().map(VDChar::as_char).collect()),
+        );
+        self
+    }
 
-            result.push(ch);
-            last = Some(ch);
-        }
+    /// Rejects generated codes within `min_distance - 1` edits of any of
+    /// `codes`, so every new code is at least `min_distance` edits
+    /// (insertions, deletions, or substitutions) away from them.
+    ///
+    /// Use this to keep a new campaign's codes from being confused with the
+    /// last one's at redemption: with `min_distance = 2`, no single typo
+    /// turns a new code into an old one. Prefix and suffix count towards
+    /// the distance, so give both campaigns different affixes only if the
+    /// distance should hold for the random parts alone. May be called
+    /// repeatedly to extend the set; the largest `min_distance` applies to
+    /// all of it.
+    ///
+    /// Every candidate is compared with every code in the set, so the cost
+    /// grows with its size; rejected codes are redrawn.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{vdstr, StepRng, VDGenerator};
+    ///
+    /// let mut rng = StepRng::new(0, 1);
+    /// let gen = VDGenerator::new().length(4).avoid(&[vdstr!("ABCE")], 2);
+    /// let generated = gen.generate_audited(&mut rng).unwrap();
+    ///
+    /// // "ABCD" is one substitution from "ABCE", so it is redrawn.
+    /// assert_eq!(&*generated.code, "EFGH");
+    /// assert_eq!(generated.rejected_by.avoided, 1);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn avoid(mut self, codes: &[VDString<A>], min_distance: usize) -> Self {
+        self.avoided.extend(codes.iter().map(|code| code.as_vdchars().to_vec()));
+        self.avoid_distance = self.avoid_distance.max(min_distance);
+        self
+    }
 
-        Ok(VDString::new(result))
+    /// Rejects generated codes containing any entry of
+    /// [`PROFANITY`](crate::PROFANITY), as with
+    /// [`deny_substrings`](Self::deny_substrings).
+    ///
+    /// This method is only available when the `profanity` feature is enabled.
+    #[cfg(all(feature = "profanity", feature = "alloc"))]
+    pub fn deny_profanity(mut self) -> Self {
+        self.denied.extend(crate::PROFANITY.iter().map(|word| word.chars().collect()));
+        self
     }
-}
 
-#[cfg(test)]
-#[cfg(feature = "generate")]
-mod tests {
-    extern crate alloc;
-    use super::*;
-    use alloc::vec;
-    use rand::SeedableRng;
-    use rand::rngs::SmallRng;
+    /// Rejects generated codes containing a pair of adjacent characters
+    /// that may be read as one other character when the code is
+    /// handwritten or printed in a tight font, e.g. `VV` for `W` or `RN`
+    /// for `M`, from [`CONFUSABLE_DIGRAPHS`](crate::distance::CONFUSABLE_DIGRAPHS).
+    ///
+    /// Pairs are denied as with [`deny_substrings`](Self::deny_substrings).
+    /// Use [`no_confusable_digraphs_from`](Self::no_confusable_digraphs_from)
+    /// to supply a different table.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{StepRng, VDGenerator};
+    ///
+    /// let gen = VDGenerator::new().length(2).no_confusable_digraphs();
+    /// let mut rng = StepRng::new(0, 1);
+    /// for _ in 0..100 {
+    ///     let code = gen.generate(&mut rng).unwrap();
+    ///     assert!(!["VV", "UU", "RN", "NN", "CJ"].contains(&code.as_str()));
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn no_confusable_digraphs(self) -> Self {
+        self.no_confusable_digraphs_from(crate::distance::CONFUSABLE_DIGRAPHS)
+    }
 
-    fn seeded_rng() -> SmallRng {
-        SmallRng::seed_from_u64(42)
+    /// Like [`no_confusable_digraphs`](Self::no_confusable_digraphs), with
+    /// the pairs from `table` instead of the built-in one.
+    ///
+    /// Pairs containing characters outside the alphabet never match. May
+    /// be called repeatedly to extend the list.
+    #[cfg(feature = "alloc")]
+    pub fn no_confusable_digraphs_from(mut self, table: &[(&str, char)]) -> Self {
+        self.denied.extend(table.iter().filter(|(pair, _)| !pair.is_empty()).map(|(pair, _)| pair.chars().collect()));
+        self
     }
 
-    #[test]
-    fn generates_expected_length() {
-        let mut rng = seeded_rng();
-        let code = VDGenerator::new().length(8).generate(&mut rng).unwrap();
-        assert_eq!(code.len(), 8);
+    /// Generates a [`VDString`] based on the current configuration and RNG.
+    ///
+    /// Returns a [`VDGeneratorError`] if the configuration is invalid.
+    ///
+    /// # Errors
+    /// - [`VDGeneratorError::LengthExceedsUniqueSet`] if `no_repeats` is enabled and
+    ///   the length exceeds the alphabet size.
+    /// - [`VDGeneratorError::AffixesExceedLength`] if the prefix and suffix
+    ///   are longer than the length.
+    /// - [`VDGeneratorError::AffixesViolateConstraints`] if the prefix and
+    ///   suffix cannot satisfy the repeat constraints.
+    /// - [`VDGeneratorError::AllCharsExcluded`] if no character is left to
+    ///   draw from.
+    /// - [`VDGeneratorError::CompositionInfeasible`] if the minimum digits
+    ///   and letters cannot be met.
+    /// - [`VDGeneratorError::DeniedSubstringsUnavoidable`],
+    ///   [`SequentialRunsUnavoidable`](VDGeneratorError::SequentialRunsUnavoidable),
+    ///   or [`ConstraintUnsatisfiable`](VDGeneratorError::ConstraintUnsatisfiable)
+    ///   if [`max_attempts`](Self::max_attempts) runs out.
+    #[cfg(feature = "alloc")]
+    pub fn generate<R: VDRng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<VDString<A>, VDGeneratorError> {
+        self.generate_audited(rng).map(|generated| generated.code)
     }
 
-    #[test]
-    fn no_adjacent_repeats_enabled() {
-        let mut rng = seeded_rng();
-        let code = VDGenerator::new()
-            .length(16)
-            .no_adjacent_repeats()
-            .generate(&mut rng)
-            .unwrap();
+    /// Validates the configuration and precomputes the character pool,
+    /// returning a [`CompiledGenerator`] that generates without redoing
+    /// either.
+    ///
+    /// # Errors
+    /// The configuration errors of [`generate`](Self::generate), e.g.
+    /// [`VDGeneratorError::LengthExceedsUniqueSet`].
+    pub fn build(self) -> Result<CompiledGenerator<A>, VDGeneratorError> {
+        let layout = self.layout(A::CHARS)?;
+        let entropy_bits = self.keyspace_bits(&layout);
+        Ok(CompiledGenerator { generator: self, layout, entropy_bits })
+    }
 
-        for pair in code.as_vdchars().windows(2) {
-            assert_ne!(pair[0], pair[1], "adjacent repeat found");
-        }
+    /// Returns an endless iterator of codes drawn from `rng`, for use with
+    /// iterator adapters instead of calling [`generate`](Self::generate) in
+    /// a loop.
+    ///
+    /// Pass `&mut rng` to keep using the RNG afterwards. Codes are not
+    /// deduplicated; see [`generate_many`](Self::generate_many) or
+    /// [`VDPool`](crate::VDPool) for that.
+    ///
+    /// # Errors
+    /// Returns the error `generate` would if the configuration is invalid.
+    /// Once created, the iterator only ends if a generation runs out of
+    /// [`max_attempts`](Self::max_attempts).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{StepRng, VDGenerator};
+    ///
+    /// let mut rng = StepRng::new(0, 1);
+    /// let gen = VDGenerator::new().length(4);
+    /// let codes: Vec<_> = gen
+    ///     .iter(&mut rng)
+    ///     .unwrap()
+    ///     .filter(|code| code.iter().any(|c| c.is_digit()))
+    ///     .take(2)
+    ///     .collect();
+    ///
+    /// assert_eq!(codes[0].as_str(), "XYZ2");
+    /// assert_eq!(codes.len(), 2);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn iter<R: VDRng>(&self, rng: R) -> Result<VDGeneratorIter<'_, R, A>, VDGeneratorError> {
+        self.layout(A::CHARS)?;
+        Ok(VDGeneratorIter { generator: self, rng, done: false })
+    }
+
+    /// Generates `count` codes that are all distinct from each other, and
+    /// at least [`min_distance`](Self::min_distance) apart.
+    ///
+    /// Duplicates are redrawn, so the codes keep the same distribution as
+    /// repeated calls to [`generate`](Self::generate). As `count`
+    /// approaches the size of the keyspace, redraws become frequent; keep
+    /// batches well below it (see [`entropy_bits`](Self::entropy_bits)).
+    ///
+    /// With a minimum distance of `d >= 2`, each candidate is compared with
+    /// every accepted code, so the cost grows quadratically with `count`.
+    ///
+    /// # Errors
+    /// - Any error of [`generate`](Self::generate).
+    /// - [`VDGeneratorError::NotEnoughDistinctCodes`] if `count` exceeds the
+    ///   number of distinct codes this configuration can produce, or, with a
+    ///   minimum distance, the Singleton bound `n^(len - d + 1)`. Also
+    ///   returned, with the number of codes found, if
+    ///   [`max_attempts`](Self::max_attempts) draws in a row were
+    ///   duplicates, e.g. because composition or deny-list filters leave
+    ///   fewer codes than that.
+    /// - [`VDGeneratorError::MinDistanceUnreachable`] if
+    ///   [`max_attempts`](Self::max_attempts) candidates in a row were too
+    ///   close to an accepted code.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{VDGenerator, VDGeneratorError};
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+    /// let codes = VDGenerator::new().length(2).generate_many(900, &mut rng).unwrap();
+    /// assert_eq!(codes.len(), 900);
+    ///
+    /// let err = VDGenerator::new().length(2).generate_many(962, &mut rng).unwrap_err();
+    /// assert_eq!(err, VDGeneratorError::NotEnoughDistinctCodes { requested: 962, available: 961 });
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn generate_many<R: VDRng + ?Sized>(
+        &self,
+        count: usize,
+        rng: &mut R,
+    ) -> Result<Vec<VDString<A>>, VDGeneratorError> {
+        let layout = self.layout(A::CHARS)?;
+        let available = match (self.keyspace_size(&layout), self.singleton_bound(&layout)) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let Some(available) = available {
+            if count as u128 > available {
+                return Err(VDGeneratorError::NotEnoughDistinctCodes { requested: count, available });
+            }
+        }
+
+        if self.min_distance >= 2 {
+            return self.generate_spaced(count, &layout, rng);
+        }
+
+        // The filters can leave fewer codes than the keyspace bound above,
+        // so give up once `max_attempts` draws in a row were duplicates.
+        let mut seen = BTreeSet::new();
+        let mut codes = Vec::with_capacity(count);
+        let mut duplicates = 0;
+        while codes.len() < count {
+            let mut indices = vec![0; layout.len];
+            self.draw(&layout, A::CHARS, &mut indices, rng)?;
+            if seen.insert(indices.clone()) {
+                codes.push(self.assemble(indices));
+                duplicates = 0;
+            } else {
+                duplicates += 1;
+                if duplicates >= self.max_attempts.max(1) {
+                    return Err(VDGeneratorError::NotEnoughDistinctCodes {
+                        requested: count,
+                        available: codes.len() as u128,
+                    });
+                }
+            }
+        }
+        Ok(codes)
+    }
+
+    /// Generates a code for which `exists` returns `false`, e.g. a URL slug
+    /// or referral code not yet in the database.
+    ///
+    /// Draws a candidate and asks `exists` about it, up to `max_attempts`
+    /// times. After every 3 collisions the length grows by one character,
+    /// so a crowded keyspace escalates to a roomier one instead of retrying
+    /// forever; the first candidate always has the configured length.
+    ///
+    /// `exists` is only a pre-check. If another process can insert the same
+    /// code between the check and your write, keep a unique constraint on
+    /// the store and retry on conflict.
+    ///
+    /// # Errors
+    /// - Any error of [`generate`](Self::generate), including for an
+    ///   escalated length, e.g. [`VDGeneratorError::LengthExceedsUniqueSet`]
+    ///   with `no_repeats`.
+    /// - [`VDGeneratorError::NoUniqueCode`] if all `max_attempts` candidates
+    ///   already existed.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use vds::{StepRng, VDGenerator, VDString};
+    ///
+    /// let issued: HashSet<VDString> = ["ABCD", "EFGH"].iter().map(|s| s.parse().unwrap()).collect();
+    /// let mut rng = StepRng::new(0, 1);
+    ///
+    /// let gen = VDGenerator::new().length(4);
+    /// let slug = gen.generate_unique(&mut rng, |code| issued.contains(code), 10).unwrap();
+    /// assert_eq!(slug, "JKMN");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn generate_unique<R, F>(
+        &self,
+        rng: &mut R,
+        mut exists: F,
+        max_attempts: usize,
+    ) -> Result<VDString<A>, VDGeneratorError>
+    where
+        R: VDRng + ?Sized,
+        F: FnMut(&VDString<A>) -> bool,
+    {
+        let mut compiled = self.clone().build()?;
+        for attempt in 0..max_attempts {
+            if attempt > 0 && attempt % COLLISIONS_PER_LENGTH == 0 {
+                compiled = self.clone().length(self.len + attempt / COLLISIONS_PER_LENGTH).build()?;
+            }
+            let code = compiled.generate(rng)?;
+            if !exists(&code) {
+                return Ok(code);
+            }
+        }
+        Err(VDGeneratorError::NoUniqueCode { attempts: max_attempts })
+    }
+
+    /// Greedily collects `count` codes pairwise at least `min_distance` apart.
+    ///
+    /// The Singleton bound ignores the filters, and greedy packing can stall
+    /// well below it, so this gives up after `max_attempts` rejections in a
+    /// row.
+    #[cfg(feature = "alloc")]
+    fn generate_spaced<R: VDRng + ?Sized>(
+        &self,
+        count: usize,
+        layout: &Layout,
+        rng: &mut R,
+    ) -> Result<Vec<VDString<A>>, VDGeneratorError> {
+        let mut accepted: Vec<Vec<u8>> = Vec::with_capacity(count);
+        let mut rejections = 0;
+        while accepted.len() < count {
+            let mut indices = vec![0; layout.len];
+            self.draw(layout, A::CHARS, &mut indices, rng)?;
+            let far_enough = accepted.iter().all(|code| {
+                code.iter().zip(&indices).filter(|(a, b)| a != b).count() >= self.min_distance
+            });
+            if far_enough {
+                accepted.push(indices);
+                rejections = 0;
+            } else {
+                rejections += 1;
+                if rejections >= self.max_attempts.max(1) {
+                    return Err(VDGeneratorError::MinDistanceUnreachable {
+                        requested: count,
+                        found: accepted.len(),
+                    });
+                }
+            }
+        }
+        Ok(accepted.into_iter().map(|indices| self.assemble(indices)).collect())
+    }
+
+    /// Surrounds the random `indices` with the prefix and suffix.
+    #[cfg(feature = "alloc")]
+    fn assemble(&self, indices: Vec<u8>) -> VDString<A> {
+        let mut chars = Vec::with_capacity(self.prefix.len() + indices.len() + self.suffix.len());
+        chars.extend_from_slice(&self.prefix);
+        chars.extend(indices.into_iter().map(VDChar::from_index_unchecked));
+        chars.extend_from_slice(&self.suffix);
+        VDString::new(chars)
+    }
+
+    /// Returns the Singleton bound on how many codes can be pairwise at
+    /// least `min_distance` apart, or `None` if it does not fit in a `u128`
+    /// or no minimum distance is set.
+    ///
+    /// Only the random part of a code can differ.
+    #[cfg(feature = "alloc")]
+    fn singleton_bound(&self, layout: &Layout) -> Option<u128> {
+        if self.min_distance < 2 {
+            return None;
+        }
+        if self.min_distance > layout.len {
+            return Some(1);
+        }
+        (layout.pool_len as u128).checked_pow((layout.len - self.min_distance + 1) as u32)
+    }
+
+    /// Generates a [`VDString`] like [`generate`](Self::generate), but only
+    /// accepts a cryptographically secure RNG.
+    ///
+    /// Use this for recovery codes, bearer tokens, and anything else an
+    /// attacker might try to predict; the `CryptoRng` bound turns passing a
+    /// fast, predictable RNG into a compile error.
+    ///
+    /// # Errors
+    /// Same as [`generate`](Self::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use vds::VDGenerator;
+    ///
+    /// let mut rng = rand::rngs::StdRng::from_os_rng();
+    /// let code = VDGenerator::new().length(10).generate_secure(&mut rng).unwrap();
+    /// assert_eq!(code.len(), 10);
+    /// ```
+    ///
+    /// Non-cryptographic RNGs are rejected:
+    /// ```compile_fail
+    /// let mut rng = vds::StepRng::new(0, 1);
+    /// vds::VDGenerator::new().generate_secure(&mut rng);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn generate_secure<R: CryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<VDString<A>, VDGeneratorError> {
+        self.generate(rng)
+    }
+
+    /// Generates a [`VDString`] like [`generate_secure`](Self::generate_secure),
+    /// drawing from the operating system's RNG.
+    ///
+    /// For the common case of a one-off secure code, this saves constructing
+    /// and passing around an RNG. Each call asks the OS for fresh entropy;
+    /// when generating many codes, seeding a `CryptoRng` once is faster.
+    ///
+    /// This method is only available when the `os-rng` feature is enabled.
+    ///
+    /// # Errors
+    /// Returns [`VDGeneratorError::EntropyUnavailable`] if the operating
+    /// system RNG fails, and otherwise the errors of
+    /// [`generate`](Self::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDGenerator;
+    ///
+    /// let code = VDGenerator::new().length(10).generate_with_os_rng().unwrap();
+    /// assert_eq!(code.len(), 10);
+    /// ```
+    #[cfg(feature = "os-rng")]
+    pub fn generate_with_os_rng(&self) -> Result<VDString<A>, VDGeneratorError> {
+        let mut rng = crate::rng::OsEntropy::default();
+        let code = self.generate(&mut rng);
+        if rng.failed {
+            return Err(VDGeneratorError::EntropyUnavailable);
+        }
+        code
+    }
+
+    /// Generates a [`VDString`] from caller-supplied entropy, such as
+    /// hardware TRNG output or a KDF output, instead of an RNG.
+    ///
+    /// Every draw consumes the next 4 bytes of `entropy` as a little-endian
+    /// `u32`, which picks a character as [`uniform_index`] does. A code of
+    /// length `n` therefore needs at least `4 * n` bytes for its random part
+    /// (prefix and suffix consume none). Rejected values, redraws for repeat
+    /// constraints, and rejected candidates consume further bytes, so pass
+    /// some spare; bytes left over are ignored. The result depends only on
+    /// `entropy`, so the same bytes always give the same code.
+    ///
+    /// # Errors
+    /// Returns [`VDGeneratorError::EntropyExhausted`] if `entropy` runs out,
+    /// and otherwise the errors of [`generate`](Self::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{VDGenerator, VDGeneratorError};
+    ///
+    /// let entropy = [0, 0, 0, 0, 1, 0, 0, 0, 30, 0, 0, 0];
+    /// let gen = VDGenerator::new().length(3);
+    /// assert_eq!(gen.generate_from_entropy(&entropy).unwrap(), "AB9");
+    /// assert_eq!(
+    ///     gen.generate_from_entropy(&entropy[..8]),
+    ///     Err(VDGeneratorError::EntropyExhausted { provided: 8 })
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn generate_from_entropy(&self, entropy: &[u8]) -> Result<VDString<A>, VDGeneratorError> {
+        let mut rng = crate::rng::SliceEntropy { bytes: entropy, exhausted: false };
+        let code = self.generate(&mut rng);
+        if rng.exhausted {
+            return Err(VDGeneratorError::EntropyExhausted { provided: entropy.len() });
+        }
+        code
+    }
+
+    /// Generates a [`VDString`] like [`generate`](Self::generate), together
+    /// with metadata describing how it was produced.
+    ///
+    /// Intended for issuance services that log why generation was slow or
+    /// need to record the entropy of each code they hand out.
+    ///
+    /// # Errors
+    /// Same as [`generate`](Self::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{StepRng, VDGenerator};
+    ///
+    /// let mut rng = StepRng::new(0, 1);
+    /// let generated = VDGenerator::new()
+    ///     .length(4)
+    ///     .no_adjacent_repeats()
+    ///     .generate_audited(&mut rng)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(generated.code.len(), 4);
+    /// assert_eq!(generated.attempts, 1);
+    /// assert!(generated.entropy_bits > 19.0);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn generate_audited<R: VDRng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<GeneratedCode<A>, VDGeneratorError> {
+        self.generate_audited_in(&self.layout(A::CHARS)?, rng)
+    }
+
+    /// Generates a [`VDString`] like [`generate`](Self::generate), reporting
+    /// how it went to `observer`.
+    ///
+    /// Intended for services exporting metrics: how many candidates each
+    /// constraint rejects, and how often generation fails, show when a
+    /// configuration is over-constrained before it starts failing outright.
+    /// Configuration errors are reported as failures with no rejections.
+    ///
+    /// # Errors
+    /// Same as [`generate`](Self::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{vdstr, GenerationObserver, Rejections, StepRng, VDGenerator};
+    ///
+    /// #[derive(Default)]
+    /// struct Metrics {
+    ///     codes: usize,
+    ///     denied: usize,
+    /// }
+    ///
+    /// impl GenerationObserver for Metrics {
+    ///     fn on_accepted(&mut self, _attempts: usize, rejected_by: &Rejections) {
+    ///         self.codes += 1;
+    ///         self.denied += rejected_by.denied_substrings;
+    ///     }
+    /// }
+    ///
+    /// let mut metrics = Metrics::default();
+    /// let gen = VDGenerator::new().length(4).deny_substrings(&[vdstr!("BC")]);
+    /// let code = gen.generate_observed(&mut StepRng::new(0, 1), &mut metrics).unwrap();
+    ///
+    /// assert_eq!(&*code, "EFGH");
+    /// assert_eq!((metrics.codes, metrics.denied), (1, 1));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn generate_observed<R, O>(&self, rng: &mut R, observer: &mut O) -> Result<VDString<A>, VDGeneratorError>
+    where
+        R: VDRng + ?Sized,
+        O: GenerationObserver + ?Sized,
+    {
+        match self.layout(A::CHARS) {
+            Ok(layout) => self.generate_observed_in(&layout, rng, observer),
+            Err(err) => {
+                observer.on_failed(&err, &Rejections::default());
+                Err(err)
+            }
+        }
+    }
+
+    /// Generates an audited code with an already validated `layout`.
+    #[cfg(feature = "alloc")]
+    fn generate_audited_in<R: VDRng + ?Sized>(
+        &self,
+        layout: &Layout,
+        rng: &mut R,
+    ) -> Result<GeneratedCode<A>, VDGeneratorError> {
+        let mut indices = vec![0; layout.len];
+        let (attempts, rejected_by) = self.draw(layout, A::CHARS, &mut indices, rng)?;
+
+        Ok(GeneratedCode {
+            code: self.assemble(indices),
+            attempts,
+            rejected_by,
+            entropy_bits: self.keyspace_bits(layout),
+        })
+    }
+
+    /// Generates with an already validated `layout`, reporting the outcome
+    /// to `observer`.
+    #[cfg(feature = "alloc")]
+    fn generate_observed_in<R, O>(
+        &self,
+        layout: &Layout,
+        rng: &mut R,
+        observer: &mut O,
+    ) -> Result<VDString<A>, VDGeneratorError>
+    where
+        R: VDRng + ?Sized,
+        O: GenerationObserver + ?Sized,
+    {
+        let mut indices = vec![0; layout.len];
+        let mut rejected_by = Rejections::default();
+        match self.draw_counted(layout, A::CHARS, &mut indices, rng, &mut rejected_by) {
+            Ok(attempts) => {
+                observer.on_accepted(attempts, &rejected_by);
+                Ok(self.assemble(indices))
+            }
+            Err(err) => {
+                observer.on_failed(&err, &rejected_by);
+                Err(err)
+            }
+        }
+    }
+
+    /// Generates a code into `buf` without allocating, returning the
+    /// written part as a [`VDStr`].
+    ///
+    /// Writes the configured [`length`](Self::length) of characters to the
+    /// start of `buf`, honoring the same constraints as
+    /// [`generate`](Self::generate). This is the only way to generate
+    /// without the `alloc` feature, where the prefix, suffix, exclusions,
+    /// and deny list are unavailable. The result implements `Display`, so
+    /// `write!(out, "{}", code)` renders it into any `fmt::Write`.
+    ///
+    /// # Errors
+    /// - [`VDGeneratorError::BufferTooSmall`] if `buf` is shorter than the
+    ///   configured length.
+    /// - Any error of [`generate`](Self::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use core::fmt::Write;
+    /// use vds::{StepRng, VDChar, VDGenerator};
+    ///
+    /// let mut buf = [VDChar::new('A').unwrap(); 8];
+    /// let code = VDGenerator::new().length(6).generate_into(&mut buf, &mut StepRng::new(0, 1)).unwrap();
+    /// assert_eq!(code, "ABCDEF");
+    ///
+    /// let mut out = String::new();
+    /// write!(out, "{}", code.display_grouped(3, "-")).unwrap();
+    /// assert_eq!(out, "ABC-DEF");
+    /// ```
+    pub fn generate_into<'b, R: VDRng + ?Sized>(
+        &self,
+        buf: &'b mut [VDChar<A>],
+        rng: &mut R,
+    ) -> Result<&'b VDStr<A>, VDGeneratorError> {
+        self.generate_into_in(&self.layout(A::CHARS)?, buf, rng)
+    }
+
+    /// Generates a code into a [`heapless::Vec`] of capacity `N`, for
+    /// firmware that keeps codes in `heapless` containers.
+    ///
+    /// Honors the same constraints as [`generate_into`](Self::generate_into)
+    /// and never allocates.
+    ///
+    /// This method is only available when the `heapless` feature is enabled.
+    ///
+    /// # Errors
+    /// - [`VDGeneratorError::BufferTooSmall`] if `N` is less than the
+    ///   configured length.
+    /// - Any error of [`generate`](Self::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{StepRng, VDGenerator, VDStr};
+    ///
+    /// let code = VDGenerator::new().length(6).generate_into_heapless::<8, _>(&mut StepRng::new(0, 1)).unwrap();
+    /// assert_eq!(<&VDStr>::from(&code), "ABCDEF");
+    /// ```
+    #[cfg(feature = "heapless")]
+    pub fn generate_into_heapless<const N: usize, R: VDRng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<heapless::Vec<VDChar<A>, N>, VDGeneratorError> {
+        let mut buf = heapless::Vec::new();
+        let len = self.generate_into(heapless_buf(&mut buf), rng)?.len();
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    /// Longest random part [`generate_to`](Self::generate_to) draws into a
+    /// stack buffer. Longer codes use a heap buffer instead, or fail with
+    /// [`VDGeneratorError::BufferTooSmall`] without the `alloc` feature.
+    pub const SCRATCH_LEN: usize = SCRATCH_LEN;
+
+    /// Generates a code and writes it to `out`, without building a
+    /// [`VDString`].
+    ///
+    /// The code is drawn into a stack buffer and written character by
+    /// character once it passes every constraint, so exporting codes in bulk
+    /// does not allocate one `VDString` per code. Honors the same
+    /// constraints as [`generate`](Self::generate), and draws from `rng`
+    /// exactly as it does.
+    ///
+    /// # Errors
+    /// - [`VDGeneratorError::WriteFailed`] if `out` returns an error. The
+    ///   sink may then hold part of the code.
+    /// - [`VDGeneratorError::BufferTooSmall`] without the `alloc` feature, if
+    ///   the length exceeds [`SCRATCH_LEN`](Self::SCRATCH_LEN).
+    /// - Any error of [`generate`](Self::generate).
+    ///
+    /// # Examples
+    /// ```
+    /// use core::fmt::Write;
+    /// use vds::{StepRng, VDGenerator};
+    ///
+    /// let gen = VDGenerator::new().length(4).build().unwrap();
+    /// let mut rng = StepRng::new(0, 1);
+    /// let mut out = String::new();
+    /// for _ in 0..3 {
+    ///     gen.generate_to(&mut out, &mut rng).unwrap();
+    ///     out.push('\n');
+    /// }
+    /// assert_eq!(out, "ABCD\nEFGH\nJKMN\n");
+    /// ```
+    pub fn generate_to<W: fmt::Write + ?Sized, R: VDRng + ?Sized>(
+        &self,
+        out: &mut W,
+        rng: &mut R,
+    ) -> Result<(), VDGeneratorError> {
+        self.generate_to_in(&self.layout(A::CHARS)?, out, rng)
+    }
+
+    /// Generates a code and writes it to `out` as UTF-8, as
+    /// [`generate_to`](Self::generate_to) does.
+    ///
+    /// Each character is written separately, so wrap files and sockets in a
+    /// [`BufWriter`](std::io::BufWriter).
+    ///
+    /// This method is only available when the `std` feature is enabled.
+    ///
+    /// # Errors
+    /// Returns the error of `out` if writing fails, and otherwise wraps the
+    /// [`VDGeneratorError`] in an [`std::io::Error`] of kind `Other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::io::Write;
+    /// use vds::{StepRng, VDGenerator};
+    ///
+    /// let mut out = Vec::new();
+    /// VDGenerator::new().length(4).generate_to_writer(&mut out, &mut StepRng::new(0, 1)).unwrap();
+    /// writeln!(out).unwrap();
+    /// assert_eq!(out, b"ABCD\n");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn generate_to_writer<W: std::io::Write + ?Sized, R: VDRng + ?Sized>(
+        &self,
+        out: &mut W,
+        rng: &mut R,
+    ) -> std::io::Result<()> {
+        let mut out = IoAdapter { inner: out, error: None };
+        self.generate_to(&mut out, rng).map_err(|err| out.into_error(err))
+    }
+
+    /// Generates into `out` with an already validated `layout`.
+    fn generate_to_in<W: fmt::Write + ?Sized, R: VDRng + ?Sized>(
+        &self,
+        layout: &Layout,
+        out: &mut W,
+        rng: &mut R,
+    ) -> Result<(), VDGeneratorError> {
+        let mut stack = [0u8; SCRATCH_LEN];
+        #[cfg(feature = "alloc")]
+        let mut heap;
+        let random = match stack.get_mut(..layout.len) {
+            Some(random) => random,
+            #[cfg(feature = "alloc")]
+            None => {
+                heap = vec![0; layout.len];
+                &mut heap[..]
+            }
+            #[cfg(not(feature = "alloc"))]
+            None => {
+                return Err(VDGeneratorError::BufferTooSmall { required: self.len, available: SCRATCH_LEN })
+            }
+        };
+        self.draw(layout, A::CHARS, random, rng)?;
+
+        let (prefix, suffix) = self.affixes();
+        prefix
+            .iter()
+            .map(|c| c.as_char())
+            .chain(random.iter().map(|&index| A::CHARS[usize::from(index)]))
+            .chain(suffix.iter().map(|c| c.as_char()))
+            .try_for_each(|c| out.write_char(c))
+            .map_err(|_| VDGeneratorError::WriteFailed)
+    }
+
+    /// Generates into `buf` with an already validated `layout`.
+    fn generate_into_in<'b, R: VDRng + ?Sized>(
+        &self,
+        layout: &Layout,
+        buf: &'b mut [VDChar<A>],
+        rng: &mut R,
+    ) -> Result<&'b VDStr<A>, VDGeneratorError> {
+        let available = buf.len();
+        let code = buf
+            .get_mut(..self.len)
+            .ok_or(VDGeneratorError::BufferTooSmall { required: self.len, available })?;
+
+        let (prefix, suffix) = self.affixes();
+        let (head, rest) = code.split_at_mut(prefix.len());
+        let (random, tail) = rest.split_at_mut(layout.len);
+        head.copy_from_slice(prefix);
+        tail.copy_from_slice(suffix);
+        self.draw(layout, A::CHARS, random, rng)?;
+        Ok(VDStr::from_vdchars(code))
+    }
+
+    /// Fills `out` with random parts over `chars` until the whole code
+    /// passes every filter, returning the attempt count and rejections.
+    fn draw<T: Slot, R: VDRng + ?Sized>(
+        &self,
+        layout: &Layout,
+        chars: &[char],
+        out: &mut [T],
+        rng: &mut R,
+    ) -> Result<(usize, Rejections), VDGeneratorError> {
+        let mut rejected_by = Rejections::default();
+        let attempts = self.draw_counted(layout, chars, out, rng, &mut rejected_by)?;
+        Ok((attempts, rejected_by))
+    }
+
+    /// Like [`draw`](Self::draw), but tallies rejections into `rejected_by`
+    /// so they are still available if drawing fails.
+    fn draw_counted<T: Slot, R: VDRng + ?Sized>(
+        &self,
+        layout: &Layout,
+        chars: &[char],
+        out: &mut [T],
+        rng: &mut R,
+        rejected_by: &mut Rejections,
+    ) -> Result<usize, VDGeneratorError> {
+        let (prefix, suffix) = self.affixes();
+        for attempts in 1..=self.max_attempts.max(1) {
+            let budget = self.max_attempts.saturating_sub(rejected_by.adjacent_repeats);
+            rejected_by.adjacent_repeats += self.sample(layout, out, rng, budget)?;
+
+            let candidate = prefix
+                .iter()
+                .map(|c| c.as_char())
+                .chain(out.iter().map(|slot| chars[usize::from(slot.index())]))
+                .chain(suffix.iter().map(|c| c.as_char()));
+            if self.is_denied(candidate.clone()) {
+                rejected_by.denied_substrings += 1;
+            } else if self.is_near_avoided(candidate.clone()) {
+                rejected_by.avoided += 1;
+            } else if self.max_run.is_some_and(|run| has_sequential_run(candidate.clone(), chars, run)) {
+                rejected_by.sequential_runs += 1;
+            } else if !self.has_composition(candidate) {
+                rejected_by.composition += 1;
+            } else {
+                return Ok(attempts);
+            }
+        }
+        // Report whichever constraint rejected the most candidates.
+        let attempts = self.max_attempts.max(1);
+        let Rejections { denied_substrings, avoided, sequential_runs, composition, .. } = *rejected_by;
+        if composition > denied_substrings.max(avoided).max(sequential_runs) {
+            Err(VDGeneratorError::CompositionInfeasible)
+        } else if sequential_runs > denied_substrings.max(avoided) {
+            Err(VDGeneratorError::SequentialRunsUnavoidable { attempts })
+        } else if avoided > denied_substrings {
+            Err(VDGeneratorError::AvoidedCodesUnavoidable { attempts })
+        } else {
+            Err(VDGeneratorError::DeniedSubstringsUnavoidable { attempts })
+        }
+    }
+
+    /// Returns `true` if `code` contains a denied substring.
+    #[cfg(feature = "alloc")]
+    fn is_denied(&self, code: impl Iterator<Item = char>) -> bool {
+        if self.denied.is_empty() {
+            return false;
+        }
+        let code: Vec<char> = code.collect();
+        self.denied.iter().any(|word| code.windows(word.len()).any(|window| window == &word[..]))
+    }
+
+    /// Returns `false`: deny lists need `alloc` to configure.
+    #[cfg(not(feature = "alloc"))]
+    fn is_denied(&self, _code: impl Iterator<Item = char>) -> bool {
+        false
+    }
+
+    /// Returns `true` if `code` is closer than the avoid distance to an
+    /// avoided code.
+    #[cfg(feature = "alloc")]
+    fn is_near_avoided(&self, code: impl Iterator<Item = char> + Clone) -> bool {
+        if self.avoid_distance == 0 {
+            return false;
+        }
+        let plain = crate::distance::ConfusionMatrix::new();
+        let limit = self.avoid_distance as f64;
+        self.avoided.iter().any(|avoided| {
+            let avoided = avoided.iter().map(|c| c.as_char());
+            crate::distance::weighted_edit_distance(avoided, code.clone(), &plain) < limit
+        })
+    }
+
+    /// Returns `false`: avoided codes need `alloc` to configure.
+    #[cfg(not(feature = "alloc"))]
+    fn is_near_avoided(&self, _code: impl Iterator<Item = char> + Clone) -> bool {
+        false
+    }
+
+    /// Returns the prefix and suffix.
+    #[cfg(feature = "alloc")]
+    fn affixes(&self) -> (&[VDChar<A>], &[VDChar<A>]) {
+        (&self.prefix, &self.suffix)
+    }
+
+    /// Returns empty slices: affixes need `alloc` to configure.
+    #[cfg(not(feature = "alloc"))]
+    fn affixes(&self) -> (&[VDChar<A>], &[VDChar<A>]) {
+        (&[], &[])
+    }
+
+    /// Returns the excluded characters.
+    #[cfg(feature = "alloc")]
+    fn excluded(&self) -> &[VDChar<A>] {
+        &self.excluded
+    }
+
+    /// Returns an empty slice: exclusions need `alloc` to configure.
+    #[cfg(not(feature = "alloc"))]
+    fn excluded(&self) -> &[VDChar<A>] {
+        &[]
+    }
+
+    /// Returns `true` if `code` has the minimum number of digits and letters.
+    fn has_composition(&self, code: impl Iterator<Item = char>) -> bool {
+        let (digits, len) = code.fold((0, 0), |(digits, len), c| (digits + usize::from(c.is_ascii_digit()), len + 1));
+        digits >= self.min_digits && len - digits >= self.min_letters
+    }
+
+    /// Splits a code over `chars` into its fixed and random parts, checking
+    /// that the prefix and suffix fit the length and repeat constraints.
+    fn layout(&self, chars: &[char]) -> Result<Layout, VDGeneratorError> {
+        let (prefix, suffix) = self.affixes();
+        let affixes = prefix.len() + suffix.len();
+        let len = self
+            .len
+            .checked_sub(affixes)
+            .ok_or(VDGeneratorError::AffixesExceedLength { length: self.len, affixes })?;
+
+        let fixed = || prefix.iter().chain(suffix).map(|c| c.as_char());
+        let conflict = if self.no_repeats {
+            fixed().enumerate().any(|(i, c)| fixed().skip(i + 1).any(|d| d == c))
+        } else if self.no_adjacent_repeats {
+            let touching = len == 0 && !prefix.is_empty() && prefix.last() == suffix.first();
+            touching || prefix.windows(2).chain(suffix.windows(2)).any(|w| w[0] == w[1])
+        } else {
+            false
+        };
+        if conflict {
+            return Err(VDGeneratorError::AffixesViolateConstraints);
+        }
+
+        let mut pool = [0; 256];
+        let mut pool_len = 0;
+        for (i, &c) in chars.iter().enumerate() {
+            let excluded = self.excluded().iter().any(|x| x.as_char() == c);
+            let fixed_repeat = self.no_repeats && fixed().any(|f| f == c);
+            if !excluded && !fixed_repeat {
+                pool[pool_len] = i as u8;
+                pool_len += 1;
+            }
+        }
+        if len > 0 && pool_len == 0 && !self.no_repeats {
+            return Err(VDGeneratorError::AllCharsExcluded);
+        }
+        if self.no_repeats && len > pool_len {
+            return Err(VDGeneratorError::LengthExceedsUniqueSet {
+                requested: self.len,
+                available: pool_len + affixes,
+            });
+        }
+
+        let fixed_digits = fixed().filter(char::is_ascii_digit).count();
+        let need_digits = self.min_digits.saturating_sub(fixed_digits);
+        let need_letters = self.min_letters.saturating_sub(affixes - fixed_digits);
+        let pool_digits = pool[..pool_len].iter().filter(|&&i| chars[usize::from(i)].is_ascii_digit()).count();
+        let pool_letters = pool_len - pool_digits;
+        // Without repeats each needed character must be a different one.
+        let (max_digits, max_letters) = if self.no_repeats {
+            (pool_digits, pool_letters)
+        } else {
+            (usize::from(pool_digits > 0) * len, usize::from(pool_letters > 0) * len)
+        };
+        if need_digits + need_letters > len || need_digits > max_digits || need_letters > max_letters {
+            return Err(VDGeneratorError::CompositionInfeasible);
+        }
+
+        let index_of = |c: &VDChar<A>| chars.iter().position(|&x| x == c.as_char()).map(|i| i as u8);
+        let layout = Layout {
+            len,
+            pool,
+            pool_len,
+            before: prefix.last().and_then(index_of),
+            after: suffix.first().and_then(index_of),
+        };
+        if self.keyspace_size(&layout) == Some(0) {
+            return Err(if affixes > 0 {
+                VDGeneratorError::AffixesViolateConstraints
+            } else {
+                VDGeneratorError::ConstraintUnsatisfiable { attempts: 0 }
+            });
+        }
+        Ok(layout)
+    }
+
+    /// Fills `out` with the random part of a code, honoring the repeat
+    /// constraints, and returns the number of characters redrawn.
+    ///
+    /// Gives up once more than `budget` characters have been redrawn.
+    fn sample<T: Slot, R: VDRng + ?Sized>(
+        &self,
+        layout: &Layout,
+        out: &mut [T],
+        rng: &mut R,
+        budget: usize,
+    ) -> Result<usize, VDGeneratorError> {
+        let exhausted = VDGeneratorError::ConstraintUnsatisfiable { attempts: self.max_attempts };
+        let mut redrawn = 0;
+
+        if self.no_repeats {
+            // Sample without replacement by shuffling. The pool excludes the
+            // prefix and suffix, so the random part cannot repeat them.
+            let mut pool = layout.pool;
+
+            // Fisher-Yates shuffle (partial)
+            for i in 0..layout.len {
+                let j = i + uniform_index(rng, (layout.pool_len - i) as u32) as usize;
+                pool.swap(i, j);
+            }
+
+            for (slot, &index) in out.iter_mut().zip(&pool[..layout.len]) {
+                *slot = T::from_index(index);
+            }
+
+            if self.no_adjacent_repeats {
+                // Rotate until no adjacent duplicates, up to `len` tries
+                for _ in 0..layout.len {
+                    if out.windows(2).any(|w| w[0] == w[1]) {
+                        out.rotate_left(1);
+                    } else {
+                        break;
+                    }
+                }
+            }
+
+            return Ok(0);
+        }
+
+        // With replacement sampling
+        let pool = layout.pool();
+        loop {
+            let mut filled = 0;
+            let mut last = layout.before;
+
+            while filled < layout.len {
+                let idx = pool[uniform_index(rng, pool.len() as u32) as usize];
+
+                if self.no_adjacent_repeats && last == Some(idx) {
+                    redrawn += 1;
+                    if redrawn > budget {
+                        return Err(exhausted);
+                    }
+                    continue;
+                }
+
+                out[filled] = T::from_index(idx);
+                filled += 1;
+                last = Some(idx);
+            }
+
+            // Redraw the whole part rather than just its last character, so
+            // every valid code stays equally likely.
+            if self.no_adjacent_repeats && layout.len > 0 && last == layout.after {
+                redrawn += 1;
+                if redrawn > budget {
+                    return Err(exhausted);
+                }
+                continue;
+            }
+
+            return Ok(redrawn);
+        }
+    }
+
+    /// Returns the number of distinct codes this configuration can produce,
+    /// or `None` if it does not fit in a `u128`.
+    fn keyspace_size(&self, layout: &Layout) -> Option<u128> {
+        let n = layout.pool_len as u128;
+        let len = layout.len as u128;
+        if self.no_repeats {
+            (0..len).try_fold(1u128, |acc, i| acc.checked_mul(n.saturating_sub(i)))
+        } else if self.no_adjacent_repeats && len > 0 {
+            let (before, after) = layout.bounds();
+            let first = n - u128::from(before.is_some());
+            let Some(after) = after else {
+                return (1..len).try_fold(first, |acc, _| acc.checked_mul(n.saturating_sub(1)));
+            };
+            // Count random parts ending in `after` and ending elsewhere; only
+            // the latter may be followed by the suffix.
+            let mut ending_after = u128::from(before != Some(after));
+            let mut ending_other = first - ending_after;
+            for _ in 1..len {
+                let next_other = ending_after
+                    .checked_mul(n - 1)?
+                    .checked_add(ending_other.checked_mul(n.saturating_sub(2))?)?;
+                ending_after = ending_other;
+                ending_other = next_other;
+            }
+            Some(ending_other)
+        } else {
+            (0..len).try_fold(1u128, |acc, _| acc.checked_mul(n))
+        }
+    }
+
+    /// Returns `log2` of the number of distinct codes this configuration can produce.
+    fn keyspace_bits(&self, layout: &Layout) -> f64 {
+        let n = layout.pool_len;
+        let len = layout.len;
+        let min_distinct = if self.no_repeats {
+            len
+        } else if self.no_adjacent_repeats {
+            len.min(2)
+        } else {
+            len.min(1)
+        };
+        if min_distinct > n {
+            return f64::NEG_INFINITY;
+        }
+
+        if self.no_repeats {
+            (0..len).map(|i| log2((n - i) as f64)).sum()
+        } else if self.no_adjacent_repeats && len > 0 {
+            let (before, after) = layout.bounds();
+            let first = (n - usize::from(before.is_some())) as f64;
+            let n = n as f64;
+            let Some(after) = after else {
+                return if first < 1.0 { f64::NEG_INFINITY } else { log2(first) + (len - 1) as f64 * log2(n - 1.0) };
+            };
+            // As in `keyspace_size`, scaled down by `n - 1` per character so
+            // long codes don't overflow; the total grows by exactly that much.
+            let mut ending_after = if before == Some(after) { 0.0 } else { 1.0 };
+            let mut ending_other = first - ending_after;
+            let mut bits = 0.0;
+            for _ in 1..len {
+                let next_other = ending_after * (n - 1.0) + ending_other * (n - 2.0);
+                ending_after = ending_other / (n - 1.0);
+                ending_other = next_other / (n - 1.0);
+                bits += log2(n - 1.0);
+            }
+            if ending_other <= 0.0 {
+                f64::NEG_INFINITY
+            } else if ending_other < 1.0 {
+                bits - log2(1.0 / ending_other)
+            } else {
+                bits + log2(ending_other)
+            }
+        } else {
+            len as f64 * log2(n as f64)
+        }
+    }
+}
+
+impl Layout {
+    /// Returns the alphabet indices the random part may use.
+    fn pool(&self) -> &[u8] {
+        &self.pool[..self.pool_len]
+    }
+
+    /// Returns the characters adjacent to the random part that it may
+    /// actually draw, i.e. those an adjacent repeat could collide with.
+    fn bounds(&self) -> (Option<u8>, Option<u8>) {
+        let in_pool = |i: &u8| self.pool().contains(i);
+        (self.before.filter(in_pool), self.after.filter(in_pool))
+    }
+}
+
+/// A generated [`VDString`] plus metadata about how it was produced.
+///
+/// Returned by [`VDGenerator::generate_audited`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedCode<A: Alphabet = DefaultAlphabet> {
+    /// The generated code.
+    pub code: VDString<A>,
+    /// Number of candidate codes produced before one satisfied every
+    /// constraint (at least `1`).
+    pub attempts: usize,
+    /// Number of random draws rejected by each constraint.
+    pub rejected_by: Rejections,
+    /// Bits of entropy of the configuration that produced this code, i.e.
+    /// `log2` of the number of codes it could have produced.
+    pub entropy_bits: f64,
+}
+
+/// Endless iterator of generated codes, returned by [`VDGenerator::iter`].
+#[cfg(feature = "alloc")]
+pub struct VDGeneratorIter<'a, R, A: Alphabet = DefaultAlphabet> {
+    generator: &'a VDGenerator<A>,
+    rng: R,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<R: VDRng, A: Alphabet> Iterator for VDGeneratorIter<'_, R, A> {
+    type Item = VDString<A>;
+
+    fn next(&mut self) -> Option<VDString<A>> {
+        if self.done {
+            return None;
+        }
+        let code = self.generator.generate(&mut self.rng).ok();
+        self.done = code.is_none();
+        code
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done { (0, Some(0)) } else { (usize::MAX, None) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: VDRng, A: Alphabet> core::iter::FusedIterator for VDGeneratorIter<'_, R, A> {}
+
+/// Receives the outcome of each generation, for exporting metrics.
+///
+/// Passed to [`VDGenerator::generate_observed`]. Both methods do nothing
+/// by default, so implementations only override what they record.
+#[cfg(feature = "alloc")]
+pub trait GenerationObserver {
+    /// Called when a code passes every constraint, after `attempts`
+    /// candidates (at least `1`).
+    fn on_accepted(&mut self, attempts: usize, rejected_by: &Rejections) {
+        let _ = (attempts, rejected_by);
+    }
+
+    /// Called when generation fails with `error`, with the rejections
+    /// counted before giving up.
+    fn on_failed(&mut self, error: &VDGeneratorError, rejected_by: &Rejections) {
+        let _ = (error, rejected_by);
+    }
+}
+
+/// Per-constraint rejection counts reported in `GeneratedCode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Rejections {
+    /// Characters redrawn because they repeated the previous character.
+    pub adjacent_repeats: usize,
+    /// Candidate codes discarded because they contained a denied substring.
+    pub denied_substrings: usize,
+    /// Candidate codes discarded for being too close to an
+    /// [avoided](VDGenerator::avoid) code.
+    pub avoided: usize,
+    /// Candidate codes discarded because they contained a sequential run.
+    pub sequential_runs: usize,
+    /// Candidate codes discarded for having too few digits or letters.
+    pub composition: usize,
+}
+
+/// Returns `true` if `code` has `run` consecutive characters stepping up or
+/// down by one, either by position in `chars` or by ASCII value.
+/// Fills `buf` to capacity with placeholders for `generate_into` to overwrite.
+#[cfg(feature = "heapless")]
+fn heapless_buf<A: Alphabet, const N: usize>(buf: &mut heapless::Vec<VDChar<A>, N>) -> &mut [VDChar<A>] {
+    // Cannot fail: the length is the capacity.
+    let _ = buf.resize(N, VDChar::from_index_unchecked(0));
+    buf
+}
+
+fn has_sequential_run(code: impl Iterator<Item = char> + Clone, chars: &[char], run: usize) -> bool {
+    let has_run = |order: &dyn Fn(char) -> Option<i64>| {
+        // Lengths of the ascending and descending runs ending at each character.
+        let (mut up, mut down) = (1, 1);
+        let mut prev = None;
+        code.clone().any(|c| {
+            let key = order(c);
+            let step = prev.zip(key).map(|(a, b)| b - a);
+            prev = key;
+            up = if step == Some(1) { up + 1 } else { 1 };
+            down = if step == Some(-1) { down + 1 } else { 1 };
+            up >= run || down >= run
+        })
+    };
+    has_run(&|c| chars.iter().position(|&x| x == c).map(|i| i as i64)) || has_run(&|c| Some(i64::from(u32::from(c))))
+}
+
+/// Base-2 logarithm for `x >= 1` without `std`.
+///
+/// Computes the integer part by halving, then the fraction one bit at a time
+/// by repeated squaring.
+pub(crate) fn log2(mut x: f64) -> f64 {
+    let mut result = 0.0;
+    while x >= 2.0 {
+        x /= 2.0;
+        result += 1.0;
+    }
+    let mut bit = 0.5;
+    for _ in 0..f64::MANTISSA_DIGITS {
+        x *= x;
+        if x >= 2.0 {
+            x /= 2.0;
+            result += bit;
+        }
+        bit /= 2.0;
+    }
+    result
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use crate::alphabet::Default31;
+    use crate::VDS_ALLOWED;
+    use alloc::vec;
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    fn seeded_rng() -> SmallRng {
+        SmallRng::seed_from_u64(42)
+    }
+
+    #[test]
+    fn affixes_frame_the_random_part() {
+        let mut rng = seeded_rng();
+        let prefix: VDString<Default31> = "EU".parse().unwrap();
+        let suffix: VDString<Default31> = "7".parse().unwrap();
+        let gen = VDGenerator::<Default31>::default().length(8).prefix(&prefix).suffix(&suffix);
+        for _ in 0..100 {
+            let code = gen.generate(&mut rng).unwrap();
+            assert_eq!(code.len(), 8);
+            assert!(code.as_str().starts_with("EU") && code.as_str().ends_with('7'));
+        }
+        assert!((gen.entropy_bits() - 5.0 * log2(31.0)).abs() < 1e-9);
+
+        for code in gen.min_distance(2).generate_many(100, &mut rng).unwrap() {
+            assert!(code.as_str().starts_with("EU"));
+        }
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(2).prefix(&prefix).suffix(&suffix).generate(&mut rng),
+            Err(VDGeneratorError::AffixesExceedLength { length: 2, affixes: 3 })
+        );
+    }
+
+    #[test]
+    fn affixes_count_toward_repeat_constraints() {
+        let mut rng = seeded_rng();
+        let prefix: VDString<Default31> = "ABC".parse().unwrap();
+        let gen = VDGenerator::<Default31>::default().length(31).no_repeats().prefix(&prefix);
+        let code = gen.generate(&mut rng).unwrap();
+        assert!(!code.has_repeats());
+        let expected: f64 = (1..=28).map(|n| log2(n as f64)).sum();
+        assert!((gen.entropy_bits() - expected).abs() < 1e-9);
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(32).no_repeats().prefix(&prefix).generate(&mut rng),
+            Err(VDGeneratorError::LengthExceedsUniqueSet { requested: 32, available: 31 })
+        );
+
+        let gen = VDGenerator::<Default31>::default().length(4).no_adjacent_repeats().prefix(&prefix[2..]).suffix(&prefix[..1]);
+        for _ in 0..200 {
+            let code = gen.generate(&mut rng).unwrap();
+            assert!(code.as_vdchars().windows(2).all(|w| w[0] != w[1]), "{}", code);
+        }
+        // C _ _ A: either A then 30 others, or one of 29 then 29 (not itself, not A).
+        assert_eq!(gen.layout(VDS_ALLOWED).map(|l| gen.keyspace_size(&l)), Ok(Some(30 + 29 * 29)));
+        assert!((gen.entropy_bits() - log2(871.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn conflicting_affixes_error() {
+        let mut rng = seeded_rng();
+        let aa: VDString<Default31> = "AA".parse().unwrap();
+        let a: VDString<Default31> = "A".parse().unwrap();
+        let err = Err(VDGeneratorError::AffixesViolateConstraints);
+
+        assert_eq!(VDGenerator::<Default31>::default().no_adjacent_repeats().prefix(&aa).generate(&mut rng), err);
+        assert_eq!(VDGenerator::<Default31>::default().no_repeats().prefix(&a).suffix(&a).generate(&mut rng), err);
+        assert_eq!(VDGenerator::<Default31>::default().length(2).no_adjacent_repeats().prefix(&a).suffix(&a).generate(&mut rng), err);
+        assert!(VDGenerator::<Default31>::default().prefix(&aa).generate(&mut rng).is_ok());
+        assert_eq!(VDGenerator::<Default31>::default().prefix(&aa).no_repeats().entropy_bits(), f64::NEG_INFINITY);
+
+        // A two-letter alphabet leaves nothing between A and B.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        struct Ab;
+        impl Alphabet for Ab {
+            const CHARS: &'static [char] = &['A', 'B'];
+        }
+        let a = VDString::<Ab>::new(vec![VDChar::from_index_unchecked(0)]);
+        let b = VDString::<Ab>::new(vec![VDChar::from_index_unchecked(1)]);
+        let gen = VDGenerator::<Ab>::default().length(3).no_adjacent_repeats();
+        assert_eq!(gen.prefix(&a).suffix(&b).generate(&mut rng), Err(VDGeneratorError::AffixesViolateConstraints));
+    }
+
+    #[test]
+    fn excluded_chars_never_appear() {
+        let mut rng = seeded_rng();
+        let excluded: Vec<VDChar<Default31>> = "B8".chars().map(|c| VDChar::<Default31>::new(c).unwrap()).collect();
+        let gen = VDGenerator::<Default31>::default().length(12).exclude_chars(&excluded);
+        for _ in 0..200 {
+            assert!(!gen.generate(&mut rng).unwrap().as_str().contains(['B', '8']));
+        }
+        assert!((gen.entropy_bits() - 12.0 * log2(29.0)).abs() < 1e-9);
+
+        let distinct = VDGenerator::<Default31>::default().length(30).no_repeats().exclude_chars(&excluded);
+        assert_eq!(
+            distinct.generate(&mut rng),
+            Err(VDGeneratorError::LengthExceedsUniqueSet { requested: 30, available: 29 })
+        );
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(2).exclude_chars(&excluded).generate_many(29 * 29 + 1, &mut rng),
+            Err(VDGeneratorError::NotEnoughDistinctCodes { requested: 842, available: 841 })
+        );
+
+        let all: Vec<VDChar<Default31>> = VDS_ALLOWED.iter().map(|&c| VDChar::<Default31>::new(c).unwrap()).collect();
+        let gen = VDGenerator::<Default31>::default().exclude_chars(&all);
+        assert_eq!(gen.generate(&mut rng), Err(VDGeneratorError::AllCharsExcluded));
+        assert_eq!(gen.entropy_bits(), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn sequential_runs_never_appear() {
+        let mut rng = seeded_rng();
+        let gen = VDGenerator::<Default31>::default().length(10).no_sequential_runs(3);
+        let mut rejected = 0;
+        for _ in 0..300 {
+            let generated = gen.generate_audited(&mut rng).unwrap();
+            let chars: Vec<char> = generated.code.as_str().chars().collect();
+            assert!(!has_sequential_run(chars.iter().copied(), VDS_ALLOWED, 3), "{}", generated.code);
+            rejected += generated.rejected_by.sequential_runs;
+        }
+        assert!(rejected > 0);
+
+        let code = |s: &'static str| s.chars();
+        assert!(has_sequential_run(code("XABC2"), VDS_ALLOWED, 3));
+        assert!(has_sequential_run(code("987"), VDS_ALLOWED, 3));
+        assert!(has_sequential_run(code("GHJ"), VDS_ALLOWED, 3), "alphabet order skips I");
+        assert!(has_sequential_run(code("Z23"), VDS_ALLOWED, 3), "alphabet order runs Z into 2");
+        assert!(!has_sequential_run(code("ABC"), VDS_ALLOWED, 4));
+        assert!(!has_sequential_run(code("ABDC"), VDS_ALLOWED, 3));
+        assert!(!has_sequential_run(code("ABA"), VDS_ALLOWED, 3));
+
+        let stuck = VDGenerator::<Default31>::default().length(3).no_sequential_runs(2);
+        assert_eq!(
+            stuck.generate(&mut crate::StepRng::new(0, 1)),
+            Err(VDGeneratorError::SequentialRunsUnavoidable { attempts: 10_000 })
+        );
+    }
+
+    #[test]
+    fn composition_is_met() {
+        let mut rng = seeded_rng();
+        let gen = VDGenerator::<Default31>::default().length(6).min_digits(2).min_letters(3);
+        for _ in 0..300 {
+            let code = gen.generate(&mut rng).unwrap();
+            let digits = code.iter().filter(|c| c.is_digit()).count();
+            assert!(digits >= 2 && code.len() - digits >= 3, "{}", code);
+        }
+
+        // Affix characters count toward the minimums.
+        let prefix: VDString<Default31> = "23".parse().unwrap();
+        let gen = VDGenerator::<Default31>::default().length(4).prefix(&prefix).min_digits(2).min_letters(2);
+        assert!(gen.generate(&mut rng).unwrap()[2..].iter().all(|c| c.is_letter()));
+        assert_eq!(gen.min_digits(3).generate(&mut rng), Err(VDGeneratorError::CompositionInfeasible));
+    }
+
+    #[test]
+    fn infeasible_composition_fails_fast() {
+        let mut rng = seeded_rng();
+        let err = Err(VDGeneratorError::CompositionInfeasible);
+        assert_eq!(VDGenerator::<Default31>::default().length(4).min_digits(3).min_letters(2).generate(&mut rng), err);
+        assert_eq!(VDGenerator::<Default31>::default().length(9).no_repeats().min_digits(9).generate(&mut rng), err);
+        assert!(VDGenerator::<Default31>::default().length(8).no_repeats().min_digits(4).generate(&mut rng).is_ok());
+
+        let digits: Vec<VDChar<Default31>> = VDChar::<Default31>::all().filter(|c| c.is_digit()).collect();
+        let gen = VDGenerator::<Default31>::default().length(8).exclude_chars(&digits);
+        assert_eq!(gen.min_digits(1).generate(&mut rng), err);
+        assert_eq!(VDGenerator::<Default31>::default().length(8).exclude_chars(&digits).min_letters(8).generate(&mut rng).unwrap().len(), 8);
+    }
+
+    #[test]
+    fn iter_yields_constrained_codes() {
+        let mut rng = seeded_rng();
+        let gen = VDGenerator::<Default31>::default().length(5).no_adjacent_repeats().min_digits(1);
+        let codes: Vec<VDString<Default31>> = gen.iter(&mut rng).unwrap().take(50).collect();
+        assert_eq!(codes.len(), 50);
+        assert!(codes.iter().all(|c| c.iter().any(|ch| ch.is_digit())));
+        assert!(gen.generate(&mut rng).is_ok(), "rng is still usable");
+
+        let bad = VDGenerator::<Default31>::default().length(32).no_repeats();
+        assert!(matches!(bad.iter(&mut rng), Err(VDGeneratorError::LengthExceedsUniqueSet { .. })));
+
+        let stuck = VDGenerator::<Default31>::default().length(3).no_sequential_runs(2);
+        let mut iter = stuck.iter(crate::StepRng::new(0, 1)).unwrap();
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn generate_into_fills_buffer() {
+        let mut rng = seeded_rng();
+        let mut buf = [VDChar::<Default31>::new('A').unwrap(); 10];
+        let prefix: VDString<Default31> = "EU".parse().unwrap();
+        let gen = VDGenerator::<Default31>::default().length(8).no_repeats().prefix(&prefix).min_digits(2);
+        let code = gen.generate_into(&mut buf, &mut rng).unwrap();
+        assert_eq!(code.len(), 8);
+        assert_eq!(&code[..2], &*prefix);
+        assert!(!code.has_repeats());
+        assert!(code.iter().filter(|c| c.is_digit()).count() >= 2);
+
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(11).generate_into(&mut buf, &mut rng),
+            Err(VDGeneratorError::BufferTooSmall { required: 11, available: 10 })
+        );
+
+        // Same draws as `generate` for the same RNG state.
+        let gen = VDGenerator::<Default31>::default().length(10).no_adjacent_repeats();
+        let owned = gen.generate(&mut SmallRng::seed_from_u64(4)).unwrap();
+        assert_eq!(gen.generate_into(&mut buf, &mut SmallRng::seed_from_u64(4)).unwrap(), &*owned);
+    }
+
+    #[test]
+    fn compiled_matches_uncompiled() {
+        let prefix: VDString<Default31> = "K".parse().unwrap();
+        let gen = VDGenerator::<Default31>::default().length(9).no_repeats().prefix(&prefix).min_letters(3);
+        let compiled = gen.clone().build().unwrap();
+        assert_eq!(compiled.code_len(), 9);
+        assert_eq!(compiled.entropy_bits(), gen.entropy_bits());
+
+        let mut a = SmallRng::seed_from_u64(8);
+        let mut b = SmallRng::seed_from_u64(8);
+        let mut buf = [VDChar::<Default31>::new('A').unwrap(); 9];
+        for _ in 0..50 {
+            assert_eq!(compiled.generate(&mut a), gen.generate(&mut b));
+        }
+        assert_eq!(compiled.generate_into(&mut buf, &mut a).unwrap(), &*gen.generate(&mut b).unwrap());
+
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(2).prefix(&prefix).suffix(&prefix).no_repeats().build().map(|_| ()),
+            Err(VDGeneratorError::AffixesViolateConstraints)
+        );
+    }
+
+    #[test]
+    fn generate_to_matches_generate() {
+        struct Full;
+        impl fmt::Write for Full {
+            fn write_str(&mut self, _: &str) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+
+        let affix: VDString<Default31> = "K7".parse().unwrap();
+        for len in [6, SCRATCH_LEN + 10] {
+            let gen = VDGenerator::<Default31>::default().length(len).prefix(&affix).suffix(&affix).no_adjacent_repeats();
+            let compiled = gen.clone().build().unwrap();
+            let (mut a, mut b) = (SmallRng::seed_from_u64(5), SmallRng::seed_from_u64(5));
+            for _ in 0..20 {
+                let mut out = String::new();
+                compiled.generate_to(&mut out, &mut a).unwrap();
+                assert_eq!(out, gen.generate(&mut b).unwrap());
+            }
+        }
+        assert_eq!(VDGenerator::<Default31>::default().generate_to(&mut Full, &mut seeded_rng()), Err(VDGeneratorError::WriteFailed));
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(32).no_repeats().generate_to(&mut String::new(), &mut seeded_rng()),
+            Err(VDGeneratorError::LengthExceedsUniqueSet { requested: 32, available: 31 })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn generate_to_writer_passes_io_errors_through() {
+        use std::io;
+
+        struct Broken;
+        impl io::Write for Broken {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let gen = VDGenerator::<Default31>::default().length(8);
+        let mut out = Vec::new();
+        gen.generate_to_writer(&mut out, &mut SmallRng::seed_from_u64(3)).unwrap();
+        assert_eq!(out, gen.generate(&mut SmallRng::seed_from_u64(3)).unwrap().as_str().as_bytes());
+
+        let err = gen.generate_to_writer(&mut Broken, &mut seeded_rng()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        let err = gen.no_repeats().length(40).generate_to_writer(&mut out, &mut seeded_rng()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn retries_are_bounded() {
+        // A constant RNG can never produce two different characters.
+        let mut stuck = crate::StepRng::new(5, 0);
+        let gen = VDGenerator::<Default31>::default().length(2).no_adjacent_repeats().max_attempts(100);
+        assert_eq!(gen.generate(&mut stuck), Err(VDGeneratorError::ConstraintUnsatisfiable { attempts: 100 }));
+
+        let denied: Vec<VDString<Default31>> = vec!["G".parse().unwrap()];
+        let gen = VDGenerator::<Default31>::default().length(1).deny_substrings(&denied).max_attempts(3);
+        let mut rng = crate::StepRng::new(6, 0);
+        assert_eq!(gen.generate(&mut rng), Err(VDGeneratorError::DeniedSubstringsUnavoidable { attempts: 3 }));
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        struct One;
+        impl Alphabet for One {
+            const CHARS: &'static [char] = &['X'];
+        }
+        let gen = VDGenerator::<One>::default().length(2).no_adjacent_repeats();
+        assert_eq!(gen.generate(&mut seeded_rng()), Err(VDGeneratorError::ConstraintUnsatisfiable { attempts: 0 }));
+        assert!(VDGenerator::<One>::default().length(1).no_adjacent_repeats().generate(&mut seeded_rng()).is_ok());
+    }
+
+    #[test]
+    fn errors_display_details() {
+        use alloc::string::ToString;
+
+        let err = VDGeneratorError::LengthExceedsUniqueSet { requested: 40, available: 31 };
+        assert_eq!(err.to_string(), "length 40 exceeds the 31 distinct characters available");
+        let err = VDGeneratorError::ConstraintUnsatisfiable { attempts: 0 };
+        assert_eq!(err.to_string(), "no code satisfies the repeat constraints");
+        let err = VDGeneratorError::ConstraintUnsatisfiable { attempts: 100 };
+        assert_eq!(err.to_string(), "repeat constraints still unmet after 100 redraws");
+    }
+
+    #[test]
+    fn denied_substrings_never_appear() {
+        let mut rng = seeded_rng();
+        let denied: Vec<VDString<Default31>> = ["A", "B2"].iter().map(|s| s.parse().unwrap()).collect();
+        let gen = VDGenerator::<Default31>::default().length(8).deny_substrings(&denied);
+        let mut rejected = 0;
+        for _ in 0..300 {
+            let generated = gen.generate_audited(&mut rng).unwrap();
+            assert!(!generated.code.as_str().contains('A'));
+            assert!(!generated.code.as_str().contains("B2"));
+            assert_eq!(generated.attempts, generated.rejected_by.denied_substrings + 1);
+            rejected += generated.rejected_by.denied_substrings;
+        }
+        assert!(rejected > 0);
+
+        for code in gen.generate_many(50, &mut rng).unwrap() {
+            assert!(!code.as_str().contains('A'));
+        }
+    }
+
+    #[test]
+    fn avoided_codes_keep_their_distance() {
+        let old: Vec<VDString<Default31>> = ["AB", "C2", "XY9"].iter().map(|s| s.parse().unwrap()).collect();
+        let gen = VDGenerator::<Default31>::default().length(2).avoid(&old, 2);
+        let mut rng = seeded_rng();
+        let mut rejected = 0;
+        let plain = crate::distance::ConfusionMatrix::new();
+        for _ in 0..200 {
+            let generated = gen.generate_audited(&mut rng).unwrap();
+            assert!(old.iter().all(|code| code.confusable_distance(&generated.code, &plain) >= 2.0), "{}", generated.code);
+            rejected += generated.rejected_by.avoided;
+        }
+        assert!(rejected > 0);
+
+        let everything: Vec<VDString<Default31>> = ["A"].iter().map(|s| s.parse().unwrap()).collect();
+        let err = VDGenerator::<Default31>::default().length(1).avoid(&everything, 2).max_attempts(5).generate(&mut rng);
+        assert_eq!(err, Err(VDGeneratorError::AvoidedCodesUnavoidable { attempts: 5 }));
+    }
+
+    #[derive(Default)]
+    struct Tally {
+        accepted: Vec<(usize, Rejections)>,
+        failed: Vec<(VDGeneratorError, Rejections)>,
+    }
+
+    impl GenerationObserver for Tally {
+        fn on_accepted(&mut self, attempts: usize, rejected_by: &Rejections) {
+            self.accepted.push((attempts, *rejected_by));
+        }
+
+        fn on_failed(&mut self, error: &VDGeneratorError, rejected_by: &Rejections) {
+            self.failed.push((error.clone(), *rejected_by));
+        }
+    }
+
+    #[test]
+    fn observer_sees_rejections_and_failures() {
+        let denied: Vec<VDString<Default31>> = vec!["G".parse().unwrap()];
+        let gen = VDGenerator::<Default31>::default().length(8).deny_substrings(&denied);
+        let mut tally = Tally::default();
+        let mut rng = seeded_rng();
+        for _ in 0..20 {
+            let observed = gen.generate_observed(&mut rng, &mut tally).unwrap();
+            assert!(!observed.as_str().contains('G'));
+        }
+        assert_eq!(tally.accepted.len(), 20);
+        assert!(tally.accepted.iter().all(|(attempts, r)| *attempts == r.denied_substrings + 1));
+        assert!(tally.accepted.iter().any(|(_, r)| r.denied_substrings > 0));
+
+        let stuck = VDGenerator::<Default31>::default().length(1).deny_substrings(&denied).max_attempts(3);
+        let compiled = VDGenerator::<Default31>::default().length(1).no_repeats().build().unwrap();
+        let mut tally = Tally::default();
+        let mut rng = crate::StepRng::new(6, 0);
+        assert!(stuck.generate_observed(&mut rng, &mut tally).is_err());
+        assert!(VDGenerator::<Default31>::default().length(40).no_repeats().generate_observed(&mut rng, &mut tally).is_err());
+        assert!(compiled.generate_observed(&mut rng, &mut tally).is_ok());
+
+        let denied_3 = Rejections { denied_substrings: 3, ..Rejections::default() };
+        assert_eq!(tally.failed[0], (VDGeneratorError::DeniedSubstringsUnavoidable { attempts: 3 }, denied_3));
+        assert_eq!(tally.failed[1].0, VDGeneratorError::LengthExceedsUniqueSet { requested: 40, available: 31 });
+        assert_eq!(tally.failed[1].1, Rejections::default());
+        assert_eq!(tally.accepted, [(1, Rejections::default())]);
+    }
+
+    #[test]
+    fn unavoidable_denied_substrings_error() {
+        let everything: Vec<VDString<Default31>> = VDChar::all().map(|c| VDString::new(vec![c])).collect();
+        let gen = VDGenerator::<Default31>::default().length(3).deny_substrings(&everything);
+        assert_eq!(
+            gen.generate(&mut seeded_rng()),
+            Err(VDGeneratorError::DeniedSubstringsUnavoidable { attempts: 10_000 })
+        );
+    }
+
+    #[test]
+    fn confusable_digraphs_never_appear() {
+        use crate::distance::CONFUSABLE_DIGRAPHS;
+
+        let mut rng = seeded_rng();
+        let gen = VDGenerator::<Default31>::default().length(2).no_confusable_digraphs();
+        for _ in 0..2000 {
+            let code = gen.generate(&mut rng).unwrap();
+            assert!(CONFUSABLE_DIGRAPHS.iter().all(|(pair, _)| !pair.chars().eq(code.iter().map(VDChar::<Default31>::as_char))));
+        }
+
+        let a: VDString<Default31> = "A".parse().unwrap();
+        let custom = VDGenerator::<Default31>::default().length(2).prefix(&a).no_confusable_digraphs_from(&[("AB", 'X')]);
+        assert_eq!(
+            custom.generate(&mut crate::StepRng::new(1, 0)),
+            Err(VDGeneratorError::DeniedSubstringsUnavoidable { attempts: 10_000 })
+        );
+    }
+
+    #[cfg(feature = "profanity")]
+    #[test]
+    fn deny_profanity_filters_builtin_list() {
+        let mut rng = crate::StepRng::new(0, 1);
+        let gen = VDGenerator::<Default31>::default().length(3).deny_profanity();
+        for _ in 0..500 {
+            let code = gen.generate(&mut rng).unwrap();
+            assert!(crate::PROFANITY.iter().all(|w| !code.as_str().contains(w)));
+        }
+    }
+
+    #[test]
+    fn generate_many_respects_min_distance() {
+        let mut rng = seeded_rng();
+        let codes = VDGenerator::<Default31>::default().length(6).min_distance(3).generate_many(200, &mut rng).unwrap();
+        for (i, a) in codes.iter().enumerate() {
+            for b in &codes[i + 1..] {
+                assert!(a.hamming_distance(b).unwrap() >= 3, "{} {}", a, b);
+            }
+        }
+
+        // Singleton bound: at most 31^(2 - 2 + 1) = 31 codes of length 2 at distance 2.
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(2).min_distance(2).generate_many(32, &mut rng),
+            Err(VDGeneratorError::NotEnoughDistinctCodes { requested: 32, available: 31 })
+        );
+        assert_eq!(VDGenerator::<Default31>::default().length(2).min_distance(3).generate_many(1, &mut rng).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn generate_many_gives_up_when_spacing_is_unreachable() {
+        let mut rng = seeded_rng();
+        // A constant RNG repeats its first code forever.
+        let mut stuck = crate::StepRng::new(5, 0);
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(4).min_distance(2).generate_many(2, &mut stuck),
+            Err(VDGeneratorError::MinDistanceUnreachable { requested: 2, found: 1 })
+        );
+        assert!(matches!(
+            VDGenerator::<Default31>::default().length(3).min_distance(4).generate_many(2, &mut rng),
+            Err(VDGeneratorError::NotEnoughDistinctCodes { available: 1, .. })
+        ));
+
+        // All-digit codes of length 2 pass the Singleton bound of 31, but
+        // at most 8 of them differ in both places.
+        let digits = VDGenerator::<Default31>::default().length(2).min_digits(2).min_distance(2).max_attempts(500);
+        assert!(matches!(
+            digits.generate_many(20, &mut rng),
+            Err(VDGeneratorError::MinDistanceUnreachable { requested: 20, found }) if found <= 8
+        ));
+    }
+
+    #[test]
+    fn generate_many_gives_up_when_filters_shrink_the_keyspace() {
+        let mut rng = seeded_rng();
+        // Only the 8 digits pass `min_digits(1)` at length 1.
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(1).min_digits(1).generate_many(31, &mut rng),
+            Err(VDGeneratorError::NotEnoughDistinctCodes { requested: 31, available: 8 })
+        );
+        assert_eq!(VDGenerator::<Default31>::default().length(1).min_digits(1).generate_many(8, &mut rng).unwrap().len(), 8);
+    }
+
+    #[test]
+    fn generate_many_returns_distinct_codes() {
+        let mut rng = seeded_rng();
+        let codes = VDGenerator::<Default31>::default().length(3).no_repeats().generate_many(2000, &mut rng).unwrap();
+        let distinct: BTreeSet<&str> = codes.iter().map(|c| c.as_str()).collect();
+        assert_eq!(distinct.len(), 2000);
+
+        // 31 * 30 = 930 codes exist; all of them can be drawn.
+        let all = VDGenerator::<Default31>::default().length(2).no_adjacent_repeats().generate_many(930, &mut rng);
+        assert_eq!(all.unwrap().len(), 930);
+        assert_eq!(
+            VDGenerator::<Default31>::default().length(2).no_adjacent_repeats().generate_many(931, &mut rng),
+            Err(VDGeneratorError::NotEnoughDistinctCodes { requested: 931, available: 930 })
+        );
+        assert!(VDGenerator::<Default31>::default().length(40).generate_many(3, &mut rng).is_ok());
+        assert!(matches!(
+            VDGenerator::<Default31>::default().length(32).no_repeats().generate_many(1, &mut rng),
+            Err(VDGeneratorError::LengthExceedsUniqueSet { .. })
+        ));
+    }
+
+    #[test]
+    fn entropy_bits_accounts_for_constraints() {
+        let plain = VDGenerator::<Default31>::default().length(8);
+        assert!((plain.entropy_bits() - 8.0 * log2(31.0)).abs() < 1e-9);
+
+        let adjacent = VDGenerator::<Default31>::default().length(8).no_adjacent_repeats();
+        assert!((adjacent.entropy_bits() - (log2(31.0) + 7.0 * log2(30.0))).abs() < 1e-9);
+
+        let distinct = VDGenerator::<Default31>::default().length(8).no_repeats();
+        assert!(distinct.entropy_bits() < adjacent.entropy_bits());
+        assert_eq!(VDGenerator::<Default31>::default().length(0).entropy_bits(), 0.0);
+        assert!(VDGenerator::<Default31>::default().length(31).no_repeats().entropy_bits().is_finite());
+        assert_eq!(VDGenerator::<Default31>::default().length(32).no_repeats().entropy_bits(), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn secure_matches_generate_for_same_rng() {
+        let gen = VDGenerator::<Default31>::default().length(12).no_adjacent_repeats();
+        let secure = gen.generate_secure(&mut rand::rngs::StdRng::seed_from_u64(7)).unwrap();
+        let plain = gen.generate(&mut rand::rngs::StdRng::seed_from_u64(7)).unwrap();
+        assert_eq!(secure, plain);
+    }
+
+    #[cfg(feature = "os-rng")]
+    #[test]
+    fn os_rng_honors_constraints() {
+        let gen = VDGenerator::<Default31>::default().length(12).no_adjacent_repeats();
+        let code = gen.generate_with_os_rng().unwrap();
+        assert_eq!(code.len(), 12);
+        assert!(code.as_vdchars().windows(2).all(|w| w[0] != w[1]));
+        assert_ne!(code, gen.generate_with_os_rng().unwrap());
+    }
+
+    #[test]
+    fn entropy_is_consumed_four_bytes_per_draw() {
+        let entropy: Vec<u8> = (0..8u32).flat_map(|i| (i * 5).to_le_bytes()).collect();
+        let gen = VDGenerator::<Default31>::default().length(8);
+        let code = gen.generate_from_entropy(&entropy).unwrap();
+        assert_eq!(code, gen.generate(&mut crate::StepRng::new(0, 5)).unwrap());
+        assert_eq!(gen.generate_from_entropy(&entropy[..31]), Err(VDGeneratorError::EntropyExhausted { provided: 31 }));
+
+        // u32::MAX is rejected as biased and costs one more draw.
+        let mut biased = u32::MAX.to_le_bytes().to_vec();
+        biased.extend_from_slice(&entropy);
+        assert_eq!(gen.generate_from_entropy(&biased[..32]), Err(VDGeneratorError::EntropyExhausted { provided: 32 }));
+        assert_eq!(gen.generate_from_entropy(&biased).unwrap(), code);
+    }
+
+    #[test]
+    fn unique_escalates_length_after_collisions() {
+        let gen = VDGenerator::<Default31>::default().length(2);
+        let mut seen = Vec::new();
+        let exists = |code: &VDString<Default31>| {
+            seen.push(code.len());
+            code.len() < 4
+        };
+        let code = gen.generate_unique(&mut seeded_rng(), exists, 10).unwrap();
+        assert_eq!(code.len(), 4);
+        assert_eq!(seen, [2, 2, 2, 3, 3, 3, 4]);
+
+        let err = gen.generate_unique(&mut seeded_rng(), |_| true, 5).unwrap_err();
+        assert_eq!(err, VDGeneratorError::NoUniqueCode { attempts: 5 });
+        let distinct = VDGenerator::<Default31>::default().length(31).no_repeats();
+        let err = distinct.generate_unique(&mut seeded_rng(), |_| true, 5).unwrap_err();
+        assert_eq!(err, VDGeneratorError::LengthExceedsUniqueSet { requested: 32, available: 31 });
+    }
+
+    #[test]
+    fn generates_expected_length() {
+        let mut rng = seeded_rng();
+        let code = VDGenerator::<Default31>::default().length(8).generate(&mut rng).unwrap();
+        assert_eq!(code.len(), 8);
+    }
+
+    #[test]
+    fn no_adjacent_repeats_enabled() {
+        let mut rng = seeded_rng();
+        let code = VDGenerator::<Default31>::default()
+            .length(16)
+            .no_adjacent_repeats()
+            .generate(&mut rng)
+            .unwrap();
+
+        for pair in code.as_vdchars().windows(2) {
+            assert_ne!(pair[0], pair[1], "adjacent repeat found");
+        }
     }
 
     #[test]
     fn no_repeats_enabled() {
         let mut rng = seeded_rng();
-        let code = VDGenerator::new()
+        let code = VDGenerator::<Default31>::default()
             .length(16)
             .no_repeats()
             .generate(&mut rng)
@@ -208,10 +2680,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn log2_matches_known_values() {
+        assert_eq!(log2(1.0), 0.0);
+        assert_eq!(log2(8.0), 3.0);
+        assert!((log2(31.0) - 4.954_196_310_386_876).abs() < 1e-12);
+    }
+
+    #[test]
+    fn audited_reports_entropy_per_configuration() {
+        let mut rng = seeded_rng();
+        let bits = |g: VDGenerator<Default31>| g.generate_audited(&mut seeded_rng()).unwrap().entropy_bits;
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        assert!(close(bits(VDGenerator::<Default31>::default().length(2)), 2.0 * log2(31.0)));
+        assert!(close(
+            bits(VDGenerator::<Default31>::default().length(2).no_adjacent_repeats()),
+            log2(31.0 * 30.0)
+        ));
+        assert!(close(
+            bits(VDGenerator::<Default31>::default().length(3).no_repeats()),
+            log2(31.0 * 30.0 * 29.0)
+        ));
+        assert_eq!(bits(VDGenerator::<Default31>::default().length(0)), 0.0);
+
+        let generated = VDGenerator::<Default31>::default().generate_audited(&mut rng).unwrap();
+        assert_eq!(generated.attempts, 1);
+        assert_eq!(generated.rejected_by, Rejections::default());
+    }
+
+    #[test]
+    fn audited_counts_adjacent_rejections() {
+        use crate::StepRng;
+        // Draws 0, 31m, 62m truncated to u32: indices 0, 0, then 27 once the
+        // doubled value wraps past 2^32.
+        let step = 31 * ((1u64 << 31) / 31 + 1);
+        let mut rng = StepRng::new(0, step);
+        let generated = VDGenerator::<Default31>::default()
+            .length(2)
+            .no_adjacent_repeats()
+            .generate_audited(&mut rng)
+            .unwrap();
+        assert_eq!(generated.rejected_by.adjacent_repeats, 1);
+        assert_eq!(&*generated.code, "A6");
+    }
+
     #[test]
     fn no_repeats_exceeds_allowed_panics() {
         let mut rng = seeded_rng();
-        let result = VDGenerator::new()
+        let result = VDGenerator::<Default31>::default()
             .length(VDS_ALLOWED.len() + 1)
             .no_repeats()
             .generate(&mut rng);
@@ -225,7 +2742,7 @@ mod tests {
     #[test]
     fn combined_flags_hold() {
         let mut rng = seeded_rng();
-        let code = VDGenerator::new()
+        let code = VDGenerator::<Default31>::default()
             .length(12)
             .no_adjacent_repeats()
             .no_repeats()
@@ -241,4 +2758,52 @@ mod tests {
             seen.push(*ch);
         }
     }
+
+    #[test]
+    fn generates_over_other_alphabets() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        struct Hex;
+        impl Alphabet for Hex {
+            const CHARS: &'static [char] = &[
+                '0', '1', '2', '3', '4', '5', '6', '7',
+                '8', '9', 'A', 'B', 'C', 'D', 'E', 'F',
+            ];
+        }
+
+        let mut rng = seeded_rng();
+        let generated = VDGenerator::<Hex>::default()
+            .length(16)
+            .no_repeats()
+            .generate_audited(&mut rng)
+            .unwrap();
+        assert!(generated.code.as_str().chars().all(|c| c.is_ascii_hexdigit()));
+        assert!(!generated.code.has_repeats());
+        let expected: f64 = (1..=16).map(|n| log2(n as f64)).sum();
+        assert!((generated.entropy_bits - expected).abs() < 1e-9);
+
+        let too_long = VDGenerator::<Hex>::default().length(17).no_repeats().generate(&mut rng);
+        assert_eq!(
+            too_long,
+            Err(VDGeneratorError::LengthExceedsUniqueSet { requested: 17, available: 16 })
+        );
+    }
+
+    #[test]
+    fn generates_over_custom_alphabet() {
+        use crate::alphabet::CustomAlphabetBuilder;
+        let alphabet = CustomAlphabetBuilder::new().chars("KMRTW347".chars()).build().unwrap();
+
+        let mut rng = seeded_rng();
+        let code = VDGenerator::new()
+            .length(8)
+            .no_repeats()
+            .no_adjacent_repeats()
+            .generate_in(&alphabet, &mut rng)
+            .unwrap();
+        assert_eq!(code.chars().count(), 8);
+        assert!(alphabet.is_valid(&code));
+
+        let too_long = VDGenerator::new().length(9).no_repeats().generate_in(&alphabet, &mut rng);
+        assert!(matches!(too_long, Err(VDGeneratorError::LengthExceedsUniqueSet { .. })));
+    }
 }
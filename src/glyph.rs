@@ -0,0 +1,101 @@
+use core::fmt;
+
+use crate::alphabet::Default31;
+use crate::vdchar::{VDChar, VDS_ALLOWED};
+
+/// Exhaustive enumeration of every character in [`VDS_ALLOWED`].
+///
+/// Each variant's discriminant is the character's index in [`VDS_ALLOWED`],
+/// so converting between [`VDGlyph`] and [`VDChar`] is free. Matching on a
+/// `VDGlyph` is checked for exhaustiveness by the compiler, so downstream code
+/// fails to build if the alphabet ever changes rather than silently falling
+/// through a `_` arm.
+///
+/// Digits are spelled out (`Two` … `Nine`) since identifiers cannot start
+/// with a number.
+///
+/// # Examples
+/// ```
+/// use vds::{VDChar, VDGlyph};
+///
+/// let ch = VDChar::new('K').unwrap();
+/// assert_eq!(ch.glyph(), VDGlyph::K);
+/// assert_eq!(VDChar::from(VDGlyph::Seven).as_char(), '7');
+/// ```
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum VDGlyph {
+    A, B, C, D, E, F, G, H, J, K,
+    M, N, P, Q, R, S, T, U, V, W,
+    X, Y, Z, Two, Three, Four, Five, Six, Seven, Eight, Nine,
+}
+
+impl VDGlyph {
+    /// Every glyph, in [`VDS_ALLOWED`] order.
+    pub const ALL: [VDGlyph; 31] = [
+        Self::A, Self::B, Self::C, Self::D, Self::E, Self::F, Self::G, Self::H, Self::J, Self::K,
+        Self::M, Self::N, Self::P, Self::Q, Self::R, Self::S, Self::T, Self::U, Self::V, Self::W,
+        Self::X, Self::Y, Self::Z, Self::Two, Self::Three, Self::Four, Self::Five, Self::Six,
+        Self::Seven, Self::Eight, Self::Nine,
+    ];
+
+    /// Returns the `char` this glyph represents.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDGlyph;
+    /// assert_eq!(VDGlyph::Two.as_char(), '2');
+    /// ```
+    pub fn as_char(self) -> char {
+        VDS_ALLOWED[self as usize]
+    }
+}
+
+impl From<VDGlyph> for VDChar<Default31> {
+    fn from(glyph: VDGlyph) -> Self {
+        VDChar::from_index_unchecked(glyph as u8)
+    }
+}
+
+impl From<VDChar<Default31>> for VDGlyph {
+    fn from(ch: VDChar<Default31>) -> Self {
+        VDGlyph::ALL[ch.index() as usize]
+    }
+}
+
+impl fmt::Display for VDGlyph {
+    /// Formats the glyph as its character value.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_char(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_matches_allowed_order() {
+        assert_eq!(VDGlyph::ALL.len(), VDS_ALLOWED.len());
+        for (i, glyph) in VDGlyph::ALL.iter().enumerate() {
+            assert_eq!(*glyph as usize, i);
+            assert_eq!(glyph.as_char(), VDS_ALLOWED[i]);
+        }
+    }
+
+    #[test]
+    fn roundtrips_through_vdchar() {
+        for ch in VDChar::all() {
+            let glyph = VDGlyph::from(ch);
+            assert_eq!(glyph.as_char(), ch.as_char());
+            assert_eq!(VDChar::from(glyph), ch);
+        }
+    }
+
+    #[test]
+    fn digit_variants_match_digits() {
+        assert_eq!(VDGlyph::Two.as_char(), '2');
+        assert_eq!(VDGlyph::Nine.as_char(), '9');
+    }
+}
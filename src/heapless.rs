@@ -0,0 +1,104 @@
+//! Conversions between codes and [`heapless`] containers.
+//!
+//! This module is only available when the `heapless` feature is enabled.
+//!
+//! Firmware built on RTIC or Embassy often keeps text in
+//! [`heapless::String`] and buffers in [`heapless::Vec`]. A [`VDStr`]
+//! converts into either with `TryFrom`, failing only on capacity, and a
+//! `heapless::Vec<VDChar, N>` borrows as a `&VDStr` for free. With the
+//! `generate` feature, [`VDGenerator::generate_into_heapless`](crate::VDGenerator::generate_into_heapless)
+//! generates straight into a `heapless::Vec`.
+//!
+//! # Examples
+//! ```
+//! use vds::{VDArrayString, VDChar, VDStr};
+//!
+//! let code: VDArrayString<8> = "AB29XY".parse().unwrap();
+//! let text = heapless::String::<8>::try_from(code.as_vdstr()).unwrap();
+//! assert_eq!(text, "AB29XY");
+//!
+//! let chars = heapless::Vec::<VDChar, 8>::try_from(code.as_vdstr()).unwrap();
+//! assert_eq!(<&VDStr>::from(&chars), "AB29XY");
+//!
+//! assert_eq!(VDArrayString::<8>::try_from(&text), Ok(code));
+//! assert!(heapless::String::<4>::try_from(code.as_vdstr()).is_err());
+//! ```
+
+use heapless::CapacityError;
+
+use crate::alphabet::Alphabet;
+use crate::{VDArrayString, VDArrayStringError, VDChar, VDStr};
+
+impl<A: Alphabet, const N: usize> TryFrom<&VDStr<A>> for heapless::String<N> {
+    type Error = CapacityError;
+
+    /// Renders the code, failing if it needs more than `N` bytes.
+    fn try_from(s: &VDStr<A>) -> Result<Self, CapacityError> {
+        let mut out = heapless::String::new();
+        s.iter().try_for_each(|c| out.push(c.as_char()))?;
+        Ok(out)
+    }
+}
+
+impl<A: Alphabet, const N: usize> TryFrom<&VDStr<A>> for heapless::Vec<VDChar<A>, N> {
+    type Error = CapacityError;
+
+    /// Copies the characters, failing if there are more than `N`.
+    fn try_from(s: &VDStr<A>) -> Result<Self, CapacityError> {
+        heapless::Vec::from_slice(s.as_vdchars())
+    }
+}
+
+impl<'a, A: Alphabet, const N: usize> From<&'a heapless::Vec<VDChar<A>, N>> for &'a VDStr<A> {
+    fn from(chars: &'a heapless::Vec<VDChar<A>, N>) -> Self {
+        VDStr::from_vdchars(chars)
+    }
+}
+
+impl<const N: usize> TryFrom<&heapless::String<N>> for VDArrayString<N> {
+    type Error = VDArrayStringError;
+
+    /// Validates the string; equivalent to `s.parse()`.
+    fn try_from(s: &heapless::String<N>) -> Result<Self, VDArrayStringError> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::{Crockford32, Default31};
+
+    #[test]
+    fn converts_at_capacity_and_rejects_beyond() {
+        let code: VDArrayString<4> = "K7M3".parse().unwrap();
+        let text = heapless::String::<4>::try_from(code.as_vdstr()).unwrap();
+        assert_eq!(text, "K7M3");
+        assert!(heapless::String::<3>::try_from(code.as_vdstr()).is_err());
+        assert!(heapless::Vec::<VDChar<Default31>, 3>::try_from(code.as_vdstr()).is_err());
+
+        let bad: heapless::String<4> = heapless::String::try_from("K0M3").unwrap();
+        assert_eq!(VDArrayString::<4>::try_from(&bad), Err(VDArrayStringError::InvalidChar('0')));
+    }
+
+    #[test]
+    fn vec_borrows_as_vdstr_for_any_alphabet() {
+        let chars: heapless::Vec<VDChar<Crockford32>, 4> =
+            Crockford32::CHARS[..3].iter().map(|&c| VDChar::<Crockford32>::from_char(c).unwrap()).collect();
+        assert_eq!(<&VDStr<Crockford32>>::from(&chars), "012");
+    }
+
+    #[cfg(feature = "generate")]
+    #[test]
+    fn generates_into_heapless_vec() {
+        use crate::{StepRng, VDGenerator, VDGeneratorError};
+
+        let gen = VDGenerator::new().length(5);
+        let code = gen.generate_into_heapless::<8, _>(&mut StepRng::new(0, 1)).unwrap();
+        assert_eq!((code.len(), code.capacity()), (5, 8));
+        assert_eq!(
+            gen.build().unwrap().generate_into_heapless::<4, _>(&mut StepRng::new(0, 1)),
+            Err(VDGeneratorError::BufferTooSmall { required: 5, available: 4 })
+        );
+    }
+}
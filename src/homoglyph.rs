@@ -0,0 +1,125 @@
+//! Folding of visually equivalent input characters onto [`VDS_ALLOWED`](crate::VDS_ALLOWED).
+//!
+//! Codes pasted from PDFs, chat apps, or IME-driven text fields often arrive
+//! as lowercase, fullwidth (`Ａ`), or Cyrillic/Greek look-alikes (`А`, `Α`)
+//! of the intended character. [`canonicalize_char`] maps each of these onto
+//! the Latin character it stands for. [`VDChar::new_folded`] combines this
+//! with validation.
+//!
+//! Folding never guesses between two allowed characters: `o`, `i`, and `l`
+//! fold to `O`, `I`, and `L`, which are still rejected, since they could
+//! equally have meant `0` or `1`.
+//!
+//! # Examples
+//! ```
+//! use vds::homoglyph::canonicalize_char;
+//! use vds::VDChar;
+//!
+//! assert_eq!(canonicalize_char('ｋ'), 'K');
+//! assert_eq!(canonicalize_char('Р'), 'P'); // Cyrillic Er
+//! assert_eq!(VDChar::new_folded('х').map(|c| c.as_char()), Some('X'));
+//! assert_eq!(VDChar::new_folded('o'), None);
+//! ```
+
+use crate::alphabet::Default31;
+use crate::VDChar;
+
+/// Cyrillic and Greek characters and the Latin character they look like.
+///
+/// Only characters whose uppercase or lowercase form is visually identical
+/// (or nearly so) in common fonts are listed.
+pub const HOMOGLYPHS: &[(char, char)] = &[
+    // Cyrillic uppercase
+    ('А', 'A'), ('В', 'B'), ('С', 'C'), ('Е', 'E'), ('Н', 'H'), ('І', 'I'),
+    ('Ј', 'J'), ('К', 'K'), ('М', 'M'), ('О', 'O'), ('Р', 'P'), ('Ѕ', 'S'),
+    ('Т', 'T'), ('Х', 'X'), ('У', 'Y'), ('З', '3'),
+    // Cyrillic lowercase
+    ('а', 'A'), ('с', 'C'), ('е', 'E'), ('і', 'I'), ('ј', 'J'), ('о', 'O'),
+    ('р', 'P'), ('ѕ', 'S'), ('х', 'X'), ('у', 'Y'),
+    // Greek uppercase
+    ('Α', 'A'), ('Β', 'B'), ('Ε', 'E'), ('Ζ', 'Z'), ('Η', 'H'), ('Ι', 'I'),
+    ('Κ', 'K'), ('Μ', 'M'), ('Ν', 'N'), ('Ο', 'O'), ('Ρ', 'P'), ('Τ', 'T'),
+    ('Υ', 'Y'), ('Χ', 'X'),
+    // Greek lowercase
+    ('ο', 'O'),
+];
+
+/// Returns the canonical form of `c`: uppercase ASCII for lowercase,
+/// fullwidth, and [`HOMOGLYPHS`] inputs, and `c` itself otherwise.
+///
+/// The result is not necessarily an allowed character; pass it to
+/// [`VDChar::new`] to validate, or use [`VDChar::new_folded`].
+pub fn canonicalize_char(c: char) -> char {
+    let c = match c {
+        // Fullwidth ASCII variants, e.g. from Japanese IMEs.
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    };
+    if c.is_ascii() {
+        return c.to_ascii_uppercase();
+    }
+    HOMOGLYPHS
+        .iter()
+        .find(|&&(from, _)| from == c)
+        .map_or(c, |&(_, to)| to)
+}
+
+impl VDChar<Default31> {
+    /// Like [`VDChar::new`], but first folds `c` with [`canonicalize_char`].
+    ///
+    /// Accepts lowercase, fullwidth, and Cyrillic/Greek look-alikes of
+    /// allowed characters.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDChar;
+    ///
+    /// assert_eq!(VDChar::new_folded('ａ'), VDChar::new('A'));
+    /// assert_eq!(VDChar::new_folded('７'), VDChar::new('7'));
+    /// assert_eq!(VDChar::new_folded('０'), None);
+    /// ```
+    pub fn new_folded(c: char) -> Option<Self> {
+        Self::new(canonicalize_char(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VDS_ALLOWED;
+
+    #[test]
+    fn allowed_chars_are_fixed_points() {
+        for &c in VDS_ALLOWED {
+            assert_eq!(canonicalize_char(c), c);
+            assert_eq!(VDChar::new_folded(c), VDChar::new(c));
+        }
+    }
+
+    #[test]
+    fn folds_lowercase_and_fullwidth() {
+        for &c in VDS_ALLOWED {
+            let lower = c.to_ascii_lowercase();
+            let fullwidth = char::from_u32(c as u32 + 0xFEE0).unwrap();
+            assert_eq!(canonicalize_char(lower), c);
+            assert_eq!(canonicalize_char(fullwidth), c);
+        }
+        assert_eq!(canonicalize_char('－'), '-');
+    }
+
+    #[test]
+    fn ambiguous_inputs_stay_rejected() {
+        for c in ['o', 'O', 'О', 'ο', 'i', 'І', 'l', '0', '1', 'Ｏ', '１'] {
+            assert_eq!(VDChar::new_folded(c), None, "{}", c);
+        }
+    }
+
+    #[test]
+    fn homoglyph_targets_are_ascii_uppercase() {
+        for &(from, to) in HOMOGLYPHS {
+            assert!(!from.is_ascii());
+            assert!(to.is_ascii_uppercase() || to.is_ascii_digit(), "{}", to);
+        }
+        assert_eq!(canonicalize_char('Ж'), 'Ж');
+    }
+}
@@ -1,5 +1,6 @@
 #![no_std]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(any(feature = "bytemuck", feature = "ffi")), forbid(unsafe_code))]
+#![cfg_attr(any(feature = "bytemuck", feature = "ffi"), deny(unsafe_code))]
 #![deny(missing_docs)]
 
 //! # vds
@@ -8,23 +9,271 @@
 //! This crate provides:
 //!
 //! - [`VDChar`]: a compact, index-based character type
-//! - [`VDString`]: a validated string of `VDChar`s
+//! - [`VDGlyph`]: an exhaustive enum over the allowed characters
+//! - [`VDCharSet`]: a compact set of `VDChar`s
+//! - [`VDStr`]: a borrowed string slice of `VDChar`s, like `str` for `String`
+//! - [`VDArrayString`]: a fixed-capacity, allocation-free string of `VDChar`s
+//! - [`VDStringFixed`]: an allocation-free string of exactly `N` `VDChar`s
+//! - [`VDString`]: a validated string of `VDChar`s, with lenient parsing via [`ParseOptions`]
+//! - [`NonEmptyVDString`]: a `VDString` guaranteed to hold at least one character
+//! - [`VDStringBounded`]: a `VDString` whose length bounds are part of its type
+//! - [`VDSharedString`]: a reference-counted `VDString` that is cheap to clone
+//! - [`VDStringBuilder`]: a mutable buffer for building a `VDString` in place
+//! - [`VDValidator`]: incremental validation of codes arriving in chunks
+//! - [`audit`]: a per-character check of existing identifiers against the VDS rules
+//! - [`homoglyph`]: folding of lowercase, fullwidth, and look-alike input characters
+//! - [`alphabet`]: the [`Alphabet`] trait, for building `VDChar`s and `VDString`s over other character sets
+//! - [`vdstr!`]: `VDString` literals validated at compile time
+//! - [`VDPattern`]: reusable masks such as `LL????D` for filtering codes
+//! - [`VDCode`]: a `VDString` ending in a verified check character (see [`checksum`])
+//! - [`SegmentedCode`]: codes like `XK29-M5TQ-7WCE` with a check character per segment
+//! - [`CodeSchema`]: codes made of named fields, such as a prefix, region, serial, and check character
+//! - [`VersionedVDString`]: codes whose first character selects the alphabet and length of the rest
+//! - [`codec`]: base-31 encoding of integers and byte payloads, counting codes in base 31, and 5-bit packing
+//! - [`ecc`]: Reed–Solomon error correction over GF(31)
+//! - [`mnemonic`]: codes as sequences of words from a versioned wordlist *(requires `mnemonic` feature)*
+//! - [`VDObfuscator`]: keyed, reversible mapping of sequential IDs to codes
+//! - [`VDDeriver`]: fixed-length codes derived from a key and counter, without allocating
 //! - [`VDGenerator`]: a builder for random string generation *(requires `generate` feature)*
+//! - [`VDPatternGenerator`]: fixed-layout generation such as `LLDD-LLDD` *(requires `generate` feature)*
+//! - [`VDPool`]: a generator that never issues the same code twice *(requires `generate` feature)*
+//! - [`VDBloom`]: a Bloom filter for screening duplicate codes *(requires `bloom` feature)*
+//! - [`VDOtp`]: HOTP/TOTP one-time codes in the VDS alphabet *(requires `otp` feature)*
+//! - [`VDSortableId`]: time-sortable, ULID-style codes *(requires `generate` feature)*
+//! - [`VDExpiringCode`]: codes carrying their own expiry, verified offline *(requires `generate` feature)*
+//! - [`simulate`]: injected channel errors for comparing check-character and ECC schemes *(requires `generate` feature)*
+//! - [`distance`]: Hamming and confusability-weighted edit distances between codes
+//! - [`fuzzy`]: "did you mean …?" lookup of noisy input among issued codes
+//! - [`qr`]: QR alphanumeric-mode values and symbol size estimates
+//! - [`code39`]: Code 39 barcode patterns
+//! - [`validate`]: bulk validation of raw byte buffers
+//! - [`stats`]: batch analysis such as glyph usage reports and uniformity statistics
 //!
 //! ## Features
 //!
-//! - `generate` — enables [`VDGenerator`] for random string creation (uses `rand_core`)
-//! - `serde` — enables `Serialize` / `Deserialize` support via the `serde` crate
+//! - `alloc` *(default)* — enables [`VDString`] and everything built on it; without it
+//!   the crate needs no allocator, leaving [`VDChar`], [`VDArrayString`], [`VDStringFixed`], validation,
+//!   and the display adapters such as [`GroupedDisplay`]
+//! - `str-cache` *(default)* — [`VDString`] keeps a rendered `String` next to its
+//!   characters for [`VDString::as_str`]; disable it to store each code once
+//! - `generate` — enables [`VDGenerator`] for random string creation (uses `rand_core`),
+//!   a [`VDAlphabet`] distribution for `rand`'s `Rng::sample`, plus a deterministic
+//!   [`StepRng`] for examples and tests; without `alloc`, codes are generated into
+//!   caller-provided buffers with [`VDGenerator::generate_into`]
+//! - `rand_core_06` — accepts RNGs written against `rand_core` 0.6 (e.g. from `rand` 0.8)
+//!   through the [`Rng06`] adapter; generators take any [`VDRng`]
+//! - `os-rng` — adds [`VDGenerator::generate_with_os_rng`], which draws from the operating
+//!   system's RNG (via `getrandom`) so no RNG has to be passed in; implies `generate`
+//! - `std` — with `generate`, adds [`VDGenerator::generate_to_writer`] for writing codes
+//!   to an `io::Write`
+//! - `serde` — enables `Serialize` / `Deserialize` support via the `serde` crate, plus
+//!   [`serde_lenient`] for fields that should accept lowercase or separated input and
+//!   [`serde_delimited`] for code lists delivered as one comma-separated string
+//! - `schemars` — implements `JsonSchema` for [`VDChar`], [`VDStr`], and [`VDString`]: strings
+//!   whose `pattern` admits only the alphabet's characters
+//! - `sqlx` — `Type`/`Encode`/`Decode` for [`VDString`] with any `sqlx` database: stored as
+//!   text and validated on decode
+//! - `diesel` — `ToSql`/`FromSql` over `Text` for [`VDString`], usable in queries and
+//!   `Queryable` structs, validated on load; implies `str-cache`
+//! - `rusqlite` — `ToSql`/`FromSql` for [`VDString`] as SQLite `TEXT`, validated on read
+//! - `simd` — validates long buffers in vectorizable 32-byte lanes
+//! - `bytemuck` — zero-copy casts between `&[u8]` and `&[VDChar]`
+//! - `uuid` — converts `uuid::Uuid` to and from fixed-width 26-character codes
+//! - `bincode` — native bincode 2 `Encode`/`Decode` for [`VDChar`] (one index byte) and
+//!   [`VDString`] (its packed form), validated on decode
+//! - `heapless` — converts codes to and from `heapless::String` and `heapless::Vec`, and adds
+//!   [`VDGenerator::generate_into_heapless`] for firmware without an allocator
+//! - `redact-debug` — `Debug` for [`VDString`], [`VDStr`], and friends prints only the
+//!   length (e.g. `VDString(REDACTED, len=8)`), keeping codes out of logs and panics
+//! - `profanity` — adds a built-in `PROFANITY` blocklist and `VDGenerator::deny_profanity`
+//! - `ffi` — adds [`ffi`], `extern "C"` functions for validation, generation, and
+//!   check characters, declared in `include/vds.h`
+//! - `python` — adds [`python`], a PyO3 extension module exposing parsing, formatting,
+//!   distances, and generation to Python
+//! - `wasm` — adds [`wasm`], `wasm-bindgen` exports of parsing, formatting, encoding, and
+//!   generation for browser front-ends
+//! - `clap` — adds [`clap`], value parsers for [`VDString`] arguments with errors that list
+//!   the allowed characters, and `ValueEnum` for [`Collation`] and [`VDCharClass`]
+//! - `cli` — builds the `vds` command-line tool (`vds gen`, `vds check`, `vds fmt`)
+//! - `mnemonic` — adds [`mnemonic`], encoding codes as words from an embedded wordlist
+//! - `bloom` — adds [`VDBloom`], a Bloom filter for screening duplicates among very many codes
+//! - `otp` — adds [`VDOtp`], RFC 4226/6238 one-time codes written in base 31
+//! - `nfkc` — adds [`ParseOptions::normalize_nfkc`] for Unicode compatibility normalization
+//!
+//! The alphabet used when none is named can be changed at build time with the
+//! `vds_default_alphabet` cfg; see [`alphabet::DefaultAlphabet`].
+
+#[cfg(feature = "std")]
+extern crate std;
 
+pub mod alphabet;
+mod index;
 mod vdchar;
+mod glyph;
+mod charset;
+mod vdstr;
+mod arraystring;
+mod fixed;
+mod siphash;
+pub mod homoglyph;
+#[cfg(feature = "alloc")]
 mod vdstring;
+#[cfg(feature = "alloc")]
+mod builder;
+mod display;
+#[cfg(feature = "alloc")]
+mod parse;
+#[cfg(feature = "alloc")]
+mod validator;
+#[cfg(feature = "alloc")]
+mod audit;
+#[cfg(feature = "alloc")]
+mod segmented;
+#[cfg(feature = "alloc")]
+mod versioned;
+#[cfg(feature = "alloc")]
+mod schema;
+#[cfg(feature = "alloc")]
+mod bounded;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+mod shared;
+#[cfg(feature = "alloc")]
+mod macros;
+mod mask;
+pub mod validate;
+pub mod qr;
+pub mod code39;
+pub mod distance;
+#[cfg(feature = "alloc")]
+pub mod fuzzy;
+#[cfg(feature = "alloc")]
+pub mod stats;
+pub mod checksum;
+#[cfg(feature = "alloc")]
+pub mod ecc;
+#[cfg(feature = "alloc")]
+pub mod codec;
+mod obfuscate;
+mod deriver;
+#[cfg(feature = "mnemonic")]
+pub mod mnemonic;
+#[cfg(feature = "bloom")]
+mod bloom;
+#[cfg(feature = "otp")]
+mod otp;
+#[cfg(feature = "otp")]
+mod sha1;
 #[cfg(feature = "generate")]
 mod generate;
+#[cfg(feature = "generate")]
+mod rng;
+#[cfg(all(feature = "generate", feature = "alloc"))]
+pub mod simulate;
+#[cfg(feature = "generate")]
+mod distr;
+#[cfg(all(feature = "generate", feature = "alloc"))]
+mod pool;
+#[cfg(all(feature = "generate", feature = "alloc"))]
+mod pattern;
+#[cfg(feature = "profanity")]
+mod profanity;
+#[cfg(all(feature = "generate", feature = "alloc"))]
+mod sortable;
+#[cfg(all(feature = "generate", feature = "alloc"))]
+mod expiring;
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub mod serde_delimited;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub mod serde_lenient;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+#[cfg(feature = "uuid")]
+mod uuid;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "clap")]
+pub mod clap;
 
-pub use vdchar::{VDChar, VDS_ALLOWED};
+pub use alphabet::Alphabet;
+pub use vdchar::{Collation, VDChar, VDCharClass, VDCharError, VDS_ALLOWED};
+pub use glyph::VDGlyph;
+pub use charset::{VDCharSet, VDCharSetIter};
+pub use vdstr::{VDStr, VDStrChunks, VDStrIndex, VDStrPattern, VDStrSplit};
+pub use arraystring::{VDArrayString, VDArrayStringError};
+pub use fixed::{VDStringFixed, VDStringFixedError};
+pub use mask::{VDPattern, VDPatternError};
+#[cfg(feature = "alloc")]
 pub use vdstring::{VDString, VDStringError};
+#[cfg(feature = "alloc")]
+pub use builder::VDStringBuilder;
+#[cfg(feature = "alloc")]
+pub use bounded::{NonEmptyVDString, VDLengthError, VDStringBounded};
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub use shared::VDSharedString;
+pub use display::{
+    DiffDisplay, FitDisplay, GroupedDisplay, LowercaseDisplay, MaskedDisplay, PrefixedDisplay, SpellOut, SsmlDisplay,
+    CONTINUATION, DIFF_MARKER, MASK,
+};
+#[cfg(feature = "alloc")]
+pub use parse::{
+    Ambiguity, ByteError, CharError, ExtractAll, OcrParse, OcrUncertainty, ParseHint, ParseOptions, PrefixError,
+    OCR_CORRECTIONS,
+};
+#[cfg(feature = "alloc")]
+pub use validator::VDValidator;
+#[cfg(feature = "alloc")]
+pub use audit::{audit, AmbiguityReport, CharAudit, CharVerdict};
+pub use checksum::ChecksumAlgorithm;
+#[cfg(feature = "alloc")]
+pub use checksum::{VDCode, VDCodeError};
+#[cfg(feature = "alloc")]
+pub use segmented::{SegmentedCode, SegmentedCodeBuilder, SegmentedCodeError};
+#[cfg(feature = "alloc")]
+pub use versioned::{VersionedCodeError, VersionedFormat, VersionedFormats, VersionedVDString};
+#[cfg(feature = "alloc")]
+pub use schema::{CodeFields, CodeSchema, CodeSchemaError};
+pub use obfuscate::{ObfuscatorError, VDObfuscator};
+pub use deriver::VDDeriver;
 
 #[cfg(feature = "generate")]
-pub use generate::{VDGenerator, VDGeneratorError};
\ No newline at end of file
+pub use generate::{CompiledGenerator, Rejections, VDGenerator, VDGeneratorError};
+#[cfg(all(feature = "generate", feature = "alloc"))]
+pub use generate::{GeneratedCode, GenerationObserver, VDGeneratorIter};
+#[cfg(feature = "generate")]
+pub use rng::{uniform_index, StepRng, VDRng};
+#[cfg(feature = "rand_core_06")]
+pub use rng::Rng06;
+#[cfg(feature = "generate")]
+pub use distr::VDAlphabet;
+#[cfg(feature = "profanity")]
+pub use profanity::PROFANITY;
+#[cfg(all(feature = "generate", feature = "alloc"))]
+pub use pattern::{PatternError, VDPatternGenerator};
+#[cfg(feature = "bloom")]
+pub use bloom::VDBloom;
+#[cfg(feature = "otp")]
+pub use otp::VDOtp;
+#[cfg(all(feature = "generate", feature = "alloc"))]
+pub use pool::{VDPool, VDPoolError, VDPoolIter};
+#[cfg(all(feature = "generate", feature = "alloc"))]
+pub use sortable::{SortableIdError, VDSortableId};
+#[cfg(all(feature = "generate", feature = "alloc"))]
+pub use expiring::{ExpiringCodeError, VDExpiringCode};
\ No newline at end of file
@@ -0,0 +1,77 @@
+//! The [`vdstr!`](crate::vdstr) literal macro and its support functions.
+
+use crate::alphabet::Default31;
+use crate::{VDChar, VDString};
+
+/// Creates a [`VDString`](crate::VDString) from a string literal validated at
+/// compile time.
+///
+/// The literal is checked by `const` evaluation, so a typo fails the build
+/// (including `cargo check`) instead of panicking at runtime. The expansion
+/// skips runtime validation.
+///
+/// # Examples
+/// ```
+/// use vds::vdstr;
+///
+/// let code = vdstr!("AB29XY");
+/// assert_eq!(&*code, "AB29XY");
+/// ```
+///
+/// Invalid characters are compile errors:
+/// ```compile_fail
+/// let code = vds::vdstr!("AB0");
+/// ```
+#[macro_export]
+macro_rules! vdstr {
+    ($s:literal) => {{
+        const _: () = ::core::assert!(
+            $crate::VDString::__is_valid_literal($s),
+            "vdstr! literal contains a character outside VDS_ALLOWED"
+        );
+        $crate::VDString::__from_valid_literal($s)
+    }};
+}
+
+impl VDString<Default31> {
+    #[doc(hidden)]
+    pub const fn __is_valid_literal(s: &str) -> bool {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            // Non-ASCII bytes are never allowed, so checking bytes is enough.
+            if VDChar::new(bytes[i] as char).is_none() {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    #[doc(hidden)]
+    pub fn __from_valid_literal(s: &'static str) -> VDString<Default31> {
+        VDString::new(
+            s.bytes()
+                .map(|b| VDChar::new(b as char).unwrap_or(VDChar::from_index_unchecked(0)))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_validation_matches_parse() {
+        for s in ["", "AB29XY", "A0", "ab", "É", "AÉ", "Z9"] {
+            assert_eq!(VDString::<Default31>::__is_valid_literal(s), s.parse::<VDString<Default31>>().is_ok(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn macro_builds_equal_string() {
+        assert_eq!(vdstr!("M3PQ"), "M3PQ".parse::<VDString<Default31>>().unwrap());
+        assert!(vdstr!("").is_empty());
+    }
+}
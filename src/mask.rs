@@ -0,0 +1,217 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use crate::alphabet::{Alphabet, DefaultAlphabet};
+use crate::{VDChar, VDStr};
+
+/// Error returned when parsing a [`VDPattern`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VDPatternError {
+    /// A literal in the pattern is not a character of the alphabet, so
+    /// nothing could match it.
+    InvalidLiteral(char),
+    /// The pattern ends with an unfinished `\` escape.
+    TrailingEscape,
+    /// The pattern has more positions than the pattern's capacity.
+    TooLong {
+        /// The fixed capacity that was exceeded.
+        capacity: usize,
+    },
+}
+
+impl fmt::Display for VDPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VDPatternError::InvalidLiteral(c) => write!(f, "invalid literal {:?} in pattern", c),
+            VDPatternError::TrailingEscape => f.write_str("pattern ends with an unfinished escape"),
+            VDPatternError::TooLong { capacity } => {
+                write!(f, "pattern exceeds the capacity of {} positions", capacity)
+            }
+        }
+    }
+}
+
+impl core::error::Error for VDPatternError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Letter,
+    Digit,
+    Any,
+    /// Alphabet index of the required character.
+    Literal(u8),
+}
+
+/// A compiled mask of up to `N` positions that codes can be matched against.
+///
+/// Pattern syntax, one position per item:
+/// - `L`: any letter
+/// - `D`: any digit
+/// - `?`: any character
+/// - `\c`: the literal character `c`, e.g. `\D` for the letter `D`
+/// - anything else: that literal character, which must be in the alphabet
+///
+/// A code matches when it has exactly as many characters as the pattern has
+/// positions and each character fits its position. The pattern is parsed
+/// once and needs no allocation, so it suits hot filtering loops and
+/// `no_std` targets alike.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::{VDPattern, VDString};
+///
+/// // Two letters, four of anything, then a digit.
+/// let pattern: VDPattern<8> = "LL????D".parse().unwrap();
+///
+/// let code: VDString = "ABX7KM3".parse().unwrap();
+/// assert!(pattern.matches(&code));
+/// assert!(!pattern.matches(&"A2X7KM3".parse::<VDString>().unwrap()));
+///
+/// // Literals pin a position; `\D` is the letter `D`, not a digit.
+/// let batch: VDPattern<8> = "K7\\DLDD".parse().unwrap();
+/// assert!(batch.matches(&"K7DX29".parse::<VDString>().unwrap()));
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct VDPattern<const N: usize, A: Alphabet = DefaultAlphabet> {
+    slots: [Slot; N],
+    len: usize,
+    alphabet: PhantomData<A>,
+}
+
+impl<const N: usize, A: Alphabet> VDPattern<N, A> {
+    /// Parses a pattern over alphabet `A`.
+    ///
+    /// # Errors
+    /// - [`VDPatternError::InvalidLiteral`] if a literal is not in `A`
+    /// - [`VDPatternError::TrailingEscape`] if the pattern ends with a lone `\`
+    /// - [`VDPatternError::TooLong`] if the pattern has more than `N` positions
+    pub fn new(pattern: &str) -> Result<Self, VDPatternError> {
+        let mut slots = [Slot::Any; N];
+        let mut len = 0;
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            let slot = match c {
+                'L' => Slot::Letter,
+                'D' => Slot::Digit,
+                '?' => Slot::Any,
+                _ => {
+                    let c = if c == '\\' { chars.next().ok_or(VDPatternError::TrailingEscape)? } else { c };
+                    let ch = VDChar::<A>::from_char(c).ok_or(VDPatternError::InvalidLiteral(c))?;
+                    Slot::Literal(ch.index())
+                }
+            };
+            *slots.get_mut(len).ok_or(VDPatternError::TooLong { capacity: N })? = slot;
+            len += 1;
+        }
+        Ok(Self { slots, len, alphabet: PhantomData })
+    }
+
+    /// Returns the number of positions, which is the length of every
+    /// matching code.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the pattern has no positions, so only the empty
+    /// code matches.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if `code` has exactly [`len`](Self::len) characters
+    /// and each one fits its position.
+    pub fn matches(&self, code: &VDStr<A>) -> bool {
+        code.len() == self.len
+            && self.slots[..self.len].iter().zip(code.iter()).all(|(&slot, ch)| match slot {
+                Slot::Letter => ch.is_letter(),
+                Slot::Digit => ch.is_digit(),
+                Slot::Any => true,
+                Slot::Literal(index) => ch.index() == index,
+            })
+    }
+}
+
+impl<const N: usize, A: Alphabet> FromStr for VDPattern<N, A> {
+    type Err = VDPatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl<const N: usize, A: Alphabet> fmt::Display for VDPattern<N, A> {
+    /// Writes the pattern in the syntax it was parsed from, escaping
+    /// literals that would otherwise read as placeholders.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for slot in &self.slots[..self.len] {
+            match *slot {
+                Slot::Letter => f.write_str("L")?,
+                Slot::Digit => f.write_str("D")?,
+                Slot::Any => f.write_str("?")?,
+                Slot::Literal(index) => {
+                    let c = VDChar::<A>::from_index_unchecked(index).as_char();
+                    if matches!(c, 'L' | 'D' | '?' | '\\') {
+                        f.write_str("\\")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize, A: Alphabet> fmt::Debug for VDPattern<N, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VDPattern(\"{}\")", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use alloc::string::ToString;
+    use crate::alphabet::{Default31, Digits10};
+    use crate::VDArrayString;
+
+    fn code(s: &str) -> VDArrayString<8> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn matches_classes_and_literals() {
+        let pattern: VDPattern<8, Default31> = "LD?\\DK".parse().unwrap();
+        assert_eq!(pattern.len(), 5);
+        assert!(pattern.matches(code("A29DK").as_vdstr()));
+        assert!(pattern.matches(code("Z2ZDK").as_vdstr()));
+        assert!(!pattern.matches(code("ZZ9DK").as_vdstr()));
+        assert!(!pattern.matches(code("29ADK").as_vdstr()));
+        assert!(!pattern.matches(code("A29MK").as_vdstr()));
+        assert!(!pattern.matches(code("A29DKA").as_vdstr()));
+        assert!(!pattern.matches(code("A29D").as_vdstr()));
+
+        let empty: VDPattern<0, Default31> = "".parse().unwrap();
+        assert!(empty.is_empty() && empty.matches(code("").as_vdstr()));
+    }
+
+    #[test]
+    fn rejects_bad_patterns() {
+        assert_eq!("LLO".parse::<VDPattern<8, Default31>>(), Err(VDPatternError::InvalidLiteral('O')));
+        assert_eq!("LL\\".parse::<VDPattern<8, Default31>>(), Err(VDPatternError::TrailingEscape));
+        assert_eq!("LLDDD".parse::<VDPattern<4, Default31>>(), Err(VDPatternError::TooLong { capacity: 4 }));
+        assert_eq!("DA".parse::<VDPattern<4, Digits10>>(), Err(VDPatternError::InvalidLiteral('A')));
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in ["LL??D", "AB\\D9\\D", ""] {
+            let pattern: VDPattern<8, Default31> = s.parse().unwrap();
+            assert_eq!(pattern.to_string(), s);
+            assert_eq!(pattern.to_string().parse(), Ok(pattern));
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Mnemonic word encoding of [`VDString`]s, in the spirit of the PGP word list.
+//!
+//! A code is read as the byte payload of [`VDString::decode_bytes`], and
+//! each byte becomes one word from a 256-word list. Spoken word sequences
+//! survive noisy phone lines and handwriting better than letters and digits.
+//! Every code maps to exactly one phrase and back.
+//!
+//! Wordlists are versioned by [`Wordlist`]. A published version never
+//! changes, so phrases stay decodable; new lists get new versions.
+//!
+//! This module is only available when the `mnemonic` feature is enabled.
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::alphabet::Default31;
+use crate::VDString;
+
+/// Words of [`Wordlist::V1`], in byte order.
+///
+/// Lowercase, sorted, at most eight letters, and unique in their first four
+/// letters.
+pub const WORDS_V1: [&str; 256] = [
+    "acid", "acorn", "actor", "adobe", "agent", "alarm", "album", "alley", "amber", "anchor",
+    "angle", "ankle", "apple", "apron", "arena", "arrow", "atlas", "attic", "audio", "bacon",
+    "badge", "bagel", "baker", "balloon", "bamboo", "banjo", "barrel", "basket", "beach",
+    "beaver", "bench", "berry", "bison", "blade", "blanket", "bloom", "bonnet", "bottle",
+    "bracket", "branch", "bread", "brick", "bridge", "bronze", "brush", "bubble", "bucket",
+    "bugle", "button", "cabin", "cactus", "camel", "candle", "canoe", "canvas", "carpet",
+    "carrot", "castle", "cedar", "cello", "chalk", "cherry", "chess", "circus", "clock",
+    "cloud", "clover", "cobra", "comet", "copper", "coral", "cotton", "cradle", "crayon",
+    "cricket", "crown", "cymbal", "daisy", "dancer", "delta", "denim", "desert", "diamond",
+    "dinner", "dolphin", "donkey", "dragon", "drum", "eagle", "easel", "echo", "elbow", "ember",
+    "engine", "falcon", "feather", "ferry", "fiddle", "finch", "flag", "flute", "forest",
+    "fossil", "fox", "galaxy", "garden", "garlic", "gecko", "geyser", "ginger", "glacier",
+    "globe", "goblet", "gopher", "granite", "grape", "guitar", "hammer", "harbor", "harp",
+    "hazel", "helmet", "heron", "hippo", "honey", "hornet", "husky", "igloo", "island", "ivory",
+    "jacket", "jaguar", "jasmine", "jelly", "jigsaw", "jockey", "judge", "jungle", "kayak",
+    "kettle", "kidney", "kitten", "koala", "ladder", "lagoon", "lantern", "lemon", "leopard",
+    "lettuce", "lizard", "lobster", "locket", "lotus", "magnet", "mango", "maple", "marble",
+    "meadow", "melon", "meteor", "mitten", "monkey", "mosaic", "muffin", "museum", "napkin",
+    "nectar", "needle", "nickel", "noodle", "nutmeg", "oasis", "ocean", "olive", "onion",
+    "orange", "orchid", "otter", "oyster", "paddle", "palace", "panda", "parrot", "peanut",
+    "pebble", "pepper", "piano", "pickle", "pilot", "pirate", "planet", "plum", "pocket",
+    "pony", "potato", "pretzel", "puffin", "pumpkin", "puzzle", "quail", "quartz", "quiver",
+    "rabbit", "radar", "radish", "raft", "raven", "ribbon", "rocket", "rodeo", "saddle",
+    "salmon", "sandal", "saturn", "scarf", "shovel", "silver", "skate", "sled", "snail",
+    "spider", "spoon", "squash", "statue", "sugar", "summit", "sunset", "swan", "tablet",
+    "tango", "teapot", "tiger", "timber", "toast", "tomato", "topaz", "tractor", "trumpet",
+    "tulip", "turtle", "valley", "velvet", "violin", "volcano", "wagon", "walnut", "walrus",
+    "whale", "whistle", "willow", "window", "wizard", "yacht", "yogurt", "zebra", "zipper",
+];
+
+/// A versioned wordlist for [`VDString::to_mnemonic`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Wordlist {
+    /// The first wordlist, [`WORDS_V1`].
+    #[default]
+    V1,
+}
+
+impl Wordlist {
+    /// Returns the wordlist with version number `version`, or `None` if
+    /// there is none.
+    pub fn from_version(version: u8) -> Option<Self> {
+        match version {
+            1 => Some(Wordlist::V1),
+            _ => None,
+        }
+    }
+
+    /// Returns this wordlist's version number, for storing next to phrases.
+    pub fn version(self) -> u8 {
+        match self {
+            Wordlist::V1 => 1,
+        }
+    }
+
+    /// Returns the 256 words, indexed by byte value.
+    pub fn words(self) -> &'static [&'static str; 256] {
+        match self {
+            Wordlist::V1 => &WORDS_V1,
+        }
+    }
+
+    /// Returns the byte encoded by `word`, ignoring ASCII case.
+    fn byte_of(self, word: &str) -> Option<u8> {
+        // Lists are sorted, so lowercase input can be binary searched.
+        let words = self.words();
+        if word.bytes().any(|b| b.is_ascii_uppercase()) {
+            words.iter().position(|w| w.eq_ignore_ascii_case(word)).map(|i| i as u8)
+        } else {
+            words.binary_search(&word).ok().map(|i| i as u8)
+        }
+    }
+}
+
+/// Error returned by [`VDString::from_mnemonic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MnemonicError {
+    /// The word at this position (counting from 0) is not in the wordlist.
+    UnknownWord(usize),
+}
+
+impl fmt::Display for MnemonicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            MnemonicError::UnknownWord(pos) => write!(f, "word {} is not in the wordlist", pos),
+        }
+    }
+}
+
+impl core::error::Error for MnemonicError {}
+
+impl VDString<Default31> {
+    /// Encodes this code as a sequence of words from `wordlist`.
+    ///
+    /// Each word carries one byte, so a code of `n` characters takes about
+    /// `0.62 * n` words. The empty code encodes as no words.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::mnemonic::Wordlist;
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "K7M3PQ".parse().unwrap();
+    /// let phrase = code.to_mnemonic(Wordlist::V1).join(" ");
+    /// assert_eq!(VDString::from_mnemonic(&phrase, Wordlist::V1), Ok(code));
+    /// ```
+    pub fn to_mnemonic(&self, wordlist: Wordlist) -> Vec<&'static str> {
+        let words = wordlist.words();
+        self.decode_bytes().into_iter().map(|b| words[usize::from(b)]).collect()
+    }
+
+    /// Decodes a whitespace-separated phrase produced by
+    /// [`to_mnemonic`](Self::to_mnemonic) with the same `wordlist`.
+    ///
+    /// Words are matched ignoring ASCII case.
+    ///
+    /// # Errors
+    /// Returns [`MnemonicError::UnknownWord`] for the first word not in the list.
+    pub fn from_mnemonic(phrase: &str, wordlist: Wordlist) -> Result<VDString<Default31>, MnemonicError> {
+        let bytes = phrase
+            .split_whitespace()
+            .enumerate()
+            .map(|(i, word)| wordlist.byte_of(word).ok_or(MnemonicError::UnknownWord(i)))
+            .collect::<Result<Vec<u8>, _>>()?;
+        Ok(VDString::encode_bytes(&bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VDChar;
+
+    #[test]
+    fn v1_words_are_sorted_and_distinct() {
+        for pair in WORDS_V1.windows(2) {
+            assert!(pair[0] < pair[1], "{} !< {}", pair[0], pair[1]);
+            let prefix = |w: &'static str| &w[..w.len().min(4)];
+            assert_ne!(prefix(pair[0]), prefix(pair[1]));
+        }
+        for word in WORDS_V1 {
+            assert!((3..=8).contains(&word.len()), "{}", word);
+            assert!(word.bytes().all(|b| b.is_ascii_lowercase()), "{}", word);
+        }
+    }
+
+    #[test]
+    fn v1_encoding_is_stable() {
+        let code: VDString<Default31> = "K7M3PQ".parse().unwrap();
+        assert_eq!(code.decode_bytes(), [16, 235, 23, 2]);
+        assert_eq!(code.to_mnemonic(Wordlist::V1), ["atlas", "topaz", "balloon", "actor"]);
+    }
+
+    #[test]
+    fn roundtrips_including_leading_a() {
+        let codes = ["", "A", "AAB", "B", "99999999", "AB29XY7KM3PQ"];
+        for s in codes {
+            let code: VDString<Default31> = s.parse().unwrap();
+            let phrase = code.to_mnemonic(Wordlist::V1).join(" ");
+            assert_eq!(VDString::<Default31>::from_mnemonic(&phrase, Wordlist::V1), Ok(code), "{:?}", phrase);
+        }
+        let long = VDString::<Default31>::new(alloc::vec![VDChar::<Default31>::from_index_unchecked(30); 40]);
+        let phrase = long.to_mnemonic(Wordlist::V1).join(" ");
+        assert_eq!(VDString::<Default31>::from_mnemonic(&phrase, Wordlist::V1), Ok(long));
+    }
+
+    #[test]
+    fn decoding_ignores_case_and_spacing() {
+        let code: VDString<Default31> = "K7M3PQ".parse().unwrap();
+        let phrase = "  Atlas\tTOPAZ balloon\n actor ";
+        assert_eq!(VDString::<Default31>::from_mnemonic(phrase, Wordlist::V1), Ok(code));
+    }
+
+    #[test]
+    fn unknown_words_are_reported() {
+        let err = VDString::<Default31>::from_mnemonic("atlas topaz baloon", Wordlist::V1);
+        assert_eq!(err, Err(MnemonicError::UnknownWord(2)));
+        assert_eq!(VDString::<Default31>::from_mnemonic("", Wordlist::V1), Ok(VDString::<Default31>::new(alloc::vec![])));
+    }
+
+    #[test]
+    fn versions_roundtrip() {
+        assert_eq!(Wordlist::from_version(Wordlist::V1.version()), Some(Wordlist::V1));
+        assert_eq!(Wordlist::from_version(0), None);
+        assert_eq!(Wordlist::default(), Wordlist::V1);
+    }
+}
@@ -0,0 +1,254 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::alphabet::Default31;
+use crate::siphash::siphash;
+use crate::vdchar::VDS_ALLOWED;
+#[cfg(feature = "alloc")]
+use crate::VDString;
+
+const BASE: u128 = VDS_ALLOWED.len() as u128;
+
+/// Number of Feistel rounds, as in NIST FF1.
+const ROUNDS: u8 = 10;
+
+/// Error returned by [`VDObfuscator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObfuscatorError {
+    /// The code length is zero or longer than [`VDObfuscator::MAX_LEN`].
+    InvalidLength(usize),
+    /// The counter does not fit in the configured number of characters.
+    CounterOutOfRange(u64),
+    /// The code does not have the configured length.
+    LengthMismatch {
+        /// The configured length.
+        expected: usize,
+        /// The length of the given code.
+        actual: usize,
+    },
+    /// The code does not decode to a `u64` counter under this key.
+    NotACounter,
+}
+
+impl fmt::Display for ObfuscatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ObfuscatorError::InvalidLength(len) => write!(f, "invalid code length {}", len),
+            ObfuscatorError::CounterOutOfRange(n) => write!(f, "counter {} does not fit the code length", n),
+            ObfuscatorError::LengthMismatch { expected, actual } => {
+                write!(f, "code has {} characters, expected {}", actual, expected)
+            }
+            ObfuscatorError::NotACounter => f.write_str("code does not decode to a counter under this key"),
+        }
+    }
+}
+
+impl core::error::Error for ObfuscatorError {}
+
+/// Maps sequential `u64` counters to fixed-length codes and back, under a key.
+///
+/// Uses a 10-round alternating Feistel network over the exact domain of
+/// `31^len` codes (the construction used by format-preserving encryption
+/// schemes such as NIST FF1), with SipHash-2-4 as the round function. Every
+/// counter maps to a distinct code of the same length, and consecutive
+/// counters produce unrelated-looking codes, so issued codes don't reveal
+/// issuance volume.
+///
+/// This is obfuscation for identifiers, not a vetted encryption scheme; do
+/// not rely on it to protect secrets.
+///
+/// [`obfuscate`](Self::obfuscate) and [`reveal`](Self::reveal) need the
+/// `alloc` feature; [`VDDeriver`](crate::VDDeriver) offers the same mapping
+/// at a fixed length without allocating.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::VDObfuscator;
+///
+/// let ob = VDObfuscator::new([42; 16], 8).unwrap();
+/// let first = ob.obfuscate(1).unwrap();
+/// let second = ob.obfuscate(2).unwrap();
+///
+/// assert_eq!(first.len(), 8);
+/// assert_ne!(first, second);
+/// assert_eq!(ob.reveal(&second), Ok(2));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct VDObfuscator {
+    key: [u8; 16],
+    len: usize,
+}
+
+impl VDObfuscator {
+    /// Maximum code length; `31^25` is the largest power of 31 below `2^128`.
+    pub const MAX_LEN: usize = 25;
+
+    /// Creates an obfuscator producing codes of exactly `len` characters.
+    ///
+    /// # Errors
+    /// Returns [`ObfuscatorError::InvalidLength`] if `len` is `0` or exceeds
+    /// [`MAX_LEN`](Self::MAX_LEN). A length of 1 is accepted, but
+    /// with only 31 codes offers little obfuscation.
+    pub fn new(key: [u8; 16], len: usize) -> Result<Self, ObfuscatorError> {
+        if len == 0 || len > Self::MAX_LEN {
+            return Err(ObfuscatorError::InvalidLength(len));
+        }
+        Ok(Self::new_unchecked(key, len))
+    }
+
+    /// Creates an obfuscator without checking `len`, which must be in
+    /// `1..=MAX_LEN`.
+    pub(crate) const fn new_unchecked(key: [u8; 16], len: usize) -> Self {
+        Self { key, len }
+    }
+
+    /// Returns the number of characters in each code.
+    pub fn code_len(&self) -> usize {
+        self.len
+    }
+
+    /// Maps `counter` to its code.
+    ///
+    /// # Errors
+    /// Returns [`ObfuscatorError::CounterOutOfRange`] if `counter >= 31^len`.
+    #[cfg(feature = "alloc")]
+    pub fn obfuscate(&self, counter: u64) -> Result<VDString<Default31>, ObfuscatorError> {
+        if u128::from(counter) >= BASE.pow(self.len as u32) {
+            return Err(ObfuscatorError::CounterOutOfRange(counter));
+        }
+        Ok(VDString::encode_u128_padded(self.permute(u128::from(counter)), self.len))
+    }
+
+    /// Applies the Feistel permutation to `x`, which must be below `31^len`.
+    pub(crate) fn permute(&self, x: u128) -> u128 {
+        let (u, v) = self.halves();
+        let (ma, mb) = (BASE.pow(u), BASE.pow(v));
+
+        let mut a = x / mb;
+        let mut b = x % mb;
+        for round in 0..ROUNDS {
+            let m = if round % 2 == 0 { ma } else { mb };
+            let c = (a + self.prf(round, b) % m) % m;
+            a = b;
+            b = c;
+        }
+        a * mb + b
+    }
+
+    /// Recovers the counter from a code produced by [`obfuscate`](Self::obfuscate).
+    ///
+    /// # Errors
+    /// - [`ObfuscatorError::LengthMismatch`] if the code has the wrong length
+    /// - [`ObfuscatorError::NotACounter`] if the code decodes past `u64::MAX`,
+    ///   which means it was not produced by this obfuscator
+    #[cfg(feature = "alloc")]
+    pub fn reveal(&self, code: &VDString<Default31>) -> Result<u64, ObfuscatorError> {
+        if code.len() != self.len {
+            return Err(ObfuscatorError::LengthMismatch { expected: self.len, actual: code.len() });
+        }
+        let (u, v) = self.halves();
+        let (ma, mb) = (BASE.pow(u), BASE.pow(v));
+        // At most 25 characters, so this cannot overflow.
+        let x = code.decode_u128().map_err(|_| ObfuscatorError::NotACounter)?;
+
+        let mut a = x / mb;
+        let mut b = x % mb;
+        for round in (0..ROUNDS).rev() {
+            let m = if round % 2 == 0 { ma } else { mb };
+            let c = b;
+            b = a;
+            a = (c + m - self.prf(round, b) % m) % m;
+        }
+
+        u64::try_from(a * mb + b).map_err(|_| ObfuscatorError::NotACounter)
+    }
+
+    /// Splits the code length into Feistel halves `(u, v)` with `u <= v`.
+    fn halves(&self) -> (u32, u32) {
+        let u = (self.len / 2) as u32;
+        (u, self.len as u32 - u)
+    }
+
+    /// Keyed round function.
+    fn prf(&self, round: u8, input: u128) -> u128 {
+        let mut block = [0u8; 18];
+        block[0] = round;
+        block[1] = self.len as u8;
+        block[2..].copy_from_slice(&input.to_le_bytes());
+        let hi = siphash(&self.key, &block);
+        block[1] |= 0x80;
+        let lo = siphash(&self.key, &block);
+        u128::from(hi) << 64 | u128::from(lo)
+    }
+}
+
+impl fmt::Debug for VDObfuscator {
+    /// Omits the key.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VDObfuscator").field("len", &self.len).finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    extern crate alloc;
+    use alloc::vec;
+
+    #[test]
+    fn permutes_small_domain() {
+        // With two characters the whole domain of 961 codes can be checked.
+        let ob = VDObfuscator::new([1; 16], 2).unwrap();
+        let mut seen = vec![false; 961];
+        for n in 0..961 {
+            let code = ob.obfuscate(n).unwrap();
+            let x = code.iter().fold(0, |acc, ch| acc * 31 + ch.index() as usize);
+            assert!(!seen[x], "collision at {}", n);
+            seen[x] = true;
+            assert_eq!(ob.reveal(&code), Ok(n));
+        }
+        assert_eq!(ob.obfuscate(961), Err(ObfuscatorError::CounterOutOfRange(961)));
+    }
+
+    #[test]
+    fn roundtrips_for_every_length() {
+        for len in 1..=VDObfuscator::MAX_LEN {
+            let ob = VDObfuscator::new([9; 16], len).unwrap();
+            let max = BASE.pow(len as u32).min(u128::from(u64::MAX) + 1) - 1;
+            for n in [0, 1, 30, max as u64] {
+                let code = ob.obfuscate(n).unwrap();
+                assert_eq!(code.len(), len);
+                assert_eq!(ob.reveal(&code), Ok(n), "len {} n {}", len, n);
+            }
+        }
+    }
+
+    #[test]
+    fn key_changes_output() {
+        let a = VDObfuscator::new([1; 16], 8).unwrap();
+        let b = VDObfuscator::new([2; 16], 8).unwrap();
+        assert_ne!(a.obfuscate(5).unwrap(), b.obfuscate(5).unwrap());
+    }
+
+    #[test]
+    fn rejects_bad_lengths_and_codes() {
+        assert_eq!(VDObfuscator::new([0; 16], 0).unwrap_err(), ObfuscatorError::InvalidLength(0));
+        assert_eq!(VDObfuscator::new([0; 16], 26).unwrap_err(), ObfuscatorError::InvalidLength(26));
+
+        let ob = VDObfuscator::new([0; 16], 4).unwrap();
+        let short: VDString<Default31> = "ABC".parse().unwrap();
+        assert_eq!(
+            ob.reveal(&short),
+            Err(ObfuscatorError::LengthMismatch { expected: 4, actual: 3 })
+        );
+
+        let wide = VDObfuscator::new([0; 16], 20).unwrap();
+        let not_counter = (0..2000)
+            .map(|i| VDString::<Default31>::encode_u128_padded(u128::MAX / 2000 * i, 20))
+            .filter(|c| c.len() == 20)
+            .any(|c| wide.reveal(&c) == Err(ObfuscatorError::NotACounter));
+        assert!(not_counter);
+    }
+}
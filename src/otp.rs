@@ -0,0 +1,194 @@
+extern crate alloc;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::alphabet::Default31;
+use crate::sha1::hmac_sha1;
+use crate::{VDStr, VDString};
+
+/// HOTP/TOTP one-time codes written in the [`VDS_ALLOWED`](crate::VDS_ALLOWED)
+/// alphabet.
+///
+/// Codes are computed exactly as in RFC 4226 (HOTP) and RFC 6238 (TOTP),
+/// with HMAC-SHA1 and dynamic truncation to a 31-bit value. Only the last
+/// step differs: instead of reducing modulo `10^digits`, the value is
+/// reduced modulo `31^len` and written as `len` base-31 characters. Six
+/// characters carry almost 30 bits, against 20 for six decimal digits,
+/// and avoid look-alike glyphs when read aloud or copied from a screen.
+///
+/// Time is passed in as Unix seconds, so the type works without `std`.
+///
+/// This type is only available when the `otp` feature is enabled.
+///
+/// # Examples
+/// ```
+/// use vds::VDOtp;
+///
+/// let otp = VDOtp::new(b"12345678901234567890");
+/// let now = 1_700_000_000;
+/// let code = otp.totp(now);
+/// assert_eq!(code.len(), 6);
+///
+/// // Accept codes from one step either side of the server's clock.
+/// assert!(otp.verify_totp(&code, now + 25, 1).is_some());
+/// assert!(otp.verify_totp(&code, now + 300, 1).is_none());
+/// ```
+#[derive(Clone)]
+pub struct VDOtp {
+    secret: Vec<u8>,
+    len: usize,
+    step: u64,
+}
+
+impl VDOtp {
+    /// Maximum code length; `31^6` is the largest power of 31 below the
+    /// `2^31` values of the truncated HMAC.
+    pub const MAX_LEN: usize = 6;
+
+    /// Creates a generator for `secret` with six-character codes and a
+    /// 30-second time step, the RFC 6238 default.
+    pub fn new(secret: &[u8]) -> Self {
+        Self { secret: secret.to_vec(), len: Self::MAX_LEN, step: 30 }
+    }
+
+    /// Sets the number of characters in each code.
+    ///
+    /// # Panics
+    /// Panics if `len` is `0` or exceeds [`MAX_LEN`](Self::MAX_LEN).
+    pub fn length(mut self, len: usize) -> Self {
+        assert!((1..=Self::MAX_LEN).contains(&len), "OTP length must be 1 to {}, got {}", Self::MAX_LEN, len);
+        self.len = len;
+        self
+    }
+
+    /// Sets the TOTP time step in seconds.
+    ///
+    /// # Panics
+    /// Panics if `seconds` is `0`.
+    pub fn time_step(mut self, seconds: u64) -> Self {
+        assert!(seconds > 0, "OTP time step must be positive");
+        self.step = seconds;
+        self
+    }
+
+    /// Returns the HOTP code for `counter`.
+    pub fn hotp(&self, counter: u64) -> VDString<Default31> {
+        let modulus = 31u32.pow(self.len as u32);
+        VDString::encode_u64_padded(u64::from(self.truncate(counter) % modulus), self.len)
+    }
+
+    /// Returns the TOTP code for the time step containing `unix_time`.
+    pub fn totp(&self, unix_time: u64) -> VDString<Default31> {
+        self.hotp(unix_time / self.step)
+    }
+
+    /// Checks `code` against the HOTP codes for `counter` and the
+    /// `look_ahead` counters after it, as RFC 4226 suggests for tokens
+    /// that advanced without a successful login.
+    ///
+    /// Returns the counter that matched; the next expected counter is one
+    /// past it.
+    pub fn verify_hotp(&self, code: &VDStr<Default31>, counter: u64, look_ahead: u64) -> Option<u64> {
+        let last = counter.saturating_add(look_ahead);
+        (counter..=last).find(|&c| constant_time_eq(code, &self.hotp(c)))
+    }
+
+    /// Checks `code` against the TOTP codes for the time step containing
+    /// `unix_time` and `window` steps either side of it, allowing for
+    /// clock drift and slow typing.
+    ///
+    /// Returns the time step that matched. Servers should reject later
+    /// codes for the same or earlier steps, so each code is used only once.
+    pub fn verify_totp(&self, code: &VDStr<Default31>, unix_time: u64, window: u64) -> Option<u64> {
+        let step = unix_time / self.step;
+        let first = step.saturating_sub(window);
+        let last = step.saturating_add(window);
+        (first..=last).find(|&s| constant_time_eq(code, &self.hotp(s)))
+    }
+
+    /// HMAC-SHA1 of the counter with RFC 4226 dynamic truncation.
+    fn truncate(&self, counter: u64) -> u32 {
+        let mac = hmac_sha1(&self.secret, &counter.to_be_bytes());
+        let offset = usize::from(mac[mac.len() - 1] & 0xf);
+        u32::from_be_bytes([mac[offset], mac[offset + 1], mac[offset + 2], mac[offset + 3]]) & 0x7fff_ffff
+    }
+}
+
+/// Compares codes without exiting early on the first mismatch.
+fn constant_time_eq(a: &VDStr<Default31>, b: &VDStr<Default31>) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).fold(0, |acc, (x, y)| acc | (x.index() ^ y.index())) == 0
+}
+
+impl fmt::Debug for VDOtp {
+    /// Omits the secret.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VDOtp").field("len", &self.len).field("step", &self.step).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    const SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn truncation_matches_rfc_4226() {
+        let otp = VDOtp::new(SECRET);
+        let expected = [755224, 287082, 359152, 969429, 338314, 254676, 287922, 162583, 399871, 520489];
+        for (counter, &digits) in expected.iter().enumerate() {
+            assert_eq!(otp.truncate(counter as u64) % 1_000_000, digits, "counter {}", counter);
+        }
+    }
+
+    #[test]
+    fn totp_steps_match_rfc_6238() {
+        let otp = VDOtp::new(SECRET);
+        for (time, digits) in [
+            (59, 94287082),
+            (1111111109, 7081804),
+            (1111111111, 14050471),
+            (1234567890, 89005924),
+            (2000000000, 69279037),
+            (20000000000, 65353130),
+        ] {
+            assert_eq!(otp.truncate(time / 30) % 100_000_000, digits, "time {}", time);
+        }
+    }
+
+    #[test]
+    fn codes_encode_truncated_value_in_base_31() {
+        let otp = VDOtp::new(SECRET).length(4);
+        for counter in 0..10 {
+            let code = otp.hotp(counter);
+            assert_eq!(code.len(), 4);
+            assert_eq!(code.decode_u64(), Ok(u64::from(otp.truncate(counter) % 923_521)));
+        }
+        assert_eq!(otp.totp(59), otp.hotp(1));
+        assert_eq!(otp.clone().time_step(60).totp(59), otp.hotp(0));
+    }
+
+    #[test]
+    fn verification_honors_windows() {
+        let otp = VDOtp::new(SECRET);
+        let code = otp.hotp(5);
+        assert_eq!(otp.verify_hotp(&code, 5, 0), Some(5));
+        assert_eq!(otp.verify_hotp(&code, 3, 2), Some(5));
+        assert_eq!(otp.verify_hotp(&code, 3, 1), None);
+        assert_eq!(otp.verify_hotp(&code, 6, 10), None);
+
+        let code = otp.totp(1_000_000);
+        let step = 1_000_000 / 30;
+        assert_eq!(otp.verify_totp(&code, 1_000_000 + 30, 1), Some(step));
+        assert_eq!(otp.verify_totp(&code, 1_000_000 - 30, 1), Some(step));
+        assert_eq!(otp.verify_totp(&code, 1_000_000 + 90, 1), None);
+        assert!(otp.verify_totp(&otp.hotp(0)[..5], 0, 0).is_none());
+    }
+
+    #[test]
+    fn debug_hides_secret() {
+        assert_eq!(format!("{:?}", VDOtp::new(SECRET)), "VDOtp { len: 6, step: 30, .. }");
+    }
+}
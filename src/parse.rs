@@ -0,0 +1,720 @@
+extern crate alloc;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::alphabet::Default31;
+use crate::homoglyph::canonicalize_char;
+use crate::{VDChar, VDString, VDStringError};
+
+/// Options for lenient parsing with [`VDString::parse_with`].
+///
+/// The default options are strict and match `FromStr`. Enable fields
+/// individually, or start from [`ParseOptions::lenient`].
+///
+/// # Examples
+/// ```
+/// use vds::{ParseOptions, VDString};
+///
+/// let options = ParseOptions { ignore_separators: true, fold_case: true, ..Default::default() };
+/// let code = VDString::parse_with("ab2-9xy", options).unwrap();
+/// assert_eq!(&*code, "AB29XY");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Skips spaces, tabs, and hyphens, e.g. `"AB29 XY"` or `"AB2-9XY"`.
+    pub ignore_separators: bool,
+    /// Accepts lowercase ASCII letters as their uppercase forms.
+    pub fold_case: bool,
+    /// Accepts lowercase, fullwidth, and Cyrillic/Greek look-alike
+    /// characters, as folded by [`canonicalize_char`]. Implies `fold_case`.
+    pub fold_homoglyphs: bool,
+    /// Applies Unicode NFKC normalization before anything else, folding
+    /// compatibility forms such as fullwidth `Ｆ`, circled `⑦`, or
+    /// mathematical `𝐀` onto plain characters.
+    ///
+    /// This field is only available when the `nfkc` feature is enabled.
+    #[cfg(feature = "nfkc")]
+    pub normalize_nfkc: bool,
+}
+
+impl ParseOptions {
+    /// Options with every leniency enabled.
+    pub const fn lenient() -> Self {
+        Self {
+            ignore_separators: true,
+            fold_case: true,
+            fold_homoglyphs: true,
+            #[cfg(feature = "nfkc")]
+            normalize_nfkc: true,
+        }
+    }
+
+    fn parse<I: Iterator<Item = char>>(&self, chars: I) -> Result<VDString<Default31>, VDStringError> {
+        chars
+            .scan(0, |offset, c| {
+                let at = *offset;
+                *offset += c.len_utf8();
+                Some((at, c))
+            })
+            .filter(|&(_, c)| !self.is_separator(c))
+            .map(|(offset, c)| VDChar::new(self.fold(c)).ok_or(VDStringError::InvalidChar { ch: c, offset }))
+            .collect::<Result<Vec<_>, _>>()
+            .map(VDString::new)
+    }
+
+    fn is_separator(&self, c: char) -> bool {
+        self.ignore_separators && matches!(c, ' ' | '\t' | '-')
+    }
+
+    fn fold(&self, c: char) -> char {
+        if self.fold_homoglyphs {
+            canonicalize_char(c)
+        } else if self.fold_case {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        }
+    }
+}
+
+/// An invalid character and its position in the parsed input.
+///
+/// Returned by [`VDString::parse_all_errors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharError {
+    /// The rejected character.
+    pub ch: char,
+    /// Position of the character, counted in `char`s.
+    pub char_index: usize,
+    /// Byte offset of the character in the input `str`.
+    pub byte_index: usize,
+}
+
+impl fmt::Display for CharError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid character {:?} at position {}", self.ch, self.char_index)
+    }
+}
+
+impl core::error::Error for CharError {}
+
+impl CharError {
+    /// Returns a [`ParseHint`] explaining why the character was rejected, if
+    /// there is a common reason.
+    pub fn hint(&self) -> Option<ParseHint> {
+        ParseHint::for_char(self.ch)
+    }
+}
+
+/// Error returned by [`VDString::parse_with_prefix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrefixError {
+    /// The input does not start with the required prefix.
+    WrongPrefix,
+    /// The input after the prefix is not a valid code.
+    Invalid(VDStringError),
+}
+
+impl fmt::Display for PrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefixError::WrongPrefix => f.write_str("missing or wrong prefix"),
+            PrefixError::Invalid(err) => write!(f, "invalid code after prefix: {}", err),
+        }
+    }
+}
+
+impl core::error::Error for PrefixError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            PrefixError::Invalid(err) => Some(err),
+            PrefixError::WrongPrefix => None,
+        }
+    }
+}
+
+impl From<VDStringError> for PrefixError {
+    fn from(err: VDStringError) -> Self {
+        PrefixError::Invalid(err)
+    }
+}
+
+/// A rejected byte and its offset in the input.
+///
+/// Returned by [`VDString::from_ascii`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteError {
+    /// The rejected byte, which may not be ASCII.
+    pub byte: u8,
+    /// Offset of the byte in the input.
+    pub index: usize,
+}
+
+impl fmt::Display for ByteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.byte.is_ascii() {
+            write!(f, "invalid character {:?} at position {}", char::from(self.byte), self.index)
+        } else {
+            write!(f, "non-ASCII byte {:#04x} at position {}", self.byte, self.index)
+        }
+    }
+}
+
+impl core::error::Error for ByteError {}
+
+impl ByteError {
+    /// Returns a [`ParseHint`] explaining why the byte was rejected, if it is
+    /// an ASCII character and there is a common reason.
+    pub fn hint(&self) -> Option<ParseHint> {
+        if self.byte.is_ascii() {
+            ParseHint::for_char(char::from(self.byte))
+        } else {
+            None
+        }
+    }
+}
+
+/// Why an excluded character is left out of [`VDS_ALLOWED`](crate::VDS_ALLOWED).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Ambiguity {
+    /// `O` and `o` are easily read as the digit `0`.
+    LooksLikeZero,
+    /// `0` is easily read as the letter `O`.
+    LooksLikeLetterO,
+    /// `I`, `i`, `L`, and `l` are easily read as the digit `1`.
+    LooksLikeOne,
+    /// `1` is easily read as the letter `I` or `l`.
+    LooksLikeLetterI,
+}
+
+impl Ambiguity {
+    /// Returns a short, user-facing explanation.
+    pub fn explanation(self) -> &'static str {
+        match self {
+            Ambiguity::LooksLikeZero => "the letter O is not used because it looks like the digit 0",
+            Ambiguity::LooksLikeLetterO => "the digit 0 is not used because it looks like the letter O",
+            Ambiguity::LooksLikeOne => "the letters I and L are not used because they look like the digit 1",
+            Ambiguity::LooksLikeLetterI => "the digit 1 is not used because it looks like the letters I and L",
+        }
+    }
+}
+
+/// A structured suggestion for a character rejected by parsing.
+///
+/// Lets callers turn parse failures into guidance without keeping their own
+/// table of explanations. Hints are relative to the default alphabet.
+///
+/// # Examples
+/// ```
+/// use vds::{Ambiguity, ParseHint, VDString};
+///
+/// let err = "AB0".parse::<VDString>().unwrap_err();
+/// assert_eq!(
+///     err.hint(),
+///     Some(ParseHint::ExcludedAmbiguous { got: '0', reason: Ambiguity::LooksLikeLetterO })
+/// );
+///
+/// let err = "ab".parse::<VDString>().unwrap_err();
+/// assert_eq!(err.hint().unwrap().to_string(), "use uppercase 'A' instead of 'a'");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseHint {
+    /// The character is deliberately excluded because it is ambiguous.
+    ExcludedAmbiguous {
+        /// The rejected character.
+        got: char,
+        /// Why it is excluded.
+        reason: Ambiguity,
+    },
+    /// A lowercase letter whose uppercase form is allowed.
+    Lowercase {
+        /// The rejected character.
+        got: char,
+        /// The allowed uppercase character.
+        suggestion: VDChar<Default31>,
+    },
+}
+
+impl ParseHint {
+    /// Returns the hint for a rejected character, or `None` if there is no
+    /// specific advice (e.g. punctuation).
+    pub fn for_char(c: char) -> Option<Self> {
+        let reason = match c {
+            'O' | 'o' => Some(Ambiguity::LooksLikeZero),
+            '0' => Some(Ambiguity::LooksLikeLetterO),
+            'I' | 'i' | 'L' | 'l' => Some(Ambiguity::LooksLikeOne),
+            '1' => Some(Ambiguity::LooksLikeLetterI),
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            return Some(ParseHint::ExcludedAmbiguous { got: c, reason });
+        }
+        if c.is_ascii_lowercase() {
+            let suggestion = VDChar::new(c.to_ascii_uppercase())?;
+            return Some(ParseHint::Lowercase { got: c, suggestion });
+        }
+        None
+    }
+}
+
+impl fmt::Display for ParseHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ParseHint::ExcludedAmbiguous { reason, .. } => f.write_str(reason.explanation()),
+            ParseHint::Lowercase { got, suggestion } => {
+                write!(f, "use uppercase '{}' instead of '{}'", suggestion, got)
+            }
+        }
+    }
+}
+
+impl VDStringError {
+    /// Returns a [`ParseHint`] for the rejected character, if there is one.
+    pub fn hint(&self) -> Option<ParseHint> {
+        match *self {
+            VDStringError::InvalidChar { ch, .. } => ParseHint::for_char(ch),
+        }
+    }
+}
+
+impl VDString<Default31> {
+    /// Parses `s` like `FromStr`, but reports every invalid character
+    /// instead of stopping at the first.
+    ///
+    /// Useful for highlighting all problems in a form field at once.
+    ///
+    /// # Errors
+    /// Returns a [`CharError`] for each rejected character, in input order.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let errors = VDString::parse_all_errors("AB0ÉX1").unwrap_err();
+    /// let positions: Vec<_> = errors.iter().map(|e| (e.ch, e.char_index, e.byte_index)).collect();
+    /// assert_eq!(positions, [('0', 2, 2), ('É', 3, 3), ('1', 5, 6)]);
+    /// ```
+    pub fn parse_all_errors(s: &str) -> Result<VDString<Default31>, Vec<CharError>> {
+        let mut chars = Vec::with_capacity(s.len());
+        let mut errors = Vec::new();
+        for (char_index, (byte_index, ch)) in s.char_indices().enumerate() {
+            match VDChar::new(ch) {
+                Some(c) => chars.push(c),
+                None => errors.push(CharError { ch, char_index, byte_index }),
+            }
+        }
+        if errors.is_empty() {
+            Ok(VDString::new(chars))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parses many codes at once, such as the rows of an imported file,
+    /// keeping the valid ones and the row number of each failure.
+    ///
+    /// Each row is parsed strictly, like `FromStr`, through the alphabet's
+    /// ASCII lookup table. The output vectors are sized up front from the
+    /// iterator's length hint.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{VDString, VDStringError};
+    ///
+    /// let file = "AB29\nXK73\nK7O3\nM5TQ\n";
+    /// let (codes, errors) = VDString::parse_many(file.lines());
+    /// assert_eq!(codes, ["AB29", "XK73", "M5TQ"]);
+    /// assert_eq!(errors, [(2, VDStringError::InvalidChar { ch: 'O', offset: 2 })]);
+    /// ```
+    pub fn parse_many<'a, I>(lines: I) -> (Vec<VDString<Default31>>, Vec<(usize, VDStringError)>)
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let lines = lines.into_iter();
+        let mut codes = Vec::with_capacity(lines.size_hint().0);
+        let mut errors = Vec::new();
+        for (row, line) in lines.enumerate() {
+            match line.parse() {
+                Ok(code) => codes.push(code),
+                Err(err) => errors.push((row, err)),
+            }
+        }
+        (codes, errors)
+    }
+
+    /// Parses `s` like `FromStr`, with the leniencies enabled in `options`.
+    ///
+    /// Use this for input typed or pasted by people; `FromStr` stays strict
+    /// for machine-produced codes.
+    ///
+    /// # Errors
+    /// Returns [`VDStringError::InvalidChar`] with the first rejected input
+    /// character, as it appeared before folding, and its byte offset in `s`.
+    /// With NFKC normalization, both refer to the normalized text.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{ParseOptions, VDString, VDStringError};
+    ///
+    /// let code = VDString::parse_with(" ab29 xy ", ParseOptions::lenient()).unwrap();
+    /// assert_eq!(&*code, "AB29XY");
+    ///
+    /// let err = VDString::parse_with("ab2-9xy", ParseOptions::default());
+    /// assert_eq!(err, Err(VDStringError::InvalidChar { ch: 'a', offset: 0 }));
+    /// ```
+    pub fn parse_with(s: &str, options: ParseOptions) -> Result<VDString<Default31>, VDStringError> {
+        #[cfg(feature = "nfkc")]
+        if options.normalize_nfkc {
+            use unicode_normalization::UnicodeNormalization;
+            return options.parse(s.nfkc());
+        }
+        options.parse(s.chars())
+    }
+
+    /// Parses `s` after stripping the literal `prefix`, such as `"VD-"`.
+    ///
+    /// The prefix must match exactly, including case; the rest is parsed
+    /// strictly, like `FromStr`. Use
+    /// [`display_with_prefix`](crate::VDStr::display_with_prefix) to render
+    /// the code with its prefix again.
+    ///
+    /// # Errors
+    /// - [`PrefixError::WrongPrefix`] if `s` does not start with `prefix`
+    /// - [`PrefixError::Invalid`] if the rest is not a valid code, with the
+    ///   offset of the rejected character in `s`
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{PrefixError, VDString, VDStringError};
+    ///
+    /// let code = VDString::parse_with_prefix("VD-AB29XY", "VD-").unwrap();
+    /// assert_eq!(&*code, "AB29XY");
+    /// assert_eq!(code.display_with_prefix("VD-").to_string(), "VD-AB29XY");
+    ///
+    /// assert_eq!(VDString::parse_with_prefix("AB29XY", "VD-"), Err(PrefixError::WrongPrefix));
+    /// assert_eq!(
+    ///     VDString::parse_with_prefix("VD-AB20XY", "VD-"),
+    ///     Err(PrefixError::Invalid(VDStringError::InvalidChar { ch: '0', offset: 6 }))
+    /// );
+    /// ```
+    pub fn parse_with_prefix(s: &str, prefix: &str) -> Result<VDString<Default31>, PrefixError> {
+        let rest = s.strip_prefix(prefix).ok_or(PrefixError::WrongPrefix)?;
+        rest.parse().map_err(|VDStringError::InvalidChar { ch, offset }| {
+            PrefixError::Invalid(VDStringError::InvalidChar { ch, offset: prefix.len() + offset })
+        })
+    }
+
+    /// Scans free text for candidate codes of at least `min_len` characters.
+    ///
+    /// A token is a maximal run of alphanumeric characters (as
+    /// [`char::is_alphanumeric`]); it is a candidate when every one of its
+    /// characters is allowed. Tokens containing any other letter or digit
+    /// are skipped whole, so `"HELLO"` yields nothing rather than `"HE"`.
+    /// Punctuation and whitespace separate tokens, which means a grouped
+    /// code such as `"AB29-XY7K"` comes out as two tokens.
+    ///
+    /// Each candidate is returned with its byte span in `text`.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let text = "Hi! Your code is K7M3PQ (or AB29), thanks.";
+    /// let found: Vec<_> = VDString::extract_all(text, 4).collect();
+    /// assert_eq!(found.len(), 2);
+    /// assert_eq!(found[0].0, 17..23);
+    /// assert_eq!(found[0].1, "K7M3PQ");
+    /// assert_eq!(&text[found[1].0.clone()], "AB29");
+    /// ```
+    pub fn extract_all(text: &str, min_len: usize) -> ExtractAll<'_> {
+        ExtractAll { text, pos: 0, min_len }
+    }
+}
+
+/// Iterator over candidate codes in free text, with their byte spans.
+///
+/// Returned by [`VDString::extract_all`].
+#[derive(Debug, Clone)]
+pub struct ExtractAll<'a> {
+    text: &'a str,
+    pos: usize,
+    min_len: usize,
+}
+
+impl Iterator for ExtractAll<'_> {
+    type Item = (Range<usize>, VDString<Default31>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.text.len() {
+            let rest = &self.text[self.pos..];
+            let Some(start) = rest.find(char::is_alphanumeric) else {
+                self.pos = self.text.len();
+                break;
+            };
+            let token = &rest[start..];
+            let len = token.find(|c: char| !c.is_alphanumeric()).unwrap_or(token.len());
+            let span = self.pos + start..self.pos + start + len;
+            self.pos = span.end;
+
+            let token = &token[..len];
+            if token.chars().count() < self.min_len {
+                continue;
+            }
+            if let Ok(code) = token.parse() {
+                return Some((span, code));
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for ExtractAll<'_> {}
+
+/// Characters that OCR engines read in place of allowed ones, each with the
+/// allowed characters it most likely stands for, most likely first.
+///
+/// None of these characters can appear in a printed code, so
+/// [`VDString::parse_from_ocr`] replaces each with its first candidate and
+/// flags the position.
+pub const OCR_CORRECTIONS: &[(char, &str)] = &[
+    ('0', "DQ"), ('O', "DQ"),
+    ('1', "7TJ"), ('I', "7TJ"), ('L', "7TJ"), ('|', "7TJ"),
+];
+
+/// A position in an OCR result that a person should confirm.
+///
+/// Returned in [`OcrParse::uncertain`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OcrUncertainty {
+    /// Position in the parsed code, counted in characters.
+    pub position: usize,
+    /// The character as scanned.
+    pub read: char,
+    /// The plausible characters, starting with the one placed in the code.
+    pub candidates: &'static str,
+}
+
+/// The result of [`VDString::parse_from_ocr`]: a best-guess code and the
+/// positions to confirm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcrParse {
+    /// The code with every correction applied.
+    pub code: VDString<Default31>,
+    /// The corrected or doubtful positions, in order.
+    pub uncertain: Vec<OcrUncertainty>,
+}
+
+impl OcrParse {
+    /// Returns `true` if no position needs confirming.
+    pub fn is_certain(&self) -> bool {
+        self.uncertain.is_empty()
+    }
+}
+
+impl VDString<Default31> {
+    /// Parses the text of a scanned code, correcting common OCR misreads
+    /// and reporting the positions a person should confirm.
+    ///
+    /// Input is first read as by [`ParseOptions::lenient`]. Characters in
+    /// [`OCR_CORRECTIONS`], such as `0`, `O`, `1`, `l`, and `|`, are
+    /// replaced by their most likely candidate and flagged. An `S` among
+    /// digits, or a `5` among letters, is kept but flagged, since OCR
+    /// confuses the two and the neighbours suggest the other.
+    ///
+    /// # Errors
+    /// Returns [`VDStringError::InvalidChar`] for the first character that
+    /// is neither allowed nor a known misread.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let scan = VDString::parse_from_ocr("K0 M3-PQ").unwrap();
+    /// assert_eq!(scan.code, "KDM3PQ");
+    /// assert_eq!(scan.uncertain[0].position, 1);
+    /// assert_eq!(scan.uncertain[0].candidates, "DQ");
+    ///
+    /// let scan = VDString::parse_from_ocr("29S4").unwrap();
+    /// assert_eq!(scan.code, "29S4");
+    /// assert_eq!(scan.uncertain[0].candidates, "S5");
+    ///
+    /// assert!(VDString::parse_from_ocr("K7M3").unwrap().is_certain());
+    /// ```
+    pub fn parse_from_ocr(s: &str) -> Result<OcrParse, VDStringError> {
+        let options = ParseOptions::lenient();
+        let mut chars = Vec::with_capacity(s.len());
+        let mut uncertain = Vec::new();
+        for (offset, read) in s.char_indices().filter(|&(_, c)| !options.is_separator(c)) {
+            let folded = canonicalize_char(read);
+            let c = match OCR_CORRECTIONS.iter().find(|&&(from, _)| from == folded) {
+                Some(&(_, candidates)) => {
+                    uncertain.push(OcrUncertainty { position: chars.len(), read, candidates });
+                    candidates.chars().next().and_then(VDChar::new)
+                }
+                None => VDChar::new(folded),
+            };
+            chars.push(c.ok_or(VDStringError::InvalidChar { ch: read, offset })?);
+        }
+
+        for (position, &c) in chars.iter().enumerate() {
+            let candidates = match c.as_char() {
+                'S' => "S5",
+                '5' => "5S",
+                _ => continue,
+            };
+            let before = position.checked_sub(1).and_then(|i| chars.get(i));
+            let mut neighbours = before.into_iter().chain(chars.get(position + 1)).peekable();
+            let doubtful = neighbours.peek().is_some() && neighbours.all(|n| n.is_digit() == c.is_letter());
+            if doubtful && !uncertain.iter().any(|u| u.position == position) {
+                uncertain.push(OcrUncertainty { position, read: c.as_char(), candidates });
+            }
+        }
+        uncertain.sort_by_key(|u| u.position);
+
+        Ok(OcrParse { code: VDString::new(chars), uncertain })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_options_match_from_str() {
+        for input in ["AB29XY", "ab29xy", "AB-29", "", "Ｆ"] {
+            assert_eq!(VDString::<Default31>::parse_with(input, ParseOptions::default()), input.parse());
+        }
+    }
+
+    #[test]
+    fn options_apply_independently() {
+        let separators = ParseOptions { ignore_separators: true, ..Default::default() };
+        assert_eq!(&*VDString::<Default31>::parse_with("AB-29 XY", separators).unwrap(), "AB29XY");
+        assert_eq!(
+            VDString::<Default31>::parse_with("ab-29", separators),
+            Err(VDStringError::InvalidChar { ch: 'a', offset: 0 })
+        );
+
+        let case = ParseOptions { fold_case: true, ..Default::default() };
+        assert_eq!(&*VDString::<Default31>::parse_with("ab29", case).unwrap(), "AB29");
+        assert_eq!(VDString::<Default31>::parse_with("ab 29", case), Err(VDStringError::InvalidChar { ch: ' ', offset: 2 }));
+        assert_eq!(VDString::<Default31>::parse_with("Ａ", case), Err(VDStringError::InvalidChar { ch: 'Ａ', offset: 0 }));
+    }
+
+    #[test]
+    fn prefix_is_literal_and_round_trips() {
+        let code = VDString::<Default31>::parse_with_prefix("vd-AB29", "vd-").unwrap();
+        assert_eq!(alloc::format!("{}", code.display_with_prefix("vd-")), "vd-AB29");
+        assert_eq!(VDString::<Default31>::parse_with_prefix("VD-AB29", "vd-"), Err(PrefixError::WrongPrefix));
+        assert_eq!(VDString::<Default31>::parse_with_prefix("VD", "VD-"), Err(PrefixError::WrongPrefix));
+        assert_eq!(VDString::<Default31>::parse_with_prefix("VD-", "VD-").map(|c| c.len()), Ok(0));
+        assert_eq!(VDString::<Default31>::parse_with_prefix("AB29", "").unwrap(), code);
+
+        let err = VDString::<Default31>::parse_with_prefix("VD-VD-AB29", "VD-").unwrap_err();
+        assert_eq!(err, PrefixError::Invalid(VDStringError::InvalidChar { ch: '-', offset: 5 }));
+        assert!(core::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn collects_every_error_with_positions() {
+        assert_eq!(VDString::<Default31>::parse_all_errors("AB29"), "AB29".parse().map_err(|_| Vec::new()));
+        assert_eq!(&*VDString::<Default31>::parse_all_errors("").unwrap(), "");
+
+        let errors = VDString::<Default31>::parse_all_errors("oA€1").unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                CharError { ch: 'o', char_index: 0, byte_index: 0 },
+                CharError { ch: '€', char_index: 2, byte_index: 2 },
+                CharError { ch: '1', char_index: 3, byte_index: 5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_many_keeps_row_numbers() {
+        let rows = ["AB29", "", "ab29", "XK73", "K7 M3", "M5TQ"];
+        let (codes, errors) = VDString::<Default31>::parse_many(rows);
+        assert_eq!(codes, ["AB29", "", "XK73", "M5TQ"]);
+        assert_eq!(errors, [
+            (2, VDStringError::InvalidChar { ch: 'a', offset: 0 }),
+            (4, VDStringError::InvalidChar { ch: ' ', offset: 2 }),
+        ]);
+
+        let (codes, errors) = VDString::<Default31>::parse_many(core::iter::empty());
+        assert!(codes.is_empty() && errors.is_empty());
+    }
+
+    #[test]
+    fn hints_cover_excluded_and_lowercase_chars() {
+        for (c, reason) in [
+            ('O', Ambiguity::LooksLikeZero),
+            ('o', Ambiguity::LooksLikeZero),
+            ('0', Ambiguity::LooksLikeLetterO),
+            ('I', Ambiguity::LooksLikeOne),
+            ('l', Ambiguity::LooksLikeOne),
+            ('1', Ambiguity::LooksLikeLetterI),
+        ] {
+            assert_eq!(ParseHint::for_char(c), Some(ParseHint::ExcludedAmbiguous { got: c, reason }));
+        }
+        assert_eq!(
+            ParseHint::for_char('k'),
+            Some(ParseHint::Lowercase { got: 'k', suggestion: VDChar::<Default31>::new('K').unwrap() })
+        );
+        assert_eq!(ParseHint::for_char('!'), None);
+        assert_eq!(ParseHint::for_char('É'), None);
+
+        let errors = VDString::<Default31>::parse_all_errors("A1!").unwrap_err();
+        assert!(errors[0].hint().is_some());
+        assert!(errors[1].hint().is_none());
+    }
+
+    #[test]
+    fn lenient_folds_homoglyphs_but_not_ambiguous_chars() {
+        let lenient = ParseOptions::lenient();
+        assert_eq!(&*VDString::<Default31>::parse_with("Ａb-Р7", lenient).unwrap(), "ABP7");
+        assert_eq!(VDString::<Default31>::parse_with("ab-o7", lenient), Err(VDStringError::InvalidChar { ch: 'o', offset: 3 }));
+    }
+
+    #[cfg(feature = "nfkc")]
+    #[test]
+    fn nfkc_normalizes_compatibility_forms() {
+        let nfkc = ParseOptions { normalize_nfkc: true, ..Default::default() };
+        assert_eq!(&*VDString::<Default31>::parse_with("ＡＢ２９", nfkc).unwrap(), "AB29");
+        assert_eq!(&*VDString::<Default31>::parse_with("⑦𝐗²", nfkc).unwrap(), "7X2");
+        assert_eq!(VDString::<Default31>::parse_with("ａ", nfkc), Err(VDStringError::InvalidChar { ch: 'a', offset: 0 }));
+
+        let lenient = ParseOptions::lenient();
+        assert_eq!(&*VDString::<Default31>::parse_with("ａｂ－２９", lenient).unwrap(), "AB29");
+    }
+
+    #[test]
+    fn ocr_corrects_misreads_and_flags_doubtful_s_and_5() {
+        let scan = VDString::<Default31>::parse_from_ocr("o5H-|S9").unwrap();
+        assert_eq!(scan.code, "D5H7S9");
+        let flagged: Vec<_> = scan.uncertain.iter().map(|u| (u.position, u.read, u.candidates)).collect();
+        assert_eq!(flagged, [(0, 'o', "DQ"), (1, '5', "5S"), (3, '|', "7TJ"), (4, 'S', "S5")]);
+
+        assert_eq!(VDString::<Default31>::parse_from_ocr("2S").unwrap().uncertain[0].position, 1);
+        assert!(VDString::<Default31>::parse_from_ocr("S").unwrap().is_certain());
+        assert!(VDString::<Default31>::parse_from_ocr("AS9").unwrap().is_certain());
+        assert_eq!(VDString::<Default31>::parse_from_ocr("K7€"), Err(VDStringError::InvalidChar { ch: '€', offset: 2 }));
+    }
+
+    #[test]
+    fn extracts_whole_tokens_with_spans() {
+        let text = "Réf: AB29XY7K, HELLO K7M3\nCODE: 2345-6789 ÉTÉ xAB29";
+        let found: Vec<_> = VDString::<Default31>::extract_all(text, 4).map(|(span, code)| (&text[span], code)).collect();
+        let expected = ["AB29XY7K", "K7M3", "2345", "6789"].map(|s| (s, s.parse::<VDString<Default31>>().unwrap()));
+        assert_eq!(found, expected);
+
+        assert_eq!(VDString::<Default31>::extract_all(text, 5).count(), 1);
+        assert_eq!(VDString::<Default31>::extract_all("", 0).count(), 0);
+        assert_eq!(VDString::<Default31>::extract_all("A B", 1).map(|(span, _)| span).collect::<Vec<_>>(), [0..1, 2..3]);
+    }
+}
@@ -0,0 +1,242 @@
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::alphabet::{Alphabet, DefaultAlphabet};
+use crate::{uniform_index, VDChar, VDGenerator, VDRng, VDStr, VDString};
+
+/// Error returned when parsing a generation pattern.
+///
+/// This type is only available when the `generate` feature is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    /// The pattern has no placeholders, so it would generate nothing random.
+    NoPlaceholders,
+    /// The pattern ends with an unfinished `\` escape.
+    TrailingEscape,
+    /// The alphabet has no characters of the class a placeholder asks for.
+    EmptyClass(char),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PatternError::NoPlaceholders => f.write_str("pattern has no placeholders"),
+            PatternError::TrailingEscape => f.write_str("pattern ends with an unfinished escape"),
+            PatternError::EmptyClass(c) => {
+                write!(f, "alphabet has no characters for placeholder {:?}", c)
+            }
+        }
+    }
+}
+
+impl core::error::Error for PatternError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Letter,
+    Digit,
+    Any,
+    Literal(char),
+}
+
+/// Generates codes following a fixed letter/digit layout such as `LLDD-LLDD`.
+///
+/// Pattern syntax:
+/// - `L`: a random letter
+/// - `D`: a random digit
+/// - `*`: any random character
+/// - `\c`: the literal character `c`
+/// - anything else: a literal, e.g. a `-` separator
+///
+/// Literals appear only in [`generate_formatted`](Self::generate_formatted)
+/// and [`format`](Self::format); the generated [`VDString`] holds just the
+/// random characters.
+///
+/// This type is only available when the `generate` feature is enabled.
+///
+/// # Examples
+/// ```
+/// use vds::{StepRng, VDGenerator};
+///
+/// let gen = VDGenerator::pattern("LLDD-LLDD").unwrap();
+/// let mut rng = StepRng::new(0, 1);
+///
+/// let code = gen.generate(&mut rng);
+/// assert_eq!(&*code, "AB45EF89");
+/// assert_eq!(gen.format(&code).unwrap(), "AB45-EF89");
+/// assert_eq!(gen.generate_formatted(&mut rng), "JK45-PQ89");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VDPatternGenerator<A: Alphabet = DefaultAlphabet> {
+    slots: Vec<Slot>,
+    letters: Vec<u8>,
+    digits: Vec<u8>,
+    alphabet: PhantomData<A>,
+}
+
+impl VDGenerator {
+    /// Parses a pattern for fixed-layout generation over the
+    /// [`DefaultAlphabet`](crate::alphabet::DefaultAlphabet).
+    ///
+    /// See [`VDPatternGenerator`] for the syntax.
+    ///
+    /// # Errors
+    /// - [`PatternError::NoPlaceholders`] if the pattern has no `L`, `D`, or `*`
+    /// - [`PatternError::TrailingEscape`] if it ends with a lone `\`
+    pub fn pattern(pattern: &str) -> Result<VDPatternGenerator, PatternError> {
+        VDPatternGenerator::from_pattern(pattern)
+    }
+}
+
+impl<A: Alphabet> VDPatternGenerator<A> {
+    /// Parses a pattern for fixed-layout generation over alphabet `A`.
+    ///
+    /// # Errors
+    /// Same as [`VDGenerator::pattern`], plus [`PatternError::EmptyClass`]
+    /// if the pattern uses `L` or `D` and `A` has no letters or digits.
+    pub fn from_pattern(pattern: &str) -> Result<Self, PatternError> {
+        let mut slots = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            slots.push(match c {
+                'L' => Slot::Letter,
+                'D' => Slot::Digit,
+                '*' => Slot::Any,
+                '\\' => Slot::Literal(chars.next().ok_or(PatternError::TrailingEscape)?),
+                _ => Slot::Literal(c),
+            });
+        }
+
+        let (letters, digits): (Vec<u8>, Vec<u8>) = (0..A::CHARS.len() as u8)
+            .partition(|&i| VDChar::<A>::from_index_unchecked(i).is_letter());
+
+        if slots.iter().all(|s| matches!(s, Slot::Literal(_))) {
+            return Err(PatternError::NoPlaceholders);
+        }
+        if letters.is_empty() && slots.contains(&Slot::Letter) {
+            return Err(PatternError::EmptyClass('L'));
+        }
+        if digits.is_empty() && slots.contains(&Slot::Digit) {
+            return Err(PatternError::EmptyClass('D'));
+        }
+
+        Ok(Self { slots, letters, digits, alphabet: PhantomData })
+    }
+
+    /// Returns the number of random characters in each code.
+    pub fn code_len(&self) -> usize {
+        self.slots.iter().filter(|s| !matches!(s, Slot::Literal(_))).count()
+    }
+
+    /// Generates the random characters of a code, without literals.
+    pub fn generate<R: VDRng + ?Sized>(&self, rng: &mut R) -> VDString<A> {
+        self.slots
+            .iter()
+            .filter_map(|slot| {
+                let pick = |set: &[u8], rng: &mut R| set[uniform_index(rng, set.len() as u32) as usize];
+                let index = match slot {
+                    Slot::Letter => pick(&self.letters, rng),
+                    Slot::Digit => pick(&self.digits, rng),
+                    Slot::Any => uniform_index(rng, A::CHARS.len() as u32) as u8,
+                    Slot::Literal(_) => return None,
+                };
+                Some(VDChar::from_index_unchecked(index))
+            })
+            .collect()
+    }
+
+    /// Generates a code and formats it with the pattern's literals.
+    pub fn generate_formatted<R: VDRng + ?Sized>(&self, rng: &mut R) -> String {
+        let code = self.generate(rng);
+        self.format(&code).unwrap_or_default()
+    }
+
+    /// Interleaves `code` with the pattern's literals.
+    ///
+    /// Returns `None` if `code` does not fit the pattern, i.e. has the wrong
+    /// length or a letter where a digit is expected (or vice versa).
+    pub fn format(&self, code: &VDStr<A>) -> Option<String> {
+        if code.len() != self.code_len() {
+            return None;
+        }
+        let mut chars = code.iter();
+        let mut out = String::with_capacity(self.slots.len());
+        for slot in &self.slots {
+            let ch = match *slot {
+                Slot::Literal(c) => {
+                    out.push(c);
+                    continue;
+                }
+                Slot::Letter => chars.next().filter(|c| c.is_letter())?,
+                Slot::Digit => chars.next().filter(|c| c.is_digit())?,
+                Slot::Any => chars.next()?,
+            };
+            out.push(ch.as_char());
+        }
+        Some(out)
+    }
+
+    /// Returns the bits of entropy of codes from this pattern.
+    pub fn entropy_bits(&self) -> f64 {
+        let bits = |n: usize| crate::generate::log2(n as f64);
+        self.slots
+            .iter()
+            .map(|slot| match slot {
+                Slot::Letter => bits(self.letters.len()),
+                Slot::Digit => bits(self.digits.len()),
+                Slot::Any => bits(A::CHARS.len()),
+                Slot::Literal(_) => 0.0,
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::{Default31, Digits10};
+    use rand::SeedableRng;
+
+    #[test]
+    fn generated_codes_follow_layout() {
+        let gen = VDPatternGenerator::<Default31>::from_pattern("LLDD-LLDD").unwrap();
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(5);
+        for _ in 0..200 {
+            let formatted = gen.generate_formatted(&mut rng);
+            let classes: String = formatted
+                .chars()
+                .map(|c| match VDChar::<Default31>::new(c) {
+                    Some(ch) if ch.is_letter() => 'L',
+                    Some(_) => 'D',
+                    None => c,
+                })
+                .collect();
+            assert_eq!(classes, "LLDD-LLDD");
+        }
+        assert_eq!(gen.code_len(), 8);
+        assert!((gen.entropy_bits() - (4.0 * crate::generate::log2(23.0) + 12.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn escapes_and_any_slots() {
+        let gen = VDPatternGenerator::<Default31>::from_pattern(r"\L-**\\").unwrap();
+        let code: VDString<Default31> = "Z7".parse().unwrap();
+        assert_eq!(gen.format(&code).unwrap(), r"L-Z7\");
+        assert_eq!(gen.format(&code[..1]), None);
+
+        let layout = VDPatternGenerator::<Default31>::from_pattern("LD").unwrap();
+        assert_eq!(layout.format(&code), Some("Z7".into()));
+        assert_eq!(layout.format(&"7Z".parse::<VDString<Default31>>().unwrap()), None);
+    }
+
+    #[test]
+    fn rejects_bad_patterns() {
+        assert_eq!(VDGenerator::pattern("--"), Err(PatternError::NoPlaceholders));
+        assert_eq!(VDGenerator::pattern(r"LL\"), Err(PatternError::TrailingEscape));
+        assert_eq!(VDPatternGenerator::<Digits10>::from_pattern("DL"), Err(PatternError::EmptyClass('L')));
+        assert!(VDPatternGenerator::<Digits10>::from_pattern("DD*").is_ok());
+    }
+}
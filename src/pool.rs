@@ -0,0 +1,235 @@
+extern crate alloc;
+use alloc::collections::btree_set::{self, BTreeSet};
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::alphabet::{Alphabet, DefaultAlphabet};
+use crate::{VDChar, VDGenerator, VDGeneratorError, VDRng, VDStr, VDString};
+
+/// Error returned by [`VDPool::next_unique`].
+///
+/// This type is only available when the `generate` feature is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VDPoolError {
+    /// The generator configuration is invalid.
+    Generator(VDGeneratorError),
+    /// Every attempt produced a code that was already issued.
+    Exhausted {
+        /// The number of codes generated before giving up.
+        attempts: usize,
+    },
+}
+
+impl fmt::Display for VDPoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VDPoolError::Generator(_) => f.write_str("invalid generator configuration"),
+            VDPoolError::Exhausted { attempts } => {
+                write!(f, "all {} generated codes were already issued", attempts)
+            }
+        }
+    }
+}
+
+impl core::error::Error for VDPoolError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            VDPoolError::Generator(err) => Some(err),
+            VDPoolError::Exhausted { .. } => None,
+        }
+    }
+}
+
+impl From<VDGeneratorError> for VDPoolError {
+    fn from(e: VDGeneratorError) -> Self {
+        Self::Generator(e)
+    }
+}
+
+/// A [`VDGenerator`] that remembers every code it has issued and never
+/// issues one twice.
+///
+/// The issued set can be seeded from storage with [`insert`](Self::insert)
+/// or `Extend`, and exported with [`iter`](Self::iter), so uniqueness
+/// survives restarts. It is kept in a `BTreeSet`, which needs no hasher and
+/// so works without `std`.
+///
+/// This type is only available when the `generate` feature is enabled.
+///
+/// # Examples
+/// ```
+/// use vds::{StepRng, VDGenerator, VDPool, VDString};
+///
+/// let mut pool = VDPool::new(VDGenerator::new().length(4));
+/// pool.insert(&"ABCD".parse::<VDString>().unwrap());
+///
+/// // StepRng(0, 1) would yield "ABCD" first; the pool redraws it.
+/// let mut rng = StepRng::new(0, 1);
+/// let code = pool.next_unique(&mut rng).unwrap();
+/// assert_eq!(&*code, "EFGH");
+/// assert_eq!(pool.len(), 2);
+/// ```
+pub struct VDPool<A: Alphabet = DefaultAlphabet> {
+    generator: VDGenerator<A>,
+    // Codes are keyed by alphabet index, so no ordering on `VDString` is needed.
+    issued: BTreeSet<Vec<u8>>,
+    max_retries: usize,
+}
+
+impl<A: Alphabet> VDPool<A> {
+    /// Creates an empty pool issuing codes from `generator`.
+    ///
+    /// Defaults:
+    /// - Max retries: 100
+    pub fn new(generator: VDGenerator<A>) -> Self {
+        Self { generator, issued: BTreeSet::new(), max_retries: 100 }
+    }
+
+    /// Sets how many duplicate codes [`next_unique`](Self::next_unique) may
+    /// redraw before giving up.
+    pub fn max_retries(mut self, retries: usize) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Generates a code that has not been issued before, and records it.
+    ///
+    /// # Errors
+    /// - [`VDPoolError::Generator`] if the generator configuration is invalid.
+    /// - [`VDPoolError::Exhausted`] if `max_retries + 1` attempts all produced
+    ///   already-issued codes, which usually means the keyspace is nearly full.
+    pub fn next_unique<R: VDRng + ?Sized>(&mut self, rng: &mut R) -> Result<VDString<A>, VDPoolError> {
+        for _ in 0..=self.max_retries {
+            let code = self.generator.generate(rng)?;
+            if self.issued.insert(key(&code)) {
+                return Ok(code);
+            }
+        }
+        Err(VDPoolError::Exhausted { attempts: self.max_retries.saturating_add(1) })
+    }
+
+    /// Records `code` as issued, returning `true` if it was not already.
+    pub fn insert(&mut self, code: &VDStr<A>) -> bool {
+        self.issued.insert(key(code))
+    }
+
+    /// Returns `true` if `code` has been issued.
+    pub fn contains(&self, code: &VDStr<A>) -> bool {
+        self.issued.contains(&key(code))
+    }
+
+    /// Returns the number of issued codes.
+    pub fn len(&self) -> usize {
+        self.issued.len()
+    }
+
+    /// Returns `true` if no codes have been issued.
+    pub fn is_empty(&self) -> bool {
+        self.issued.is_empty()
+    }
+
+    /// Returns an iterator over the issued codes, e.g. for persisting them.
+    ///
+    /// Codes are yielded in alphabet-index order.
+    pub fn iter(&self) -> VDPoolIter<'_, A> {
+        VDPoolIter { inner: self.issued.iter(), alphabet: core::marker::PhantomData }
+    }
+}
+
+impl<'a, A: Alphabet> Extend<&'a VDStr<A>> for VDPool<A> {
+    fn extend<I: IntoIterator<Item = &'a VDStr<A>>>(&mut self, iter: I) {
+        self.issued.extend(iter.into_iter().map(key));
+    }
+}
+
+impl<A: Alphabet> Extend<VDString<A>> for VDPool<A> {
+    fn extend<I: IntoIterator<Item = VDString<A>>>(&mut self, iter: I) {
+        self.issued.extend(iter.into_iter().map(|code| key(&code)));
+    }
+}
+
+/// Iterator over the codes in a [`VDPool`], returned by [`VDPool::iter`].
+pub struct VDPoolIter<'a, A: Alphabet = DefaultAlphabet> {
+    inner: btree_set::Iter<'a, Vec<u8>>,
+    alphabet: core::marker::PhantomData<A>,
+}
+
+impl<A: Alphabet> Iterator for VDPoolIter<'_, A> {
+    type Item = VDString<A>;
+
+    fn next(&mut self) -> Option<VDString<A>> {
+        self.inner
+            .next()
+            .map(|indices| indices.iter().map(|&i| VDChar::from_index_unchecked(i)).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<A: Alphabet> ExactSizeIterator for VDPoolIter<'_, A> {}
+
+fn key<A: Alphabet>(code: &VDStr<A>) -> Vec<u8> {
+    code.iter().map(VDChar::index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Default31;
+    use crate::StepRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn never_issues_twice() {
+        let mut pool = VDPool::new(VDGenerator::<Default31>::default().length(2));
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(9);
+        for _ in 0..500 {
+            pool.next_unique(&mut rng).unwrap();
+        }
+        assert_eq!(pool.len(), 500);
+        assert_eq!(pool.iter().len(), 500);
+    }
+
+    #[test]
+    fn gives_up_after_max_retries() {
+        let mut pool = VDPool::new(VDGenerator::<Default31>::default().length(3)).max_retries(2);
+        let mut rng = StepRng::new(5, 0);
+        let first = pool.next_unique(&mut rng).unwrap();
+        assert!(pool.contains(&first));
+        assert_eq!(pool.next_unique(&mut rng), Err(VDPoolError::Exhausted { attempts: 3 }));
+
+        let mut bad = VDPool::new(VDGenerator::<Default31>::default().length(40).no_repeats());
+        assert!(matches!(bad.next_unique(&mut rng), Err(VDPoolError::Generator(_))));
+    }
+
+    #[test]
+    fn generator_error_is_the_source() {
+        use alloc::string::ToString;
+        use core::error::Error;
+
+        let err = VDPool::new(VDGenerator::<Default31>::default().length(40).no_repeats())
+            .next_unique(&mut StepRng::new(0, 1))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "invalid generator configuration");
+        let source = err.source().unwrap().downcast_ref::<VDGeneratorError>();
+        assert_eq!(source, Some(&VDGeneratorError::LengthExceedsUniqueSet { requested: 40, available: 31 }));
+    }
+
+    #[test]
+    fn exports_and_imports_issued_set() {
+        let mut pool = VDPool::new(VDGenerator::<Default31>::default());
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        for _ in 0..10 {
+            pool.next_unique(&mut rng).unwrap();
+        }
+
+        let mut restored = VDPool::new(VDGenerator::<Default31>::default());
+        restored.extend(pool.iter());
+        assert_eq!(restored.len(), 10);
+        assert!(pool.iter().all(|code| restored.contains(&code)));
+        assert!(!restored.insert(&pool.iter().next().unwrap()));
+    }
+}
@@ -0,0 +1,37 @@
+//! Built-in blocklist for [`VDGenerator::deny_profanity`](crate::VDGenerator::deny_profanity).
+
+/// Offensive English words and common spellings of them that can be written
+/// with [`VDS_ALLOWED`](crate::VDS_ALLOWED).
+///
+/// The alphabet has no `I`, `L`, or `O`, so many words cannot appear at all;
+/// this list covers the ones that can, including vowel-dropped and
+/// digit-substituted variants (`FVCK`, `FU2K`, `5HT`). It is deliberately
+/// short and aimed at codes shown to customers, not at general text
+/// moderation.
+///
+/// This constant is only available when the `profanity` feature is enabled.
+pub const PROFANITY: &[&str] = &[
+    // Variants of one word
+    "FUCK", "FUK", "FUC", "FUQ", "FVCK", "FVK", "FCK", "FAK", "FU2K", "PHUK", "PHUCK",
+    "STFU", "GFY", "WTF",
+    // Others
+    "CUNT", "KUNT", "CNT", "TWAT", "TWT", "WANK", "WNK", "PRCK", "DCK", "DYK", "DYKE",
+    "SHT", "SH7", "5HT", "SHAT", "CRAP", "CUM", "JZZ", "JAP", "SPAZ", "SPZ",
+    "ASS", "A55", "AZZ", "ARSE", "TURD", "NAZ", "KKK", "SEX", "SXY", "XXX", "PUSSY", "PU55Y",
+    "BTCH", "8TCH", "BASTARD", "FAG", "FAGG", "FGT", "NGGR", "NGR", "N66R", "NEGR", "TRANNY",
+    "RAPE", "SKANK", "WHR", "HAG", "DAMN",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VDChar;
+
+    #[test]
+    fn entries_are_expressible() {
+        for word in PROFANITY {
+            assert!(word.len() >= 3, "{}", word);
+            assert!(word.chars().all(|c| VDChar::new(c).is_some()), "{}", word);
+        }
+    }
+}
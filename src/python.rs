@@ -0,0 +1,209 @@
+//! A Python extension module exposing parsing, formatting, distances, and
+//! generation, so Python code applies exactly the library's rules.
+//!
+//! This module is only available when the `python` feature is enabled.
+//! Like [`ffi`](crate::ffi), it is built into an extension through a small
+//! `cdylib` crate that depends on `vds` with this feature and enables
+//! `pyo3/extension-module`; the `PyInit_vds` symbol needs no re-export.
+//! Build that crate with `maturin` to get an importable `vds` module:
+//!
+//! ```python
+//! import vds
+//!
+//! code = vds.VDString(" ab29 xy ", lenient=True)
+//! assert str(code) == "AB29XY"
+//! assert code.grouped(3) == "AB2-9XY"
+//! assert vds.is_valid("AB29") and not vds.is_valid("AB20")
+//!
+//! gen = vds.VDGenerator(length=10, no_adjacent_repeats=True)
+//! assert len(gen.generate()) == 10
+//! ```
+//!
+//! Invalid input and unsatisfiable generator settings raise `ValueError`
+//! with the same message as the Rust error.
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::alphabet::Default31;
+use crate::distance::{KeyboardModel, PhoneticModel, VisualModel};
+use crate::rng::OsEntropy;
+use crate::{validate, ParseOptions, VDGenerator, VDGeneratorError, VDString};
+
+fn value_error(err: impl ToString) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// A validated code of visibly distinguishable characters.
+#[pyclass(name = "VDString", module = "vds", frozen, eq, ord, hash)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct PyVDString(VDString<Default31>);
+
+#[pymethods]
+impl PyVDString {
+    /// Parses `value` strictly, or with `lenient=True` as typed by people:
+    /// case, separators, and look-alike characters are folded.
+    #[new]
+    #[pyo3(signature = (value, lenient = false))]
+    fn new(value: &str, lenient: bool) -> PyResult<Self> {
+        let options = if lenient { ParseOptions::lenient() } else { ParseOptions::default() };
+        VDString::parse_with(value, options).map(Self).map_err(value_error)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        // Codes never contain quotes or backslashes, so this is valid Python.
+        std::format!("VDString('{}')", self.0)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Formats the code in groups of `size` characters joined by
+    /// `separator`; a `size` of `0` disables grouping.
+    #[pyo3(signature = (size, separator = "-"))]
+    fn grouped(&self, size: usize, separator: &str) -> String {
+        self.0.display_grouped(size, separator).to_string()
+    }
+
+    /// The number of positions at which the codes differ, or `None` if
+    /// their lengths differ.
+    fn hamming_distance(&self, other: &Self) -> Option<usize> {
+        self.0.hamming_distance(&other.0)
+    }
+
+    /// An edit distance in which easily confused characters cost less.
+    ///
+    /// `model` is the intake channel: `"visual"`, `"keyboard"`, or
+    /// `"phonetic"`.
+    #[pyo3(signature = (other, model = "visual"))]
+    fn confusable_distance(&self, other: &Self, model: &str) -> PyResult<f64> {
+        match model {
+            "visual" => Ok(self.0.confusable_distance(&other.0, &VisualModel)),
+            "keyboard" => Ok(self.0.confusable_distance(&other.0, &KeyboardModel)),
+            "phonetic" => Ok(self.0.confusable_distance(&other.0, &PhoneticModel)),
+            _ => Err(PyValueError::new_err(std::format!("unknown error model {:?}", model))),
+        }
+    }
+}
+
+/// A generator of random codes, drawing from the operating system's RNG.
+#[pyclass(name = "VDGenerator", module = "vds", frozen)]
+struct PyVDGenerator(VDGenerator<Default31>);
+
+#[pymethods]
+impl PyVDGenerator {
+    #[new]
+    #[pyo3(signature = (
+        length = 8,
+        *,
+        no_repeats = false,
+        no_adjacent_repeats = false,
+        prefix = None,
+        suffix = None,
+    ))]
+    fn new(
+        length: usize,
+        no_repeats: bool,
+        no_adjacent_repeats: bool,
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+    ) -> PyResult<Self> {
+        let mut gen = VDGenerator::<Default31>::default().length(length);
+        if no_repeats {
+            gen = gen.no_repeats();
+        }
+        if no_adjacent_repeats {
+            gen = gen.no_adjacent_repeats();
+        }
+        if let Some(prefix) = prefix {
+            gen = gen.prefix(&prefix.parse::<VDString<Default31>>().map_err(value_error)?);
+        }
+        if let Some(suffix) = suffix {
+            gen = gen.suffix(&suffix.parse::<VDString<Default31>>().map_err(value_error)?);
+        }
+        Ok(Self(gen))
+    }
+
+    /// Generates one code.
+    fn generate(&self) -> PyResult<PyVDString> {
+        self.0.generate_with_os_rng().map(PyVDString).map_err(value_error)
+    }
+
+    /// Generates `count` distinct codes.
+    fn generate_many(&self, count: usize) -> PyResult<Vec<PyVDString>> {
+        let mut rng = OsEntropy::default();
+        let codes = self.0.generate_many(count, &mut rng);
+        if rng.failed {
+            return Err(value_error(VDGeneratorError::EntropyUnavailable));
+        }
+        Ok(codes.map_err(value_error)?.into_iter().map(PyVDString).collect())
+    }
+
+    /// The entropy of one code, in bits.
+    fn entropy_bits(&self) -> f64 {
+        self.0.entropy_bits()
+    }
+}
+
+/// Returns `True` if every character of `value` is allowed.
+#[pyfunction]
+fn is_valid(value: &str) -> bool {
+    validate::is_valid(value.as_bytes())
+}
+
+/// The `vds` Python module.
+#[pymodule]
+fn vds(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyVDString>()?;
+    m.add_class::<PyVDGenerator>()?;
+    m.add_function(wrap_pyfunction!(is_valid, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    #[test]
+    fn runs_from_python() {
+        Python::initialize();
+        Python::attach(|py| {
+            let globals = PyDict::new(py);
+            globals.set_item("vds", pyo3::wrap_pymodule!(vds)(py)).unwrap();
+            let script = c"
+code = vds.VDString(' ab29 xy ', lenient=True)
+assert str(code) == 'AB29XY' and len(code) == 6 and repr(code) == \"VDString('AB29XY')\"
+assert code.grouped(3) == 'AB2-9XY' and code.grouped(2, ' ') == 'AB 29 XY'
+assert code == vds.VDString('AB29XY') and len({code, vds.VDString('AB29XY')}) == 1
+assert vds.VDString('A') < vds.VDString('B')
+assert code.hamming_distance(vds.VDString('AB29XZ')) == 1
+assert code.confusable_distance(vds.VDString('A829XY')) < 1.0
+assert vds.is_valid('AB29') and not vds.is_valid('AB20')
+
+gen = vds.VDGenerator(length=10, no_adjacent_repeats=True, prefix='VXP')
+codes = gen.generate_many(3)
+assert all(len(c) == 10 and str(c).startswith('VXP') for c in codes)
+assert len(set(vds.VDGenerator(length=1).generate_many(31))) == 31
+
+for bad in [lambda: vds.VDString('AB20'), lambda: vds.VDGenerator(length=40, no_repeats=True).generate(),
+            lambda: code.confusable_distance(code, model='braille'),
+            lambda: vds.VDGenerator(length=1).generate_many(32)]:
+    try:
+        bad()
+        raise AssertionError('no ValueError')
+    except ValueError:
+        pass
+";
+            py.run(script, Some(&globals), None).unwrap();
+        });
+    }
+}
@@ -0,0 +1,260 @@
+//! Helpers for printing codes as QR codes in alphanumeric mode.
+//!
+//! QR alphanumeric mode packs two characters into 11 bits, against 16 bits
+//! in byte mode. It covers `0`–`9`, `A`–`Z`, and nine symbols, so every
+//! character of the built-in alphabets except lowercase can use it.
+//!
+//! [`VDStr::qr_alphanumeric_values`] yields the values a QR encoder writes
+//! to the data stream, and [`min_version`] picks the smallest symbol that
+//! holds a code of a given length.
+//!
+//! # Examples
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use vds::qr::{self, EcLevel};
+//! use vds::VDString;
+//!
+//! let code: VDString = "AB29XY7KM3PQ".parse().unwrap();
+//! assert!(code.is_qr_alphanumeric());
+//! assert_eq!(qr::min_version(code.len(), EcLevel::M), Some(1));
+//! assert_eq!(qr::min_version(40, EcLevel::H), Some(4));
+//! # }
+//! ```
+
+use crate::alphabet::Alphabet;
+use crate::{VDChar, VDStr};
+
+/// Symbols in QR alphanumeric mode after `0`–`9` and `A`–`Z`, in value order.
+const SYMBOLS: &str = " $%*+-./:";
+
+/// Data codewords per version (1–40) for error correction levels L, M, Q, H.
+const DATA_CODEWORDS: [[u16; 4]; 40] = [
+    [19, 16, 13, 9], [34, 28, 22, 16], [55, 44, 34, 26], [80, 64, 48, 36],
+    [108, 86, 62, 46], [136, 108, 76, 60], [156, 124, 88, 66], [194, 154, 110, 86],
+    [232, 182, 132, 100], [274, 216, 154, 122], [324, 254, 180, 140], [370, 290, 206, 158],
+    [428, 334, 244, 180], [461, 365, 261, 197], [523, 415, 295, 223], [589, 453, 325, 253],
+    [647, 507, 367, 283], [721, 563, 397, 313], [795, 627, 445, 341], [861, 669, 485, 385],
+    [932, 714, 512, 406], [1006, 782, 568, 442], [1094, 860, 614, 464], [1174, 914, 664, 514],
+    [1276, 1000, 718, 538], [1370, 1062, 754, 596], [1468, 1128, 808, 628], [1531, 1193, 871, 661],
+    [1631, 1267, 911, 701], [1735, 1373, 985, 745], [1843, 1455, 1033, 793], [1955, 1541, 1115, 845],
+    [2071, 1631, 1171, 901], [2191, 1725, 1231, 961], [2306, 1812, 1286, 986], [2434, 1914, 1354, 1054],
+    [2566, 1992, 1426, 1096], [2702, 2102, 1502, 1142], [2812, 2216, 1582, 1222], [2956, 2334, 1666, 1276],
+];
+
+/// QR error correction level, from lowest (`L`, about 7% recoverable) to
+/// highest (`H`, about 30%).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EcLevel {
+    /// Recovers about 7% of codewords.
+    L,
+    /// Recovers about 15% of codewords.
+    M,
+    /// Recovers about 25% of codewords.
+    Q,
+    /// Recovers about 30% of codewords.
+    H,
+}
+
+/// Returns the QR alphanumeric-mode value of `c` (`0`–`44`), or `None` if
+/// alphanumeric mode cannot encode it.
+///
+/// # Examples
+/// ```
+/// use vds::qr::alphanumeric_value;
+///
+/// assert_eq!(alphanumeric_value('7'), Some(7));
+/// assert_eq!(alphanumeric_value('K'), Some(20));
+/// assert_eq!(alphanumeric_value('k'), None);
+/// ```
+pub fn alphanumeric_value(c: char) -> Option<u8> {
+    match c {
+        '0'..='9' => Some(c as u8 - b'0'),
+        'A'..='Z' => Some(c as u8 - b'A' + 10),
+        _ => SYMBOLS.find(c).map(|i| 36 + i as u8),
+    }
+}
+
+/// Returns the number of bits in the alphanumeric-mode segment for `len`
+/// characters in a symbol of `version`, including the mode indicator and
+/// character count.
+///
+/// Returns `None` if `version` is not in `1..=40` or `len` does not fit in
+/// the character count field.
+pub fn alphanumeric_bit_len(len: usize, version: u8) -> Option<usize> {
+    let count_bits = match version {
+        1..=9 => 9,
+        10..=26 => 11,
+        27..=40 => 13,
+        _ => return None,
+    };
+    if len >= 1 << count_bits {
+        return None;
+    }
+    Some(4 + count_bits + 11 * (len / 2) + 6 * (len % 2))
+}
+
+/// Returns the number of alphanumeric characters a symbol of `version`
+/// holds at error correction level `ec`, or `None` if `version` is not in
+/// `1..=40`.
+///
+/// # Examples
+/// ```
+/// use vds::qr::{capacity, EcLevel};
+///
+/// assert_eq!(capacity(1, EcLevel::L), Some(25));
+/// assert_eq!(capacity(40, EcLevel::H), Some(1852));
+/// ```
+pub fn capacity(version: u8, ec: EcLevel) -> Option<usize> {
+    let bits = data_bits(version, ec)?;
+    let header = alphanumeric_bit_len(0, version)?;
+    let pairs = (bits - header) / 11;
+    let single = usize::from((bits - header) % 11 >= 6);
+    Some(2 * pairs + single)
+}
+
+/// Returns the smallest QR version (`1`–`40`) that holds `len` characters in
+/// alphanumeric mode at error correction level `ec`, or `None` if even
+/// version 40 is too small.
+pub fn min_version(len: usize, ec: EcLevel) -> Option<u8> {
+    (1..=40).find(|&v| {
+        matches!((alphanumeric_bit_len(len, v), data_bits(v, ec)), (Some(n), Some(max)) if n <= max)
+    })
+}
+
+/// Returns the number of data bits in a symbol, or `None` for an invalid
+/// version.
+fn data_bits(version: u8, ec: EcLevel) -> Option<usize> {
+    let row = DATA_CODEWORDS.get(usize::from(version).checked_sub(1)?)?;
+    Some(usize::from(row[ec as usize]) * 8)
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns `true` if every character can be written in QR alphanumeric
+    /// mode.
+    ///
+    /// Always `true` for the built-in uppercase alphabets.
+    pub fn is_qr_alphanumeric(&self) -> bool {
+        self.iter().all(|c| alphanumeric_value(c.as_char()).is_some())
+    }
+
+    /// Returns the QR alphanumeric-mode data values, or `None` if
+    /// [`is_qr_alphanumeric`](Self::is_qr_alphanumeric) is `false`.
+    ///
+    /// Each pair of characters `(a, b)` yields `45 * a + b`, written in 11
+    /// bits; an odd final character yields its own value, written in 6 bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AC7".parse().unwrap();
+    /// let values: Vec<u16> = code.qr_alphanumeric_values().unwrap().collect();
+    /// assert_eq!(values, [45 * 10 + 12, 7]);
+    /// # }
+    /// ```
+    pub fn qr_alphanumeric_values(&self) -> Option<QrAlphanumericValues<'_, A>> {
+        self.is_qr_alphanumeric().then(|| QrAlphanumericValues { chars: self.as_vdchars() })
+    }
+}
+
+/// Iterator over the QR alphanumeric-mode values of a [`VDStr`].
+///
+/// Returned by [`VDStr::qr_alphanumeric_values`].
+#[derive(Debug, Clone)]
+pub struct QrAlphanumericValues<'a, A: Alphabet> {
+    chars: &'a [VDChar<A>],
+}
+
+impl<A: Alphabet> Iterator for QrAlphanumericValues<'_, A> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        let value = |c: &VDChar<A>| u16::from(alphanumeric_value(c.as_char()).unwrap_or(0));
+        match *self.chars {
+            [] => None,
+            [ref a] => {
+                self.chars = &[];
+                Some(value(a))
+            }
+            [ref a, ref b, ..] => {
+                self.chars = &self.chars[2..];
+                Some(45 * value(a) + value(b))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.chars.len().div_ceil(2);
+        (n, Some(n))
+    }
+}
+
+impl<A: Alphabet> ExactSizeIterator for QrAlphanumericValues<'_, A> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::{Crockford32, Default31, Extended36};
+    use crate::{VDArrayString, VDS_ALLOWED};
+
+    #[test]
+    fn built_in_alphabets_are_alphanumeric() {
+        fn check<A: Alphabet>() {
+            assert!(A::CHARS.iter().all(|&c| alphanumeric_value(c).is_some()));
+        }
+        check::<Default31>();
+        check::<Crockford32>();
+        check::<Extended36>();
+        assert!(VDS_ALLOWED.iter().all(|&c| alphanumeric_value(c).is_some()));
+    }
+
+    #[test]
+    fn values_follow_the_qr_table() {
+        assert_eq!(alphanumeric_value('0'), Some(0));
+        assert_eq!(alphanumeric_value('Z'), Some(35));
+        assert_eq!(alphanumeric_value(' '), Some(36));
+        assert_eq!(alphanumeric_value(':'), Some(44));
+        assert_eq!(alphanumeric_value('#'), None);
+        assert_eq!(alphanumeric_value('É'), None);
+    }
+
+    #[test]
+    fn pairs_pack_two_characters() {
+        let code: VDArrayString<8> = "AC42".parse().unwrap();
+        let values: [u16; 2] = {
+            let mut it = code.as_vdstr().qr_alphanumeric_values().unwrap();
+            assert_eq!(it.len(), 2);
+            [it.next().unwrap(), it.next().unwrap()]
+        };
+        assert_eq!(values, [45 * 10 + 12, 45 * 4 + 2]);
+        let empty: VDArrayString<1> = VDArrayString::new();
+        assert_eq!(empty.as_vdstr().qr_alphanumeric_values().unwrap().next(), None);
+    }
+
+    #[test]
+    fn capacities_match_published_tables() {
+        let cases = [
+            (1, EcLevel::L, 25), (1, EcLevel::M, 20), (1, EcLevel::Q, 16), (1, EcLevel::H, 10),
+            (2, EcLevel::L, 47), (10, EcLevel::M, 311), (25, EcLevel::Q, 1041),
+            (40, EcLevel::L, 4296), (40, EcLevel::H, 1852),
+        ];
+        for (version, ec, chars) in cases {
+            assert_eq!(capacity(version, ec), Some(chars), "{} {:?}", version, ec);
+            assert_eq!(min_version(chars, ec), Some(version));
+            assert_ne!(min_version(chars + 1, ec), Some(version));
+        }
+        assert_eq!(capacity(0, EcLevel::L), None);
+        assert_eq!(capacity(41, EcLevel::L), None);
+        assert_eq!(min_version(4297, EcLevel::L), None);
+    }
+
+    #[test]
+    fn bit_lengths_include_header() {
+        assert_eq!(alphanumeric_bit_len(5, 1), Some(4 + 9 + 22 + 6));
+        assert_eq!(alphanumeric_bit_len(5, 10), Some(4 + 11 + 22 + 6));
+        assert_eq!(alphanumeric_bit_len(5, 27), Some(4 + 13 + 22 + 6));
+        assert_eq!(alphanumeric_bit_len(512, 9), None);
+        assert_eq!(alphanumeric_bit_len(1, 0), None);
+    }
+}
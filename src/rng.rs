@@ -0,0 +1,283 @@
+use rand_core::{impls, RngCore};
+
+/// A deterministic counter-based RNG for examples and tests.
+///
+/// Each call returns the current value and then advances it by a fixed
+/// step (wrapping on overflow). The output is trivially predictable: **never**
+/// use it for codes that must be hard to guess.
+///
+/// With the generator's index mapping, a step of `1` walks the alphabet in
+/// order, which makes expected outputs easy to write down by hand.
+///
+/// This type is only available when the `generate` feature is enabled.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::{StepRng, VDGenerator};
+///
+/// let mut rng = StepRng::new(0, 1);
+/// let code = VDGenerator::new().length(4).generate(&mut rng).unwrap();
+/// assert_eq!(&*code, "ABCD");
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepRng {
+    value: u64,
+    step: u64,
+}
+
+impl StepRng {
+    /// Creates an RNG that yields `initial`, `initial + step`, `initial + 2 * step`, ….
+    pub fn new(initial: u64, step: u64) -> Self {
+        Self { value: initial, step }
+    }
+}
+
+impl RngCore for StepRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value = self.value;
+        self.value = self.value.wrapping_add(self.step);
+        value
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dst)
+    }
+}
+
+/// A source of randomness for [`VDGenerator`](crate::VDGenerator) and the
+/// other generators in this crate.
+///
+/// Every `rand_core` 0.9 `RngCore` implements it, which covers `rand` 0.9.
+/// For an RNG from `rand_core` 0.6, as used by `rand` 0.8, enable the
+/// `rand_core_06` feature and wrap it in [`Rng06`]; the two versions cannot
+/// both be covered by blanket impls, since one type could implement both.
+///
+/// This trait is only available when the `generate` feature is enabled.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::{VDGenerator, VDRng};
+///
+/// /// Replays a fixed sequence, e.g. bytes from a hardware TRNG.
+/// struct Replay<'a>(core::slice::Iter<'a, u32>);
+///
+/// impl VDRng for Replay<'_> {
+///     fn draw_u32(&mut self) -> u32 {
+///         *self.0.next().expect("out of entropy")
+///     }
+/// }
+///
+/// let mut rng = Replay([0, 1, 2, 3].iter());
+/// assert_eq!(VDGenerator::new().length(4).generate(&mut rng).unwrap(), "ABCD");
+/// # }
+/// ```
+pub trait VDRng {
+    /// Returns the next random `u32`.
+    fn draw_u32(&mut self) -> u32;
+}
+
+impl<R: RngCore + ?Sized> VDRng for R {
+    fn draw_u32(&mut self) -> u32 {
+        self.next_u32()
+    }
+}
+
+/// Adapts an RNG from `rand_core` 0.6 (as used by `rand` 0.8) to
+/// `rand_core` 0.9, and so to [`VDRng`].
+///
+/// Cryptographically secure RNGs stay `CryptoRng`, so they also work with
+/// [`VDGenerator::generate_secure`](crate::VDGenerator::generate_secure).
+///
+/// This type is only available when the `rand_core_06` feature is enabled.
+///
+/// # Examples
+/// ```
+/// use vds::{Rng06, VDGenerator};
+///
+/// /// An RNG written against `rand_core` 0.6.
+/// struct Counter(u32);
+///
+/// impl rand_core_06::RngCore for Counter {
+///     fn next_u32(&mut self) -> u32 {
+///         self.0 += 1;
+///         self.0 - 1
+///     }
+///     fn next_u64(&mut self) -> u64 {
+///         u64::from(self.next_u32())
+///     }
+///     fn fill_bytes(&mut self, dest: &mut [u8]) {
+///         rand_core_06::impls::fill_bytes_via_next(self, dest)
+///     }
+///     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core_06::Error> {
+///         Ok(self.fill_bytes(dest))
+///     }
+/// }
+///
+/// let code = VDGenerator::new().length(4).generate(&mut Rng06(Counter(0))).unwrap();
+/// assert_eq!(code, "ABCD");
+/// ```
+#[cfg(feature = "rand_core_06")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng06<R>(pub R);
+
+#[cfg(feature = "rand_core_06")]
+impl<R: rand_core_06::RngCore> RngCore for Rng06<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.0.fill_bytes(dst)
+    }
+}
+
+#[cfg(feature = "rand_core_06")]
+impl<R: rand_core_06::CryptoRng + rand_core_06::RngCore> rand_core::CryptoRng for Rng06<R> {}
+
+/// Draws from the operating system's RNG, noting whether it ever failed.
+///
+/// Failed draws return 0, so the caller must check `failed` before using
+/// anything drawn.
+#[cfg(feature = "os-rng")]
+#[derive(Default)]
+pub(crate) struct OsEntropy {
+    pub(crate) failed: bool,
+}
+
+#[cfg(feature = "os-rng")]
+impl VDRng for OsEntropy {
+    fn draw_u32(&mut self) -> u32 {
+        use rand_core::TryRngCore;
+        rand_core::OsRng.try_next_u32().unwrap_or_else(|_| {
+            self.failed = true;
+            0
+        })
+    }
+}
+
+/// Draws little-endian `u32`s from a caller-supplied byte slice, noting
+/// whether it ran out.
+///
+/// Once exhausted, draws return 0, so the caller must check `exhausted`
+/// before using anything drawn.
+#[cfg(feature = "alloc")]
+pub(crate) struct SliceEntropy<'a> {
+    pub(crate) bytes: &'a [u8],
+    pub(crate) exhausted: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl VDRng for SliceEntropy<'_> {
+    fn draw_u32(&mut self) -> u32 {
+        match self.bytes.split_first_chunk::<4>() {
+            Some((word, rest)) => {
+                self.bytes = rest;
+                u32::from_le_bytes(*word)
+            }
+            None => {
+                self.exhausted = true;
+                0
+            }
+        }
+    }
+}
+
+/// Draws a uniformly distributed index in `0..n` from `rng`.
+///
+/// Reducing `next_u32()` with `% n` favors low indices whenever `n` does
+/// not divide 2³². This rejects and redraws the few top values that cause
+/// the bias, so every index is exactly equally likely. Values below the
+/// rejection zone map to `value % n`, so small inputs (such as those from
+/// [`StepRng`]) give the same indices as plain modulo.
+///
+/// [`VDGenerator`](crate::VDGenerator) uses this for every character; it is
+/// public so custom alphabets and samplers can do the same.
+///
+/// This function is only available when the `generate` feature is enabled.
+///
+/// # Panics
+/// Panics if `n` is 0.
+///
+/// # Examples
+/// ```
+/// use vds::{uniform_index, StepRng};
+///
+/// let mut rng = StepRng::new(u64::from(u32::MAX), 1);
+/// // u32::MAX lies in the biased tail for n = 31, so it is redrawn.
+/// assert_eq!(uniform_index(&mut rng, 31), 0);
+/// ```
+pub fn uniform_index<R: VDRng + ?Sized>(rng: &mut R, n: u32) -> u32 {
+    assert!(n > 0, "uniform_index called with n = 0");
+    // 2^32 mod n: the number of values at the top of the range that would
+    // make low indices one draw more likely.
+    let excess = ((u32::MAX % n) + 1) % n;
+    loop {
+        let value = rng.draw_u32();
+        if value <= u32::MAX - excess {
+            return value % n;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uniform_index_rejects_biased_tail() {
+        // 2^32 mod 31 == 4, so the top four values are redrawn.
+        let mut rng = StepRng::new(u64::from(u32::MAX - 3), 1);
+        assert_eq!(uniform_index(&mut rng, 31), 0);
+        assert_eq!(rng.next_u64(), u64::from(u32::MAX) + 2);
+
+        let mut rng = StepRng::new(u64::from(u32::MAX - 4), 1);
+        assert_eq!(uniform_index(&mut rng, 31), (u32::MAX - 4) % 31);
+
+        let mut rng = StepRng::new(u64::from(u32::MAX), 1);
+        assert_eq!(uniform_index(&mut rng, 1 << 8), 255);
+    }
+
+    #[test]
+    fn uniform_index_matches_modulo_for_small_values() {
+        let mut rng = StepRng::new(0, 1);
+        for i in 0..100 {
+            assert_eq!(uniform_index(&mut rng, 31), i % 31);
+        }
+    }
+
+    #[test]
+    fn steps_and_wraps() {
+        let mut rng = StepRng::new(u64::MAX - 1, 1);
+        assert_eq!(rng.next_u64(), u64::MAX - 1);
+        assert_eq!(rng.next_u64(), u64::MAX);
+        assert_eq!(rng.next_u64(), 0);
+    }
+
+    #[test]
+    fn next_u32_truncates() {
+        let mut rng = StepRng::new(0x1_0000_0005, 3);
+        assert_eq!(rng.next_u32(), 5);
+        assert_eq!(rng.next_u32(), 8);
+    }
+
+    #[test]
+    fn same_seed_same_output() {
+        let mut a = StepRng::new(7, 11);
+        let mut b = StepRng::new(7, 11);
+        let (mut x, mut y) = ([0u8; 20], [0u8; 20]);
+        a.fill_bytes(&mut x);
+        b.fill_bytes(&mut y);
+        assert_eq!(x, y);
+    }
+}
@@ -0,0 +1,77 @@
+//! [`rusqlite`] support for [`VDString`].
+//!
+//! This module is only available when the `rusqlite` feature is enabled.
+//!
+//! A [`VDString`] is stored as `TEXT`. Reading a column validates it: text
+//! outside the alphabet is a [`FromSqlError::Other`] wrapping the
+//! [`VDStringError`](crate::VDStringError), and any other SQLite type is
+//! [`FromSqlError::InvalidType`].
+//!
+//! # Examples
+//! ```
+//! use rusqlite::Connection;
+//! use vds::VDString;
+//!
+//! let conn = Connection::open_in_memory().unwrap();
+//! conn.execute("CREATE TABLE codes (code TEXT NOT NULL)", ()).unwrap();
+//!
+//! let code: VDString = "K7M3PQ".parse().unwrap();
+//! conn.execute("INSERT INTO codes (code) VALUES (?1)", [&code]).unwrap();
+//!
+//! let fetched: VDString = conn.query_row("SELECT code FROM codes", [], |row| row.get(0)).unwrap();
+//! assert_eq!(fetched, code);
+//! ```
+
+extern crate alloc;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::alphabet::Alphabet;
+use crate::VDString;
+
+impl<A: Alphabet> ToSql for VDString<A> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl<A: Alphabet> FromSql for VDString<A> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|err| FromSqlError::Other(Box::new(err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use crate::alphabet::{Default31, Digits10};
+    use crate::VDStringError;
+    use rusqlite::{Connection, Error};
+
+    #[test]
+    fn round_trip_and_invalid_text() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE codes (code)", ()).unwrap();
+
+        let code: VDString<Default31> = "K7M3PQ".parse().unwrap();
+        conn.execute("INSERT INTO codes (code) VALUES (?1)", [&code]).unwrap();
+        let stored: String = conn.query_row("SELECT code FROM codes", [], |row| row.get(0)).unwrap();
+        assert_eq!(stored, "K7M3PQ");
+
+        let fetched: VDString<Default31> = conn.query_row("SELECT code FROM codes", [], |row| row.get(0)).unwrap();
+        assert_eq!(fetched, code);
+
+        let err = conn.query_row("SELECT code FROM codes", [], |row| row.get::<_, VDString<Digits10>>(0)).unwrap_err();
+        match err {
+            Error::FromSqlConversionFailure(0, _, source) => assert!(source.downcast_ref::<VDStringError>().is_some()),
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        conn.execute("INSERT INTO codes (code) VALUES (42)", ()).unwrap();
+        let err = conn.query_row("SELECT code FROM codes WHERE code = 42", [], |row| row.get::<_, VDString<Default31>>(0)).unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(..)));
+    }
+}
@@ -0,0 +1,353 @@
+extern crate alloc;
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::alphabet::Default31;
+use crate::checksum::{ChecksumAlgorithm, Damm};
+use crate::{ParseOptions, VDChar, VDCharSet, VDStr, VDString, VDStringError};
+
+/// Error returned when formatting or parsing with a [`CodeSchema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeSchemaError {
+    /// [`CodeSchema::format`] was given the wrong number of values.
+    ValueCount {
+        /// The number of fields in the schema.
+        expected: usize,
+        /// The number of values given.
+        actual: usize,
+    },
+    /// The code has the wrong number of characters.
+    Length {
+        /// The schema's [`len`](CodeSchema::len).
+        expected: usize,
+        /// The number of characters in the code.
+        actual: usize,
+    },
+    /// The input contains a character outside the alphabet.
+    Invalid(VDStringError),
+    /// A literal part of the schema does not match.
+    WrongLiteral {
+        /// Position of the literal in the code.
+        position: usize,
+    },
+    /// A field value has the wrong number of characters.
+    FieldLength {
+        /// The field's name.
+        field: &'static str,
+        /// The number of characters in the value.
+        actual: usize,
+    },
+    /// A field contains a character its set does not allow.
+    FieldChar {
+        /// The field's name.
+        field: &'static str,
+        /// The rejected character.
+        ch: VDChar<Default31>,
+    },
+    /// The check character does not match the characters before it.
+    CheckMismatch,
+}
+
+impl fmt::Display for CodeSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            CodeSchemaError::ValueCount { expected, actual } => {
+                write!(f, "got {} field values, expected {}", actual, expected)
+            }
+            CodeSchemaError::Length { expected, actual } => {
+                write!(f, "code has {} characters, expected {}", actual, expected)
+            }
+            CodeSchemaError::Invalid(ref err) => err.fmt(f),
+            CodeSchemaError::WrongLiteral { position } => write!(f, "wrong literal at position {}", position),
+            CodeSchemaError::FieldLength { field, actual } => {
+                write!(f, "field {} has the wrong length ({} characters)", field, actual)
+            }
+            CodeSchemaError::FieldChar { field, ch } => write!(f, "field {} does not allow {:?}", field, ch.as_char()),
+            CodeSchemaError::CheckMismatch => f.write_str("code is mistyped"),
+        }
+    }
+}
+
+impl core::error::Error for CodeSchemaError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            CodeSchemaError::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<VDStringError> for CodeSchemaError {
+    fn from(err: VDStringError) -> Self {
+        CodeSchemaError::Invalid(err)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(VDString<Default31>),
+    Field { name: &'static str, len: usize, allowed: VDCharSet },
+    Check,
+}
+
+impl Part {
+    fn len(&self) -> usize {
+        match self {
+            Part::Literal(literal) => literal.len(),
+            Part::Field { len, .. } => *len,
+            Part::Check => 1,
+        }
+    }
+}
+
+/// The layout of a code made of named fields, such as a literal prefix,
+/// a region, a serial number, and a check character.
+///
+/// Parts are laid out in the order they are added. A check character is
+/// computed by `C` over every character before it, literals included.
+///
+/// # Examples
+/// ```
+/// use vds::{vdstr, CodeSchema, CodeSchemaError, VDChar, VDCharSet};
+///
+/// let digits: VDCharSet = VDChar::all().filter(|c| c.is_digit()).collect();
+/// let schema = CodeSchema::new()
+///     .literal(&vdstr!("VD"))
+///     .field("region", 2)
+///     .field_in("serial", 4, digits)
+///     .check_char();
+///
+/// let code = schema.format(&[&vdstr!("KM"), &vdstr!("2749")]).unwrap();
+/// assert_eq!(code.len(), 9);
+///
+/// let fields = schema.parse(&code.to_string()).unwrap();
+/// assert_eq!(fields.get("region").unwrap(), "KM");
+/// assert_eq!(fields.get("serial").unwrap(), "2749");
+///
+/// assert_eq!(
+///     schema.format(&[&vdstr!("KM"), &vdstr!("27A9")]),
+///     Err(CodeSchemaError::FieldChar { field: "serial", ch: VDChar::new('A').unwrap() })
+/// );
+/// ```
+pub struct CodeSchema<C = Damm> {
+    parts: Vec<Part>,
+    alg: PhantomData<C>,
+}
+
+impl CodeSchema {
+    /// Creates an empty schema with [`Damm`] check characters.
+    ///
+    /// For a different algorithm, start from `CodeSchema::<Iso7064>::default()`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<C: ChecksumAlgorithm + Default> CodeSchema<C> {
+    /// Appends literal characters that every code carries, such as a
+    /// product prefix.
+    pub fn literal(mut self, literal: &VDStr<Default31>) -> Self {
+        self.parts.push(Part::Literal(literal.to_owned()));
+        self
+    }
+
+    /// Appends a field of `len` characters from the whole alphabet.
+    pub fn field(self, name: &'static str, len: usize) -> Self {
+        self.field_in(name, len, VDCharSet::full())
+    }
+
+    /// Appends a field of `len` characters from `allowed`.
+    pub fn field_in(mut self, name: &'static str, len: usize, allowed: VDCharSet) -> Self {
+        self.parts.push(Part::Field { name, len, allowed });
+        self
+    }
+
+    /// Appends a check character over every character so far.
+    pub fn check_char(mut self) -> Self {
+        self.parts.push(Part::Check);
+        self
+    }
+
+    /// Returns the number of characters in a code.
+    pub fn len(&self) -> usize {
+        self.parts.iter().map(Part::len).sum()
+    }
+
+    /// Returns `true` if the schema has no parts.
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Returns the field names, in order.
+    pub fn field_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.parts.iter().filter_map(|part| match *part {
+            Part::Field { name, .. } => Some(name),
+            _ => None,
+        })
+    }
+
+    /// Assembles a code from one value per field, in field order, adding
+    /// the literals and check characters.
+    ///
+    /// # Errors
+    /// - [`CodeSchemaError::ValueCount`] unless there is one value per field
+    /// - [`CodeSchemaError::FieldLength`] or [`CodeSchemaError::FieldChar`]
+    ///   for the first value its field rejects
+    pub fn format(&self, values: &[&VDStr<Default31>]) -> Result<VDString<Default31>, CodeSchemaError> {
+        let expected = self.field_names().count();
+        if values.len() != expected {
+            return Err(CodeSchemaError::ValueCount { expected, actual: values.len() });
+        }
+
+        let alg = C::default();
+        let mut values = values.iter();
+        let mut chars = Vec::with_capacity(self.len());
+        for part in &self.parts {
+            match part {
+                Part::Literal(literal) => chars.extend(literal.iter()),
+                Part::Field { name, len, allowed } => {
+                    let value = values.next().expect("value count checked above");
+                    if value.len() != *len {
+                        return Err(CodeSchemaError::FieldLength { field: name, actual: value.len() });
+                    }
+                    check_field(name, value, allowed)?;
+                    chars.extend(value.iter());
+                }
+                Part::Check => chars.push(alg.compute(chars.iter().copied())),
+            }
+        }
+        Ok(VDString::new(chars))
+    }
+
+    /// Parses `s` and splits it into its fields.
+    ///
+    /// Separators, lowercase, and look-alike characters are accepted as by
+    /// [`ParseOptions::lenient`].
+    ///
+    /// # Errors
+    /// - [`CodeSchemaError::Invalid`] for a character outside the alphabet
+    /// - [`CodeSchemaError::Length`] unless the code has [`len`](Self::len)
+    ///   characters
+    /// - otherwise the first literal, field, or check character that does
+    ///   not match, in order
+    pub fn parse(&self, s: &str) -> Result<CodeFields, CodeSchemaError> {
+        let code = VDString::parse_with(s, ParseOptions::lenient())?;
+        if code.len() != self.len() {
+            return Err(CodeSchemaError::Length { expected: self.len(), actual: code.len() });
+        }
+
+        let alg = C::default();
+        let mut fields = Vec::new();
+        let mut pos = 0;
+        for part in &self.parts {
+            let span = pos..pos + part.len();
+            match part {
+                Part::Literal(literal) => {
+                    if code[span.clone()] != **literal {
+                        return Err(CodeSchemaError::WrongLiteral { position: pos });
+                    }
+                }
+                Part::Field { name, allowed, .. } => {
+                    check_field(name, &code[span.clone()], allowed)?;
+                    fields.push((*name, span.clone()));
+                }
+                Part::Check => {
+                    if !alg.verify(code[..span.end].iter()) {
+                        return Err(CodeSchemaError::CheckMismatch);
+                    }
+                }
+            }
+            pos = span.end;
+        }
+        Ok(CodeFields { code, fields })
+    }
+}
+
+fn check_field(name: &'static str, value: &VDStr<Default31>, allowed: &VDCharSet) -> Result<(), CodeSchemaError> {
+    match value.iter().find(|&c| !allowed.contains(c)) {
+        Some(ch) => Err(CodeSchemaError::FieldChar { field: name, ch }),
+        None => Ok(()),
+    }
+}
+
+impl<C: ChecksumAlgorithm + Default> Default for CodeSchema<C> {
+    fn default() -> Self {
+        Self { parts: Vec::new(), alg: PhantomData }
+    }
+}
+
+impl<C> fmt::Debug for CodeSchema<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CodeSchema").field(&self.parts).finish()
+    }
+}
+
+impl<C> Clone for CodeSchema<C> {
+    fn clone(&self) -> Self {
+        Self { parts: self.parts.clone(), alg: PhantomData }
+    }
+}
+
+/// A code parsed by a [`CodeSchema`], split into its fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeFields {
+    code: VDString<Default31>,
+    fields: Vec<(&'static str, Range<usize>)>,
+}
+
+impl CodeFields {
+    /// Returns the value of the field called `name`.
+    pub fn get(&self, name: &str) -> Option<&VDStr<Default31>> {
+        self.iter().find(|&(field, _)| field == name).map(|(_, value)| value)
+    }
+
+    /// Returns each field's name and value, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &VDStr<Default31>)> + '_ {
+        self.fields.iter().map(|(name, span)| (*name, &self.code[span.clone()]))
+    }
+
+    /// Returns the whole code, literals and check characters included.
+    pub fn as_vdstring(&self) -> &VDString<Default31> {
+        &self.code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checksum::LuhnModN;
+
+    fn s(s: &str) -> VDString<Default31> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn round_trips_fields() {
+        let schema = CodeSchema::<LuhnModN>::default().field("region", 2).check_char().literal(&s("X")).field("serial", 3);
+        assert_eq!((schema.len(), schema.field_names().count()), (7, 2));
+
+        let code = schema.format(&[&s("KM"), &s("Q29")]).unwrap();
+        let fields = schema.parse(&alloc::format!("{}", code).to_lowercase()).unwrap();
+        assert_eq!(fields.as_vdstring(), &code);
+        let pairs: Vec<_> = fields.iter().map(|(name, value)| (name, value.to_owned())).collect();
+        assert_eq!(pairs, [("region", s("KM")), ("serial", s("Q29"))]);
+        assert_eq!(fields.get("check"), None);
+    }
+
+    #[test]
+    fn reports_first_bad_part() {
+        let schema = CodeSchema::new().literal(&s("VD")).field("serial", 3).check_char();
+        assert_eq!(schema.format(&[]), Err(CodeSchemaError::ValueCount { expected: 1, actual: 0 }));
+        assert_eq!(schema.format(&[&s("K7")]), Err(CodeSchemaError::FieldLength { field: "serial", actual: 2 }));
+
+        let code = alloc::format!("{}", schema.format(&[&s("K7M")]).unwrap());
+        assert_eq!(schema.parse(&code.replace("VD", "VA")), Err(CodeSchemaError::WrongLiteral { position: 0 }));
+        assert_eq!(schema.parse(&code.replace("K7M", "K7N")), Err(CodeSchemaError::CheckMismatch));
+        assert_eq!(schema.parse("VDK7"), Err(CodeSchemaError::Length { expected: 6, actual: 4 }));
+        assert_eq!(schema.parse("VDK0MA"), Err(CodeSchemaError::Invalid(VDStringError::InvalidChar { ch: '0', offset: 3 })));
+    }
+}
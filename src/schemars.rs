@@ -0,0 +1,147 @@
+//! [`JsonSchema`] support for [`VDChar`], [`VDStr`], and [`VDString`].
+//!
+//! This module is only available when the `schemars` feature is enabled.
+//!
+//! The schemas describe the human-readable serde representation: a string
+//! whose `pattern` only matches characters of the alphabet, built from
+//! [`Alphabet::CHARS`] ([`VDS_ALLOWED`](crate::VDS_ALLOWED) for the
+//! default). A [`VDChar`] is a string of exactly one such character.
+//!
+//! # Examples
+//! ```
+//! use vds::VDString;
+//!
+//! let schema = schemars::schema_for!(VDString);
+//! assert_eq!(schema.get("type").unwrap(), "string");
+//! assert_eq!(schema.get("pattern").unwrap(), "^[ABCDEFGHJKMNPQRSTUVWXYZ23456789]*$");
+//! ```
+
+extern crate alloc;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::alphabet::Alphabet;
+use crate::{VDChar, VDStr, VDString};
+
+/// Returns a regex character class matching exactly the characters of `A`.
+fn char_class<A: Alphabet>() -> String {
+    let mut class = String::with_capacity(A::CHARS.len() + 2);
+    class.push('[');
+    for &c in A::CHARS {
+        if matches!(c, '\\' | '[' | ']' | '^' | '-') {
+            class.push('\\');
+        }
+        class.push(c);
+    }
+    class.push(']');
+    class
+}
+
+/// Returns the schema of a string of any number of characters of `A`.
+fn string_schema<A: Alphabet>() -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": format!("^{}*$", char_class::<A>()),
+    })
+}
+
+impl<A: Alphabet> JsonSchema for VDChar<A> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "VDChar".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        format!("vds::VDChar<{}>", core::any::type_name::<A>()).into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "minLength": 1,
+            "maxLength": 1,
+            "pattern": format!("^{}$", char_class::<A>()),
+        })
+    }
+}
+
+impl<A: Alphabet> JsonSchema for VDStr<A> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "VDString".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        format!("vds::VDString<{}>", core::any::type_name::<A>()).into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema::<A>()
+    }
+}
+
+impl<A: Alphabet> JsonSchema for VDString<A> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        VDStr::<A>::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        VDStr::<A>::schema_id()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema::<A>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::{Crockford32, Default31};
+    use crate::VDS_ALLOWED;
+    use serde_json::json;
+
+    #[test]
+    fn pattern_lists_the_alphabet() {
+        let allowed: String = VDS_ALLOWED.iter().collect();
+        let schema = schemars::schema_for!(VDString<Default31>);
+        assert_eq!(schema.get("type"), Some(&json!("string")));
+        assert_eq!(schema.get("pattern"), Some(&json!(format!("^[{}]*$", allowed))));
+
+        let schema = schemars::schema_for!(VDChar<Default31>);
+        assert_eq!(schema.get("pattern"), Some(&json!(format!("^[{}]$", allowed))));
+        assert_eq!((schema.get("minLength"), schema.get("maxLength")), (Some(&json!(1)), Some(&json!(1))));
+
+        assert_eq!(
+            schemars::schema_for!(VDString<Crockford32>).get("pattern"),
+            Some(&json!("^[0123456789ABCDEFGHJKMNPQRSTVWXYZ]*$"))
+        );
+        assert_eq!(schemars::schema_for!(&VDStr<Default31>).get("pattern"), schemars::schema_for!(VDString<Default31>).get("pattern"));
+        assert_ne!(VDString::<Default31>::schema_id(), VDString::<Crockford32>::schema_id());
+    }
+
+    #[test]
+    fn escapes_class_metacharacters() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        struct Punct;
+
+        impl Alphabet for Punct {
+            const CHARS: &'static [char] = &['A', '-', ']', '\\', '^'];
+        }
+
+        assert_eq!(char_class::<Punct>(), r"[A\-\]\\\^]");
+    }
+}
@@ -0,0 +1,420 @@
+extern crate alloc;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::alphabet::Default31;
+use crate::checksum::{ChecksumAlgorithm, Damm};
+use crate::{ParseOptions, VDStr, VDStrChunks, VDString, VDStringError};
+
+/// Error returned when building or parsing a [`SegmentedCode`].
+///
+/// Segment numbers are zero-based; `Display` counts them from 1, so the
+/// message can be shown to the person who typed the code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SegmentedCodeError {
+    /// The payload given to [`SegmentedCodeBuilder::build`] has the wrong length.
+    PayloadLength {
+        /// The layout's [`payload_len`](SegmentedCodeBuilder::payload_len).
+        expected: usize,
+        /// The length of the given payload.
+        actual: usize,
+    },
+    /// The input has the wrong number of segments.
+    SegmentCount {
+        /// The layout's number of segments.
+        expected: usize,
+        /// The number of segments in the input.
+        actual: usize,
+    },
+    /// A segment has the wrong number of characters.
+    SegmentLength {
+        /// The zero-based segment.
+        segment: usize,
+        /// The number of characters in the segment.
+        actual: usize,
+    },
+    /// A segment contains a character outside the alphabet.
+    Invalid {
+        /// The zero-based segment.
+        segment: usize,
+        /// The underlying error, with its offset within the segment.
+        error: VDStringError,
+    },
+    /// A segment's check character does not match the rest of the segment.
+    CheckMismatch {
+        /// The zero-based segment.
+        segment: usize,
+    },
+}
+
+impl fmt::Display for SegmentedCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            SegmentedCodeError::PayloadLength { expected, actual } => {
+                write!(f, "payload has {} characters, expected {}", actual, expected)
+            }
+            SegmentedCodeError::SegmentCount { expected, actual } => {
+                write!(f, "code has {} segments, expected {}", actual, expected)
+            }
+            SegmentedCodeError::SegmentLength { segment, actual } => {
+                write!(f, "segment {} has the wrong length ({} characters)", segment + 1, actual)
+            }
+            SegmentedCodeError::Invalid { segment, ref error } => write!(f, "segment {}: {}", segment + 1, error),
+            SegmentedCodeError::CheckMismatch { segment } => write!(f, "segment {} is mistyped", segment + 1),
+        }
+    }
+}
+
+impl core::error::Error for SegmentedCodeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            SegmentedCodeError::Invalid { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// A code in separated segments, each ending in its own check character,
+/// such as `XK29-M5TQ-7WCE`.
+///
+/// Because every segment is checked on its own, a typo can be pinned to
+/// the block it is in, rather than just rejecting the whole code. Codes are
+/// built and parsed by a [`SegmentedCodeBuilder`], which fixes the layout.
+///
+/// # Examples
+/// ```
+/// use vds::{SegmentedCode, SegmentedCodeError, VDString};
+///
+/// let layout = SegmentedCode::builder().segments(3).segment_len(4);
+/// let payload: VDString = "XK2M5T7WC".parse().unwrap();
+/// let code = layout.build(&payload).unwrap();
+/// let printed = code.to_string();
+/// assert_eq!(printed.len(), 14);
+///
+/// assert_eq!(layout.parse(&printed.to_lowercase()), Ok(code));
+///
+/// // Swap two characters of the second segment.
+/// let mut typo: Vec<char> = printed.chars().collect();
+/// typo.swap(5, 6);
+/// let typo: String = typo.into_iter().collect();
+/// assert_eq!(layout.parse(&typo), Err(SegmentedCodeError::CheckMismatch { segment: 1 }));
+/// ```
+pub struct SegmentedCode<C = Damm> {
+    /// Every segment, check characters included, without separators.
+    code: VDString<Default31>,
+    segment_len: usize,
+    separator: char,
+    alg: PhantomData<C>,
+}
+
+impl SegmentedCode {
+    /// Returns a builder for the default layout: three segments of four
+    /// characters, joined by `-`, with [`Damm`] check characters.
+    pub fn builder() -> SegmentedCodeBuilder {
+        SegmentedCodeBuilder::new()
+    }
+}
+
+impl<C> SegmentedCode<C> {
+    /// Returns the segments, each including its check character.
+    pub fn segments(&self) -> VDStrChunks<'_, Default31> {
+        self.code.chunks(self.segment_len)
+    }
+
+    /// Returns the payload: every character except the check characters.
+    pub fn payload(&self) -> VDString<Default31> {
+        let chars = self.segments().flat_map(|segment| &segment.as_vdchars()[..segment.len() - 1]).copied();
+        VDString::new(chars.collect())
+    }
+
+    /// Returns every segment, check characters included, without separators.
+    pub fn as_vdstring(&self) -> &VDString<Default31> {
+        &self.code
+    }
+}
+
+impl<C> fmt::Display for SegmentedCode<C> {
+    /// Writes the segments joined by the layout's separator.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments().enumerate() {
+            if i > 0 {
+                write!(f, "{}", self.separator)?;
+            }
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl<C> fmt::Debug for SegmentedCode<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SegmentedCode").field(&self.code).field(&self.segment_len).finish()
+    }
+}
+
+impl<C> Clone for SegmentedCode<C> {
+    fn clone(&self) -> Self {
+        Self { code: self.code.clone(), segment_len: self.segment_len, separator: self.separator, alg: PhantomData }
+    }
+}
+
+impl<C> PartialEq for SegmentedCode<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code && self.segment_len == other.segment_len
+    }
+}
+
+impl<C> Eq for SegmentedCode<C> {}
+
+/// Layout of a [`SegmentedCode`]: how many segments, how long, and how
+/// they are separated.
+///
+/// Each segment holds `segment_len - 1` payload characters followed by a
+/// check character computed by `C` over that segment alone.
+pub struct SegmentedCodeBuilder<C = Damm> {
+    segments: usize,
+    segment_len: usize,
+    separator: char,
+    alg: PhantomData<C>,
+}
+
+impl<C: ChecksumAlgorithm + Default> SegmentedCodeBuilder<C> {
+    /// Creates a builder for three segments of four characters, joined by `-`.
+    ///
+    /// Name a different algorithm as `SegmentedCodeBuilder::<Iso7064>::new()`.
+    pub fn new() -> Self {
+        Self { segments: 3, segment_len: 4, separator: '-', alg: PhantomData }
+    }
+
+    /// Sets the number of segments.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the number of characters in each segment, including its check
+    /// character.
+    ///
+    /// # Panics
+    /// Panics if `len` is less than 2, which would leave no payload.
+    pub fn segment_len(mut self, len: usize) -> Self {
+        assert!(len >= 2, "segment length must be at least 2, got {}", len);
+        self.segment_len = len;
+        self
+    }
+
+    /// Sets the separator written between segments and split on when parsing.
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Returns the number of payload characters a code holds.
+    pub fn payload_len(&self) -> usize {
+        self.segments * (self.segment_len - 1)
+    }
+
+    /// Splits `payload` into segments and appends a check character to each.
+    ///
+    /// # Errors
+    /// Returns [`SegmentedCodeError::PayloadLength`] unless `payload` has
+    /// exactly [`payload_len`](Self::payload_len) characters.
+    pub fn build(&self, payload: &VDStr<Default31>) -> Result<SegmentedCode<C>, SegmentedCodeError> {
+        if payload.len() != self.payload_len() {
+            return Err(SegmentedCodeError::PayloadLength { expected: self.payload_len(), actual: payload.len() });
+        }
+        Ok(self.assemble(payload))
+    }
+
+    /// Generates a random code with this layout.
+    ///
+    /// This method is only available when the `generate` feature is enabled.
+    #[cfg(feature = "generate")]
+    pub fn generate<R: crate::VDRng + ?Sized>(&self, rng: &mut R) -> SegmentedCode<C> {
+        use crate::VDChar;
+
+        let payload: Vec<VDChar<Default31>> = (0..self.payload_len())
+            .map(|_| VDChar::ALL[crate::uniform_index(rng, VDChar::ALL.len() as u32) as usize])
+            .collect();
+        self.assemble(VDStr::from_vdchars(&payload))
+    }
+
+    /// Parses and verifies `s`, reporting the first bad segment.
+    ///
+    /// Segments are split on the separator and may be surrounded by
+    /// whitespace; empty segments, as from a doubled separator, are skipped.
+    /// Lowercase and look-alike characters are folded as by
+    /// [`ParseOptions::lenient`].
+    ///
+    /// # Errors
+    /// The first error that [`segment_errors`](Self::segment_errors) would
+    /// report.
+    pub fn parse(&self, s: &str) -> Result<SegmentedCode<C>, SegmentedCodeError> {
+        let mut chars = Vec::with_capacity(self.segments * self.segment_len);
+        for result in self.check_segments(s) {
+            chars.extend(result?.iter());
+        }
+        Ok(self.wrap(VDString::new(chars)))
+    }
+
+    /// Verifies every segment of `s`, returning one error per bad segment.
+    ///
+    /// Returns an empty list if `s` parses. When the number of segments is
+    /// wrong, that is the only error, as segments cannot be matched up.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{SegmentedCode, SegmentedCodeError, VDString};
+    ///
+    /// let layout = SegmentedCode::builder();
+    /// let payload: VDString = "XK2M5T7WC".parse().unwrap();
+    /// let code = layout.build(&payload).unwrap().to_string();
+    ///
+    /// let typo = code.replace("XK2", "XK3").replace("7WC", "7WD");
+    /// let errors = layout.segment_errors(&typo);
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0], SegmentedCodeError::CheckMismatch { segment: 0 });
+    /// assert_eq!(errors[1], SegmentedCodeError::CheckMismatch { segment: 2 });
+    /// ```
+    pub fn segment_errors(&self, s: &str) -> Vec<SegmentedCodeError> {
+        self.check_segments(s).filter_map(Result::err).collect()
+    }
+
+    /// Parses and verifies each segment of `s`.
+    fn check_segments<'s>(&'s self, s: &'s str) -> impl Iterator<Item = Result<VDString<Default31>, SegmentedCodeError>> + 's {
+        let pieces = move || s.split(self.separator).map(str::trim).filter(|piece| !piece.is_empty());
+        let count = pieces().count();
+        let wrong_count = count != self.segments;
+        let count_error =
+            wrong_count.then_some(Err(SegmentedCodeError::SegmentCount { expected: self.segments, actual: count }));
+        let segments = pieces().take(if wrong_count { 0 } else { count }).enumerate();
+        count_error.into_iter().chain(segments.map(move |(segment, text)| self.check_segment(segment, text)))
+    }
+
+    fn check_segment(&self, segment: usize, text: &str) -> Result<VDString<Default31>, SegmentedCodeError> {
+        let options = ParseOptions { fold_case: true, fold_homoglyphs: true, ..ParseOptions::default() };
+        let chars =
+            VDString::parse_with(text, options).map_err(|error| SegmentedCodeError::Invalid { segment, error })?;
+        if chars.len() != self.segment_len {
+            return Err(SegmentedCodeError::SegmentLength { segment, actual: chars.len() });
+        }
+        if !C::default().verify(chars.iter()) {
+            return Err(SegmentedCodeError::CheckMismatch { segment });
+        }
+        Ok(chars)
+    }
+
+    /// Appends a check character to each segment of a payload of
+    /// `payload_len` characters.
+    fn assemble(&self, payload: &VDStr<Default31>) -> SegmentedCode<C> {
+        let alg = C::default();
+        let mut chars = Vec::with_capacity(self.segments * self.segment_len);
+        for chunk in payload.chunks(self.segment_len - 1) {
+            chars.extend(chunk);
+            chars.push(alg.compute(chunk.iter()));
+        }
+        self.wrap(VDString::new(chars))
+    }
+
+    fn wrap(&self, code: VDString<Default31>) -> SegmentedCode<C> {
+        SegmentedCode { code, segment_len: self.segment_len, separator: self.separator, alg: PhantomData }
+    }
+}
+
+impl<C: ChecksumAlgorithm + Default> Default for SegmentedCodeBuilder<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Clone for SegmentedCodeBuilder<C> {
+    fn clone(&self) -> Self {
+        Self { segments: self.segments, segment_len: self.segment_len, separator: self.separator, alg: PhantomData }
+    }
+}
+
+impl<C> fmt::Debug for SegmentedCodeBuilder<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SegmentedCodeBuilder")
+            .field("segments", &self.segments)
+            .field("segment_len", &self.segment_len)
+            .field("separator", &self.separator)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checksum::Iso7064;
+    use alloc::string::ToString;
+
+    fn payload(s: &str) -> VDString<Default31> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn builds_segments_with_their_own_check_chars() {
+        let layout = SegmentedCodeBuilder::<Damm>::new();
+        let code = layout.build(&payload("XK2M5T7WC")).unwrap();
+        assert_eq!(code.segments().count(), 3);
+        for (segment, chunk) in code.segments().zip(payload("XK2M5T7WC").chunks(3)) {
+            assert_eq!(&segment[..3], chunk);
+            assert!(Damm.verify(segment.iter()));
+        }
+        assert_eq!(code.payload(), payload("XK2M5T7WC"));
+        assert_eq!(code.to_string().matches('-').count(), 2);
+        assert_eq!(
+            layout.build(&payload("XK2M")),
+            Err(SegmentedCodeError::PayloadLength { expected: 9, actual: 4 })
+        );
+    }
+
+    #[test]
+    fn parses_leniently_and_pins_errors_to_segments() {
+        let layout = SegmentedCodeBuilder::<Iso7064>::new().segments(2).segment_len(5).separator(' ');
+        let code = layout.build(&payload("AB29XY7K")).unwrap();
+        let printed = code.to_string();
+        assert_eq!(layout.parse(&printed), Ok(code.clone()));
+        assert_eq!(layout.parse(&alloc::format!("  {} ", printed.to_lowercase().replace(' ', "  "))), Ok(code));
+
+        let (first, second) = printed.split_once(' ').unwrap();
+        let bad = alloc::format!("{} {}", first, second.replacen('7', "8", 1));
+        assert_eq!(layout.segment_errors(&bad), [SegmentedCodeError::CheckMismatch { segment: 1 }]);
+        let short = alloc::format!("{} {}", &first[..4], second);
+        assert_eq!(layout.parse(&short), Err(SegmentedCodeError::SegmentLength { segment: 0, actual: 4 }));
+        assert_eq!(
+            layout.segment_errors(first),
+            [SegmentedCodeError::SegmentCount { expected: 2, actual: 1 }]
+        );
+        assert_eq!(
+            layout.parse(&alloc::format!("{} {}!", first, second)),
+            Err(SegmentedCodeError::Invalid { segment: 1, error: VDStringError::InvalidChar { ch: '!', offset: second.len() } })
+        );
+    }
+
+    #[cfg(feature = "generate")]
+    #[test]
+    fn generated_codes_parse() {
+        let layout = SegmentedCode::builder().segments(4).segment_len(5);
+        let mut rng = crate::StepRng::new(3, 7);
+        for _ in 0..10 {
+            let code = layout.generate(&mut rng);
+            assert_eq!(code.payload().len(), layout.payload_len());
+            assert_eq!(layout.parse(&code.to_string()), Ok(code));
+        }
+    }
+
+    #[test]
+    fn errors_number_segments_from_one() {
+        assert_eq!(SegmentedCodeError::CheckMismatch { segment: 1 }.to_string(), "segment 2 is mistyped");
+    }
+
+    #[test]
+    #[should_panic(expected = "segment length")]
+    fn rejects_segments_without_payload() {
+        let _ = SegmentedCode::builder().segment_len(1);
+    }
+}
@@ -6,62 +6,608 @@
 //!
 //! - [`VDChar`] is serialized as a single `char`, e.g. `'A'`
 //! - [`VDString`] is serialized as a `str`, e.g. `"ABC234"`
+//! - [`VDArrayString`] and [`VDStringFixed`] are serialized the same way as
+//!   `VDString`
+//! - [`NonEmptyVDString`](crate::NonEmptyVDString) is serialized the same
+//!   way as `VDString`, and rejects empty input; likewise
+//!   [`VDStringBounded`](crate::VDStringBounded) rejects out-of-bounds lengths
+//! - [`VDSharedString`](crate::VDSharedString) is serialized the same way as
+//!   `VDString`
 //!
 //! These formats are human-friendly, compact, and interoperable with
 //! other text-based formats like JSON, TOML, and YAML.
 //!
+//! Formats that are not human-readable (see
+//! [`Serializer::is_human_readable`]), such as postcard, bincode, or CBOR,
+//! get a compact form instead: a [`VDChar`] is its alphabet index as a `u8`,
+//! and the string types are byte arrays of indices.
+//!
 //! Invalid deserialization inputs will produce an error at runtime.
+//!
+//! With the `generate` feature, [`VDGenerator`](crate::VDGenerator) is
+//! serialized as a map of its settings, named after the builder methods
+//! that set them, so generation policy can live in a config file:
+//!
+//! ```toml
+//! length = 10
+//! no_adjacent_repeats = true
+//! prefix = "EU"
+//! deny_substrings = ["BAD", "FEE"]
+//! ```
+//!
+//! Every setting is optional and defaults as in
+//! [`VDGenerator::new`](crate::VDGenerator::new); unknown settings are
+//! rejected, so a misspelled constraint is never silently ignored.
+
+use crate::alphabet::Alphabet;
+use crate::{VDArrayString, VDChar, VDStringFixed, VDStringFixedError};
+#[cfg(feature = "alloc")]
+use crate::{NonEmptyVDString, VDString, VDStringBounded};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(any(feature = "alloc", feature = "generate"))]
+use core::marker::PhantomData;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{VDChar, VDString};
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+/// Converts an alphabet index from the compact representation.
+fn from_index<A: Alphabet, E: de::Error>(index: u8) -> Result<VDChar<A>, E> {
+    VDChar::try_from_index(index).map_err(|_| E::custom(format_args!("invalid VDChar index: {}", index)))
+}
 
-/// Serializes a [`VDChar`] as a single `char`.
+/// Serializes a [`VDChar`] as a single `char`, or as its alphabet index in
+/// non-human-readable formats.
 ///
 /// # Example (JSON)
 /// ```json
 /// "X"
 /// ```
-impl Serialize for VDChar {
+impl<A: Alphabet> Serialize for VDChar<A> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        serializer.serialize_char(self.as_char())
+        if serializer.is_human_readable() {
+            serializer.serialize_char(self.as_char())
+        } else {
+            serializer.serialize_u8(self.index())
+        }
     }
 }
 
-/// Deserializes a [`VDChar`] from a `char`.
+/// Deserializes a [`VDChar`] from a `char`, or from its alphabet index in
+/// non-human-readable formats.
 ///
-/// Returns an error if the character is not in the visibly distinguishable set.
-impl<'de> Deserialize<'de> for VDChar {
+/// Returns an error if the character is not in the alphabet.
+impl<'de, A: Alphabet> Deserialize<'de> for VDChar<A> {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return from_index(u8::deserialize(deserializer)?);
+        }
         let c = <char>::deserialize(deserializer)?;
-        VDChar::new(c).ok_or_else(|| serde::de::Error::custom(format_args!("invalid VDChar: {}", c)))
+        VDChar::from_char(c).ok_or_else(|| serde::de::Error::custom(format_args!("invalid VDChar: {}", c)))
+    }
+}
+
+/// Serializes a [`VDString`] as a `str`, e.g. `"ABC29"`, or as a byte array
+/// of alphabet indices in non-human-readable formats.
+#[cfg(feature = "alloc")]
+impl<A: Alphabet> Serialize for VDString<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            #[cfg(feature = "str-cache")]
+            return serializer.serialize_str(self.as_str());
+            #[cfg(not(feature = "str-cache"))]
+            return serializer.collect_str(self);
+        } else {
+            let indices: Vec<u8> = self.iter().map(VDChar::index).collect();
+            serializer.serialize_bytes(&indices)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct StringVisitor<A>(PhantomData<A>);
+
+#[cfg(feature = "alloc")]
+impl<'de, A: Alphabet> de::Visitor<'de> for StringVisitor<A> {
+    type Value = VDString<A>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("visibly distinguishable characters, as a string or index bytes")
+    }
+
+    // Borrowed and owned strings are forwarded here by serde's defaults.
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        s.parse().map_err(|_| E::custom("invalid VDString"))
+    }
+
+    // Likewise for byte arrays, which hold alphabet indices.
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        bytes.iter().map(|&i| from_index(i)).collect()
+    }
+
+    // Some formats encode byte arrays as sequences.
+    fn visit_seq<S: de::SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut chars = Vec::new();
+        while let Some(index) = seq.next_element()? {
+            chars.push(from_index(index)?);
+        }
+        Ok(VDString::new(chars))
+    }
+}
+
+/// Deserializes a [`VDString`] from a `str`, or from a byte array of
+/// alphabet indices in non-human-readable formats.
+///
+/// Accepts both borrowed and owned strings, so it also works with readers
+/// and formats that cannot borrow from their input.
+///
+/// Returns an error if any character is not in the alphabet.
+#[cfg(feature = "alloc")]
+impl<'de, A: Alphabet> Deserialize<'de> for VDString<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(StringVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_bytes(StringVisitor(PhantomData))
+        }
+    }
+}
+
+/// Serializes a [`NonEmptyVDString`] the same way as `VDString`.
+#[cfg(feature = "alloc")]
+impl<A: Alphabet> Serialize for NonEmptyVDString<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_vdstring().serialize(serializer)
+    }
+}
+
+/// Deserializes a [`NonEmptyVDString`] the same way as `VDString`.
+///
+/// Returns an error if the code is empty.
+#[cfg(feature = "alloc")]
+impl<'de, A: Alphabet> Deserialize<'de> for NonEmptyVDString<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        NonEmptyVDString::from_vdstring(VDString::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// Serializes a [`VDStringBounded`] the same way as `VDString`.
+#[cfg(feature = "alloc")]
+impl<const MIN: usize, const MAX: usize, A: Alphabet> Serialize for VDStringBounded<MIN, MAX, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_vdstring().serialize(serializer)
+    }
+}
+
+/// Deserializes a [`VDStringBounded`] the same way as `VDString`.
+///
+/// Returns an error if the code's length is out of bounds.
+#[cfg(feature = "alloc")]
+impl<'de, const MIN: usize, const MAX: usize, A: Alphabet> Deserialize<'de> for VDStringBounded<MIN, MAX, A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        VDStringBounded::from_vdstring(VDString::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// Serializes a [`VDSharedString`](crate::VDSharedString) the same way as
+/// `VDString`.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<A: Alphabet> Serialize for crate::VDSharedString<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            let indices: Vec<u8> = self.iter().map(VDChar::index).collect();
+            serializer.serialize_bytes(&indices)
+        }
+    }
+}
+
+/// Deserializes a [`VDSharedString`](crate::VDSharedString) the same way as
+/// `VDString`.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<'de, A: Alphabet> Deserialize<'de> for crate::VDSharedString<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        VDString::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// Serializes a [`VDArrayString`] as a `str`, e.g. `"ABC29"`, or as a byte
+/// array of alphabet indices in non-human-readable formats.
+impl<const N: usize> Serialize for VDArrayString<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.serialize_str(self);
+        }
+        let chars = self.as_vdchars();
+        let mut indices = [0; N];
+        for (index, ch) in indices.iter_mut().zip(chars) {
+            *index = ch.index();
+        }
+        serializer.serialize_bytes(&indices[..chars.len()])
+    }
+}
+
+struct ArrayStringVisitor<const N: usize>;
+
+impl<'de, const N: usize> de::Visitor<'de> for ArrayStringVisitor<N> {
+    type Value = VDArrayString<N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at most {} visibly distinguishable characters, as a string or index bytes", N)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        s.parse().map_err(|_| E::custom("invalid VDArrayString"))
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        let mut s = VDArrayString::new();
+        for &index in bytes {
+            s.try_push(from_index(index)?).map_err(|_| E::custom("invalid VDArrayString"))?;
+        }
+        Ok(s)
+    }
+
+    fn visit_seq<S: de::SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut s = VDArrayString::new();
+        while let Some(index) = seq.next_element()? {
+            s.try_push(from_index(index)?).map_err(|_| de::Error::custom("invalid VDArrayString"))?;
+        }
+        Ok(s)
+    }
+}
+
+/// Deserializes a [`VDArrayString`] from a `str`, or from a byte array of
+/// alphabet indices in non-human-readable formats, without allocating.
+///
+/// Returns an error if any character is not allowed or the string is too long.
+impl<'de, const N: usize> Deserialize<'de> for VDArrayString<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(ArrayStringVisitor)
+        } else {
+            deserializer.deserialize_bytes(ArrayStringVisitor)
+        }
+    }
+}
+
+/// Serializes a [`VDStringFixed`] as a `str`, or as a byte array of
+/// alphabet indices in non-human-readable formats, without allocating.
+impl<const N: usize> Serialize for VDStringFixed<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let bytes = self.to_ascii();
+            serializer.serialize_str(core::str::from_utf8(&bytes).unwrap_or_default())
+        } else {
+            serializer.serialize_bytes(&self.as_array().map(VDChar::index))
+        }
+    }
+}
+
+struct FixedStringVisitor<const N: usize>;
+
+impl<const N: usize> FixedStringVisitor<N> {
+    fn from_indices<E: de::Error, I>(indices: I) -> Result<VDStringFixed<N>, E>
+    where
+        I: IntoIterator<Item = Result<u8, E>>,
+    {
+        let mut chars = [VDChar::from_index_unchecked(0); N];
+        let mut len = 0;
+        for index in indices {
+            let ch = from_index(index?)?;
+            // Keep counting past `N` so the error reports the real length.
+            if let Some(slot) = chars.get_mut(len) {
+                *slot = ch;
+            }
+            len += 1;
+        }
+        if len != N {
+            return Err(E::custom(VDStringFixedError::WrongLength { len, expected: N }));
+        }
+        Ok(VDStringFixed::new(chars))
+    }
+}
+
+impl<'de, const N: usize> de::Visitor<'de> for FixedStringVisitor<N> {
+    type Value = VDStringFixed<N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exactly {} visibly distinguishable characters, as a string or index bytes", N)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        s.parse().map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        Self::from_indices(bytes.iter().map(|&i| Ok(i)))
+    }
+
+    fn visit_seq<S: de::SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        Self::from_indices(core::iter::from_fn(|| seq.next_element().transpose()))
+    }
+}
+
+/// Deserializes a [`VDStringFixed`] from a `str`, or from a byte array of
+/// alphabet indices in non-human-readable formats, without allocating.
+///
+/// Returns an error if any character is not allowed or the length is not
+/// exactly `N`.
+impl<'de, const N: usize> Deserialize<'de> for VDStringFixed<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FixedStringVisitor)
+        } else {
+            deserializer.deserialize_bytes(FixedStringVisitor)
+        }
+    }
+}
+
+/// The serialized form of a [`VDGenerator`](crate::VDGenerator).
+#[cfg(feature = "generate")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "VDGenerator", default, deny_unknown_fields, bound = "")]
+struct GeneratorConfig<A: Alphabet> {
+    length: usize,
+    no_adjacent_repeats: bool,
+    no_repeats: bool,
+    #[cfg(feature = "alloc")]
+    prefix: VDString<A>,
+    #[cfg(feature = "alloc")]
+    suffix: VDString<A>,
+    #[cfg(feature = "alloc")]
+    exclude_chars: Vec<VDChar<A>>,
+    #[cfg(feature = "alloc")]
+    deny_substrings: Vec<alloc::string::String>,
+    #[cfg(feature = "alloc")]
+    avoid: Vec<VDString<A>>,
+    #[cfg(feature = "alloc")]
+    avoid_distance: usize,
+    no_sequential_runs: Option<usize>,
+    min_digits: usize,
+    min_letters: usize,
+    min_distance: usize,
+    max_attempts: usize,
+    #[serde(skip)]
+    alphabet: PhantomData<A>,
+}
+
+#[cfg(feature = "generate")]
+impl<A: Alphabet> From<&crate::VDGenerator<A>> for GeneratorConfig<A> {
+    fn from(generator: &crate::VDGenerator<A>) -> Self {
+        Self {
+            length: generator.len,
+            no_adjacent_repeats: generator.no_adjacent_repeats,
+            no_repeats: generator.no_repeats,
+            #[cfg(feature = "alloc")]
+            prefix: VDString::new(generator.prefix.clone()),
+            #[cfg(feature = "alloc")]
+            suffix: VDString::new(generator.suffix.clone()),
+            #[cfg(feature = "alloc")]
+            exclude_chars: generator.excluded.clone(),
+            #[cfg(feature = "alloc")]
+            deny_substrings: generator.denied.iter().map(|s| s.iter().collect()).collect(),
+            #[cfg(feature = "alloc")]
+            avoid: generator.avoided.iter().map(|code| VDString::new(code.clone())).collect(),
+            #[cfg(feature = "alloc")]
+            avoid_distance: generator.avoid_distance,
+            no_sequential_runs: generator.max_run,
+            min_digits: generator.min_digits,
+            min_letters: generator.min_letters,
+            min_distance: generator.min_distance,
+            max_attempts: generator.max_attempts,
+            alphabet: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "generate")]
+impl<A: Alphabet> Default for GeneratorConfig<A> {
+    fn default() -> Self {
+        Self::from(&crate::VDGenerator::default())
     }
 }
 
-/// Serializes a [`VDString`] as a `str`, e.g. `"ABC29"`.
-impl Serialize for VDString {
+/// Serializes a [`VDGenerator`](crate::VDGenerator) as a map of its settings.
+#[cfg(feature = "generate")]
+impl<A: Alphabet> Serialize for crate::VDGenerator<A> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        serializer.serialize_str(self)
+        GeneratorConfig::from(self).serialize(serializer)
     }
 }
 
-/// Deserializes a [`VDString`] from a `str`.
+/// Deserializes a [`VDGenerator`](crate::VDGenerator) from a map of
+/// settings, defaulting any that are missing.
 ///
-/// Returns an error if any character is not in the allowed set.
-impl<'de> Deserialize<'de> for VDString {
+/// Only the representation is checked; invalid combinations, such as a
+/// prefix longer than the length, are reported by
+/// [`VDGenerator::build`](crate::VDGenerator::build) as usual.
+#[cfg(feature = "generate")]
+impl<'de, A: Alphabet> Deserialize<'de> for crate::VDGenerator<A> {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let s = <&str>::deserialize(deserializer)?;
-        s.parse().map_err(|_| serde::de::Error::custom("invalid VDString"))
+        let config = GeneratorConfig::<A>::deserialize(deserializer)?;
+        Ok(crate::VDGenerator {
+            len: config.length,
+            no_adjacent_repeats: config.no_adjacent_repeats,
+            no_repeats: config.no_repeats,
+            min_distance: config.min_distance,
+            #[cfg(feature = "alloc")]
+            denied: config
+                .deny_substrings
+                .iter()
+                .filter(|s| !s.is_empty())
+                .map(|s| s.chars().collect())
+                .collect(),
+            #[cfg(feature = "alloc")]
+            avoided: config.avoid.into_iter().map(VDString::into_vdchars).collect(),
+            #[cfg(feature = "alloc")]
+            avoid_distance: config.avoid_distance,
+            #[cfg(feature = "alloc")]
+            prefix: config.prefix.as_vdchars().to_vec(),
+            #[cfg(feature = "alloc")]
+            suffix: config.suffix.as_vdchars().to_vec(),
+            #[cfg(feature = "alloc")]
+            excluded: config.exclude_chars,
+            max_run: config.no_sequential_runs.filter(|&len| len >= 2),
+            min_digits: config.min_digits,
+            min_letters: config.min_letters,
+            max_attempts: config.max_attempts,
+            alphabet: PhantomData,
+        })
     }
 }
 
 #[cfg(test)]
-#[cfg(feature = "serde")]
+#[cfg(feature = "alloc")]
 mod tests {
     use super::*;
+    use crate::alphabet::Default31;
     use serde_json;
 
-    fn vd(c: char) -> VDChar {
-        VDChar::new(c).unwrap()
+    fn vd(c: char) -> VDChar<Default31> {
+        VDChar::<Default31>::new(c).unwrap()
+    }
+
+    use alloc::vec;
+    use serde::de::value::Error;
+    use serde::de::Visitor;
+    use serde::ser::{self, Impossible};
+
+    /// A minimal non-human-readable format: integers and byte arrays are
+    /// written as raw bytes, everything else is unsupported.
+    struct Binary;
+
+    macro_rules! unsupported {
+        ($($name:ident($($ty:ty),*);)*) => {$(
+            fn $name(self, $(_: $ty),*) -> Result<Vec<u8>, Error> {
+                Err(ser::Error::custom("unsupported"))
+            }
+        )*};
+    }
+
+    impl Serializer for Binary {
+        type Ok = Vec<u8>;
+        type Error = Error;
+        type SerializeSeq = Impossible<Vec<u8>, Error>;
+        type SerializeTuple = Impossible<Vec<u8>, Error>;
+        type SerializeTupleStruct = Impossible<Vec<u8>, Error>;
+        type SerializeTupleVariant = Impossible<Vec<u8>, Error>;
+        type SerializeMap = Impossible<Vec<u8>, Error>;
+        type SerializeStruct = Impossible<Vec<u8>, Error>;
+        type SerializeStructVariant = Impossible<Vec<u8>, Error>;
+
+        fn is_human_readable(&self) -> bool {
+            false
+        }
+
+        fn serialize_u8(self, v: u8) -> Result<Vec<u8>, Error> {
+            Ok(vec![v])
+        }
+
+        fn serialize_bytes(self, v: &[u8]) -> Result<Vec<u8>, Error> {
+            Ok(v.to_vec())
+        }
+
+        unsupported! {
+            serialize_bool(bool); serialize_i8(i8); serialize_i16(i16); serialize_i32(i32);
+            serialize_i64(i64); serialize_u16(u16); serialize_u32(u32); serialize_u64(u64);
+            serialize_f32(f32); serialize_f64(f64); serialize_char(char); serialize_str(&str);
+            serialize_none(); serialize_unit(); serialize_unit_struct(&'static str);
+            serialize_unit_variant(&'static str, u32, &'static str);
+        }
+
+        fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<Vec<u8>, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _: &'static str, _: &T) -> Result<Vec<u8>, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_newtype_variant<T: ?Sized + Serialize>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<Vec<u8>, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, Error> {
+            Err(ser::Error::custom("unsupported"))
+        }
+    }
+
+    /// Reads back what [`Binary`] wrote for a single value.
+    struct BinaryDe<'a>(&'a [u8]);
+
+    impl<'de> Deserializer<'de> for BinaryDe<'de> {
+        type Error = Error;
+
+        fn is_human_readable(&self) -> bool {
+            false
+        }
+
+        fn deserialize_any<V: de::Visitor<'de>>(self, _: V) -> Result<V::Value, Error> {
+            Err(de::Error::custom("unsupported"))
+        }
+
+        fn deserialize_u8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.0 {
+                [v] => visitor.visit_u8(*v),
+                _ => Err(de::Error::invalid_length(self.0.len(), &"1 byte")),
+            }
+        }
+
+        fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_borrowed_bytes(self.0)
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u16 u32 u64 u128 f32 f64 char str string
+            byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
     }
 
     #[test]
@@ -70,35 +616,188 @@ mod tests {
         let serialized = serde_json::to_string(&c).unwrap();
         assert_eq!(serialized, "\"M\"");
 
-        let deserialized: VDChar = serde_json::from_str(&serialized).unwrap();
+        let deserialized: VDChar<Default31> = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized, c);
     }
 
     #[test]
     fn vdstring_roundtrip_json() {
-        let original: VDString = "K2Z7".parse().unwrap();
+        let original: VDString<Default31> = "K2Z7".parse().unwrap();
         let json = serde_json::to_string(&original).unwrap();
         assert_eq!(json, "\"K2Z7\"");
 
-        let decoded: VDString = serde_json::from_str(&json).unwrap();
+        let decoded: VDString<Default31> = serde_json::from_str(&json).unwrap();
         assert_eq!(decoded, original);
     }
 
+    #[test]
+    fn vdstring_deserializes_from_owned_input() {
+        let expected: VDString<Default31> = "K2Z7".parse().unwrap();
+
+        let from_reader: VDString<Default31> = serde_json::from_reader(&b"\"K2Z7\""[..]).unwrap();
+        assert_eq!(from_reader, expected);
+
+        // Escapes force serde_json to hand over an owned string.
+        let escaped: VDString<Default31> = serde_json::from_str("\"\\u004B2Z7\"").unwrap();
+        assert_eq!(escaped, expected);
+
+        let value = serde_json::Value::String("K2Z7".into());
+        assert_eq!(serde_json::from_value::<VDString<Default31>>(value).unwrap(), expected);
+        assert!(serde_json::from_reader::<_, VDString<Default31>>(&b"\"K2O\""[..]).is_err());
+    }
+
     #[test]
     fn invalid_vdchar_fails() {
-        let err = serde_json::from_str::<VDChar>("\"O\"");
+        let err = serde_json::from_str::<VDChar<Default31>>("\"O\"");
         assert!(err.is_err());
 
-        let err2 = serde_json::from_str::<VDChar>("\"!\"");
+        let err2 = serde_json::from_str::<VDChar<Default31>>("\"!\"");
         assert!(err2.is_err());
     }
 
     #[test]
     fn invalid_vdstring_fails() {
-        let err = serde_json::from_str::<VDString>("\"ABCO\"");
+        let err = serde_json::from_str::<VDString<Default31>>("\"ABCO\"");
         assert!(err.is_err());
 
-        let err2 = serde_json::from_str::<VDString>("\"abc\"");
+        let err2 = serde_json::from_str::<VDString<Default31>>("\"abc\"");
         assert!(err2.is_err());
     }
+
+    #[test]
+    fn nonempty_vdstring_rejects_empty() {
+        let code: crate::NonEmptyVDString<Default31> = serde_json::from_str("\"K2Z7\"").unwrap();
+        assert_eq!(serde_json::to_string(&code).unwrap(), "\"K2Z7\"");
+
+        let err = serde_json::from_str::<crate::NonEmptyVDString<Default31>>("\"\"").unwrap_err();
+        assert!(alloc::string::ToString::to_string(&err).contains("empty"), "{}", err);
+        assert!(serde_json::from_str::<crate::NonEmptyVDString<Default31>>("\"K2O\"").is_err());
+    }
+
+    #[test]
+    fn shared_vdstring_roundtrip_json() {
+        let code: crate::VDSharedString<Default31> = serde_json::from_str("\"K2Z7\"").unwrap();
+        assert_eq!(serde_json::to_string(&code).unwrap(), "\"K2Z7\"");
+        assert!(serde_json::from_str::<crate::VDSharedString<Default31>>("\"K2O\"").is_err());
+    }
+
+    #[test]
+    fn bounded_vdstring_checks_length() {
+        type Code = crate::VDStringBounded<4, 6, Default31>;
+        let code: Code = serde_json::from_str("\"K2Z7\"").unwrap();
+        assert_eq!(serde_json::to_string(&code).unwrap(), "\"K2Z7\"");
+        assert!(serde_json::from_str::<Code>("\"K2Z\"").is_err());
+        assert!(serde_json::from_str::<Code>("\"K2Z7K2Z\"").is_err());
+    }
+
+    #[test]
+    fn vdarraystring_roundtrip_json() {
+        let original: VDArrayString<6> = "K2Z7".parse().unwrap();
+        let json = serde_json::to_string(&original).unwrap();
+        assert_eq!(json, "\"K2Z7\"");
+        assert_eq!(serde_json::from_str::<VDArrayString<6>>(&json).unwrap(), original);
+
+        assert!(serde_json::from_str::<VDArrayString<3>>(&json).is_err());
+        assert!(serde_json::from_str::<VDArrayString<6>>("\"K2O\"").is_err());
+    }
+
+    #[test]
+    fn binary_formats_use_indices() {
+        let ch = vd('M');
+        assert_eq!(ch.serialize(Binary).unwrap(), [10]);
+        assert_eq!(VDChar::<Default31>::deserialize(BinaryDe(&[10])).unwrap(), ch);
+        assert!(VDChar::<crate::alphabet::Default31>::deserialize(BinaryDe(&[31])).is_err());
+
+        let code: VDString<Default31> = "K2Z9".parse().unwrap();
+        let bytes = code.serialize(Binary).unwrap();
+        assert_eq!(bytes, [9, 23, 22, 30]);
+        assert_eq!(VDString::<Default31>::deserialize(BinaryDe(&bytes)).unwrap(), code);
+        assert!(VDString::<crate::alphabet::Default31>::deserialize(BinaryDe(&[9, 99])).is_err());
+
+        let array: VDArrayString<4> = "K2Z9".parse().unwrap();
+        assert_eq!(array.serialize(Binary).unwrap(), bytes);
+        assert_eq!(VDArrayString::<4>::deserialize(BinaryDe(&bytes)).unwrap(), array);
+        assert!(VDArrayString::<3>::deserialize(BinaryDe(&bytes)).is_err());
+
+        let shared = crate::VDSharedString::from(code.clone());
+        assert_eq!(shared.serialize(Binary).unwrap(), bytes);
+        assert_eq!(crate::VDSharedString::deserialize(BinaryDe(&bytes)).unwrap(), shared);
+
+        let fixed: VDStringFixed<4> = "K2Z9".parse().unwrap();
+        assert_eq!(fixed.serialize(Binary).unwrap(), bytes);
+        assert_eq!(VDStringFixed::<4>::deserialize(BinaryDe(&bytes)).unwrap(), fixed);
+        assert!(VDStringFixed::<3>::deserialize(BinaryDe(&bytes)).is_err());
+        assert!(VDStringFixed::<5>::deserialize(BinaryDe(&bytes)).is_err());
+    }
+
+    #[test]
+    fn vdstringfixed_roundtrip_json() {
+        let original: VDStringFixed<4> = "K2Z7".parse().unwrap();
+        let json = serde_json::to_string(&original).unwrap();
+        assert_eq!(json, "\"K2Z7\"");
+        assert_eq!(serde_json::from_str::<VDStringFixed<4>>(&json).unwrap(), original);
+
+        let err = serde_json::from_str::<VDStringFixed<6>>(&json).unwrap_err();
+        assert!(alloc::string::ToString::to_string(&err).contains("expected 6 characters, found 4"), "{}", err);
+        assert!(serde_json::from_str::<VDStringFixed<4>>("\"K2O7\"").is_err());
+
+        let value = serde::de::value::SeqDeserializer::<_, Error>::new([9u8, 23, 22].into_iter());
+        let err = FixedStringVisitor::<2>.visit_seq(value).unwrap_err();
+        assert!(alloc::string::ToString::to_string(&err).contains("found 3"), "{}", err);
+    }
+
+    #[test]
+    fn binary_sequences_of_indices_are_accepted() {
+        let value = serde::de::value::SeqDeserializer::<_, Error>::new([9u8, 23].into_iter());
+        let code: VDString<Default31> = StringVisitor(PhantomData).visit_seq(value).unwrap();
+        assert_eq!(code.as_str(), "K2");
+    }
+
+    #[cfg(feature = "generate")]
+    #[test]
+    fn generator_config_roundtrips_json() {
+        use crate::{vdstr, StepRng, VDGenerator};
+
+        let generator = VDGenerator::<Default31>::default()
+            .length(10)
+            .no_adjacent_repeats()
+            .prefix(&vdstr!("EU"))
+            .exclude_chars(&[vd('B'), vd('8')])
+            .deny_substrings(&[vdstr!("FEE")])
+            .avoid(&[vdstr!("EU29XY7KM3")], 2)
+            .no_sequential_runs(3)
+            .min_digits(2);
+        let json = serde_json::to_string(&generator).unwrap();
+        let restored: VDGenerator<Default31> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        let mut a = StepRng::new(3, 11);
+        let mut b = StepRng::new(3, 11);
+        assert_eq!(generator.generate(&mut a), restored.generate(&mut b));
+    }
+
+    #[cfg(feature = "generate")]
+    #[test]
+    fn generator_config_defaults_missing_settings() {
+        use crate::VDGenerator;
+
+        let generator: VDGenerator<Default31> = serde_json::from_str(r#"{"length": 8, "prefix": "K"}"#).unwrap();
+        let expected = VDGenerator::<Default31>::default().length(8).prefix(&"K".parse::<VDString<Default31>>().unwrap());
+        assert_eq!(serde_json::to_value(&generator).unwrap(), serde_json::to_value(&expected).unwrap());
+
+        let value = serde_json::to_value(VDGenerator::<Default31>::default()).unwrap();
+        assert_eq!(value["length"], 6);
+        assert_eq!(value["no_sequential_runs"], serde_json::Value::Null);
+        assert_eq!(value["deny_substrings"], serde_json::json!([]));
+    }
+
+    #[cfg(feature = "generate")]
+    #[test]
+    fn generator_config_rejects_bad_settings() {
+        use crate::VDGenerator;
+
+        assert!(serde_json::from_str::<VDGenerator>(r#"{"no_repeat": true}"#).is_err());
+        assert!(serde_json::from_str::<VDGenerator>(r#"{"prefix": "O0"}"#).is_err());
+        assert!(serde_json::from_str::<VDGenerator>(r#"{"length": -1}"#).is_err());
+    }
 }
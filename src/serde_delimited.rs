@@ -0,0 +1,167 @@
+//! Delimited lists of [`VDString`]s, for use with
+//! `#[serde(with = "vds::serde_delimited")]` on `Vec<VDString>` fields.
+//!
+//! Some formats deliver a batch of codes as one string rather than an
+//! array, such as a CSV cell holding `"AB29,XK73"` or a webhook field with
+//! one code per line. Fields marked with this module accept codes separated
+//! by commas, newlines, or both. Whitespace around each code and empty
+//! entries, such as from a trailing newline, are ignored, and each code is
+//! parsed strictly.
+//!
+//! Serialization writes the codes joined by commas, or by newlines with
+//! [`newline`]. Non-human-readable formats use a plain sequence instead.
+//!
+//! This module is only available when the `serde` and `alloc` features are
+//! enabled.
+//!
+//! # Examples
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use vds::VDString;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Batch {
+//!     #[serde(with = "vds::serde_delimited")]
+//!     codes: Vec<VDString>,
+//! }
+//!
+//! let batch: Batch = serde_json::from_str(r#"{"codes": "AB29, XK73\nK7M3\n"}"#).unwrap();
+//! assert_eq!(batch.codes, ["AB29", "XK73", "K7M3"]);
+//! assert_eq!(serde_json::to_string(&batch).unwrap(), r#"{"codes":"AB29,XK73,K7M3"}"#);
+//! ```
+
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::alphabet::Default31;
+use crate::{VDChar, VDString};
+
+/// Serializes `codes` as one comma-separated string.
+pub fn serialize<S: Serializer>(codes: &[VDString<Default31>], serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_joined(codes, ',', serializer)
+}
+
+/// Deserializes a list of [`VDString`]s from a comma- or newline-separated
+/// string.
+///
+/// # Errors
+/// Returns a deserialization error if the input is not a string, or if any
+/// entry is not a valid [`VDString`]. The error names the entry's position
+/// in the list.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<VDString<Default31>>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(DelimitedVisitor)
+    } else {
+        Vec::deserialize(deserializer)
+    }
+}
+
+fn serialize_joined<S: Serializer>(codes: &[VDString<Default31>], sep: char, serializer: S) -> Result<S::Ok, S::Error> {
+    if !serializer.is_human_readable() {
+        return codes.serialize(serializer);
+    }
+    let mut joined = String::with_capacity(codes.iter().map(|code| code.len() + 1).sum());
+    for (i, code) in codes.iter().enumerate() {
+        if i > 0 {
+            joined.push(sep);
+        }
+        joined.extend(code.iter().map(VDChar::as_char));
+    }
+    serializer.serialize_str(&joined)
+}
+
+struct DelimitedVisitor;
+
+impl de::Visitor<'_> for DelimitedVisitor {
+    type Value = Vec<VDString<Default31>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a comma- or newline-separated list of VDStrings")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<VDString<Default31>>, E> {
+        v.split([',', '\n'])
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .enumerate()
+            .map(|(i, entry)| {
+                entry
+                    .parse()
+                    .map_err(|err| E::custom(format_args!("invalid VDString {:?} at entry {}: {}", entry, i, err)))
+            })
+            .collect()
+    }
+}
+
+/// Like the parent module, but serializes one code per line, for use with
+/// `#[serde(with = "vds::serde_delimited::newline")]`.
+///
+/// Deserialization is the same, so either separator is accepted.
+pub mod newline {
+    use super::*;
+
+    /// Serializes `codes` as one newline-separated string.
+    pub fn serialize<S: Serializer>(codes: &[VDString<Default31>], serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_joined(codes, '\n', serializer)
+    }
+
+    /// Deserializes a list of [`VDString`]s, as [`deserialize`](super::deserialize) does.
+    ///
+    /// # Errors
+    /// Same as [`deserialize`](super::deserialize).
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<VDString<Default31>>, D::Error> {
+        super::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Webhook {
+        #[serde(with = "crate::serde_delimited")]
+        codes: Vec<VDString<Default31>>,
+        #[serde(with = "crate::serde_delimited::newline")]
+        lines: Vec<VDString<Default31>>,
+    }
+
+    fn codes(list: &[&str]) -> Vec<VDString<Default31>> {
+        list.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn accepts_either_separator() {
+        let hook: Webhook =
+            serde_json::from_str(r#"{"codes": "AB29,XK73", "lines": " AB29\r\nXK73,\n\nK7M3\n"}"#).unwrap();
+        assert_eq!(hook.codes, codes(&["AB29", "XK73"]));
+        assert_eq!(hook.lines, codes(&["AB29", "XK73", "K7M3"]));
+
+        let hook: Webhook = serde_json::from_str(r#"{"codes": "", "lines": "\n"}"#).unwrap();
+        assert!(hook.codes.is_empty() && hook.lines.is_empty());
+    }
+
+    #[test]
+    fn serializes_joined() {
+        let hook = Webhook { codes: codes(&["AB29", "XK73"]), lines: codes(&["AB29", "XK73"]) };
+        let json = serde_json::to_string(&hook).unwrap();
+        assert_eq!(json, r#"{"codes":"AB29,XK73","lines":"AB29\nXK73"}"#);
+        assert_eq!(serde_json::from_str::<Webhook>(&json).unwrap(), hook);
+
+        let empty = Webhook { codes: Vec::new(), lines: Vec::new() };
+        assert_eq!(serde_json::to_string(&empty).unwrap(), r#"{"codes":"","lines":""}"#);
+    }
+
+    #[test]
+    fn reports_bad_entry() {
+        let err = serde_json::from_str::<Webhook>(r#"{"codes": "AB29,,XK7O", "lines": ""}"#).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("\"XK7O\"") && msg.contains("entry 1"), "{}", msg);
+        assert!(serde_json::from_str::<Webhook>(r#"{"codes": ["AB29"], "lines": ""}"#).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Lenient deserialization of [`VDString`] fields, for use with
+//! `#[serde(with = "vds::serde_lenient")]`.
+//!
+//! The `Deserialize` impl of [`VDString`] is strict. Fields marked with this
+//! module instead accept input as [`ParseOptions::lenient`] does: lowercase,
+//! look-alike characters, and separators such as `"ab29-xy7k"` all become
+//! the canonical `"AB29XY7K"`. Serialization always writes the canonical
+//! form, and non-human-readable formats stay strict.
+//!
+//! This module is only available when the `serde` and `alloc` features are
+//! enabled.
+//!
+//! # Examples
+//! ```
+//! use serde::Deserialize;
+//! use vds::VDString;
+//!
+//! #[derive(Deserialize)]
+//! struct Redeem {
+//!     #[serde(with = "vds::serde_lenient")]
+//!     code: VDString,
+//!     #[serde(default, with = "vds::serde_lenient::option")]
+//!     referral: Option<VDString>,
+//! }
+//!
+//! let req: Redeem = serde_json::from_str(r#"{"code": "ab29-xy7k", "referral": "k7m3"}"#).unwrap();
+//! assert_eq!(&*req.code, "AB29XY7K");
+//! assert_eq!(req.referral.unwrap(), "K7M3");
+//! ```
+
+use core::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::alphabet::Default31;
+use crate::{ParseOptions, VDString};
+
+/// Serializes `code` in its canonical form, exactly as [`VDString`]'s own
+/// `Serialize` impl does.
+pub fn serialize<S: Serializer>(code: &VDString<Default31>, serializer: S) -> Result<S::Ok, S::Error> {
+    code.serialize(serializer)
+}
+
+/// Deserializes a [`VDString`], parsing strings with
+/// [`ParseOptions::lenient`].
+///
+/// # Errors
+/// Returns a deserialization error if the input is not a string, or still
+/// contains a character outside the alphabet after folding.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<VDString<Default31>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(LenientVisitor)
+    } else {
+        VDString::deserialize(deserializer)
+    }
+}
+
+struct LenientVisitor;
+
+impl de::Visitor<'_> for LenientVisitor {
+    type Value = VDString<Default31>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a VDString, possibly lowercase or with separators")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<VDString<Default31>, E> {
+        VDString::parse_with(v, ParseOptions::lenient())
+            .map_err(|err| E::custom(format_args!("invalid VDString {:?}: {}", v, err)))
+    }
+}
+
+/// Lenient deserialization of `Option<VDString>` fields, for use with
+/// `#[serde(default, with = "vds::serde_lenient::option")]`.
+pub mod option {
+    use super::*;
+
+    /// Serializes `Some` codes in canonical form, and `None` as none.
+    pub fn serialize<S: Serializer>(code: &Option<VDString<Default31>>, serializer: S) -> Result<S::Ok, S::Error> {
+        code.serialize(serializer)
+    }
+
+    /// Deserializes an optional [`VDString`], as [`deserialize`](super::deserialize) does.
+    ///
+    /// # Errors
+    /// Same as [`deserialize`](super::deserialize).
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<VDString<Default31>>, D::Error> {
+        #[derive(Deserialize)]
+        struct Lenient(#[serde(with = "super")] VDString<Default31>);
+
+        Option::<Lenient>::deserialize(deserializer).map(|code| code.map(|Lenient(code)| code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use alloc::string::ToString;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Form {
+        #[serde(with = "crate::serde_lenient")]
+        code: VDString<Default31>,
+        #[serde(default, with = "crate::serde_lenient::option")]
+        backup: Option<VDString<Default31>>,
+    }
+
+    fn code(s: &str) -> VDString<Default31> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn accepts_sloppy_input() {
+        let form: Form = serde_json::from_str(r#"{"code": " ab29-xy7k ", "backup": "k7 m3"}"#).unwrap();
+        assert_eq!(form, Form { code: code("AB29XY7K"), backup: Some(code("K7M3")) });
+
+        let form: Form = serde_json::from_str(r#"{"code": "AB29", "backup": null}"#).unwrap();
+        assert_eq!(form.backup, None);
+        let form: Form = serde_json::from_str(r#"{"code": "AB29"}"#).unwrap();
+        assert_eq!(form.backup, None);
+    }
+
+    #[test]
+    fn serializes_canonical_form() {
+        let form = Form { code: code("AB29XY7K"), backup: None };
+        assert_eq!(serde_json::to_string(&form).unwrap(), r#"{"code":"AB29XY7K","backup":null}"#);
+    }
+
+    #[test]
+    fn rejects_what_folding_cannot_fix() {
+        let err = serde_json::from_str::<Form>(r#"{"code": "ab2?"}"#).unwrap_err();
+        assert!(err.to_string().contains("ab2?"), "{}", err);
+        assert!(serde_json::from_str::<Form>(r#"{"code": 42}"#).is_err());
+        assert!(serde_json::from_str::<VDString<Default31>>(r#""ab29""#).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+//! SHA-1 and HMAC-SHA1, as required by HOTP/TOTP (RFC 4226, RFC 6238).
+//!
+//! Implemented here, like the SipHash in `siphash`, to keep the crate
+//! `no_std` and dependency-free. SHA-1 is broken for collision resistance,
+//! but HMAC-SHA1 remains sound as the PRF the OTP standards specify.
+
+const BLOCK: usize = 64;
+
+/// Length of a SHA-1 digest in bytes.
+pub(crate) const DIGEST_LEN: usize = 20;
+
+struct Sha1 {
+    state: [u32; 5],
+    block: [u8; BLOCK],
+    filled: usize,
+    len: u64,
+}
+
+impl Sha1 {
+    fn new() -> Self {
+        Self {
+            state: [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476, 0xc3d2_e1f0],
+            block: [0; BLOCK],
+            filled: 0,
+            len: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.len += data.len() as u64;
+        while !data.is_empty() {
+            let take = (BLOCK - self.filled).min(data.len());
+            self.block[self.filled..self.filled + take].copy_from_slice(&data[..take]);
+            self.filled += take;
+            data = &data[take..];
+            if self.filled == BLOCK {
+                self.compress();
+                self.filled = 0;
+            }
+        }
+    }
+
+    fn finish(mut self) -> [u8; DIGEST_LEN] {
+        let bits = self.len.wrapping_mul(8);
+        self.update(&[0x80]);
+        while self.filled != BLOCK - 8 {
+            self.update(&[0]);
+        }
+        self.update(&bits.to_be_bytes());
+
+        let mut out = [0; DIGEST_LEN];
+        for (chunk, word) in out.chunks_exact_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+
+    fn compress(&mut self) {
+        let mut w = [0u32; 80];
+        for (i, chunk) in self.block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = self.state;
+        for (i, &wi) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+                _ => (b ^ c ^ d, 0xca62_c1d6),
+            };
+            let t = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(wi);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = t;
+        }
+        for (s, v) in self.state.iter_mut().zip([a, b, c, d, e]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+}
+
+/// Computes HMAC-SHA1 of `message` under `key` (RFC 2104).
+pub(crate) fn hmac_sha1(key: &[u8], message: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut block_key = [0u8; BLOCK];
+    if key.len() > BLOCK {
+        let mut h = Sha1::new();
+        h.update(key);
+        block_key[..DIGEST_LEN].copy_from_slice(&h.finish());
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha1::new();
+    inner.update(&block_key.map(|b| b ^ 0x36));
+    inner.update(message);
+    let inner = inner.finish();
+
+    let mut outer = Sha1::new();
+    outer.update(&block_key.map(|b| b ^ 0x5c));
+    outer.update(&inner);
+    outer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha1(data: &[u8]) -> [u8; DIGEST_LEN] {
+        let mut h = Sha1::new();
+        h.update(data);
+        h.finish()
+    }
+
+    fn hex(bytes: &[u8]) -> [u8; 2 * DIGEST_LEN] {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut out = [0; 2 * DIGEST_LEN];
+        for (i, &b) in bytes.iter().enumerate() {
+            out[2 * i] = DIGITS[usize::from(b >> 4)];
+            out[2 * i + 1] = DIGITS[usize::from(b & 0xf)];
+        }
+        out
+    }
+
+    #[test]
+    fn sha1_matches_reference_vectors() {
+        // FIPS 180 examples.
+        assert_eq!(&hex(&sha1(b"")), b"da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(&hex(&sha1(b"abc")), b"a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            &hex(&sha1(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+            b"84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+        let mut h = Sha1::new();
+        for _ in 0..10_000 {
+            h.update(&[b'a'; 100]);
+        }
+        assert_eq!(&hex(&h.finish()), b"34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+    }
+
+    #[test]
+    fn hmac_matches_rfc_2202() {
+        assert_eq!(&hex(&hmac_sha1(&[0x0b; 20], b"Hi There")), b"b617318655057264e28bc0b6fb378c8ef146be00");
+        assert_eq!(
+            &hex(&hmac_sha1(b"Jefe", b"what do ya want for nothing?")),
+            b"effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+        );
+        assert_eq!(
+            &hex(&hmac_sha1(&[0xaa; 80], b"Test Using Larger Than Block-Size Key - Hash Key First")),
+            b"aa4ae5e15272d00e95705637ce8a3b55ed402112"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+extern crate alloc;
+use alloc::sync::Arc;
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::alphabet::{Alphabet, DefaultAlphabet};
+use crate::{VDChar, VDStr, VDString, VDStringError};
+
+/// An immutable, reference-counted [`VDString`] that is cheap to clone.
+///
+/// Cloning a `VDString` copies its characters and, with the `str-cache`
+/// feature, its rendered string. Cloning a `VDSharedString` only bumps a
+/// reference count, so codes passed through channels, tasks, and caches
+/// are never copied. The characters are stored once, in an `Arc`.
+///
+/// Dereferences to [`VDStr`], and hashes, compares, and orders like it, so
+/// a `HashMap<VDSharedString, _>` can be looked up by `&VDStr`.
+///
+/// This type is only available on targets with atomic pointers.
+///
+/// # Examples
+/// ```
+/// use vds::{VDSharedString, VDString};
+///
+/// let code: VDString = "AB29XY".parse().unwrap();
+/// let shared = VDSharedString::from(code);
+/// let copy = shared.clone();
+/// assert!(VDSharedString::ptr_eq(&shared, &copy));
+/// assert_eq!(copy, "AB29XY");
+/// assert_eq!(copy.to_string(), "AB29XY");
+/// ```
+#[derive(Clone)]
+pub struct VDSharedString<A: Alphabet = DefaultAlphabet> {
+    // An `Arc<[VDChar]>` viewed as a `VDStr` on access, since converting it
+    // to an `Arc<VDStr>` would take `unsafe`.
+    chars: Arc<[VDChar<A>]>,
+}
+
+impl<A: Alphabet> VDSharedString<A> {
+    /// Borrows the characters as a [`VDStr`].
+    pub fn as_vdstr(&self) -> &VDStr<A> {
+        VDStr::from_vdchars(&self.chars)
+    }
+
+    /// Returns `true` if `a` and `b` share the same allocation.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.chars, &b.chars)
+    }
+
+    /// Copies the characters into an owned [`VDString`].
+    pub fn to_vdstring(&self) -> VDString<A> {
+        VDString::from(self.as_vdstr())
+    }
+}
+
+impl<A: Alphabet> Deref for VDSharedString<A> {
+    type Target = VDStr<A>;
+
+    fn deref(&self) -> &VDStr<A> {
+        self.as_vdstr()
+    }
+}
+
+impl<A: Alphabet> Borrow<VDStr<A>> for VDSharedString<A> {
+    fn borrow(&self) -> &VDStr<A> {
+        self.as_vdstr()
+    }
+}
+
+impl<A: Alphabet> AsRef<VDStr<A>> for VDSharedString<A> {
+    fn as_ref(&self) -> &VDStr<A> {
+        self.as_vdstr()
+    }
+}
+
+impl<A: Alphabet> From<VDString<A>> for VDSharedString<A> {
+    fn from(code: VDString<A>) -> Self {
+        Self::from(code.as_vdstr())
+    }
+}
+
+impl<A: Alphabet> From<&VDStr<A>> for VDSharedString<A> {
+    fn from(code: &VDStr<A>) -> Self {
+        Self { chars: Arc::from(code.as_vdchars()) }
+    }
+}
+
+impl<A: Alphabet> From<VDSharedString<A>> for VDString<A> {
+    fn from(code: VDSharedString<A>) -> Self {
+        code.to_vdstring()
+    }
+}
+
+impl<A: Alphabet> FromStr for VDSharedString<A> {
+    type Err = VDStringError;
+
+    /// Parses a `&str` like [`VDString`] does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<VDString<A>>().map(Self::from)
+    }
+}
+
+impl<A: Alphabet> TryFrom<&str> for VDSharedString<A> {
+    type Error = VDStringError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<A: Alphabet> PartialEq for VDSharedString<A> {
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other) || self.as_vdstr() == other.as_vdstr()
+    }
+}
+
+impl<A: Alphabet> Eq for VDSharedString<A> {}
+
+impl<A: Alphabet> PartialEq<str> for VDSharedString<A> {
+    fn eq(&self, other: &str) -> bool {
+        *self.as_vdstr() == *other
+    }
+}
+
+impl<A: Alphabet> PartialEq<&str> for VDSharedString<A> {
+    fn eq(&self, other: &&str) -> bool {
+        *self.as_vdstr() == **other
+    }
+}
+
+impl<A: Alphabet> PartialOrd for VDSharedString<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Alphabet> Ord for VDSharedString<A> {
+    /// Orders like the borrowed [`VDStr`], i.e. by alphabet index.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_vdstr().cmp(other.as_vdstr())
+    }
+}
+
+impl<A: Alphabet> Hash for VDSharedString<A> {
+    /// Hashes like the borrowed [`VDStr`], as required by `Borrow`.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_vdstr().hash(state);
+    }
+}
+
+impl<A: Alphabet> fmt::Display for VDSharedString<A> {
+    /// Honors width, fill, alignment, and precision like a `str`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_vdstr(), f)
+    }
+}
+
+impl<A: Alphabet> fmt::Debug for VDSharedString<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VDSharedString").field(&self.as_vdstr()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Default31;
+    use alloc::collections::BTreeSet;
+
+    #[test]
+    fn clones_share_storage() {
+        let shared: VDSharedString<Default31> = "K7M3".parse().unwrap();
+        let copy = shared.clone();
+        assert!(VDSharedString::ptr_eq(&shared, &copy));
+
+        let separate = VDSharedString::from(shared.to_vdstring());
+        assert!(!VDSharedString::ptr_eq(&shared, &separate));
+        assert_eq!(shared, separate);
+        assert_eq!(VDString::from(separate), "K7M3");
+        assert_eq!("K7O".parse::<VDSharedString<Default31>>(), Err(VDStringError::InvalidChar { ch: 'O', offset: 2 }));
+    }
+
+    #[test]
+    fn looks_up_by_vdstr() {
+        let set: BTreeSet<VDSharedString<Default31>> = ["AB29", "K7M3"].iter().map(|s| s.parse().unwrap()).collect();
+        let key: VDString<Default31> = "K7M3".parse().unwrap();
+        assert!(set.contains(key.as_vdstr()));
+        assert!(!set.contains(&key[..2]));
+    }
+}
@@ -0,0 +1,263 @@
+//! Simulated channel errors, for comparing check-character and
+//! error-correction schemes before choosing one.
+//!
+//! An [`ErrorModel`] corrupts codes the way a channel would: substituting,
+//! transposing, and deleting characters at configurable rates, with
+//! substitutions optionally drawn from a confusion table such as
+//! [`VISUAL_CONFUSIONS`](crate::distance::VISUAL_CONFUSIONS). [`run`] issues
+//! codes, corrupts them, and tallies what a scheme's decoder made of each
+//! one in [`Outcomes`].
+//!
+//! Everything is deterministic given the [`VDRng`], so a comparison can be
+//! reproduced from a seed.
+//!
+//! This module is only available when the `generate` feature is enabled.
+//!
+//! # Examples
+//! ```
+//! use vds::simulate::{self, ErrorModel};
+//! use vds::{StepRng, VDGenerator, VDString};
+//!
+//! let model = ErrorModel::new().substitution(0.05).transposition(0.02);
+//! let generator = VDGenerator::new().length(8).build().unwrap();
+//! let mut rng = StepRng::new(1, 0x9e37_79b9_7f4a_7c15);
+//!
+//! // A Damm check character: detects errors, corrects none.
+//! let outcomes = simulate::run(
+//!     &model,
+//!     1000,
+//!     &mut rng,
+//!     |rng| generator.generate(rng).unwrap().with_check_char(),
+//!     |received| Some(received.to_owned()).filter(VDString::verify_check_char),
+//! );
+//! assert_eq!(outcomes.trials, 1000);
+//! assert!(outcomes.undetected <= outcomes.detected);
+//! ```
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::alphabet::Alphabet;
+use crate::rng::{uniform_index, VDRng};
+use crate::{VDChar, VDStr, VDString};
+
+/// How a channel corrupts codes.
+///
+/// Each rate is the probability per character, from `0.0` to `1.0`.
+/// Characters are visited left to right: each is deleted, or else swapped
+/// with the next one, or else substituted, at most one of the three.
+///
+/// Not to be confused with [`distance::ErrorModel`](crate::distance::ErrorModel),
+/// which scores errors rather than injecting them.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ErrorModel {
+    substitution: f64,
+    transposition: f64,
+    deletion: f64,
+    confusions: &'static [(char, char, f64)],
+}
+
+impl ErrorModel {
+    /// Creates a model of an error-free channel.
+    pub const fn new() -> Self {
+        Self { substitution: 0.0, transposition: 0.0, deletion: 0.0, confusions: &[] }
+    }
+
+    /// Sets the probability that a character is replaced by another.
+    pub const fn substitution(mut self, rate: f64) -> Self {
+        self.substitution = rate;
+        self
+    }
+
+    /// Sets the probability that a character is swapped with the next one.
+    pub const fn transposition(mut self, rate: f64) -> Self {
+        self.transposition = rate;
+        self
+    }
+
+    /// Sets the probability that a character is dropped.
+    pub const fn deletion(mut self, rate: f64) -> Self {
+        self.deletion = rate;
+        self
+    }
+
+    /// Draws substitutions from `table`, in the format of
+    /// [`VISUAL_CONFUSIONS`](crate::distance::VISUAL_CONFUSIONS).
+    ///
+    /// A character is replaced by one of its partners in the table, each
+    /// weighted by `1.0 - cost`. Characters without a partner are replaced
+    /// uniformly, as they are without a table.
+    pub const fn confusions(mut self, table: &'static [(char, char, f64)]) -> Self {
+        self.confusions = table;
+        self
+    }
+
+    /// Returns a corrupted copy of `code`.
+    pub fn corrupt<A: Alphabet, R: VDRng + ?Sized>(&self, code: &VDStr<A>, rng: &mut R) -> VDString<A> {
+        let mut chars: Vec<VDChar<A>> = Vec::with_capacity(code.len());
+        let mut input = code.as_vdchars().iter().copied().peekable();
+        while let Some(c) = input.next() {
+            if chance(rng, self.deletion) {
+                continue;
+            }
+            if let Some(&next) = input.peek() {
+                if chance(rng, self.transposition) {
+                    input.next();
+                    chars.extend([next, c]);
+                    continue;
+                }
+            }
+            if chance(rng, self.substitution) {
+                chars.push(self.substitute(c, rng));
+            } else {
+                chars.push(c);
+            }
+        }
+        VDString::new(chars)
+    }
+
+    fn substitute<A: Alphabet, R: VDRng + ?Sized>(&self, c: VDChar<A>, rng: &mut R) -> VDChar<A> {
+        let partner = |&(a, b, cost): &(char, char, f64)| {
+            let other = if a == c.as_char() {
+                b
+            } else if b == c.as_char() {
+                a
+            } else {
+                return None;
+            };
+            VDChar::<A>::from_char(other).map(|other| (other, 1.0 - cost)).filter(|&(_, weight)| weight > 0.0)
+        };
+        let total: f64 = self.confusions.iter().filter_map(partner).map(|(_, weight)| weight).sum();
+        if total > 0.0 {
+            let mut target = unit(rng) * total;
+            for (other, weight) in self.confusions.iter().filter_map(partner) {
+                if target < weight {
+                    return other;
+                }
+                target -= weight;
+            }
+        }
+
+        let n = A::CHARS.len() as u32;
+        if n < 2 {
+            return c;
+        }
+        // Draw from the other n - 1 characters.
+        let index = uniform_index(rng, n - 1);
+        let index = if index >= u32::from(c.index()) { index + 1 } else { index };
+        VDChar::from_index_unchecked(index as u8)
+    }
+}
+
+/// Returns a uniform draw from `[0, 1)`.
+fn unit<R: VDRng + ?Sized>(rng: &mut R) -> f64 {
+    f64::from(rng.draw_u32()) / 4_294_967_296.0
+}
+
+fn chance<R: VDRng + ?Sized>(rng: &mut R, rate: f64) -> bool {
+    rate > 0.0 && unit(rng) < rate
+}
+
+/// Tally of a [`run`]: what became of each corrupted code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Outcomes {
+    /// Number of codes issued.
+    pub trials: u64,
+    /// Codes that crossed the channel unchanged.
+    pub clean: u64,
+    /// Corrupted codes the decoder rejected.
+    pub detected: u64,
+    /// Corrupted codes the decoder restored to the issued code.
+    pub corrected: u64,
+    /// Corrupted codes the decoder accepted as some other code.
+    pub undetected: u64,
+}
+
+impl Outcomes {
+    /// Returns the fraction of corrupted codes that were accepted as the
+    /// wrong code, or `0.0` if none were corrupted.
+    pub fn undetected_rate(&self) -> f64 {
+        let corrupted = self.trials - self.clean;
+        if corrupted == 0 {
+            0.0
+        } else {
+            self.undetected as f64 / corrupted as f64
+        }
+    }
+}
+
+/// Issues `trials` codes, sends each through `model`, and tallies how
+/// `decode` handles the result.
+///
+/// `issue` creates a code as the scheme under test would, e.g. with its
+/// check characters appended. `decode` returns the code the scheme
+/// recovers from a received one, or `None` if it rejects it.
+pub fn run<A, R, I, D>(model: &ErrorModel, trials: u64, rng: &mut R, mut issue: I, mut decode: D) -> Outcomes
+where
+    A: Alphabet,
+    R: VDRng + ?Sized,
+    I: FnMut(&mut R) -> VDString<A>,
+    D: FnMut(&VDStr<A>) -> Option<VDString<A>>,
+{
+    let mut outcomes = Outcomes { trials, ..Outcomes::default() };
+    for _ in 0..trials {
+        let sent = issue(rng);
+        let received = model.corrupt(&sent, rng);
+        if received == sent {
+            outcomes.clean += 1;
+            continue;
+        }
+        match decode(&received) {
+            None => outcomes.detected += 1,
+            Some(decoded) if decoded == sent => outcomes.corrected += 1,
+            Some(_) => outcomes.undetected += 1,
+        }
+    }
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Default31;
+    use crate::distance::VISUAL_CONFUSIONS;
+    use crate::StepRng;
+
+    fn code(s: &str) -> VDString<Default31> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn error_free_and_certain_channels() {
+        let mut rng = StepRng::new(7, 0x9e37_79b9_7f4a_7c15);
+        let code = code("K7M3PQ");
+        assert_eq!(ErrorModel::new().corrupt(&code, &mut rng), code);
+        assert_eq!(ErrorModel::new().deletion(1.0).corrupt(&code, &mut rng), "");
+        assert_eq!(ErrorModel::new().transposition(1.0).corrupt(&code, &mut rng), "7K3MQP");
+
+        let substituted = ErrorModel::new().substitution(1.0).corrupt(&code, &mut rng);
+        assert!(substituted.iter().zip(code.iter()).all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn confusions_pick_partners() {
+        let mut rng = StepRng::new(3, 0x9e37_79b9_7f4a_7c15);
+        let model = ErrorModel::new().substitution(1.0).confusions(VISUAL_CONFUSIONS);
+        for _ in 0..20 {
+            assert_eq!(model.corrupt(&code("S"), &mut rng), "5");
+            let b = model.corrupt(&code("B"), &mut rng);
+            assert!(b == "8" || b == "P", "{:?}", b);
+        }
+    }
+
+    #[test]
+    fn run_tallies_decoder_verdicts() {
+        let mut rng = StepRng::new(0, 1);
+        let model = ErrorModel::new().deletion(1.0);
+        let outcomes = run(&model, 3, &mut rng, |_| code("AB"), |received| (received.len() == 1).then(|| code("AB")));
+        assert_eq!(outcomes, Outcomes { trials: 3, clean: 0, detected: 3, corrected: 0, undetected: 0 });
+
+        let outcomes = run(&ErrorModel::new(), 2, &mut rng, |_| code("AB"), |_| None);
+        assert_eq!((outcomes.clean, outcomes.undetected_rate()), (2, 0.0));
+    }
+}
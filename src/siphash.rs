@@ -0,0 +1,112 @@
+//! SipHash-2-4, used as a keyed pseudo-random function.
+//!
+//! Implemented here so keyed features stay `no_std` and dependency-free, and
+//! so their output is stable across Rust releases (unlike `core::hash`).
+
+#[derive(Clone)]
+pub(crate) struct SipHasher {
+    v: [u64; 4],
+    tail: u64,
+    ntail: usize,
+    len: usize,
+}
+
+impl SipHasher {
+    pub(crate) fn new(key: &[u8; 16]) -> Self {
+        let mut k0 = [0; 8];
+        let mut k1 = [0; 8];
+        k0.copy_from_slice(&key[..8]);
+        k1.copy_from_slice(&key[8..]);
+        let (k0, k1) = (u64::from_le_bytes(k0), u64::from_le_bytes(k1));
+        Self {
+            v: [
+                k0 ^ 0x736f_6d65_7073_6575,
+                k1 ^ 0x646f_7261_6e64_6f6d,
+                k0 ^ 0x6c79_6765_6e65_7261,
+                k1 ^ 0x7465_6462_7974_6573,
+            ],
+            tail: 0,
+            ntail: 0,
+            len: 0,
+        }
+    }
+
+    fn round(&mut self) {
+        let v = &mut self.v;
+        v[0] = v[0].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(13) ^ v[0];
+        v[0] = v[0].rotate_left(32);
+        v[2] = v[2].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(16) ^ v[2];
+        v[0] = v[0].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(21) ^ v[0];
+        v[2] = v[2].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(17) ^ v[2];
+        v[2] = v[2].rotate_left(32);
+    }
+
+    fn compress(&mut self, m: u64) {
+        self.v[3] ^= m;
+        self.round();
+        self.round();
+        self.v[0] ^= m;
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.tail |= u64::from(b) << (8 * self.ntail);
+            self.ntail += 1;
+            if self.ntail == 8 {
+                let m = self.tail;
+                self.compress(m);
+                self.tail = 0;
+                self.ntail = 0;
+            }
+        }
+        self.len += bytes.len();
+    }
+
+    pub(crate) fn finish(mut self) -> u64 {
+        let m = self.tail | ((self.len as u64 & 0xff) << 56);
+        self.compress(m);
+        self.v[2] ^= 0xff;
+        for _ in 0..4 {
+            self.round();
+        }
+        self.v[0] ^ self.v[1] ^ self.v[2] ^ self.v[3]
+    }
+}
+
+/// Computes SipHash-2-4 of `data` under `key`.
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+pub(crate) fn siphash(key: &[u8; 16], data: &[u8]) -> u64 {
+    let mut h = SipHasher::new(key);
+    h.write(data);
+    h.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_vectors() {
+        // Test vectors from the SipHash paper: key 00..0f, messages 00..(n-1).
+        let key: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let msg: [u8; 64] = core::array::from_fn(|i| i as u8);
+        assert_eq!(siphash(&key, &msg[..0]), 0x726f_db47_dd0e_0e31);
+        assert_eq!(siphash(&key, &msg[..1]), 0x74f8_39c5_93dc_67fd);
+        assert_eq!(siphash(&key, &msg[..8]), 0x93f5_f579_9a93_2462);
+        assert_eq!(siphash(&key, &msg[..15]), 0xa129_ca61_49be_45e5);
+        assert_eq!(siphash(&key, &msg[..63]), 0x958a_324c_eb06_4572);
+    }
+
+    #[test]
+    fn incremental_writes_match_one_shot() {
+        let key = [7; 16];
+        let mut h = SipHasher::new(&key);
+        h.write(b"hello ");
+        h.write(b"world");
+        assert_eq!(h.finish(), siphash(&key, b"hello world"));
+    }
+}
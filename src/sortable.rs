@@ -0,0 +1,187 @@
+extern crate alloc;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::alphabet::Default31;
+use crate::vdchar::{VDChar, VDS_ALLOWED};
+use crate::{uniform_index, VDRng, VDString};
+
+const BASE: u64 = VDS_ALLOWED.len() as u64;
+
+/// Number of digits in [`VDS_ALLOWED`], which sort before letters in ASCII.
+const DIGITS: u8 = 8;
+
+/// Maps a base-31 digit value to the character with that rank in ASCII order.
+fn ascii_rank_to_char(rank: u8) -> VDChar<Default31> {
+    let letters = VDS_ALLOWED.len() as u8 - DIGITS;
+    if rank < DIGITS { VDChar::from_index_unchecked(letters + rank) } else { VDChar::from_index_unchecked(rank - DIGITS) }
+}
+
+/// Inverse of [`ascii_rank_to_char`].
+fn char_to_ascii_rank(ch: VDChar<Default31>) -> u8 {
+    let letters = VDS_ALLOWED.len() as u8 - DIGITS;
+    if ch.index() >= letters { ch.index() - letters } else { ch.index() + DIGITS }
+}
+
+/// Error returned by [`VDSortableId::generate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SortableIdError {
+    /// The timestamp does not fit in [`VDSortableId::TIMESTAMP_LEN`] characters.
+    TimestampOutOfRange(u64),
+}
+
+impl fmt::Display for SortableIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            SortableIdError::TimestampOutOfRange(ts) => write!(f, "timestamp {} is out of range", ts),
+        }
+    }
+}
+
+impl core::error::Error for SortableIdError {}
+
+/// A generator for time-sortable codes, in the spirit of ULIDs.
+///
+/// Each code is a fixed-width millisecond timestamp followed by random
+/// characters. The timestamp is written in base 31 with digits ordered as in
+/// ASCII (`2` < … < `9` < `A` < … < `Z`), so codes compare lexicographically
+/// by creation time as plain strings, e.g. in a database index.
+///
+/// The caller supplies the timestamp, which keeps this type usable without
+/// `std` and makes generation deterministic in tests.
+///
+/// This type is only available when the `generate` feature is enabled.
+///
+/// # Examples
+/// ```
+/// use vds::{StepRng, VDSortableId};
+///
+/// let ids = VDSortableId::new();
+/// let mut rng = StepRng::new(0, 7);
+///
+/// let earlier = ids.generate(1_700_000_000_000, &mut rng).unwrap();
+/// let later = ids.generate(1_700_000_000_001, &mut rng).unwrap();
+///
+/// assert!(earlier.as_str() < later.as_str());
+/// assert_eq!(earlier.len(), 20);
+/// assert_eq!(VDSortableId::timestamp(&later), Some(1_700_000_000_001));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VDSortableId {
+    random_len: usize,
+}
+
+impl VDSortableId {
+    /// Number of characters used for the timestamp prefix.
+    ///
+    /// Ten base-31 characters cover every millisecond for over 26,000 years,
+    /// including the full 48-bit range used by ULIDs.
+    pub const TIMESTAMP_LEN: usize = 10;
+
+    /// Creates a generator with a 10-character random suffix (about 49 bits).
+    pub fn new() -> Self {
+        Self { random_len: 10 }
+    }
+
+    /// Sets the length of the random suffix.
+    pub fn random_len(mut self, len: usize) -> Self {
+        self.random_len = len;
+        self
+    }
+
+    /// Generates a code for `timestamp_ms`, filling the suffix from `rng`.
+    ///
+    /// # Errors
+    /// Returns [`SortableIdError::TimestampOutOfRange`] if the timestamp needs
+    /// more than [`TIMESTAMP_LEN`](Self::TIMESTAMP_LEN) characters.
+    pub fn generate<R: VDRng + ?Sized>(
+        &self,
+        timestamp_ms: u64,
+        rng: &mut R,
+    ) -> Result<VDString<Default31>, SortableIdError> {
+        let mut chars = Vec::with_capacity(Self::TIMESTAMP_LEN + self.random_len);
+
+        let mut t = timestamp_ms;
+        for _ in 0..Self::TIMESTAMP_LEN {
+            chars.push(ascii_rank_to_char((t % BASE) as u8));
+            t /= BASE;
+        }
+        if t > 0 {
+            return Err(SortableIdError::TimestampOutOfRange(timestamp_ms));
+        }
+        chars.reverse();
+
+        for _ in 0..self.random_len {
+            chars.push(VDChar::from_index_unchecked(uniform_index(rng, BASE as u32) as u8));
+        }
+
+        Ok(VDString::new(chars))
+    }
+
+    /// Extracts the millisecond timestamp from a code produced by [`generate`](Self::generate).
+    ///
+    /// Returns `None` if the code is shorter than the timestamp prefix.
+    pub fn timestamp(code: &VDString<Default31>) -> Option<u64> {
+        let prefix = code.as_vdchars().get(..Self::TIMESTAMP_LEN)?;
+        Some(prefix.iter().fold(0, |acc, &ch| acc * BASE + u64::from(char_to_ascii_rank(ch))))
+    }
+}
+
+impl Default for VDSortableId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StepRng;
+
+    #[test]
+    fn ascii_ranks_follow_char_order() {
+        let mut prev = None;
+        for rank in 0..BASE as u8 {
+            let ch = ascii_rank_to_char(rank);
+            assert_eq!(char_to_ascii_rank(ch), rank);
+            if let Some(p) = prev {
+                assert!(p < ch.as_char());
+            }
+            prev = Some(ch.as_char());
+        }
+    }
+
+    #[test]
+    fn codes_sort_by_timestamp() {
+        let ids = VDSortableId::new();
+        let mut rng = StepRng::new(u64::MAX, u64::MAX / 3);
+        let times = [0, 1, 30, 31, 32, 1_000_000, 1 << 40, (1 << 48) - 1];
+        let codes: Vec<VDString<Default31>> =
+            times.iter().map(|&t| ids.generate(t, &mut rng).unwrap()).collect();
+
+        for pair in codes.windows(2) {
+            assert!(pair[0].as_str() < pair[1].as_str(), "{} !< {}", pair[0], pair[1]);
+        }
+        for (code, &t) in codes.iter().zip(&times) {
+            assert_eq!(VDSortableId::timestamp(code), Some(t));
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_timestamps() {
+        let mut rng = StepRng::new(0, 1);
+        assert_eq!(
+            VDSortableId::new().generate(u64::MAX, &mut rng),
+            Err(SortableIdError::TimestampOutOfRange(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn suffix_length_is_configurable() {
+        let mut rng = StepRng::new(0, 1);
+        let code = VDSortableId::new().random_len(4).generate(5, &mut rng).unwrap();
+        assert_eq!(code.len(), VDSortableId::TIMESTAMP_LEN + 4);
+        assert_eq!(VDSortableId::timestamp(&VDString::<Default31>::new(Vec::new())), None);
+    }
+}
@@ -0,0 +1,103 @@
+//! [`sqlx`] support for [`VDString`].
+//!
+//! This module is only available when the `sqlx` feature is enabled.
+//!
+//! A [`VDString`] is stored as text, in whatever column type the driver
+//! uses for `String`, so it can be bound as a query parameter and read
+//! back from a row with any database. Decoding validates the text: a
+//! column holding characters outside the alphabet is a decode error, not
+//! a panic or an invalid code.
+//!
+//! # Examples
+//! ```
+//! use sqlx::{Connection, SqliteConnection};
+//! use vds::VDString;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), sqlx::Error> {
+//! let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+//! sqlx::query("CREATE TABLE codes (code TEXT NOT NULL)").execute(&mut conn).await?;
+//!
+//! let code: VDString = "K7M3PQ".parse().unwrap();
+//! sqlx::query("INSERT INTO codes (code) VALUES (?)").bind(&code).execute(&mut conn).await?;
+//!
+//! let fetched: VDString = sqlx::query_scalar("SELECT code FROM codes").fetch_one(&mut conn).await?;
+//! assert_eq!(fetched, code);
+//! # Ok(())
+//! # }
+//! ```
+
+extern crate alloc;
+use alloc::string::{String, ToString};
+
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type};
+
+use crate::alphabet::Alphabet;
+use crate::VDString;
+
+impl<A: Alphabet, DB: Database> Type<DB> for VDString<A>
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, A: Alphabet, DB: Database> Encode<'q, DB> for VDString<A>
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        self.to_string().encode(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'r, A: Alphabet, DB: Database> Decode<'r, DB> for VDString<A>
+where
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let text = <&'r str as Decode<'r, DB>>::decode(value)?;
+        Ok(text.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::{Default31, Digits10};
+    use crate::VDStringError;
+    use sqlx::{Connection, Row, SqliteConnection};
+
+    #[tokio::test]
+    async fn round_trip_and_invalid_text() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE codes (code TEXT NOT NULL)").execute(&mut conn).await.unwrap();
+
+        let code: VDString<Default31> = "K7M3PQ".parse().unwrap();
+        sqlx::query("INSERT INTO codes (code) VALUES (?)").bind(&code).execute(&mut conn).await.unwrap();
+        let stored: String = sqlx::query_scalar("SELECT code FROM codes").fetch_one(&mut conn).await.unwrap();
+        assert_eq!(stored, "K7M3PQ");
+
+        let fetched: VDString<Default31> = sqlx::query_scalar("SELECT code FROM codes").fetch_one(&mut conn).await.unwrap();
+        assert_eq!(fetched, code);
+
+        let row = sqlx::query("SELECT code FROM codes").fetch_one(&mut conn).await.unwrap();
+        let err = row.try_get::<VDString<Digits10>, _>("code").unwrap_err();
+        match err {
+            sqlx::Error::ColumnDecode { source, .. } => assert!(source.downcast_ref::<VDStringError>().is_some()),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
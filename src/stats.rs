@@ -0,0 +1,246 @@
+//! Batch analysis over collections of [`VDString`]s.
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::alphabet::Default31;
+use crate::vdchar::{VDChar, VDS_ALLOWED};
+use crate::{VDCharSet, VDString};
+
+/// Per-glyph usage counts across a batch of codes.
+///
+/// Useful for font subsetting: [`GlyphUsage::used`] is exactly the set of
+/// glyphs a print or engraving vendor needs to support.
+///
+/// # Examples
+/// ```
+/// use vds::{stats, VDChar, VDString};
+///
+/// let codes: Vec<VDString> = ["AB2", "BB9"].iter().map(|s| s.parse().unwrap()).collect();
+/// let usage = stats::glyph_usage(&codes);
+///
+/// assert_eq!(usage.count(VDChar::new('B').unwrap()), 3);
+/// assert_eq!(usage.used().len(), 4);
+/// assert_eq!(usage.total(), 6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlyphUsage {
+    counts: [u64; VDS_ALLOWED.len()],
+}
+
+impl GlyphUsage {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self { counts: [0; VDS_ALLOWED.len()] }
+    }
+
+    /// Adds every character of `code` to the report.
+    pub fn add(&mut self, code: &VDString<Default31>) {
+        for ch in code {
+            self.counts[ch.index() as usize] += 1;
+        }
+    }
+
+    /// Returns how many times `ch` appeared.
+    pub fn count(&self, ch: VDChar<Default31>) -> u64 {
+        self.counts[ch.index() as usize]
+    }
+
+    /// Returns the total number of characters counted.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the set of glyphs that appeared at least once.
+    pub fn used(&self) -> VDCharSet {
+        self.iter().map(|(ch, _)| ch).collect()
+    }
+
+    /// Returns `(glyph, count)` pairs for glyphs that appeared, in alphabet order.
+    pub fn iter(&self) -> impl Iterator<Item = (VDChar<Default31>, u64)> + '_ {
+        VDChar::all()
+            .map(|ch| (ch, self.count(ch)))
+            .filter(|&(_, n)| n > 0)
+    }
+}
+
+impl Default for GlyphUsage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Extend<&'a VDString<Default31>> for GlyphUsage {
+    fn extend<I: IntoIterator<Item = &'a VDString<Default31>>>(&mut self, codes: I) {
+        for code in codes {
+            self.add(code);
+        }
+    }
+}
+
+/// Counts glyph usage across `codes`.
+///
+/// See [`GlyphUsage`].
+pub fn glyph_usage<'a, I: IntoIterator<Item = &'a VDString<Default31>>>(codes: I) -> GlyphUsage {
+    let mut usage = GlyphUsage::new();
+    usage.extend(codes);
+    usage
+}
+
+/// Uniformity statistics for a batch of codes, for checking that an RNG
+/// and generator configuration produce unbiased codes.
+///
+/// For a uniform source, each [chi-square](Self::chi_square) statistic
+/// follows a chi-square distribution with
+/// [`DEGREES_OF_FREEDOM`](Self::DEGREES_OF_FREEDOM) = 30: values near 30
+/// are expected, and a value above 59.7 has a probability below 0.1%.
+/// Generator settings that exclude characters, such as profanity filters
+/// or digit-only patterns, bias the statistics by design.
+///
+/// # Examples
+/// ```
+/// use vds::{stats, VDChar, VDString};
+///
+/// // Every two-character code once: perfectly uniform.
+/// let codes: Vec<VDString> =
+///     VDChar::all().flat_map(|a| VDChar::all().map(move |b| VDString::new(vec![a, b]))).collect();
+///
+/// let report = stats::analyze(&codes);
+/// assert_eq!(report.codes(), 961);
+/// assert_eq!(report.chi_square(), 0.0);
+/// assert!(report.positional_chi_square().all(|x| x == 0.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchAnalysis {
+    codes: u64,
+    overall: GlyphUsage,
+    positions: Vec<GlyphUsage>,
+}
+
+impl BatchAnalysis {
+    /// Degrees of freedom of each chi-square statistic.
+    pub const DEGREES_OF_FREEDOM: usize = VDS_ALLOWED.len() - 1;
+
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `code` to the report.
+    pub fn add(&mut self, code: &VDString<Default31>) {
+        self.codes += 1;
+        self.overall.add(code);
+        if self.positions.len() < code.len() {
+            self.positions.resize_with(code.len(), GlyphUsage::new);
+        }
+        for (usage, ch) in self.positions.iter_mut().zip(code) {
+            usage.counts[ch.index() as usize] += 1;
+        }
+    }
+
+    /// Returns the number of codes analyzed.
+    pub fn codes(&self) -> u64 {
+        self.codes
+    }
+
+    /// Returns the character counts over all positions.
+    pub fn usage(&self) -> &GlyphUsage {
+        &self.overall
+    }
+
+    /// Returns the fraction of all characters that were `ch`, or `0.0` for
+    /// an empty batch.
+    pub fn frequency(&self, ch: VDChar<Default31>) -> f64 {
+        match self.overall.total() {
+            0 => 0.0,
+            total => self.overall.count(ch) as f64 / total as f64,
+        }
+    }
+
+    /// Returns the character counts at `position`, or `None` past the
+    /// longest code.
+    pub fn position(&self, position: usize) -> Option<&GlyphUsage> {
+        self.positions.get(position)
+    }
+
+    /// Returns Pearson's chi-square statistic of the character counts over
+    /// all positions against a uniform distribution.
+    pub fn chi_square(&self) -> f64 {
+        chi_square(&self.overall)
+    }
+
+    /// Returns the chi-square statistic of each position, from the first.
+    ///
+    /// A position standing out from the rest points at positional bias,
+    /// e.g. a modulo-biased first character.
+    pub fn positional_chi_square(&self) -> impl Iterator<Item = f64> + '_ {
+        self.positions.iter().map(chi_square)
+    }
+}
+
+impl<'a> Extend<&'a VDString<Default31>> for BatchAnalysis {
+    fn extend<I: IntoIterator<Item = &'a VDString<Default31>>>(&mut self, codes: I) {
+        for code in codes {
+            self.add(code);
+        }
+    }
+}
+
+/// Returns `sum((observed - expected)^2 / expected)` against equal counts,
+/// or `0.0` if nothing was counted.
+fn chi_square(usage: &GlyphUsage) -> f64 {
+    let total = usage.total();
+    if total == 0 {
+        return 0.0;
+    }
+    let expected = total as f64 / VDS_ALLOWED.len() as f64;
+    usage.counts.iter().map(|&n| (n as f64 - expected) * (n as f64 - expected) / expected).sum()
+}
+
+/// Analyzes the uniformity of `codes`.
+///
+/// See [`BatchAnalysis`].
+pub fn analyze<'a, I: IntoIterator<Item = &'a VDString<Default31>>>(codes: I) -> BatchAnalysis {
+    let mut analysis = BatchAnalysis::new();
+    analysis.extend(codes);
+    analysis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_batch_uses_nothing() {
+        let usage = glyph_usage(&Vec::<VDString<Default31>>::new());
+        assert_eq!(usage.total(), 0);
+        assert!(usage.used().is_empty());
+        assert_eq!(usage.iter().count(), 0);
+    }
+
+    #[test]
+    fn analysis_flags_positional_bias() {
+        let codes: Vec<VDString<Default31>> = ["AB", "AC", "AD", "A"].iter().map(|s| s.parse().unwrap()).collect();
+        let report = analyze(&codes);
+        assert_eq!((report.codes(), report.usage().total()), (4, 7));
+        assert_eq!(report.frequency(VDChar::<Default31>::new('A').unwrap()), 4.0 / 7.0);
+        assert_eq!(report.position(1).unwrap().total(), 3);
+        assert!(report.position(2).is_none());
+
+        // Four As at one position: (4 - 4/31)^2 / (4/31) + 30 * 4/31.
+        let expected = (4.0 - 4.0 / 31.0) * (4.0 - 4.0 / 31.0) / (4.0 / 31.0) + 30.0 * 4.0 / 31.0;
+        let first = report.positional_chi_square().next().unwrap();
+        assert!((first - expected).abs() < 1e-9, "{}", first);
+        assert_eq!(analyze(&Vec::new()).chi_square(), 0.0);
+    }
+
+    #[test]
+    fn counts_across_codes() {
+        let codes: Vec<VDString<Default31>> = ["XYZ", "ZZ", "29"].iter().map(|s| s.parse().unwrap()).collect();
+        let usage = glyph_usage(&codes);
+
+        let pairs: Vec<(char, u64)> = usage.iter().map(|(c, n)| (c.as_char(), n)).collect();
+        assert_eq!(pairs, [('X', 1), ('Y', 1), ('Z', 3), ('2', 1), ('9', 1)]);
+        assert_eq!(usage.total(), 7);
+    }
+}
@@ -0,0 +1,69 @@
+//! Conversions between [`Uuid`] and [`VDString`](crate::VDString).
+//!
+//! This module is only available when the `uuid` feature is enabled.
+//!
+//! A UUID is encoded as its 128-bit big-endian value in base 31 (the same
+//! value the byte codec produces), left-padded with `A` to a fixed
+//! [`U128_WIDTH`] of 26 characters so every UUID yields a code of the same
+//! length.
+
+use uuid::Uuid;
+
+use crate::alphabet::Default31;
+use crate::codec::{DecodeError, U128_WIDTH};
+use crate::VDString;
+
+impl VDString<Default31> {
+    /// Encodes a UUID as a fixed-width, 26-character code.
+    ///
+    /// # Examples
+    /// ```
+    /// use uuid::Uuid;
+    /// use vds::VDString;
+    ///
+    /// let id = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+    /// let code = VDString::from_uuid(&id);
+    /// assert_eq!(code.len(), 26);
+    /// assert_eq!(code.to_uuid(), Ok(id));
+    /// ```
+    pub fn from_uuid(uuid: &Uuid) -> VDString<Default31> {
+        VDString::encode_u128_padded(uuid.as_u128(), U128_WIDTH)
+    }
+
+    /// Decodes a code produced by [`from_uuid`](Self::from_uuid).
+    ///
+    /// # Errors
+    /// Returns [`DecodeError::Overflow`] if the value does not fit in 128 bits.
+    pub fn to_uuid(&self) -> Result<Uuid, DecodeError> {
+        self.decode_u128().map(Uuid::from_u128)
+    }
+}
+
+impl From<Uuid> for VDString<Default31> {
+    fn from(uuid: Uuid) -> Self {
+        VDString::from_uuid(&uuid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nil_and_max_roundtrip() {
+        for id in [Uuid::nil(), Uuid::max()] {
+            let code = VDString::from(id);
+            assert_eq!(code.len(), U128_WIDTH);
+            assert_eq!(code.to_uuid(), Ok(id));
+        }
+        assert!(VDString::from_uuid(&Uuid::nil()).as_str().chars().all(|c| c == 'A'));
+    }
+
+    #[test]
+    fn matches_byte_codec_value() {
+        let id = Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+        let code = VDString::from_uuid(&id);
+        let bytes = code.decode_bytes();
+        assert_eq!(&bytes[bytes.len() - 16..], id.as_bytes());
+    }
+}
@@ -0,0 +1,133 @@
+//! Bulk validation of raw byte buffers against an alphabet,
+//! [`VDS_ALLOWED`](crate::VDS_ALLOWED) unless another is named.
+//!
+//! Every allowed character of the standard alphabets is ASCII, so a buffer
+//! is valid exactly when each byte is one of the allowed glyphs. These
+//! functions answer that question without decoding UTF-8 or constructing
+//! [`VDChar`](crate::VDChar)s, by testing each byte against the alphabet's
+//! 128-bit ASCII membership mask. `FromStr` and `TryFrom` for
+//! [`VDString`](crate::VDString) use the same check before building
+//! characters.
+//!
+//! Buffers are checked in chunks without branching inside a chunk, so the
+//! loop stays vectorizable. With the `simd` feature enabled, the chunks are
+//! [`LANES`] bytes wide, which the compiler lowers to SSE2/AVX2/NEON vector
+//! instructions on stable Rust, without `unsafe` or nightly `portable_simd`.
+
+use crate::alphabet::{Alphabet, AsciiIndex, Default31};
+
+/// Number of bytes checked per iteration by the vectorized path.
+#[cfg(feature = "simd")]
+pub const LANES: usize = 32;
+
+#[cfg(feature = "simd")]
+const CHUNK: usize = LANES;
+#[cfg(not(feature = "simd"))]
+const CHUNK: usize = 8;
+
+/// Branch-free test of whether `b` is in the ASCII membership `mask`.
+#[inline(always)]
+fn in_mask(mask: u128, b: u8) -> bool {
+    (b < 0x80) & ((mask >> (b & 0x7f)) & 1 == 1)
+}
+
+/// Returns the offset of the first byte that is not an ASCII character of
+/// alphabet `A`, or `None` if every byte is.
+///
+/// A non-ASCII character of `A`, or an alias accepted by
+/// [`Alphabet::decode`], is reported at its first byte; decode such input
+/// as a `str` instead.
+///
+/// # Examples
+/// ```
+/// use vds::alphabet::{Crockford32, Default31};
+/// use vds::validate::find_invalid;
+///
+/// assert_eq!(find_invalid::<Default31>(b"AB29XY"), None);
+/// assert_eq!(find_invalid::<Default31>(b"AB20"), Some(3));
+/// assert_eq!(find_invalid::<Crockford32>(b"AB20"), None);
+/// ```
+pub fn find_invalid<A: Alphabet>(bytes: &[u8]) -> Option<usize> {
+    let mask = AsciiIndex::<A>::MASK;
+    let mut chunks = bytes.chunks_exact(CHUNK);
+    let mut offset = 0;
+    for chunk in &mut chunks {
+        // No early exit inside the chunk so the fold stays vectorizable.
+        if !chunk.iter().fold(true, |acc, &b| acc & in_mask(mask, b)) {
+            break;
+        }
+        offset += CHUNK;
+    }
+    bytes[offset..].iter().position(|&b| !in_mask(mask, b)).map(|i| offset + i)
+}
+
+/// Returns `true` if every byte in `bytes` is a character in [`VDS_ALLOWED`](crate::VDS_ALLOWED).
+///
+/// # Examples
+/// ```
+/// use vds::validate::is_valid;
+///
+/// assert!(is_valid(b"7ZPQ"));
+/// assert!(!is_valid(b"7zpq"));
+/// ```
+pub fn is_valid(bytes: &[u8]) -> bool {
+    find_invalid::<Default31>(bytes).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use crate::alphabet::Crockford32;
+    use crate::VDChar;
+    use alloc::vec;
+
+    #[test]
+    fn every_byte_matches_allowed_set() {
+        for b in 0..=255u8 {
+            assert_eq!(is_valid(&[b]), b.is_ascii() && VDChar::new(char::from(b)).is_some(), "byte {:#04x}", b);
+            let crockford = b.is_ascii() && Crockford32::CHARS.contains(&char::from(b));
+            assert_eq!(find_invalid::<Crockford32>(&[b]).is_none(), crockford, "byte {:#04x}", b);
+        }
+    }
+
+    #[test]
+    fn reports_first_invalid_offset() {
+        assert_eq!(find_invalid::<Default31>(b""), None);
+        assert_eq!(find_invalid::<Default31>(b"ABC"), None);
+        assert_eq!(find_invalid::<Default31>(b"A0C"), Some(1));
+        assert_eq!(find_invalid::<Default31>("AB\u{e9}".as_bytes()), Some(2));
+        assert_eq!(find_invalid::<Default31>(&[b'A', 0xc1]), Some(1));
+    }
+
+    #[test]
+    fn long_buffers_report_offsets_past_first_chunk() {
+        let mut buf = vec![b'K'; 100];
+        assert_eq!(find_invalid::<Default31>(&buf), None);
+
+        buf[70] = b'1';
+        buf[90] = b'O';
+        assert_eq!(find_invalid::<Default31>(&buf), Some(70));
+
+        buf[70] = b'2';
+        assert_eq!(find_invalid::<Default31>(&buf), Some(90));
+
+        buf[99] = b'i';
+        buf[90] = b'9';
+        assert_eq!(find_invalid::<Default31>(&buf), Some(99));
+    }
+
+    #[test]
+    fn each_offset_is_found_for_other_alphabets() {
+        let mut buf = vec![b'K'; 40];
+        for i in [0, 7, 8, 13, 31, 32, 39] {
+            buf[i] = b'O';
+            assert_eq!(find_invalid::<Default31>(&buf), Some(i));
+            assert_eq!(find_invalid::<Crockford32>(&buf), Some(i));
+            buf[i] = b'0';
+            assert_eq!(find_invalid::<Default31>(&buf), Some(i));
+            assert_eq!(find_invalid::<Crockford32>(&buf), None);
+            buf[i] = b'K';
+        }
+    }
+}
@@ -0,0 +1,239 @@
+extern crate alloc;
+use alloc::vec::Vec;
+
+use core::str;
+
+use crate::alphabet::Default31;
+use crate::{CharError, VDChar, VDString};
+
+/// Incremental validator for codes that arrive in chunks.
+///
+/// Feed input with [`push_str`](Self::push_str) or
+/// [`push_bytes`](Self::push_bytes) as it is received, then call
+/// [`finish`](Self::finish). The result is the same as calling
+/// [`VDString::parse_all_errors`] on the concatenated input, without ever
+/// holding it in one buffer.
+///
+/// Byte chunks may split a multi-byte UTF-8 character; the partial
+/// sequence is kept until the rest arrives. Bytes that are not valid UTF-8
+/// are reported as [`char::REPLACEMENT_CHARACTER`], one per invalid
+/// sequence, as [`String::from_utf8_lossy`](alloc::string::String::from_utf8_lossy)
+/// would replace them.
+///
+/// # Examples
+/// ```
+/// use vds::VDValidator;
+///
+/// let mut validator = VDValidator::new();
+/// validator.push_bytes(b"AB2");
+/// validator.push_bytes(b"9XY");
+/// assert!(validator.is_valid());
+/// assert_eq!(validator.finish().unwrap(), "AB29XY");
+///
+/// let mut validator = VDValidator::new();
+/// validator.push_str("AB");
+/// validator.push_str("0X");
+/// assert!(!validator.is_valid());
+/// let errors = validator.finish().unwrap_err();
+/// assert_eq!((errors[0].ch, errors[0].char_index), ('0', 2));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VDValidator {
+    chars: Vec<VDChar<Default31>>,
+    errors: Vec<CharError>,
+    /// Characters seen so far, valid or not.
+    char_count: usize,
+    /// Bytes seen so far, not counting `pending`.
+    byte_count: usize,
+    /// Start of a UTF-8 sequence split across byte chunks.
+    pending: [u8; 4],
+    pending_len: usize,
+}
+
+impl VDValidator {
+    /// Creates a validator that has seen no input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates the next chunk of input.
+    ///
+    /// An incomplete UTF-8 sequence left by [`push_bytes`](Self::push_bytes)
+    /// cannot be completed by a `str`, so it is reported as invalid first.
+    pub fn push_str(&mut self, s: &str) {
+        self.flush_pending();
+        self.push_chars(s);
+    }
+
+    /// Validates the next chunk of input as UTF-8 bytes.
+    pub fn push_bytes(&mut self, mut bytes: &[u8]) {
+        while self.pending_len > 0 {
+            let Some((&b, rest)) = bytes.split_first() else {
+                return;
+            };
+            self.pending[self.pending_len] = b;
+            self.pending_len += 1;
+            match str::from_utf8(&self.pending[..self.pending_len]) {
+                Ok(s) => {
+                    let ch = s.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER);
+                    self.push_char(ch);
+                    self.pending_len = 0;
+                    bytes = rest;
+                }
+                Err(err) if err.error_len().is_none() => bytes = rest,
+                Err(_) => {
+                    // `b` does not continue the sequence; report what came
+                    // before it and read `b` afresh.
+                    self.push_invalid(self.pending_len - 1);
+                    self.pending_len = 0;
+                }
+            }
+        }
+
+        loop {
+            match str::from_utf8(bytes) {
+                Ok(s) => return self.push_chars(s),
+                Err(err) => {
+                    let (valid, after) = bytes.split_at(err.valid_up_to());
+                    // `valid_up_to` marks the end of well-formed UTF-8.
+                    self.push_chars(str::from_utf8(valid).unwrap_or_default());
+                    match err.error_len() {
+                        Some(len) => {
+                            self.push_invalid(len);
+                            bytes = &after[len..];
+                        }
+                        None => {
+                            self.pending[..after.len()].copy_from_slice(after);
+                            self.pending_len = after.len();
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if no invalid character has been seen so far.
+    ///
+    /// A UTF-8 sequence still waiting for its remaining bytes does not count
+    /// as invalid yet.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the invalid characters seen so far, in input order.
+    ///
+    /// Positions are counted from the start of the first chunk.
+    pub fn errors(&self) -> &[CharError] {
+        &self.errors
+    }
+
+    /// Ends the input and returns the accumulated code.
+    ///
+    /// # Errors
+    /// Returns a [`CharError`] for each rejected character, in input order,
+    /// including a trailing incomplete UTF-8 sequence.
+    pub fn finish(mut self) -> Result<VDString<Default31>, Vec<CharError>> {
+        self.flush_pending();
+        if self.errors.is_empty() {
+            Ok(VDString::new(self.chars))
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    fn push_chars(&mut self, s: &str) {
+        for ch in s.chars() {
+            self.push_char(ch);
+        }
+    }
+
+    fn push_char(&mut self, ch: char) {
+        match VDChar::new(ch) {
+            Some(c) => self.chars.push(c),
+            None => self.errors.push(CharError {
+                ch,
+                char_index: self.char_count,
+                byte_index: self.byte_count,
+            }),
+        }
+        self.char_count += 1;
+        self.byte_count += ch.len_utf8();
+    }
+
+    /// Records `len` bytes of invalid UTF-8 as one replacement character.
+    fn push_invalid(&mut self, len: usize) {
+        self.errors.push(CharError {
+            ch: char::REPLACEMENT_CHARACTER,
+            char_index: self.char_count,
+            byte_index: self.byte_count,
+        });
+        self.char_count += 1;
+        self.byte_count += len;
+    }
+
+    fn flush_pending(&mut self) {
+        if self.pending_len > 0 {
+            self.push_invalid(self.pending_len);
+            self.pending_len = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    fn feed(chunks: &[&[u8]]) -> Result<VDString<Default31>, Vec<CharError>> {
+        let mut validator = VDValidator::new();
+        for chunk in chunks {
+            validator.push_bytes(chunk);
+        }
+        validator.finish()
+    }
+
+    #[test]
+    fn matches_parse_all_errors_at_every_split() {
+        for input in ["AB29XY", "", "AB0ÉX1", "K€7💡M", "oA€1"] {
+            let bytes = input.as_bytes();
+            for split in 0..=bytes.len() {
+                let (a, b) = bytes.split_at(split);
+                assert_eq!(feed(&[a, b]), VDString::<Default31>::parse_all_errors(input), "{:?} at {}", input, split);
+            }
+            let singles: Vec<&[u8]> = bytes.chunks(1).collect();
+            assert_eq!(feed(&singles), VDString::<Default31>::parse_all_errors(input), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn reports_invalid_utf8_like_lossy_decoding() {
+        let cases: [&[u8]; 4] = [b"AB\xffC", b"A\xe2\x82B", b"\xf0\x9f", b"A\xe2\x82\xe2\x82\xacB"];
+        for bytes in cases {
+            let lossy = String::from_utf8_lossy(bytes);
+            let expected = VDString::<Default31>::parse_all_errors(&lossy).unwrap_err();
+            let split: Vec<&[u8]> = bytes.chunks(1).collect();
+            for errors in [feed(&[bytes]).unwrap_err(), feed(&split).unwrap_err()] {
+                let got: Vec<_> = errors.iter().map(|e| (e.ch, e.char_index)).collect();
+                let want: Vec<_> = expected.iter().map(|e| (e.ch, e.char_index)).collect();
+                assert_eq!(got, want, "{:?}", bytes);
+            }
+        }
+        let errors = feed(&[b"A\xe2\x82", b"B"]).unwrap_err();
+        assert_eq!(errors, [CharError { ch: char::REPLACEMENT_CHARACTER, char_index: 1, byte_index: 1 }]);
+    }
+
+    #[test]
+    fn str_after_partial_bytes_reports_the_fragment() {
+        let mut validator = VDValidator::new();
+        validator.push_bytes(b"AB\xe2");
+        assert!(validator.is_valid());
+        validator.push_str("C");
+        assert_eq!(validator.errors().len(), 1);
+        assert_eq!(validator.errors()[0].byte_index, 2);
+        assert_eq!(validator.errors()[0].char_index, 2);
+        validator.push_str("D");
+        let errors = validator.finish().unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -1,4 +1,10 @@
+use core::cmp::Ordering;
 use core::fmt;
+use core::marker::PhantomData;
+
+use crate::alphabet::{ascii_lookup, ascii_table, Alphabet, Default31, DefaultAlphabet};
+use crate::index::Index;
+use crate::VDGlyph;
 
 /// Allowed characters for [`VDChar`].
 ///
@@ -13,6 +19,79 @@ pub const VDS_ALLOWED: &[char] = &[
     'X', 'Y', 'Z', '2', '3', '4', '5', '6', '7', '8', '9',
 ];
 
+/// The class of a [`VDChar`]: a letter or a digit.
+///
+/// # Examples
+/// ```
+/// use vds::{VDChar, VDCharClass};
+///
+/// assert_eq!(VDChar::new('Q').unwrap().class(), VDCharClass::Letter);
+/// assert_eq!(VDChar::new('7').unwrap().class(), VDCharClass::Digit);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VDCharClass {
+    /// An uppercase Latin letter.
+    Letter,
+    /// An ASCII digit.
+    Digit,
+}
+
+/// Index of each ASCII byte in [`VDS_ALLOWED`], for O(1) [`VDChar::new`].
+pub(crate) const ASCII_INDEX: [u8; 128] = ascii_table(VDS_ALLOWED);
+
+/// ICAO/NATO spelling words for `A`–`Z`, in alphabetical order.
+const LETTER_WORDS: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India",
+    "Juliett", "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo",
+    "Sierra", "Tango", "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+/// Spoken English names for `0`–`9`.
+const DIGIT_WORDS: [&str; 10] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine",
+];
+
+/// Error returned when converting an index into a [`VDChar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VDCharError {
+    /// The index is not below the alphabet's length.
+    IndexOutOfRange(u8),
+}
+
+impl fmt::Display for VDCharError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VDCharError::IndexOutOfRange(index) => write!(f, "index {} is out of range", index),
+        }
+    }
+}
+
+impl core::error::Error for VDCharError {}
+
+/// An order for [`VDChar`]s and strings of them.
+///
+/// The `Ord` impls use [`Collation::Index`]; pass a collation to
+/// [`VDChar::cmp_with`] or [`VDStr::cmp_with`](crate::VDStr::cmp_with) to
+/// sort another way.
+///
+/// # Examples
+/// ```
+/// use vds::{Collation, VDChar};
+///
+/// let (a, two) = (VDChar::new('A').unwrap(), VDChar::new('2').unwrap());
+/// assert!(a < two);
+/// assert!(a.cmp_with(two, Collation::Ascii).is_gt());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Collation {
+    /// Alphabet index order. For [`Default31`], letters sort before digits.
+    #[default]
+    Index,
+    /// `char` value order. For ASCII alphabets such as [`Default31`], digits
+    /// sort before letters, matching sorted `str`s.
+    Ascii,
+}
+
 /// A single visibly distinguishable character from a restricted set.
 ///
 /// Internally stores an index into [`VDS_ALLOWED`], a curated set of
@@ -23,6 +102,9 @@ pub const VDS_ALLOWED: &[char] = &[
 /// [`VDChar`] is also used to construct [`VDString`](crate::VDString), which represents a
 /// sequence of validated `VDChar`s.
 ///
+/// The alphabet parameter defaults to [`DefaultAlphabet`], normally
+/// [`Default31`]; see [`alphabet`](crate::alphabet) for using other character sets.
+///
 /// # Examples
 ///
 /// ```
@@ -32,10 +114,214 @@ pub const VDS_ALLOWED: &[char] = &[
 /// assert!(VDChar::new('o').is_none()); // lowercase rejected
 /// assert!(VDChar::new('O').is_none()); // O is excluded for clarity
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct VDChar(pub(crate) u8);
+///
+/// A `VDChar` is one byte, and so is `Option<VDChar>`: the index leaves a
+/// niche for `None`.
+///
+/// ```
+/// use vds::VDChar;
+///
+/// assert_eq!(size_of::<Option<VDChar>>(), 1);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct VDChar<A: Alphabet = DefaultAlphabet>(Index, PhantomData<A>);
+
+/// Rejects alphabets too large for [`Index`] when `VDChar<A>` is first used.
+struct CheckLen<A>(PhantomData<A>);
+
+impl<A: Alphabet> CheckLen<A> {
+    const OK: () = assert!(A::CHARS.len() <= Index::MAX as usize + 1, "alphabets hold at most 255 characters");
+}
+
+impl<A: Alphabet> VDChar<A> {
+    /// Creates a `VDChar` from an index into `A::CHARS`, which must be in range.
+    pub(crate) const fn from_index_unchecked(index: u8) -> Self {
+        let () = CheckLen::<A>::OK;
+        Self(Index::new(index), PhantomData)
+    }
+
+    /// Attempts to create a `VDChar` in alphabet `A` from a `char`.
+    ///
+    /// Returns `None` if the character is not in `A::CHARS` and is not an
+    /// alias accepted by [`Alphabet::decode`]. For the default alphabet,
+    /// [`VDChar::new`] is equivalent and needs no type annotation.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::alphabet::Default31;
+    /// use vds::VDChar;
+    ///
+    /// assert_eq!(VDChar::<Default31>::from_char('X'), VDChar::new('X'));
+    /// ```
+    pub fn from_char(c: char) -> Option<Self> {
+        A::decode(c).map(Self::from_index_unchecked)
+    }
+
+    /// Creates a `VDChar` in alphabet `A` from its index into `A::CHARS`.
+    ///
+    /// The inverse of [`index`](Self::index), for codecs and storage formats
+    /// that keep compact `u8` indices. Also available as `TryFrom<u8>`.
+    ///
+    /// # Errors
+    /// Returns [`VDCharError::IndexOutOfRange`] if `index` is not below
+    /// `A::CHARS.len()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::alphabet::Default31;
+    /// use vds::{VDChar, VDCharError};
+    ///
+    /// let ch = VDChar::<Default31>::try_from_index(22).unwrap();
+    /// assert_eq!(ch.as_char(), 'Z');
+    /// assert_eq!(VDChar::try_from(ch.index()), Ok(ch));
+    /// assert_eq!(VDChar::<Default31>::try_from(31), Err(VDCharError::IndexOutOfRange(31)));
+    /// ```
+    pub const fn try_from_index(index: u8) -> Result<Self, VDCharError> {
+        if (index as usize) < A::CHARS.len() {
+            Ok(Self::from_index_unchecked(index))
+        } else {
+            Err(VDCharError::IndexOutOfRange(index))
+        }
+    }
+
+    /// Returns the underlying `char` represented by this `VDChar`.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDChar;
+    /// let c = VDChar::new('V').unwrap();
+    /// assert_eq!(c.as_char(), 'V');
+    /// ```
+    pub const fn as_char(self) -> char {
+        A::CHARS[self.index() as usize]
+    }
+
+    /// Returns the NATO phonetic word for this character, e.g. `"Kilo"` for
+    /// `K` and `"Seven"` for `7`.
+    ///
+    /// Every character of the built-in alphabets has a word; returns `None`
+    /// for characters of custom alphabets other than `A`–`Z` and `0`–`9`.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDChar;
+    /// assert_eq!(VDChar::new('A').unwrap().phonetic(), Some("Alfa"));
+    /// assert_eq!(VDChar::new('9').unwrap().phonetic(), Some("Nine"));
+    /// ```
+    pub const fn phonetic(self) -> Option<&'static str> {
+        match self.as_char() {
+            c @ 'A'..='Z' => Some(LETTER_WORDS[(c as u8 - b'A') as usize]),
+            c @ '0'..='9' => Some(DIGIT_WORDS[(c as u8 - b'0') as usize]),
+            _ => None,
+        }
+    }
+
+    /// Returns this character's index into the alphabet.
+    ///
+    /// The inverse of [`try_from_index`](Self::try_from_index). Also
+    /// available as `u8::from`.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDChar;
+    /// assert_eq!(VDChar::new('C').unwrap().index(), 2);
+    /// ```
+    pub const fn index(self) -> u8 {
+        self.0.get()
+    }
+
+    /// Returns the [`VDCharClass`] of this character.
+    ///
+    /// Characters other than ASCII digits count as letters.
+    pub const fn class(self) -> VDCharClass {
+        if self.as_char().is_ascii_digit() {
+            VDCharClass::Digit
+        } else {
+            VDCharClass::Letter
+        }
+    }
+
+    /// Returns `true` if this character is a letter.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDChar;
+    /// assert!(VDChar::new('M').unwrap().is_letter());
+    /// assert!(!VDChar::new('5').unwrap().is_letter());
+    /// ```
+    pub const fn is_letter(self) -> bool {
+        matches!(self.class(), VDCharClass::Letter)
+    }
+
+    /// Returns `true` if this character is a digit.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDChar;
+    /// assert!(VDChar::new('5').unwrap().is_digit());
+    /// assert!(!VDChar::new('M').unwrap().is_digit());
+    /// ```
+    pub const fn is_digit(self) -> bool {
+        matches!(self.class(), VDCharClass::Digit)
+    }
+
+    /// Compares two characters under the given [`Collation`].
+    ///
+    /// `Ord` is equivalent to [`Collation::Index`].
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{Collation, VDChar};
+    ///
+    /// let (z, nine) = (VDChar::new('Z').unwrap(), VDChar::new('9').unwrap());
+    /// assert!(z.cmp_with(nine, Collation::Index).is_lt());
+    /// assert!(z.cmp_with(nine, Collation::Ascii).is_gt());
+    /// ```
+    pub fn cmp_with(self, other: Self, collation: Collation) -> Ordering {
+        match collation {
+            Collation::Index => self.0.cmp(&other.0),
+            Collation::Ascii => self.as_char().cmp(&other.as_char()),
+        }
+    }
+}
+
+impl VDChar<Default31> {
+    /// Every [`VDChar`], in [`VDS_ALLOWED`] order.
+    ///
+    /// Usable in `const` contexts, e.g. to size or seed per-character tables.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{VDChar, VDS_ALLOWED};
+    ///
+    /// const WEIGHTS: [u32; VDChar::ALL.len()] = [1; VDChar::ALL.len()];
+    /// assert_eq!(WEIGHTS.len(), VDS_ALLOWED.len());
+    /// assert_eq!(VDChar::ALL[0].as_char(), 'A');
+    /// ```
+    pub const ALL: [Self; VDS_ALLOWED.len()] = {
+        let mut all = [Self::from_index_unchecked(0); VDS_ALLOWED.len()];
+        let mut i = 0;
+        while i < all.len() {
+            all[i] = Self::from_index_unchecked(i as u8);
+            i += 1;
+        }
+        all
+    };
+
+    /// Returns an iterator over every [`VDChar`], in [`VDS_ALLOWED`] order.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDChar;
+    ///
+    /// let chars: String = VDChar::all().map(|c| c.as_char()).take(4).collect();
+    /// assert_eq!(chars, "ABCD");
+    /// ```
+    pub fn all() -> core::array::IntoIter<Self, { VDS_ALLOWED.len() }> {
+        Self::ALL.into_iter()
+    }
 
-impl VDChar {
     /// Attempts to create a [`VDChar`] from a `char`.
     ///
     /// Returns `None` if the input character is not in [`VDS_ALLOWED`],
@@ -48,30 +334,127 @@ impl VDChar {
     /// assert!(VDChar::new('X').is_some());
     /// assert!(VDChar::new('x').is_none()); // lowercase
     /// assert!(VDChar::new('0').is_none()); // excluded
+    ///
+    /// const K: VDChar = VDChar::new('K').unwrap();
+    /// assert_eq!(K.as_char(), 'K');
     /// ```
-    pub fn new(c: char) -> Option<Self> {
-        VDS_ALLOWED.iter().position(|&x| x == c).map(|i| Self(i as u8))
+    pub const fn new(c: char) -> Option<Self> {
+        if !c.is_ascii() {
+            return None;
+        }
+        match ascii_lookup(&ASCII_INDEX, c as u8) {
+            Some(index) => Some(Self::from_index_unchecked(index)),
+            None => None,
+        }
     }
 
-    /// Returns the underlying `char` represented by this `VDChar`.
+    /// Creates a [`VDChar`] from its index into [`VDS_ALLOWED`].
+    ///
+    /// Returns `None` if `index` is out of range.
     ///
     /// # Examples
     /// ```
     /// use vds::VDChar;
-    /// let c = VDChar::new('V').unwrap();
-    /// assert_eq!(c.as_char(), 'V');
+    ///
+    /// const TABLE: [VDChar; 2] = [VDChar::from_index(0).unwrap(), VDChar::from_index(30).unwrap()];
+    /// assert_eq!(TABLE[1].as_char(), '9');
+    /// assert!(VDChar::from_index(31).is_none());
+    /// ```
+    pub const fn from_index(index: u8) -> Option<Self> {
+        if (index as usize) < VDS_ALLOWED.len() {
+            Some(Self::from_index_unchecked(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [`VDGlyph`] variant for this character.
+    ///
+    /// Useful for exhaustive `match`es over the alphabet.
+    ///
+    /// # Examples
     /// ```
-    pub fn as_char(self) -> char {
-        VDS_ALLOWED[self.0 as usize]
+    /// use vds::{VDChar, VDGlyph};
+    /// let c = VDChar::new('3').unwrap();
+    /// assert_eq!(c.glyph(), VDGlyph::Three);
+    /// ```
+    pub fn glyph(self) -> VDGlyph {
+        VDGlyph::from(self)
     }
 }
 
-impl fmt::Display for VDChar {
+/// Compares with the character's `char` value.
+///
+/// # Examples
+/// ```
+/// use vds::VDChar;
+///
+/// let ch = VDChar::new('K').unwrap();
+/// assert_eq!(ch, 'K');
+/// assert_eq!('K', ch);
+/// assert!(ch != 'k');
+/// ```
+impl<A: Alphabet> PartialEq<char> for VDChar<A> {
+    fn eq(&self, other: &char) -> bool {
+        self.as_char() == *other
+    }
+}
+
+impl<A: Alphabet> PartialEq<VDChar<A>> for char {
+    fn eq(&self, other: &VDChar<A>) -> bool {
+        other == self
+    }
+}
+
+impl<A: Alphabet> TryFrom<u8> for VDChar<A> {
+    type Error = VDCharError;
+
+    /// Equivalent to [`VDChar::try_from_index`].
+    fn try_from(index: u8) -> Result<Self, VDCharError> {
+        Self::try_from_index(index)
+    }
+}
+
+impl<A: Alphabet> From<VDChar<A>> for u8 {
+    /// Equivalent to [`VDChar::index`].
+    fn from(ch: VDChar<A>) -> u8 {
+        ch.index()
+    }
+}
+
+impl<A: Alphabet> From<VDChar<A>> for char {
+    /// Equivalent to [`VDChar::as_char`].
+    fn from(ch: VDChar<A>) -> char {
+        ch.as_char()
+    }
+}
+
+impl<A: Alphabet> PartialOrd for VDChar<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Alphabet> Ord for VDChar<A> {
+    /// Orders by alphabet index, i.e. [`Collation::Index`].
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<A: Alphabet> fmt::Debug for VDChar<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VDChar").field(&self.index()).finish()
+    }
+}
+
+impl<A: Alphabet> fmt::Display for VDChar<A> {
     /// Formats the `VDChar` as its character value.
     ///
-    /// Equivalent to calling `.as_char()`.
+    /// Equivalent to calling `.as_char()`, and honors width, fill, and
+    /// alignment like a `char`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.as_char())
+        fmt::Display::fmt(&self.as_char(), f)
     }
 }
 
@@ -81,40 +464,127 @@ mod tests {
     use super::*;
     use alloc::string::ToString;
     
+    #[test]
+    fn built_in_alphabets_have_phonetic_words() {
+        use crate::alphabet::{Crockford32, Digits10, Extended36};
+
+        fn check<A: Alphabet>() {
+            for i in 0..A::CHARS.len() as u8 {
+                let ch = VDChar::<A>::from_index_unchecked(i);
+                let word = ch.phonetic().unwrap();
+                assert!(ch.is_digit() || word.starts_with(ch.as_char()), "{} -> {}", ch, word);
+            }
+        }
+        check::<Default31>();
+        check::<Crockford32>();
+        check::<Digits10>();
+        check::<Extended36>();
+        assert_eq!(VDChar::<Default31>::new('X').unwrap().phonetic(), Some("X-ray"));
+        assert_eq!(VDChar::<Digits10>::from_index_unchecked(0).phonetic(), Some("Zero"));
+    }
+
     #[test]
     fn valid_vdchar_constructs() {
-        assert!(VDChar::new('A').is_some());
-        assert!(VDChar::new('Z').is_some());
-        assert!(VDChar::new('2').is_some());
-        assert!(VDChar::new('9').is_some());
+        assert!(VDChar::<Default31>::new('A').is_some());
+        assert!(VDChar::<Default31>::new('Z').is_some());
+        assert!(VDChar::<Default31>::new('2').is_some());
+        assert!(VDChar::<Default31>::new('9').is_some());
     }
 
     #[test]
     fn excluded_chars_are_rejected() {
-        assert!(VDChar::new('O').is_none()); // intentionally excluded
-        assert!(VDChar::new('I').is_none()); // ambiguous
-        assert!(VDChar::new('0').is_none()); // looks like 'O'
-        assert!(VDChar::new('1').is_none()); // looks like 'I'
+        assert!(VDChar::<Default31>::new('O').is_none()); // intentionally excluded
+        assert!(VDChar::<Default31>::new('I').is_none()); // ambiguous
+        assert!(VDChar::<Default31>::new('0').is_none()); // looks like 'O'
+        assert!(VDChar::<Default31>::new('1').is_none()); // looks like 'I'
+    }
+
+    #[test]
+    fn new_accepts_exactly_allowed_chars() {
+        for c in (0..=0x7f).filter_map(char::from_u32) {
+            let expected = VDS_ALLOWED.iter().position(|&x| x == c).map(|i| i as u8);
+            assert_eq!(VDChar::<Default31>::new(c).map(VDChar::<Default31>::index), expected, "{:?}", c);
+        }
+        assert!(VDChar::<Default31>::from_index(30).is_some());
+        assert!(VDChar::<Default31>::from_index(31).is_none());
+    }
+
+    #[test]
+    fn index_roundtrips_for_any_alphabet() {
+        use crate::alphabet::Digits10;
+
+        for ch in VDChar::<Default31>::all() {
+            assert_eq!(VDChar::<Default31>::try_from(u8::from(ch)), Ok(ch));
+        }
+        let seven = VDChar::<Digits10>::try_from_index(7).unwrap();
+        assert_eq!(seven.as_char(), '7');
+        assert_eq!(VDChar::<Digits10>::try_from(10), Err(VDCharError::IndexOutOfRange(10)));
     }
 
     #[test]
     fn lowercase_chars_are_rejected() {
-        assert!(VDChar::new('a').is_none());
-        assert!(VDChar::new('z').is_none());
-        assert!(VDChar::new('o').is_none());
+        assert!(VDChar::<Default31>::new('a').is_none());
+        assert!(VDChar::<Default31>::new('z').is_none());
+        assert!(VDChar::<Default31>::new('o').is_none());
     }
 
     #[test]
     fn as_char_returns_original_char() {
         for &c in VDS_ALLOWED {
-            let vd = VDChar::new(c).expect("should be allowed");
+            let vd = VDChar::<Default31>::new(c).expect("should be allowed");
             assert_eq!(vd.as_char(), c);
         }
     }
 
+    #[test]
+    fn all_covers_allowed_in_order() {
+        assert_eq!(VDChar::<Default31>::all().len(), VDS_ALLOWED.len());
+        for (ch, &c) in VDChar::<Default31>::all().zip(VDS_ALLOWED) {
+            assert_eq!(ch.as_char(), c);
+            assert_eq!(VDChar::<Default31>::new(c), Some(ch));
+        }
+    }
+
+    #[test]
+    fn class_partitions_alphabet() {
+        let letters = VDChar::<Default31>::all().filter(|c| c.is_letter()).count();
+        let digits = VDChar::<Default31>::all().filter(|c| c.is_digit()).count();
+        assert_eq!(letters, 23);
+        assert_eq!(digits, 8);
+
+        for ch in VDChar::<Default31>::all() {
+            assert_eq!(ch.is_letter(), ch.as_char().is_ascii_uppercase());
+            assert_ne!(ch.is_letter(), ch.is_digit());
+        }
+    }
+
+    #[test]
+    fn collations_order_letters_and_digits() {
+        let mut chars = VDChar::<Default31>::ALL;
+        chars.sort();
+        assert_eq!(chars, VDChar::<Default31>::ALL);
+        assert!(chars.windows(2).all(|w| w[0].cmp_with(w[1], Collation::Index).is_lt()));
+
+        chars.sort_by(|a, b| a.cmp_with(*b, Collation::Ascii));
+        assert_eq!(chars[0], '2');
+        assert_eq!(chars[8], 'A');
+        assert!(chars.windows(2).all(|w| w[0].as_char() < w[1].as_char()));
+    }
+
+    #[test]
+    fn compares_with_char() {
+        let ch = VDChar::<Default31>::new('X').unwrap();
+        assert_eq!(ch, 'X');
+        assert_eq!('X', ch);
+        assert_ne!(ch, 'x');
+        assert_ne!('Y', ch);
+    }
+
     #[test]
     fn display_matches_as_char() {
-        let ch = VDChar::new('X').unwrap();
+        let ch = VDChar::<Default31>::new('X').unwrap();
         assert_eq!(ch.to_string(), "X");
+        assert_eq!(alloc::format!("[{:>3}]", ch), "[  X]");
+        assert_eq!(alloc::format!("[{:-<3}]", ch), "[X--]");
     }
 }
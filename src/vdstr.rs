@@ -0,0 +1,857 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+use core::ops::{Bound, Index, RangeBounds};
+
+use ref_cast::RefCast;
+
+use crate::alphabet::{Alphabet, Default31, DefaultAlphabet};
+use crate::siphash::SipHasher;
+use crate::{Collation, VDChar, VDCharSet};
+
+/// A borrowed, validated string slice of [`VDChar`]s.
+///
+/// `VDStr` is to [`VDString`](crate::VDString) what `str` is to `String`:
+/// an unsized view that is always used behind a reference. `VDString` derefs
+/// to it, so functions can take `&VDStr` and accept both owned strings and
+/// sub-slices of them, and [`VDStr::get_range`] hands out sub-slices without
+/// allocating.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vds::{VDStr, VDString};
+///
+/// fn prefix(code: &VDStr) -> &VDStr {
+///     code.get_range(..2).unwrap_or(code)
+/// }
+///
+/// let code: VDString = "AB29XY".parse().unwrap();
+/// assert_eq!(prefix(&code), "AB");
+/// assert_eq!(prefix(&code[2..]), "29");
+/// # }
+/// ```
+#[derive(PartialEq, Eq, Hash, RefCast)]
+#[repr(transparent)]
+pub struct VDStr<A: Alphabet = DefaultAlphabet>([VDChar<A>]);
+
+impl<A: Alphabet> VDStr<A> {
+    /// Views a slice of [`VDChar`]s as a `VDStr`, without copying.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{VDChar, VDStr};
+    ///
+    /// let chars = [VDChar::new('K').unwrap(), VDChar::new('7').unwrap()];
+    /// assert_eq!(VDStr::from_vdchars(&chars), "K7");
+    /// ```
+    pub fn from_vdchars(chars: &[VDChar<A>]) -> &Self {
+        Self::ref_cast(chars)
+    }
+
+    /// Returns the underlying [`VDChar`] slice.
+    pub fn as_vdchars(&self) -> &[VDChar<A>] {
+        &self.0
+    }
+
+    /// Returns the number of characters.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the slice has no characters.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the [`VDChar`]s.
+    pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, VDChar<A>>> {
+        self.0.iter().copied()
+    }
+
+    /// Returns the character at a `usize` index, or the sub-slice for a
+    /// range of indices, or `None` if out of bounds.
+    ///
+    /// Non-panicking alternative to indexing with `s[index]` or `s[range]`.
+    /// Indices are in characters. Range results borrow from `self`; call
+    /// `to_owned()` on them for an owned `VDString`.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let s: VDString = "AB29XY7K".parse().unwrap();
+    /// assert_eq!(s.get(1).map(|c| c.as_char()), Some('B'));
+    /// assert_eq!(s.get(8), None);
+    ///
+    /// let (region, serial) = (s.get(..2).unwrap(), s.get(2..).unwrap());
+    /// assert_eq!(region, "AB");
+    /// assert_eq!(serial.to_owned(), "29XY7K".parse::<VDString>().unwrap());
+    /// assert!(s.get(4..9).is_none());
+    /// # }
+    /// ```
+    pub fn get<'a, I: VDStrIndex<'a, A>>(&'a self, index: I) -> Option<I::Output> {
+        index.get(self)
+    }
+
+    /// Returns the characters in `range` as a sub-slice, or `None` if the
+    /// range is out of bounds or decreasing.
+    ///
+    /// Accepts any `RangeBounds`, including `(Bound, Bound)` pairs; for the
+    /// standard range types this is the same as [`get`](Self::get).
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let s: VDString = "AB29XY".parse().unwrap();
+    /// assert_eq!(s.get_range(2..4).unwrap(), "29");
+    /// assert_eq!(s.get_range(4..).unwrap(), "XY");
+    /// assert!(s.get_range(4..9).is_none());
+    /// # }
+    /// ```
+    pub fn get_range<R: RangeBounds<usize>>(&self, range: R) -> Option<&Self> {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1)?,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.0.len(),
+        };
+        self.0.get(start..end).map(Self::from_vdchars)
+    }
+
+    /// Returns an iterator over consecutive groups of `size` characters.
+    ///
+    /// The last group is shorter if `size` does not divide the length.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let s: VDString = "AB29XY7KM".parse().unwrap();
+    /// let groups: Vec<String> = s.chunks(4).map(|g| g.to_string()).collect();
+    /// assert_eq!(groups, ["AB29", "XY7K", "M"]);
+    /// # }
+    /// ```
+    pub fn chunks(&self, size: usize) -> VDStrChunks<'_, A> {
+        VDStrChunks(self.0.chunks(size))
+    }
+
+    /// Returns `true` if any character appears more than once.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// assert!("ABBA".parse::<VDString>().unwrap().has_repeats());
+    /// assert!(!"AB29".parse::<VDString>().unwrap().has_repeats());
+    /// # }
+    /// ```
+    pub fn has_repeats(&self) -> bool {
+        let mut seen = [false; 256];
+        !self.0.iter().all(|c| !core::mem::replace(&mut seen[c.index() as usize], true))
+    }
+
+    /// Compares two strings character by character under the given
+    /// [`Collation`]; a proper prefix sorts first.
+    ///
+    /// `Ord` is equivalent to [`Collation::Index`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::{Collation, VDString};
+    ///
+    /// let mut codes: Vec<VDString> = ["B2", "A9", "9Z", "A"].iter().map(|s| s.parse().unwrap()).collect();
+    /// codes.sort();
+    /// assert_eq!(codes, ["A", "A9", "B2", "9Z"]);
+    ///
+    /// codes.sort_by(|a, b| a.cmp_with(b, Collation::Ascii));
+    /// assert_eq!(codes, ["9Z", "A", "A9", "B2"]);
+    /// # }
+    /// ```
+    pub fn cmp_with(&self, other: &Self, collation: Collation) -> Ordering {
+        self.iter()
+            .zip(other.iter())
+            .map(|(a, b)| a.cmp_with(b, collation))
+            .find(|ord| ord.is_ne())
+            .unwrap_or_else(|| self.len().cmp(&other.len()))
+    }
+
+    /// Returns `true` if `input` spells this code, ignoring case, fullwidth
+    /// forms, and the separators skipped by lenient parsing (spaces, tabs,
+    /// and hyphens).
+    ///
+    /// This compares in place, without parsing `input` into a new string,
+    /// so it suits redemption checks on a hot path. Look-alike letters such
+    /// as Cyrillic `А` are not folded; use
+    /// [`VDString::parse_with`](crate::VDString::parse_with) for that.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AB29XY".parse().unwrap();
+    /// assert!(code.eq_lenient("ab2-9xy"));
+    /// assert!(code.eq_lenient(" ＡＢ２９ ｘｙ "));
+    /// assert!(!code.eq_lenient("AB29X"));
+    /// assert!(!code.eq_lenient("AB29XY7"));
+    /// # }
+    /// ```
+    pub fn eq_lenient(&self, input: &str) -> bool {
+        input
+            .chars()
+            .map(|c| match c {
+                '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+                '\u{3000}' => ' ',
+                _ => c,
+            })
+            .filter(|c| !matches!(c, ' ' | '\t' | '-'))
+            .map(|c| c.to_ascii_uppercase())
+            .eq(self.iter().map(VDChar::as_char))
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns `true` if `pat` occurs anywhere in this string.
+    ///
+    /// `pat` is a [`VDChar`] or a `&VDStr` (including `&VDString`); see
+    /// [`VDStrPattern`]. An empty pattern matches every string.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::{vdstr, VDChar, VDString};
+    ///
+    /// let code: VDString = "Q4XY7K".parse().unwrap();
+    /// assert!(code.contains(&vdstr!("XY")));
+    /// assert!(code.contains(VDChar::new('7').unwrap()));
+    /// assert!(!code.contains(&vdstr!("YX")));
+    /// # }
+    /// ```
+    pub fn contains<P: VDStrPattern<A>>(&self, pat: P) -> bool {
+        self.find(pat).is_some()
+    }
+
+    /// Returns the character index of the first occurrence of `pat`, or
+    /// `None` if it does not occur.
+    ///
+    /// Indices count characters, so they can be used directly with
+    /// [`get`](Self::get) and indexing. An empty pattern matches at `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::{vdstr, VDChar, VDString};
+    ///
+    /// let code: VDString = "AB29AB".parse().unwrap();
+    /// assert_eq!(code.find(&vdstr!("AB")), Some(0));
+    /// assert_eq!(code.rfind(&vdstr!("AB")), Some(4));
+    /// assert_eq!(code.find(VDChar::new('9').unwrap()), Some(3));
+    /// assert_eq!(code.find(&vdstr!("XY")), None);
+    /// # }
+    /// ```
+    pub fn find<P: VDStrPattern<A>>(&self, pat: P) -> Option<usize> {
+        let needle = pat.as_vdchars();
+        if needle.is_empty() {
+            return Some(0);
+        }
+        self.0.windows(needle.len()).position(|w| w == needle)
+    }
+
+    /// Returns the character index of the last occurrence of `pat`, or
+    /// `None` if it does not occur. An empty pattern matches at
+    /// [`len`](Self::len).
+    pub fn rfind<P: VDStrPattern<A>>(&self, pat: P) -> Option<usize> {
+        let needle = pat.as_vdchars();
+        if needle.is_empty() {
+            return Some(self.len());
+        }
+        self.0.windows(needle.len()).rposition(|w| w == needle)
+    }
+
+    /// Returns `true` if this string begins with `pat`.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::{vdstr, VDString};
+    ///
+    /// let code: VDString = "Q4XY7K".parse().unwrap();
+    /// let region = if code.starts_with(&vdstr!("Q4")) { "X" } else { "default" };
+    /// assert_eq!(region, "X");
+    /// assert!(code.ends_with(&vdstr!("7K")));
+    /// # }
+    /// ```
+    pub fn starts_with<P: VDStrPattern<A>>(&self, pat: P) -> bool {
+        self.0.starts_with(pat.as_vdchars())
+    }
+
+    /// Returns `true` if this string ends with `pat`.
+    pub fn ends_with<P: VDStrPattern<A>>(&self, pat: P) -> bool {
+        self.0.ends_with(pat.as_vdchars())
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Divides the string into two at character index `mid`.
+    ///
+    /// # Panics
+    /// Panics if `mid > len`. Use [`split_at_checked`](Self::split_at_checked)
+    /// for a non-panicking alternative.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "EU29XY7K".parse().unwrap();
+    /// let (region, serial) = code.split_at(2);
+    /// assert_eq!(region, "EU");
+    /// assert_eq!(serial, "29XY7K");
+    /// # }
+    /// ```
+    pub fn split_at(&self, mid: usize) -> (&Self, &Self) {
+        let (a, b) = self.0.split_at(mid);
+        (Self::from_vdchars(a), Self::from_vdchars(b))
+    }
+
+    /// Divides the string into two at character index `mid`, or returns
+    /// `None` if `mid > len`.
+    pub fn split_at_checked(&self, mid: usize) -> Option<(&Self, &Self)> {
+        (mid <= self.len()).then(|| self.split_at(mid))
+    }
+
+    /// Returns an iterator over the parts of the string separated by `sep`.
+    ///
+    /// As with `str::split`, consecutive, leading, or trailing separators
+    /// produce empty parts, and an empty string yields one empty part.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::{VDChar, VDString};
+    ///
+    /// let code: VDString = "EU9K2X9M".parse().unwrap();
+    /// let parts: Vec<String> = code.split(VDChar::new('9').unwrap()).map(|p| p.to_string()).collect();
+    /// assert_eq!(parts, ["EU", "K2X", "M"]);
+    /// # }
+    /// ```
+    pub fn split(&self, sep: VDChar<A>) -> VDStrSplit<'_, A> {
+        self.splitn(usize::MAX, sep)
+    }
+
+    /// Returns an iterator over at most `n` parts of the string separated by
+    /// `sep`; the last part holds the rest of the string.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::{VDChar, VDString};
+    ///
+    /// let code: VDString = "EU9K2X9M".parse().unwrap();
+    /// let nine = VDChar::new('9').unwrap();
+    /// let parts: Vec<String> = code.splitn(2, nine).map(|p| p.to_string()).collect();
+    /// assert_eq!(parts, ["EU", "K2X9M"]);
+    /// assert_eq!(code.splitn(0, nine).count(), 0);
+    /// # }
+    /// ```
+    pub fn splitn(&self, n: usize, sep: VDChar<A>) -> VDStrSplit<'_, A> {
+        VDStrSplit { rest: Some(&self.0), sep, remaining: n }
+    }
+
+    /// Returns a stable 64-bit hash of this code, suitable for persisting
+    /// as a compact key or feeding a Bloom filter.
+    ///
+    /// Unlike [`Hash`](core::hash::Hash), whose output may change between
+    /// Rust releases, the fingerprint is fixed: it is SipHash-2-4 under an
+    /// all-zero key, over one byte per character holding its alphabet
+    /// index. It is the same on every platform and in every version of
+    /// this crate. Because it is computed from indices, codes in different
+    /// alphabets with the same indices share a fingerprint.
+    ///
+    /// The key is public, so the fingerprint offers no protection against
+    /// deliberately crafted collisions.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "K7M3PQ".parse().unwrap();
+    /// assert_eq!(code.fingerprint(), 0x5bf7_c4b7_a0d5_a945);
+    /// # }
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = SipHasher::new(&[0; 16]);
+        for ch in self {
+            hasher.write(&[ch.index()]);
+        }
+        hasher.finish()
+    }
+}
+
+impl VDStr<Default31> {
+    /// Returns the set of distinct characters in this string.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let s: VDString = "ABBA29".parse().unwrap();
+    /// let distinct: String = s.unique_chars().iter().map(|c| c.as_char()).collect();
+    /// assert_eq!(distinct, "AB29");
+    /// # }
+    /// ```
+    pub fn unique_chars(&self) -> VDCharSet {
+        self.iter().collect()
+    }
+}
+
+impl<A: Alphabet> Default for &VDStr<A> {
+    fn default() -> Self {
+        VDStr::from_vdchars(&[])
+    }
+}
+
+impl<A: Alphabet> fmt::Display for VDStr<A> {
+    /// Honors width, fill, alignment, and precision like a `str`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `Formatter::pad` needs a `&str`, which a `VDStr` can't lend without
+        // allocating, so the padding is written by hand.
+        let len = f.precision().map_or(self.len(), |p| p.min(self.len()));
+        let padding = f.width().map_or(0, |w| w.saturating_sub(len));
+        let (before, after) = match f.align() {
+            None | Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        };
+        let fill = f.fill();
+        (0..before).try_for_each(|_| f.write_char(fill))?;
+        self.iter().take(len).try_for_each(|c| f.write_char(c.as_char()))?;
+        (0..after).try_for_each(|_| f.write_char(fill))
+    }
+}
+
+impl<A: Alphabet> fmt::Debug for VDStr<A> {
+    /// Formats like a `str`, e.g. `"AB29"`.
+    ///
+    /// With the `redact-debug` feature, prints only the length instead, e.g.
+    /// `VDStr(REDACTED, len=4)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if cfg!(feature = "redact-debug") {
+            write!(f, "VDStr(REDACTED, len={})", self.len())
+        } else {
+            write!(f, "\"{}\"", self)
+        }
+    }
+}
+
+impl<A: Alphabet> PartialOrd for VDStr<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Alphabet> Ord for VDStr<A> {
+    /// Orders lexicographically by alphabet index, i.e. [`Collation::Index`].
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<A: Alphabet> AsRef<[VDChar<A>]> for VDStr<A> {
+    fn as_ref(&self) -> &[VDChar<A>] {
+        &self.0
+    }
+}
+
+impl<A: Alphabet> PartialEq<str> for VDStr<A> {
+    fn eq(&self, other: &str) -> bool {
+        self.iter().map(VDChar::as_char).eq(other.chars())
+    }
+}
+
+impl<A: Alphabet> PartialEq<VDStr<A>> for str {
+    fn eq(&self, other: &VDStr<A>) -> bool {
+        other == self
+    }
+}
+
+impl<A: Alphabet> Index<usize> for VDStr<A> {
+    type Output = VDChar<A>;
+
+    /// # Panics
+    /// Panics if the index is out of bounds. Use [`VDStr::get`] for a
+    /// non-panicking alternative.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use vds::VDString;
+    ///
+    /// let s: VDString = "B7X".parse().unwrap();
+    /// assert_eq!(s[1].as_char(), '7');
+    /// # }
+    /// ```
+    fn index(&self, index: usize) -> &VDChar<A> {
+        &self.0[index]
+    }
+}
+
+/// Iterator over groups of characters, returned by [`VDStr::chunks`].
+#[derive(Debug, Clone)]
+pub struct VDStrChunks<'a, A: Alphabet = DefaultAlphabet>(core::slice::Chunks<'a, VDChar<A>>);
+
+impl<'a, A: Alphabet> Iterator for VDStrChunks<'a, A> {
+    type Item = &'a VDStr<A>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(VDStr::from_vdchars)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<A: Alphabet> DoubleEndedIterator for VDStrChunks<'_, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(VDStr::from_vdchars)
+    }
+}
+
+impl<A: Alphabet> ExactSizeIterator for VDStrChunks<'_, A> {}
+
+impl<A: Alphabet> core::iter::FusedIterator for VDStrChunks<'_, A> {}
+
+/// An iterator over the parts of a [`VDStr`] separated by a [`VDChar`].
+///
+/// Returned by [`VDStr::split`] and [`VDStr::splitn`].
+#[derive(Debug, Clone)]
+pub struct VDStrSplit<'a, A: Alphabet = DefaultAlphabet> {
+    rest: Option<&'a [VDChar<A>]>,
+    sep: VDChar<A>,
+    remaining: usize,
+}
+
+impl<'a, A: Alphabet> Iterator for VDStrSplit<'a, A> {
+    type Item = &'a VDStr<A>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let rest = self.rest?;
+        match rest.iter().position(|&c| c == self.sep).filter(|_| self.remaining > 0) {
+            Some(i) => {
+                self.rest = Some(&rest[i + 1..]);
+                Some(VDStr::from_vdchars(&rest[..i]))
+            }
+            None => {
+                self.rest = None;
+                Some(VDStr::from_vdchars(rest))
+            }
+        }
+    }
+}
+
+impl<A: Alphabet> core::iter::FusedIterator for VDStrSplit<'_, A> {}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Index types accepted by [`VDStr::get`]: a `usize` for a single
+/// [`VDChar`], or a range for a sub-slice.
+///
+/// This trait is sealed and cannot be implemented outside this crate.
+pub trait VDStrIndex<'a, A: Alphabet>: private::Sealed {
+    /// What a successful lookup returns.
+    type Output;
+
+    /// Looks up `self` in `s`, returning `None` if out of bounds.
+    fn get(self, s: &'a VDStr<A>) -> Option<Self::Output>;
+}
+
+impl private::Sealed for usize {}
+
+/// Patterns accepted by [`VDStr::find`] and the other search methods: a
+/// single [`VDChar`], or a `&VDStr` or `&VDString` to match as a whole.
+///
+/// This trait is sealed and cannot be implemented outside this crate.
+pub trait VDStrPattern<A: Alphabet>: private::Sealed {
+    /// Returns the characters to match, in order.
+    fn as_vdchars(&self) -> &[VDChar<A>];
+}
+
+impl<A: Alphabet> private::Sealed for VDChar<A> {}
+
+impl<A: Alphabet> VDStrPattern<A> for VDChar<A> {
+    fn as_vdchars(&self) -> &[VDChar<A>] {
+        core::slice::from_ref(self)
+    }
+}
+
+impl<A: Alphabet> private::Sealed for &VDStr<A> {}
+
+impl<A: Alphabet> VDStrPattern<A> for &VDStr<A> {
+    fn as_vdchars(&self) -> &[VDChar<A>] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Alphabet> private::Sealed for &crate::VDString<A> {}
+
+#[cfg(feature = "alloc")]
+impl<A: Alphabet> VDStrPattern<A> for &crate::VDString<A> {
+    fn as_vdchars(&self) -> &[VDChar<A>] {
+        VDStr::as_vdchars(self)
+    }
+}
+
+impl<'a, A: Alphabet> VDStrIndex<'a, A> for usize {
+    type Output = VDChar<A>;
+
+    fn get(self, s: &'a VDStr<A>) -> Option<VDChar<A>> {
+        s.0.get(self).copied()
+    }
+}
+
+macro_rules! impl_range_index {
+    ($($range:ty),*) => {$(
+        impl private::Sealed for $range {}
+
+        impl<'a, A: Alphabet> VDStrIndex<'a, A> for $range {
+            type Output = &'a VDStr<A>;
+
+            fn get(self, s: &'a VDStr<A>) -> Option<&'a VDStr<A>> {
+                s.get_range(self)
+            }
+        }
+
+        impl<A: Alphabet> Index<$range> for VDStr<A> {
+            type Output = VDStr<A>;
+
+            /// # Panics
+            /// Panics if the range is out of bounds. Use [`VDStr::get_range`]
+            /// for a non-panicking alternative.
+            fn index(&self, range: $range) -> &VDStr<A> {
+                VDStr::from_vdchars(&self.0[range])
+            }
+        }
+    )*};
+}
+
+impl_range_index!(
+    core::ops::Range<usize>,
+    core::ops::RangeFrom<usize>,
+    core::ops::RangeTo<usize>,
+    core::ops::RangeInclusive<usize>,
+    core::ops::RangeToInclusive<usize>,
+    core::ops::RangeFull
+);
+
+impl<'a, A: Alphabet> IntoIterator for &'a VDStr<A> {
+    type Item = VDChar<A>;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, VDChar<A>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, A: Alphabet> From<&'a [VDChar<A>]> for &'a VDStr<A> {
+    fn from(chars: &'a [VDChar<A>]) -> Self {
+        VDStr::from_vdchars(chars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vd(c: char) -> VDChar<Default31> {
+        VDChar::<Default31>::new(c).unwrap()
+    }
+
+    #[test]
+    fn search_returns_char_indices() {
+        let chars = [vd('Q'), vd('4'), vd('X'), vd('Q'), vd('4')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        let q4 = &s[..2];
+        assert_eq!(s.find(q4), Some(0));
+        assert_eq!(s.rfind(q4), Some(3));
+        assert_eq!(s.find(vd('X')), Some(2));
+        assert_eq!(s.rfind(vd('Q')), Some(3));
+        assert_eq!(s.find(vd('Z')), None);
+        assert_eq!(s[1..].find(q4), Some(2));
+        assert_eq!(q4.find(s), None);
+        assert!(s.starts_with(q4) && s.ends_with(q4));
+        assert!(s.starts_with(vd('Q')) && !s.ends_with(vd('Q')));
+        assert!(!q4.starts_with(s));
+    }
+
+    #[test]
+    fn empty_pattern_matches_everywhere() {
+        let chars = [vd('A'), vd('B')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        let empty = <&VDStr<Default31>>::default();
+        assert_eq!(s.find(empty), Some(0));
+        assert_eq!(s.rfind(empty), Some(2));
+        assert!(s.contains(empty) && s.starts_with(empty) && s.ends_with(empty));
+        assert_eq!(empty.find(empty), Some(0));
+        assert!(!empty.contains(vd('A')));
+    }
+
+    #[test]
+    fn split_at_divides_by_char_index() {
+        let chars = [vd('A'), vd('B'), vd('2')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        assert_eq!(s.split_at(0), (&s[..0], s));
+        assert_eq!(s.split_at(1), (&s[..1], &s[1..]));
+        assert_eq!(s.split_at(3), (s, &s[3..]));
+        assert_eq!(s.split_at_checked(3), Some(s.split_at(3)));
+        assert_eq!(s.split_at_checked(4), None);
+    }
+
+    #[test]
+    fn split_matches_str_semantics() {
+        let cases = ["", "9", "A9B", "99", "9A9", "AB9C99D9", "ABC"];
+        for case in cases {
+            let chars: [VDChar<Default31>; 8] = core::array::from_fn(|i| vd(case.chars().nth(i).unwrap_or('A')));
+            let s = VDStr::<Default31>::from_vdchars(&chars[..case.len()]);
+            for n in [0, 1, 2, 3, usize::MAX] {
+                let mut ours = s.splitn(n, vd('9'));
+                let mut expected = case.splitn(n, '9');
+                loop {
+                    match (ours.next(), expected.next()) {
+                        (Some(a), Some(b)) => assert_eq!(a, b, "{:?} n={}", case, n),
+                        (None, None) => break,
+                        (a, b) => panic!("{:?} n={}: {:?} vs {:?}", case, n, a, b),
+                    }
+                }
+                assert!(ours.next().is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn slices_share_storage() {
+        let chars = [vd('A'), vd('B'), vd('2'), vd('9')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        let mid = s.get_range(1..3).unwrap();
+        assert_eq!(mid, "B2");
+        assert_eq!(mid.as_vdchars().as_ptr(), chars[1..].as_ptr());
+        assert_eq!(&s[2..], "29");
+        assert_eq!(s[..=0][0], vd('A'));
+        assert_eq!(s.get(1..=2), Some(mid));
+        assert_eq!(s.get(3), Some(vd('9')));
+        assert_eq!(s.get(4), None);
+        assert_eq!(s.get(..5), None);
+    }
+
+    #[test]
+    fn get_range_handles_all_bounds() {
+        let chars = [vd('A'), vd('B'), vd('2'), vd('9'), vd('X'), vd('Y')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        assert_eq!(s.get_range(..).unwrap(), "AB29XY");
+        assert_eq!(s.get_range(1..=2).unwrap(), "B2");
+        assert_eq!(s.get_range(..2).unwrap(), "AB");
+        assert!(s.get_range(6..).unwrap().is_empty());
+        assert!(s.get_range(7..).is_none());
+        assert!(s.get_range(..=usize::MAX).is_none());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = s.get_range(3..1);
+        assert!(reversed.is_none());
+    }
+
+    #[test]
+    fn chunks_cover_the_slice() {
+        let chars = [vd('A'), vd('B'), vd('2'), vd('9'), vd('X')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        let mut chunks = s.chunks(2);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.next(), Some(&s[..2]));
+        assert_eq!(chunks.next_back(), Some(&s[4..]));
+        assert_eq!(chunks.next(), Some(&s[2..4]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(<&VDStr<Default31>>::default().chunks(3).count(), 0);
+    }
+
+    #[test]
+    fn compares_with_str() {
+        let chars = [vd('K'), vd('7')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        assert!(*s == *"K7");
+        assert!(*"K7" == *s);
+        assert!(*s != *"K");
+        assert!(*s != *"K77");
+        assert!(<&VDStr<Default31>>::default().is_empty());
+    }
+
+    #[test]
+    fn eq_lenient_folds_case_width_and_separators() {
+        let chars = [vd('K'), vd('7'), vd('M'), vd('3')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        for input in ["K7M3", "k7m3", "K7-M3", "\tk7 m3 ", "Ｋ７ｍ３", "ｋ７－ｍ３", "K7\u{3000}M3"] {
+            assert!(s.eq_lenient(input), "{:?}", input);
+        }
+        for input in ["K7M", "K7M33", "K7N3", "К7M3", "K7_M3", ""] {
+            assert!(!s.eq_lenient(input), "{:?}", input);
+        }
+        assert!(<&VDStr<Default31>>::default().eq_lenient(" - "));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn display_honors_formatter_flags() {
+        extern crate alloc;
+        use alloc::format;
+
+        let chars = [vd('K'), vd('7'), vd('Q')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        assert_eq!(format!("{}", s), "K7Q");
+        assert_eq!(format!("[{:6}]", s), "[K7Q   ]");
+        assert_eq!(format!("[{:>6}]", s), "[   K7Q]");
+        assert_eq!(format!("[{:*^6}]", s), "[*K7Q**]");
+        assert_eq!(format!("[{:.2}]", s), "[K7]");
+        assert_eq!(format!("[{:>4.2}]", s), "[  K7]");
+        assert_eq!(format!("[{:2}]", s), "[K7Q]");
+
+        let owned: crate::VDString<Default31> = "K7Q".parse().unwrap();
+        assert_eq!(format!("[{:*^6}]", owned), "[*K7Q**]");
+        assert_eq!(format!("[{:>4.2}]", owned), "[  K7]");
+    }
+
+    #[test]
+    fn fingerprint_is_siphash_of_indices() {
+        use crate::alphabet::Crockford32;
+        use crate::siphash::siphash;
+
+        let chars = [vd('K'), vd('7'), vd('M'), vd('3'), vd('P'), vd('Q')];
+        let s = VDStr::<Default31>::from_vdchars(&chars);
+        let indices = chars.map(|c| c.index());
+        assert_eq!(s.fingerprint(), siphash(&[0; 16], &indices));
+        assert_eq!(s.fingerprint(), 0x5bf7_c4b7_a0d5_a945);
+        assert_eq!(<&VDStr<Default31>>::default().fingerprint(), 0x1e92_4b9d_7377_00d7);
+        assert_ne!(s[..5].fingerprint(), s.fingerprint());
+
+        let same_indices = indices.map(VDChar::<Crockford32>::from_index_unchecked);
+        assert_eq!(VDStr::from_vdchars(&same_indices).fingerprint(), s.fingerprint());
+    }
+}
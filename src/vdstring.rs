@@ -1,31 +1,82 @@
 extern crate alloc;
-use alloc::{vec::Vec, string::String};
+use alloc::{borrow::ToOwned, vec::Vec, string::String};
 
-use core::{fmt, ops::{Deref, Index}};
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::{fmt, ops::{Add, AddAssign, Deref}};
 use core::str::FromStr;
 
-use crate::VDChar;
+use crate::alphabet::{Alphabet, AsciiIndex, DefaultAlphabet};
+use crate::{validate, ByteError, VDChar, VDStr};
 
 /// Error returned when constructing or parsing a [`VDString`].
 ///
 /// This error occurs when an input string contains characters not in the
-/// allowed visible set defined by [`VDS_ALLOWED`](crate::VDS_ALLOWED).
+/// target alphabet, [`VDS_ALLOWED`](crate::VDS_ALLOWED) by default.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VDStringError {
     /// A character in the input was not part of the allowed set.
-    InvalidChar(char),
+    InvalidChar {
+        /// The rejected character.
+        ch: char,
+        /// Byte offset of `ch` in the input.
+        offset: usize,
+    },
 }
 
+impl VDStringError {
+    /// Returns the rejected character.
+    pub const fn invalid_char(&self) -> char {
+        match *self {
+            VDStringError::InvalidChar { ch, .. } => ch,
+        }
+    }
+
+    /// Returns the byte offset of the rejected character in the input.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let err = "K7M3O".parse::<VDString>().unwrap_err();
+    /// assert_eq!((err.invalid_char(), err.offset()), ('O', 4));
+    /// ```
+    pub const fn offset(&self) -> usize {
+        match *self {
+            VDStringError::InvalidChar { offset, .. } => offset,
+        }
+    }
+}
+
+impl fmt::Display for VDStringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VDStringError::InvalidChar { ch, offset } => write!(f, "invalid character {:?} at byte {}", ch, offset),
+        }
+    }
+}
+
+impl core::error::Error for VDStringError {}
+
 /// A validated, immutable string composed entirely of [`VDChar`]s.
 ///
-/// All characters are guaranteed to come from [`VDS_ALLOWED`](crate::VDS_ALLOWED),
-/// a curated uppercase set that avoids visually ambiguous glyphs like `0`, `O`, `1`, `I`.
+/// All characters are guaranteed to come from the alphabet, by default
+/// [`VDS_ALLOWED`](crate::VDS_ALLOWED), a curated uppercase set that avoids visually ambiguous glyphs like `0`, `O`, `1`, `I`.
 ///
 /// Internally backed by a `Vec<VDChar>` and a cached `String`, `VDString` ensures both:
 /// - **Safe rendering** in user interfaces or printed material
 /// - **Fast access** to the string representation
 ///
-/// Construct via `.parse()`, `TryFrom<&str>`, or from a list of `VDChar`s.
+/// The cache comes from the default `str-cache` feature. Without it, each
+/// code is stored once, as its characters, and rendered on demand by
+/// `Display`; [`as_str`](Self::as_str) and `AsRef<str>` are then unavailable.
+///
+/// Construct via `.parse()`, `TryFrom<&str>`, `TryFrom<String>`, or from a list of `VDChar`s.
+///
+/// The alphabet parameter defaults to [`DefaultAlphabet`], normally
+/// [`Default31`](crate::alphabet::Default31); see [`alphabet`](crate::alphabet) for
+/// using other character sets.
 ///
 /// # Examples
 /// ```
@@ -38,16 +89,19 @@ pub enum VDStringError {
 ///     print!("{},", ch);
 /// }
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct VDString {
-    chars: Vec<VDChar>,
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "redact-debug"), derive(Debug))]
+pub struct VDString<A: Alphabet = DefaultAlphabet> {
+    chars: Vec<VDChar<A>>,
+    #[cfg(feature = "str-cache")]
     cache: String,
 }
 
-impl VDString {
+impl<A: Alphabet> VDString<A> {
     /// Creates a new `VDString` from a validated list of [`VDChar`]s.
     ///
-    /// Caches the string representation for formatting and fast lookup.
+    /// With the `str-cache` feature, caches the string representation for
+    /// formatting and fast lookup.
     ///
     /// # Examples
     /// ```
@@ -57,58 +111,471 @@ impl VDString {
     /// let code = VDString::new(chars);
     /// assert_eq!(&*code, "AB2");
     /// ```
-    pub fn new(chars: Vec<VDChar>) -> Self {
+    pub fn new(chars: Vec<VDChar<A>>) -> Self {
+        #[cfg(feature = "str-cache")]
         let cache = chars.iter().map(|c| c.as_char()).collect();
-        Self { chars, cache }
+        Self {
+            chars,
+            #[cfg(feature = "str-cache")]
+            cache,
+        }
     }
 
-    /// Returns a slice of the internal [`VDChar`] list.
+    /// Validates an ASCII byte slice, such as a field read from a network
+    /// packet or flash storage, without going through `&str` first.
     ///
-    /// Useful for inspecting, transforming, or re-encoding the raw data.
-    pub fn as_vdchars(&self) -> &[VDChar] {
-        &self.chars
+    /// # Errors
+    /// Returns a [`ByteError`] with the first byte that is not an ASCII
+    /// character of the alphabet, and its offset.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::alphabet::Default31;
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = VDString::from_ascii(b"AB29").unwrap();
+    /// assert_eq!(&*code, "AB29");
+    ///
+    /// let err = VDString::<Default31>::from_ascii(b"AB\xff9").unwrap_err();
+    /// assert_eq!((err.byte, err.index), (0xff, 2));
+    /// ```
+    pub fn from_ascii(bytes: &[u8]) -> Result<Self, ByteError> {
+        if let Some(index) = validate::find_invalid::<A>(bytes) {
+            return Err(ByteError { byte: bytes[index], index });
+        }
+        let table = &AsciiIndex::<A>::TABLE;
+        Ok(Self::new(bytes.iter().map(|&b| VDChar::from_index_unchecked(table[usize::from(b)])).collect()))
+    }
+
+    /// Returns the string as a `&str`.
+    ///
+    /// Requires the `str-cache` feature (enabled by default).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AB29".parse().unwrap();
+    /// assert!(code.as_str().starts_with("AB"));
+    /// ```
+    #[cfg(feature = "str-cache")]
+    pub fn as_str(&self) -> &str {
+        &self.cache
+    }
+
+    /// Returns `true` if the string's characters are exactly `s`.
+    fn eq_str(&self, s: &str) -> bool {
+        #[cfg(feature = "str-cache")]
+        return self.cache == s;
+        #[cfg(not(feature = "str-cache"))]
+        return *self.as_vdstr() == *s;
+    }
+
+    /// Concatenates `parts` into a single `VDString`.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{vdstr, VDString};
+    ///
+    /// let (region, serial) = (vdstr!("AB"), vdstr!("29XY"));
+    /// assert_eq!(&*VDString::concat([&*region, &serial[..2]]), "AB29");
+    /// ```
+    pub fn concat<'a, I: IntoIterator<Item = &'a VDStr<A>>>(parts: I) -> Self {
+        let mut out = Self::new(Vec::new());
+        for part in parts {
+            out += part;
+        }
+        out
+    }
+
+    /// Consumes the string and returns its character buffer.
+    pub(crate) fn into_vdchars(self) -> Vec<VDChar<A>> {
+        self.chars
+    }
+
+    /// Borrows the whole string as a [`VDStr`].
+    pub fn as_vdstr(&self) -> &VDStr<A> {
+        VDStr::from_vdchars(&self.chars)
+    }
+
+    /// Returns a new `VDString` with characters rearranged by `permutation`.
+    ///
+    /// Position `i` of the result holds `self[permutation[i]]`, so the result
+    /// always contains the same multiset of characters.
+    ///
+    /// Returns `None` if `permutation` is not a permutation of `0..self.len()`
+    /// (wrong length, out-of-range, or repeated indices).
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code: VDString = "AB29".parse().unwrap();
+    /// assert_eq!(&*code.permute_with(&[3, 2, 1, 0]).unwrap(), "92BA");
+    /// assert!(code.permute_with(&[0, 0, 1, 2]).is_none());
+    /// ```
+    pub fn permute_with(&self, permutation: &[usize]) -> Option<Self> {
+        if permutation.len() != self.chars.len() {
+            return None;
+        }
+
+        let mut seen = alloc::vec![false; self.chars.len()];
+        let mut chars = Vec::with_capacity(self.chars.len());
+
+        for &i in permutation {
+            if core::mem::replace(seen.get_mut(i)?, true) {
+                return None;
+            }
+            chars.push(self.chars[i]);
+        }
+
+        Some(Self::new(chars))
+    }
+
+    /// Returns a copy of this string with its characters shuffled by `rng`.
+    ///
+    /// Uses a Fisher-Yates shuffle, so the result keeps the same multiset of
+    /// characters in a random order.
+    ///
+    /// This method is only available when the `generate` feature is enabled.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{StepRng, VDString};
+    ///
+    /// let mut rng = StepRng::new(0, 7);
+    /// let code: VDString = "AB29XY".parse().unwrap();
+    /// let shuffled = code.shuffled(&mut rng);
+    ///
+    /// assert_eq!(shuffled.len(), code.len());
+    /// ```
+    #[cfg(feature = "generate")]
+    pub fn shuffled<R: crate::VDRng + ?Sized>(&self, rng: &mut R) -> Self {
+        let mut chars = self.chars.clone();
+        for i in (1..chars.len()).rev() {
+            let j = crate::uniform_index(rng, (i + 1) as u32) as usize;
+            chars.swap(i, j);
+        }
+        Self::new(chars)
+    }
+}
+
+impl VDString {
+    /// Validates and collects characters into a `VDString`.
+    ///
+    /// # Errors
+    /// Returns [`VDStringError::InvalidChar`] for the first character not
+    /// in the alphabet, with the byte offset it has in the characters
+    /// written out as a string.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code = VDString::try_from_chars("ab29".chars().map(|c| c.to_ascii_uppercase()));
+    /// assert_eq!(&*code.unwrap(), "AB29");
+    /// assert!(VDString::try_from_chars(['A', '0']).is_err());
+    /// ```
+    pub fn try_from_chars<I: IntoIterator<Item = char>>(chars: I) -> Result<Self, VDStringError> {
+        let mut offset = 0;
+        chars
+            .into_iter()
+            .map(|c| {
+                let ch = VDChar::from_char(c).ok_or(VDStringError::InvalidChar { ch: c, offset });
+                offset += c.len_utf8();
+                ch
+            })
+            .collect()
+    }
+
+    /// Parses `s` over the default alphabet; see the [`FromStr`] impl.
+    ///
+    /// A type's default parameter does not guide inference in expressions,
+    /// so this keeps `VDString::from_str(s)` resolving to `VDString` on its
+    /// own. Other alphabets use the trait, e.g.
+    /// `VDString::<Crockford32>::from_str(s)`.
+    ///
+    /// # Errors
+    /// Returns [`VDStringError::InvalidChar`] for the first character not
+    /// in the alphabet.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code = VDString::from_str("7ZPQ").unwrap();
+    /// assert_eq!(code, "7ZPQ");
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, VDStringError> {
+        <Self as FromStr>::from_str(s)
+    }
+
+    /// Converts `value` over the default alphabet; see the [`TryFrom`]
+    /// impls.
+    ///
+    /// Like [`from_str`](Self::from_str), keeps `VDString::try_from(value)`
+    /// resolving to `VDString` without annotations. Other alphabets name
+    /// theirs, e.g. `VDString::<Crockford32>::try_from(value)`.
+    ///
+    /// # Errors
+    /// Returns the error of the matching `TryFrom` impl.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let code = VDString::try_from("7ZPQ").unwrap();
+    /// assert_eq!(code, VDString::try_from(String::from("7ZPQ")).unwrap());
+    /// ```
+    pub fn try_from<T>(value: T) -> Result<Self, <Self as TryFrom<T>>::Error>
+    where
+        Self: TryFrom<T>,
+    {
+        <Self as TryFrom<T>>::try_from(value)
     }
 }
 
-impl Deref for VDString {
-    type Target = str;
+impl<A: Alphabet> Deref for VDString<A> {
+    type Target = VDStr<A>;
 
-    /// Allows `VDString` to behave like a `&str` (e.g., `&*vdstring == "ABC"`).
+    /// Allows `VDString` to be used wherever a `&VDStr` is expected.
     fn deref(&self) -> &Self::Target {
+        self.as_vdstr()
+    }
+}
+
+/// Lets a `HashMap<VDString, _>` or `BTreeMap<VDString, _>` be queried
+/// with a `&VDStr`, such as a slice of a longer code, without allocating.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use vds::VDString;
+///
+/// let mut balances: HashMap<VDString, u32> = HashMap::new();
+/// balances.insert("AB29".parse().unwrap(), 50);
+///
+/// let scanned: VDString = "AB29XY".parse().unwrap();
+/// assert_eq!(balances.get(&scanned[..4]), Some(&50));
+/// ```
+impl<A: Alphabet> Borrow<VDStr<A>> for VDString<A> {
+    fn borrow(&self) -> &VDStr<A> {
+        self.as_vdstr()
+    }
+}
+
+impl<A: Alphabet> AsRef<VDStr<A>> for VDString<A> {
+    fn as_ref(&self) -> &VDStr<A> {
+        self.as_vdstr()
+    }
+}
+
+#[cfg(feature = "str-cache")]
+impl<A: Alphabet> AsRef<str> for VDString<A> {
+    fn as_ref(&self) -> &str {
         &self.cache
     }
 }
 
-impl fmt::Display for VDString {
-    /// Displays the cached string of visible characters.
+impl<A: Alphabet> AsRef<[VDChar<A>]> for VDString<A> {
+    fn as_ref(&self) -> &[VDChar<A>] {
+        &self.chars
+    }
+}
+
+impl<A: Alphabet> From<VDString<A>> for String {
+    /// Returns the cached string without copying, or renders the
+    /// characters without the `str-cache` feature.
+    fn from(s: VDString<A>) -> String {
+        #[cfg(feature = "str-cache")]
+        return s.cache;
+        #[cfg(not(feature = "str-cache"))]
+        return s.chars.iter().map(|c| c.as_char()).collect();
+    }
+}
+
+impl<A: Alphabet> ToOwned for VDStr<A> {
+    type Owned = VDString<A>;
+
+    /// Copies the slice into a new `VDString`.
+    fn to_owned(&self) -> VDString<A> {
+        VDString::new(self.as_vdchars().to_vec())
+    }
+}
+
+impl<A: Alphabet> From<&VDStr<A>> for VDString<A> {
+    fn from(s: &VDStr<A>) -> Self {
+        s.to_owned()
+    }
+}
+
+impl<A: Alphabet> PartialEq<String> for VDStr<A> {
+    fn eq(&self, other: &String) -> bool {
+        *self == **other
+    }
+}
+
+impl<A: Alphabet> PartialEq<String> for &VDStr<A> {
+    fn eq(&self, other: &String) -> bool {
+        **self == **other
+    }
+}
+
+/// Compares with the string's characters, so `code == "AB29"` works
+/// without dereferencing.
+///
+/// # Examples
+/// ```
+/// use vds::VDString;
+///
+/// let code: VDString = "AB29".parse().unwrap();
+/// assert_eq!(code, "AB29");
+/// assert_eq!("AB29", code);
+/// assert!(code != *"AB2");
+/// assert_eq!(code, String::from("AB29"));
+/// ```
+impl<A: Alphabet> PartialEq<str> for VDString<A> {
+    fn eq(&self, other: &str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl<A: Alphabet> PartialEq<&str> for VDString<A> {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl<A: Alphabet> PartialEq<String> for VDString<A> {
+    fn eq(&self, other: &String) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl<A: Alphabet> PartialEq<VDString<A>> for str {
+    fn eq(&self, other: &VDString<A>) -> bool {
+        other == self
+    }
+}
+
+impl<A: Alphabet> PartialEq<VDString<A>> for &str {
+    fn eq(&self, other: &VDString<A>) -> bool {
+        other == self
+    }
+}
+
+impl<A: Alphabet> PartialEq<VDString<A>> for String {
+    fn eq(&self, other: &VDString<A>) -> bool {
+        other == self
+    }
+}
+
+impl<A: Alphabet> VDStr<A> {
+    /// Returns a `VDString` holding `n` copies of this slice.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::vdstr;
+    ///
+    /// assert_eq!(&*vdstr!("A2").repeat(3), "A2A2A2");
+    /// ```
+    pub fn repeat(&self, n: usize) -> VDString<A> {
+        VDString::new(self.as_vdchars().repeat(n))
+    }
+}
+
+impl<A: Alphabet> Add<&VDStr<A>> for VDString<A> {
+    type Output = Self;
+
+    /// Appends `rhs`, reusing this string's buffers.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::vdstr;
+    ///
+    /// let code = vdstr!("AB") + &vdstr!("29");
+    /// assert_eq!(&*code, "AB29");
+    /// ```
+    fn add(mut self, rhs: &VDStr<A>) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<A: Alphabet> AddAssign<&VDStr<A>> for VDString<A> {
+    fn add_assign(&mut self, rhs: &VDStr<A>) {
+        self.extend(rhs);
+    }
+}
+
+impl<A: Alphabet> PartialOrd for VDString<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: Alphabet> Ord for VDString<A> {
+    /// Orders like the borrowed [`VDStr`], i.e. by alphabet index.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_vdstr().cmp(other.as_vdstr())
+    }
+}
+
+impl<A: Alphabet> Hash for VDString<A> {
+    /// Hashes like the borrowed [`VDStr`], as required by `Borrow`.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_vdstr().hash(state);
+    }
+}
+
+#[cfg(feature = "redact-debug")]
+impl<A: Alphabet> fmt::Debug for VDString<A> {
+    /// Prints only the length, e.g. `VDString(REDACTED, len=8)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VDString(REDACTED, len={})", self.chars.len())
+    }
+}
+
+impl<A: Alphabet> fmt::Display for VDString<A> {
+    /// Displays the string of visible characters, from the cache if the
+    /// `str-cache` feature is enabled.
+    ///
+    /// Honors width, fill, alignment, and precision like a `str`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.cache)
+        #[cfg(feature = "str-cache")]
+        return f.pad(&self.cache);
+        #[cfg(not(feature = "str-cache"))]
+        return fmt::Display::fmt(self.as_vdstr(), f);
     }
 }
 
-impl Index<usize> for VDString {
-    type Output = VDChar;
+impl<A: Alphabet> IntoIterator for VDString<A> {
+    type Item = VDChar<A>;
+    type IntoIter = alloc::vec::IntoIter<VDChar<A>>;
 
-    /// Indexes into the underlying [`VDChar`] list.
+    /// Consumes the string, returning an iterator over its [`VDChar`]s.
     ///
-    /// # Panics
-    /// Panics if the index is out of bounds.
+    /// The iterator is double-ended and knows its length, so a code can be
+    /// reversed, e.g. for check digits weighted from the right, without
+    /// cloning it.
     ///
     /// # Examples
     /// ```
     /// use vds::VDString;
     ///
-    /// let s: VDString = "B7X".parse().unwrap();
-    /// assert_eq!(s[1].as_char(), '7');
+    /// let s: VDString = "3MV".parse().unwrap();
+    /// let reversed: String = s.into_iter().rev().map(|c| c.as_char()).collect();
+    /// assert_eq!(reversed, "VM3");
     /// ```
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.chars[index]
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vdchars().into_iter()
     }
 }
 
-impl<'a> IntoIterator for &'a VDString {
-    type Item = VDChar;
-    type IntoIter = core::iter::Copied<core::slice::Iter<'a, VDChar>>;
+impl<'a, A: Alphabet> IntoIterator for &'a VDString<A> {
+    type Item = VDChar<A>;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, VDChar<A>>>;
 
     /// Returns an iterator over the [`VDChar`]s in this string.
     ///
@@ -117,7 +584,7 @@ impl<'a> IntoIterator for &'a VDString {
     /// use vds::VDString;
     ///
     /// let s: VDString = "3MV".parse().unwrap();
-    /// let chars: Vec<_> = s.into_iter().map(|c| c.as_char()).collect();
+    /// let chars: Vec<_> = (&s).into_iter().map(|c| c.as_char()).collect();
     /// assert_eq!(chars, vec!['3', 'M', 'V']);
     /// ```
     fn into_iter(self) -> Self::IntoIter {
@@ -125,7 +592,33 @@ impl<'a> IntoIterator for &'a VDString {
     }
 }
 
-impl FromStr for VDString {
+impl<A: Alphabet> FromIterator<VDChar<A>> for VDString<A> {
+    /// Collects [`VDChar`]s into a `VDString`.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::{VDChar, VDString};
+    ///
+    /// let code: VDString = "A2B9".chars().filter_map(VDChar::new).collect();
+    /// assert_eq!(&*code, "A2B9");
+    /// ```
+    fn from_iter<I: IntoIterator<Item = VDChar<A>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<A: Alphabet> Extend<VDChar<A>> for VDString<A> {
+    /// Appends [`VDChar`]s, keeping the cached string in step.
+    fn extend<I: IntoIterator<Item = VDChar<A>>>(&mut self, iter: I) {
+        for ch in iter {
+            self.chars.push(ch);
+            #[cfg(feature = "str-cache")]
+            self.cache.push(ch.as_char());
+        }
+    }
+}
+
+impl<A: Alphabet> FromStr for VDString<A> {
     type Err = VDStringError;
 
     /// Parses a `&str` into a `VDString`, validating each character.
@@ -143,21 +636,87 @@ impl FromStr for VDString {
     /// assert!(invalid.is_err());
     /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.chars()
-            .map(|c| VDChar::new(c).ok_or(VDStringError::InvalidChar(c)))
-            .collect::<Result<Vec<_>, _>>()
-            .map(VDString::new)
+        let (chars, canonical) = Self::decode_str(s)?;
+        if !canonical {
+            return Ok(Self::new(chars));
+        }
+        Ok(Self {
+            chars,
+            #[cfg(feature = "str-cache")]
+            cache: s.to_owned(),
+        })
     }
 }
 
-impl TryFrom<&str> for VDString {
+impl<A: Alphabet> VDString<A> {
+    /// Validates `s`, returning its characters and whether `s` is already
+    /// their rendering, i.e. contains no aliases accepted by
+    /// [`Alphabet::decode`].
+    fn decode_str(s: &str) -> Result<(Vec<VDChar<A>>, bool), VDStringError> {
+        // Exact ASCII characters are validated in bulk and mapped through the
+        // lookup table; only the rest go through `Alphabet::decode`.
+        let valid = validate::find_invalid::<A>(s.as_bytes()).unwrap_or(s.len());
+        let table = &AsciiIndex::<A>::TABLE;
+        let mut chars = Vec::with_capacity(s.len());
+        chars.extend(s.as_bytes()[..valid].iter().map(|&b| VDChar::from_index_unchecked(table[usize::from(b)])));
+        let mut canonical = true;
+        for (i, c) in s[valid..].char_indices() {
+            let ch = VDChar::from_char(c).ok_or(VDStringError::InvalidChar { ch: c, offset: valid + i })?;
+            canonical &= ch.as_char() == c;
+            chars.push(ch);
+        }
+        Ok((chars, canonical))
+    }
+}
+
+impl<A: Alphabet> TryFrom<&str> for VDString<A> {
     type Error = VDStringError;
 
     /// Tries to convert a string slice into a `VDString`.
     ///
     /// Equivalent to `VDString::from_str`.
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        VDString::from_str(s)
+        Self::from_str(s)
+    }
+}
+
+impl<A: Alphabet> TryFrom<String> for VDString<A> {
+    type Error = VDStringError;
+
+    /// Validates an owned string, reusing its allocation as the cached
+    /// string instead of copying it.
+    ///
+    /// Aliases accepted by [`Alphabet::decode`], such as lowercase for
+    /// [`Crockford32`](crate::alphabet::Crockford32), are rewritten in
+    /// place. Without the `str-cache` feature, the string is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use vds::VDString;
+    ///
+    /// let body = String::from("K7M3PQ");
+    /// let code = VDString::try_from(body).unwrap();
+    /// assert_eq!(code, "K7M3PQ");
+    /// assert!(VDString::try_from(String::from("K0")).is_err());
+    /// ```
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let (chars, canonical) = Self::decode_str(&s)?;
+        #[cfg(feature = "str-cache")]
+        let cache = {
+            let mut s = s;
+            if !canonical {
+                s.clear();
+                s.extend(chars.iter().map(|c| c.as_char()));
+            }
+            s
+        };
+        #[cfg(not(feature = "str-cache"))]
+        let _ = (s, canonical);
+        Ok(Self {
+            chars,
+            #[cfg(feature = "str-cache")]
+            cache,
+        })
     }
 }
 
@@ -166,40 +725,120 @@ mod tests {
     extern crate alloc;
     use alloc::{string::ToString, vec, vec::Vec};
     use super::*;
+    use crate::alphabet::{Default31, DefaultAlphabet};
 
-    fn vd(c: char) -> VDChar {
-        VDChar::new(c).unwrap()
+    fn vd(c: char) -> VDChar<Default31> {
+        VDChar::<Default31>::new(c).unwrap()
     }
 
     #[test]
     fn construct_from_vec() {
         let chars = vec![vd('A'), vd('B'), vd('2')];
-        let s = VDString::new(chars.clone());
+        let s = VDString::<Default31>::new(chars.clone());
         assert_eq!(s.as_vdchars(), &chars[..]);
         assert_eq!(&*s, "AB2");
     }
 
     #[test]
     fn parse_valid_string() {
-        let s: VDString = "M29W".parse().unwrap();
+        let s: VDString<Default31> = "M29W".parse().unwrap();
         assert_eq!(&*s, "M29W");
         assert_eq!(s.len(), 4);
         assert_eq!(s[0], vd('M'));
         assert_eq!(s[3].as_char(), 'W');
     }
 
+    #[test]
+    fn into_iter_by_value_is_double_ended() {
+        let s: VDString<Default31> = "M29W".parse().unwrap();
+        let mut iter = s.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(vd('W')));
+        assert_eq!(iter.next(), Some(vd('M')));
+        assert_eq!(iter.rev().collect::<VDString<Default31>>(), "92");
+    }
+
     #[test]
     fn parse_invalid_string() {
-        let err = "HELLO!".parse::<VDString>();
+        let err = "HELLO!".parse::<VDString<Default31>>();
         assert!(err.is_err());
 
-        let err2 = "O0I1".parse::<VDString>();
+        let err2 = "O0I1".parse::<VDString<Default31>>();
         assert!(err2.is_err());
     }
 
+    #[cfg(feature = "str-cache")]
+    #[test]
+    fn try_from_string_reuses_allocation() {
+        use crate::alphabet::Crockford32;
+        use alloc::string::String;
+
+        let body = String::from("M29W");
+        let ptr = body.as_ptr();
+        let code = VDString::<Default31>::try_from(body).unwrap();
+        assert_eq!((code.as_str(), code.as_str().as_ptr()), ("M29W", ptr));
+
+        // Aliases are rewritten in the same buffer.
+        let body = String::from("abo1");
+        let ptr = body.as_ptr();
+        let code = VDString::<Crockford32>::try_from(body).unwrap();
+        assert_eq!((code.as_str(), code.as_str().as_ptr()), ("AB01", ptr));
+        assert_eq!(code, "abo1".parse::<VDString<Crockford32>>().unwrap());
+
+        assert_eq!(VDString::<Default31>::try_from(String::from("AÉ")), Err(VDStringError::InvalidChar { ch: 'É', offset: 1 }));
+    }
+
+    #[test]
+    fn orders_by_index_and_looks_up_by_vdstr() {
+        use alloc::collections::BTreeSet;
+
+        let set: BTreeSet<VDString<Default31>> = ["Z", "2", "AB", "A"].iter().map(|s| s.parse().unwrap()).collect();
+        let sorted: Vec<&str> = set.iter().map(VDString::<Default31>::as_str).collect();
+        assert_eq!(sorted, ["A", "AB", "Z", "2"]);
+
+        let key: VDString<Default31> = "AB".parse().unwrap();
+        assert!(set.contains(key.as_vdstr()));
+
+        let (two, a) = (&"2".parse::<VDString<Default31>>().unwrap(), &"A".parse::<VDString<Default31>>().unwrap());
+        assert!(two > a);
+        assert!(two.cmp_with(a, crate::Collation::Ascii).is_lt());
+    }
+
+    #[test]
+    fn compares_with_strings() {
+        let code: VDString<Default31> = "K2Z7".parse().unwrap();
+        assert_eq!(code, "K2Z7");
+        assert_eq!(code, *"K2Z7");
+        assert_eq!(code, String::from("K2Z7"));
+        assert_eq!("K2Z7", code);
+        assert_eq!(String::from("K2Z7"), code);
+        assert_ne!(code, "K2Z");
+        assert_ne!("k2z7", code);
+    }
+
+    #[test]
+    fn converts_and_borrows() {
+        let code: VDString<Default31> = "K2Z7".parse().unwrap();
+        let mut map = alloc::collections::BTreeMap::new();
+        map.insert(code.clone(), 1);
+        map.insert("9A".parse().unwrap(), 2);
+        assert_eq!(map.get(code.as_vdstr()), Some(&1));
+        assert_eq!(map.get(&"X9A".parse::<VDString<Default31>>().unwrap()[1..]), Some(&2));
+
+        assert_eq!(AsRef::<[VDChar<Default31>]>::as_ref(&code), code.as_vdchars());
+        assert_eq!(char::from(code[0]), 'K');
+        assert_eq!(String::from(code), "K2Z7");
+    }
+
+    #[test]
+    fn error_displays_rejected_char() {
+        let err = "HELLO!".parse::<VDString<Default31>>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid character 'L' at byte 2");
+    }
+
     #[test]
     fn index_returns_correct_char() {
-        let s: VDString = "5K7".parse().unwrap();
+        let s: VDString<Default31> = "5K7".parse().unwrap();
         assert_eq!(s[0], vd('5'));
         assert_eq!(s[1].as_char(), 'K');
         assert_eq!(s[2].to_string(), "7");
@@ -207,16 +846,139 @@ mod tests {
 
     #[test]
     fn iterates_over_chars() {
-        let s: VDString = "X2Z".parse().unwrap();
+        let s: VDString<Default31> = "X2Z".parse().unwrap();
         let collected: Vec<char> = s.into_iter().map(|c| c.as_char()).collect();
         assert_eq!(collected, vec!['X', '2', 'Z']);
     }
 
+    #[test]
+    fn unique_chars_and_repeats() {
+        let s: VDString<Default31> = "XYZXZ".parse().unwrap();
+        assert_eq!(s.unique_chars().len(), 3);
+        assert!(s.has_repeats());
+
+        let empty = VDString::<Default31>::new(Vec::new());
+        assert!(empty.unique_chars().is_empty());
+        assert!(!empty.has_repeats());
+    }
+
+    #[test]
+    fn permute_with_rearranges() {
+        let s: VDString<Default31> = "ABC".parse().unwrap();
+        assert_eq!(&*s.permute_with(&[1, 2, 0]).unwrap(), "BCA");
+        assert_eq!(s.permute_with(&[0, 1, 2]).unwrap(), s);
+    }
+
+    #[test]
+    fn permute_with_rejects_non_permutations() {
+        let s: VDString<Default31> = "ABC".parse().unwrap();
+        assert!(s.permute_with(&[0, 1]).is_none());
+        assert!(s.permute_with(&[0, 1, 3]).is_none());
+        assert!(s.permute_with(&[2, 2, 0]).is_none());
+    }
+
+    #[cfg(feature = "generate")]
+    #[test]
+    fn shuffled_keeps_multiset() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+        let s: VDString<Default31> = "AABXYZ29".parse().unwrap();
+        let shuffled = s.shuffled(&mut rng);
+
+        let mut a: Vec<_> = s.as_vdchars().iter().map(|c| c.as_char()).collect();
+        let mut b: Vec<_> = shuffled.as_vdchars().iter().map(|c| c.as_char()).collect();
+        a.sort_unstable();
+        b.sort_unstable();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn works_over_other_alphabets() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        struct Binary;
+        impl Alphabet for Binary {
+            const CHARS: &'static [char] = &['0', '1'];
+        }
+
+        let s: VDString<Binary> = "0110".parse().unwrap();
+        assert_eq!(&*s, "0110");
+        assert_eq!(s[1].as_char(), '1');
+        assert!(s[0].is_digit());
+        assert!(s.has_repeats());
+        assert_eq!(&*s.permute_with(&[1, 0, 3, 2]).unwrap(), "1001");
+        assert_eq!("012".parse::<VDString<Binary>>(), Err(VDStringError::InvalidChar { ch: '2', offset: 2 }));
+        assert_eq!(VDString::<Binary>::from_ascii(b"0110"), Ok(s));
+        assert_eq!(VDString::<Binary>::from_ascii(b"01A"), Err(ByteError { byte: b'A', index: 2 }));
+    }
+
+    #[test]
+    fn from_ascii_matches_from_str() {
+        for s in ["", "AB29", "K7M3XYZ2ABCD5678EFGH", "AB2O", "ab29", "AB29XY7K\n"] {
+            let bytes = VDString::<Default31>::from_ascii(s.as_bytes()).map_err(|e| e.index);
+            let chars = s.parse::<VDString<Default31>>().map_err(|_| s.find(|c| VDChar::<Default31>::new(c).is_none()).unwrap());
+            assert_eq!(bytes, chars, "{:?}", s);
+        }
+        let err = VDString::<Default31>::from_ascii(b"K7\xc3\x89").unwrap_err();
+        assert_eq!(err, ByteError { byte: 0xc3, index: 2 });
+        assert_eq!(err.to_string(), "non-ASCII byte 0xc3 at position 2");
+        assert!(VDString::<Default31>::from_ascii(b"K7O").unwrap_err().hint().is_some());
+    }
+
+    #[test]
+    fn collects_and_extends() {
+        let mut s: VDString<Default31> = [vd('K'), vd('7')].into_iter().collect();
+        s.extend("M3".chars().filter_map(VDChar::<Default31>::new));
+        assert_eq!(&*s, "K7M3");
+        assert_eq!(s.as_str(), "K7M3");
+    }
+
+    // `try_from_chars` only exists for the default alphabet, whichever
+    // one the build selected.
+    #[test]
+    fn try_from_chars_reports_offset() {
+        let (a, b) = (DefaultAlphabet::CHARS[0], DefaultAlphabet::CHARS[1]);
+        assert_eq!(VDString::try_from_chars([a, b]).unwrap().len(), 2);
+        assert_eq!(VDString::try_from_chars([a, b, '!']), Err(VDStringError::InvalidChar { ch: '!', offset: 2 }));
+    }
+
+    #[test]
+    fn concatenates_and_repeats() {
+        let a: VDString<Default31> = "AB".parse().unwrap();
+        let b: VDString<Default31> = "29".parse().unwrap();
+        let mut joined = a.clone() + &b;
+        assert_eq!(joined.as_str(), "AB29");
+        joined += &a[..1];
+        assert_eq!(&*joined, "AB29A");
+        assert_eq!(VDString::<Default31>::concat([&*a, &b, &a]).as_str(), "AB29AB");
+        assert!(VDString::<Default31>::concat(core::iter::empty::<&VDStr<Default31>>()).is_empty());
+        assert_eq!(b.repeat(2).as_str(), "2929");
+        assert!(b.repeat(0).is_empty());
+    }
+
+    #[cfg(feature = "redact-debug")]
+    #[test]
+    fn debug_is_redacted() {
+        let s: VDString<Default31> = "AB29XY7K".parse().unwrap();
+        assert_eq!(alloc::format!("{:?}", s), "VDString(REDACTED, len=8)");
+        assert_eq!(alloc::format!("{:?}", &*s), "VDStr(REDACTED, len=8)");
+    }
+
+    #[test]
+    fn constructors_infer_default_alphabet() {
+        // No annotations: each binding must resolve to `VDString<DefaultAlphabet>`.
+        let s: alloc::string::String = DefaultAlphabet::CHARS[..3].iter().collect();
+        let parsed = VDString::from_str(&s).unwrap();
+        let tried = VDString::try_from(s.as_str()).unwrap();
+        let owned = VDString::try_from(s.clone()).unwrap();
+        assert_eq!((parsed.len(), tried.as_vdchars()), (3, owned.as_vdchars()));
+        assert!(VDString::from_str("!").is_err());
+    }
+
     #[test]
     fn from_str_and_try_from_match() {
         let a = "Q4V";
-        let parsed = a.parse::<VDString>().unwrap();
-        let tried = VDString::try_from(a).unwrap();
+        let parsed = a.parse::<VDString<Default31>>().unwrap();
+        let tried = VDString::<Default31>::try_from(a).unwrap();
         assert_eq!(parsed, tried);
     }
 }
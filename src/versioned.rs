@@ -0,0 +1,271 @@
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use crate::alphabet::{Alphabet, CustomAlphabet, Default31};
+use crate::{VDChar, VDStringError};
+
+/// Error returned when encoding or parsing a [`VersionedVDString`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionedCodeError {
+    /// The input has no version character.
+    Empty,
+    /// The version character is not registered in the [`VersionedFormats`].
+    UnknownVersion(char),
+    /// The payload's length is outside the version's allowed range.
+    Length {
+        /// The version character.
+        version: VDChar<Default31>,
+        /// The number of characters in the payload.
+        actual: usize,
+    },
+    /// The payload contains a character outside the version's alphabet.
+    Invalid {
+        /// The version character.
+        version: VDChar<Default31>,
+        /// The underlying error, with its offset within the payload.
+        error: VDStringError,
+    },
+}
+
+impl fmt::Display for VersionedCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VersionedCodeError::Empty => f.write_str("code is empty"),
+            VersionedCodeError::UnknownVersion(c) => write!(f, "unknown code version {:?}", c),
+            VersionedCodeError::Length { version, actual } => {
+                write!(f, "version {} code has the wrong length ({} characters)", version, actual)
+            }
+            VersionedCodeError::Invalid { version, ref error } => write!(f, "version {} code: {}", version, error),
+        }
+    }
+}
+
+impl core::error::Error for VersionedCodeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            VersionedCodeError::Invalid { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The alphabet and length of the payload that follows one version
+/// character.
+///
+/// # Examples
+/// ```
+/// use vds::alphabet::Crockford32;
+/// use vds::VersionedFormat;
+///
+/// let format = VersionedFormat::of::<Crockford32>().length(10);
+/// assert_eq!(format.length_range(), 10..=10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionedFormat {
+    alphabet: CustomAlphabet,
+    len: RangeInclusive<usize>,
+}
+
+impl VersionedFormat {
+    /// Creates a format over `alphabet` that accepts payloads of any
+    /// non-zero length.
+    pub fn new(alphabet: CustomAlphabet) -> Self {
+        Self { alphabet, len: 1..=usize::MAX }
+    }
+
+    /// Creates a format over a compile-time alphabet.
+    pub fn of<A: Alphabet>() -> Self {
+        Self::new(CustomAlphabet::of::<A>())
+    }
+
+    /// Sets the exact payload length.
+    pub fn length(self, len: usize) -> Self {
+        self.length_between(len..=len)
+    }
+
+    /// Sets the allowed payload lengths.
+    pub fn length_between(mut self, len: RangeInclusive<usize>) -> Self {
+        self.len = len;
+        self
+    }
+
+    /// Returns the payload's alphabet.
+    pub fn alphabet(&self) -> &CustomAlphabet {
+        &self.alphabet
+    }
+
+    /// Returns the allowed payload lengths, not counting the version
+    /// character.
+    pub fn length_range(&self) -> RangeInclusive<usize> {
+        self.len.clone()
+    }
+}
+
+/// The registry of code versions, mapping each version character to the
+/// [`VersionedFormat`] of the payload after it.
+///
+/// Version characters come from [`VDS_ALLOWED`](crate::VDS_ALLOWED), so the
+/// version of any code can be read before its alphabet is known. Keep every
+/// version that was ever printed registered; new versions are added beside
+/// them.
+///
+/// # Examples
+/// ```
+/// use vds::alphabet::{Crockford32, Default31};
+/// use vds::{VDChar, VersionedFormat, VersionedFormats, VersionedVDString};
+///
+/// let formats = VersionedFormats::new()
+///     .format(VDChar::new('A').unwrap(), VersionedFormat::of::<Default31>().length(6))
+///     .format(VDChar::new('B').unwrap(), VersionedFormat::of::<Crockford32>().length(8));
+///
+/// let old = VersionedVDString::parse("AK7M3PQ", &formats).unwrap();
+/// assert_eq!(old.payload(), "K7M3PQ");
+/// let new = VersionedVDString::parse("B0K7M3PQ1", &formats).unwrap();
+/// assert_eq!(new.version(), VDChar::new('B').unwrap());
+///
+/// // Version A predates Crockford's `0`.
+/// assert!(VersionedVDString::parse("A0K7M3P", &formats).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionedFormats {
+    formats: Vec<(VDChar<Default31>, VersionedFormat)>,
+}
+
+impl VersionedFormats {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the format for `version`, replacing any earlier one.
+    pub fn format(mut self, version: VDChar<Default31>, format: VersionedFormat) -> Self {
+        match self.formats.iter_mut().find(|(v, _)| *v == version) {
+            Some(entry) => entry.1 = format,
+            None => self.formats.push((version, format)),
+        }
+        self
+    }
+
+    /// Returns the format registered for `version`.
+    pub fn get(&self, version: VDChar<Default31>) -> Option<&VersionedFormat> {
+        self.formats.iter().find(|(v, _)| *v == version).map(|(_, format)| format)
+    }
+
+    /// Returns the registered version characters, in registration order.
+    pub fn versions(&self) -> impl Iterator<Item = VDChar<Default31>> + '_ {
+        self.formats.iter().map(|&(v, _)| v)
+    }
+
+    /// Prefixes `payload` with `version` after checking it against that
+    /// version's format.
+    pub fn encode(&self, version: VDChar<Default31>, payload: &str) -> Result<VersionedVDString, VersionedCodeError> {
+        let format = self.get(version).ok_or(VersionedCodeError::UnknownVersion(version.as_char()))?;
+        let actual = payload.chars().count();
+        if !format.len.contains(&actual) {
+            return Err(VersionedCodeError::Length { version, actual });
+        }
+        format.alphabet.validate(payload).map_err(|error| VersionedCodeError::Invalid { version, error })?;
+
+        let mut code = String::with_capacity(1 + payload.len());
+        code.push(version.as_char());
+        code.push_str(payload);
+        Ok(VersionedVDString { version, code })
+    }
+}
+
+/// A code whose first character names the version of its format.
+///
+/// The version decides the alphabet and length of the rest of the code,
+/// so the format can change over the years while codes printed under
+/// older versions still parse. Versions are registered in a
+/// [`VersionedFormats`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VersionedVDString {
+    version: VDChar<Default31>,
+    /// The whole code, version character included.
+    code: String,
+}
+
+impl VersionedVDString {
+    /// Parses `s`, reading the version from its first character and
+    /// checking the rest against that version's format.
+    pub fn parse(s: &str, formats: &VersionedFormats) -> Result<Self, VersionedCodeError> {
+        let mut chars = s.chars();
+        let first = chars.next().ok_or(VersionedCodeError::Empty)?;
+        let version = VDChar::new(first).ok_or(VersionedCodeError::UnknownVersion(first))?;
+        formats.encode(version, chars.as_str())
+    }
+
+    /// Returns the version character.
+    pub fn version(&self) -> VDChar<Default31> {
+        self.version
+    }
+
+    /// Returns the code without its version character.
+    pub fn payload(&self) -> &str {
+        &self.code[self.version.as_char().len_utf8()..]
+    }
+
+    /// Returns the whole code, version character included.
+    pub fn as_str(&self) -> &str {
+        &self.code
+    }
+}
+
+impl fmt::Display for VersionedVDString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.code)
+    }
+}
+
+impl AsRef<str> for VersionedVDString {
+    fn as_ref(&self) -> &str {
+        &self.code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::{Crockford32, Default31};
+
+    fn v(c: char) -> VDChar<Default31> {
+        VDChar::<Default31>::new(c).unwrap()
+    }
+
+    #[test]
+    fn dispatches_on_version_character() {
+        let formats = VersionedFormats::new()
+            .format(v('A'), VersionedFormat::of::<Default31>().length(4))
+            .format(v('B'), VersionedFormat::of::<Crockford32>().length_between(4..=6));
+
+        let code = formats.encode(v('B'), "0K1Z").unwrap();
+        assert_eq!(code.as_str(), "B0K1Z");
+        assert_eq!(VersionedVDString::parse("B0K1Z", &formats), Ok(code));
+
+        assert_eq!(
+            VersionedVDString::parse("A0K1Z", &formats),
+            Err(VersionedCodeError::Invalid { version: v('A'), error: VDStringError::InvalidChar { ch: '0', offset: 0 } })
+        );
+        assert_eq!(
+            VersionedVDString::parse("AK7M", &formats),
+            Err(VersionedCodeError::Length { version: v('A'), actual: 3 })
+        );
+        assert_eq!(VersionedVDString::parse("CK7M3", &formats), Err(VersionedCodeError::UnknownVersion('C')));
+        assert_eq!(VersionedVDString::parse("0K7M3", &formats), Err(VersionedCodeError::UnknownVersion('0')));
+        assert_eq!(VersionedVDString::parse("", &formats), Err(VersionedCodeError::Empty));
+    }
+
+    #[test]
+    fn later_registration_replaces_version() {
+        let formats = VersionedFormats::new()
+            .format(v('A'), VersionedFormat::of::<Default31>().length(4))
+            .format(v('A'), VersionedFormat::of::<Default31>().length(5));
+        assert_eq!(formats.versions().count(), 1);
+        assert_eq!(formats.get(v('A')).unwrap().length_range(), 5..=5);
+    }
+}
@@ -0,0 +1,222 @@
+//! WebAssembly bindings exposing parsing, formatting, encoding, and
+//! generation, so browser front-ends validate and pre-generate codes with
+//! exactly the library's rules.
+//!
+//! This module is only available when the `wasm` feature is enabled. Like
+//! [`python`](crate::python), it is built through a small `cdylib` crate
+//! that depends on `vds` with this feature; compile it for
+//! `wasm32-unknown-unknown` and run `wasm-bindgen` (or `wasm-pack`) on the
+//! result to get a JavaScript module:
+//!
+//! ```js
+//! import { VDString, VDGenerator, isValid, encodeU64 } from "./vds.js";
+//!
+//! const code = new VDString(" ab29 xy ", true);
+//! console.assert(code.toString() === "AB29XY");
+//! console.assert(code.grouped(3) === "AB2-9XY");
+//! console.assert(isValid("AB29") && !isValid("AB20"));
+//! console.assert(encodeU64(31n).toString() === "BA");
+//!
+//! const gen = new VDGenerator(10).noAdjacentRepeats();
+//! console.assert(gen.generate().length === 10);
+//! ```
+//!
+//! Invalid input and unsatisfiable generator settings throw an `Error`
+//! with the same message as the Rust error. Codes are drawn from
+//! `crypto.getRandomValues`, available in browsers and Node.js 19+.
+
+extern crate alloc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::alphabet::Default31;
+use crate::rng::VDRng;
+use crate::{validate, ParseOptions, VDGenerator, VDGeneratorError, VDString};
+
+fn js_error(err: impl ToString) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_namespace = crypto, js_name = getRandomValues)]
+    fn get_random_values(buf: &mut [u8]) -> Result<(), JsValue>;
+}
+
+/// Draws from `crypto.getRandomValues`, noting whether it failed.
+struct CryptoEntropy {
+    failed: bool,
+}
+
+impl VDRng for CryptoEntropy {
+    fn draw_u32(&mut self) -> u32 {
+        let mut word = [0; 4];
+        if get_random_values(&mut word).is_err() {
+            self.failed = true;
+        }
+        u32::from_le_bytes(word)
+    }
+}
+
+/// A validated code of visibly distinguishable characters, exported to
+/// JavaScript as `VDString`.
+#[wasm_bindgen(js_name = VDString)]
+pub struct WasmVDString(VDString<Default31>);
+
+#[wasm_bindgen(js_class = VDString)]
+impl WasmVDString {
+    /// Parses `value`, throwing if it contains a character outside the
+    /// alphabet. Pass `true` for `lenient` to accept what users type into
+    /// a form field: lowercase, spaces and dashes, and fullwidth or
+    /// Cyrillic look-alikes are normalized first.
+    #[wasm_bindgen(constructor)]
+    pub fn new(value: &str, lenient: Option<bool>) -> Result<WasmVDString, JsError> {
+        let options = if lenient.unwrap_or(false) { ParseOptions::lenient() } else { ParseOptions::default() };
+        VDString::parse_with(value, options).map(Self).map_err(js_error)
+    }
+
+    /// The code as a string.
+    #[wasm_bindgen(js_name = toString)]
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// The number of characters.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Formats the code in groups of `size` characters joined by
+    /// `separator` (`"-"` if omitted); a `size` of `0` disables grouping.
+    pub fn grouped(&self, size: usize, separator: Option<String>) -> String {
+        self.0.display_grouped(size, separator.as_deref().unwrap_or("-")).to_string()
+    }
+
+    /// Returns `true` if both codes have the same characters.
+    pub fn equals(&self, other: &WasmVDString) -> bool {
+        self.0 == other.0
+    }
+
+    /// The number of positions at which the codes differ, or `undefined`
+    /// if their lengths differ.
+    #[wasm_bindgen(js_name = hammingDistance)]
+    pub fn hamming_distance(&self, other: &WasmVDString) -> Option<usize> {
+        self.0.hamming_distance(&other.0)
+    }
+
+    /// Decodes a code made by [`encode_u64`] back to its number.
+    #[wasm_bindgen(js_name = decodeU64)]
+    pub fn decode_u64(&self) -> Result<u64, JsError> {
+        self.0.decode_u64().map_err(js_error)
+    }
+}
+
+/// A generator of random codes, exported to JavaScript as `VDGenerator`.
+///
+/// The settings methods consume the generator and return the updated one,
+/// so they chain: `new VDGenerator(10).noRepeats()`.
+#[wasm_bindgen(js_name = VDGenerator)]
+pub struct WasmVDGenerator(VDGenerator<Default31>);
+
+#[wasm_bindgen(js_class = VDGenerator)]
+impl WasmVDGenerator {
+    /// Creates a generator of codes of `length` characters (8 if omitted).
+    #[wasm_bindgen(constructor)]
+    pub fn new(length: Option<usize>) -> WasmVDGenerator {
+        Self(VDGenerator::<Default31>::default().length(length.unwrap_or(8)))
+    }
+
+    /// Never repeats a character within a code.
+    #[wasm_bindgen(js_name = noRepeats)]
+    pub fn no_repeats(self) -> WasmVDGenerator {
+        Self(self.0.no_repeats())
+    }
+
+    /// Never places the same character twice in a row.
+    #[wasm_bindgen(js_name = noAdjacentRepeats)]
+    pub fn no_adjacent_repeats(self) -> WasmVDGenerator {
+        Self(self.0.no_adjacent_repeats())
+    }
+
+    /// Starts every code with `prefix`, which counts toward the length.
+    pub fn prefix(self, prefix: &str) -> Result<WasmVDGenerator, JsError> {
+        let prefix: VDString<Default31> = prefix.parse().map_err(js_error)?;
+        Ok(Self(self.0.prefix(&prefix)))
+    }
+
+    /// Ends every code with `suffix`, which counts toward the length.
+    pub fn suffix(self, suffix: &str) -> Result<WasmVDGenerator, JsError> {
+        let suffix: VDString<Default31> = suffix.parse().map_err(js_error)?;
+        Ok(Self(self.0.suffix(&suffix)))
+    }
+
+    /// Generates one code, throwing if `crypto.getRandomValues` is missing
+    /// or the settings cannot be satisfied.
+    pub fn generate(&self) -> Result<WasmVDString, JsError> {
+        let mut rng = CryptoEntropy { failed: false };
+        let code = self.0.generate(&mut rng);
+        if rng.failed {
+            return Err(js_error(VDGeneratorError::EntropyUnavailable));
+        }
+        code.map(WasmVDString).map_err(js_error)
+    }
+
+    /// Returns an array of `count` distinct codes, e.g. to pre-fill a batch
+    /// of vouchers. Throws if the settings allow fewer than `count` codes.
+    #[wasm_bindgen(js_name = generateMany)]
+    pub fn generate_many(&self, count: usize) -> Result<Vec<WasmVDString>, JsError> {
+        let mut rng = CryptoEntropy { failed: false };
+        let codes = self.0.generate_many(count, &mut rng);
+        if rng.failed {
+            return Err(js_error(VDGeneratorError::EntropyUnavailable));
+        }
+        Ok(codes.map_err(js_error)?.into_iter().map(WasmVDString).collect())
+    }
+
+    /// The entropy of one code, in bits.
+    #[wasm_bindgen(getter, js_name = entropyBits)]
+    pub fn entropy_bits(&self) -> f64 {
+        self.0.entropy_bits()
+    }
+}
+
+/// Returns `true` if every character of `value` is allowed.
+#[wasm_bindgen(js_name = isValid)]
+pub fn is_valid(value: &str) -> bool {
+    validate::is_valid(value.as_bytes())
+}
+
+/// Encodes `n` as its shortest base-31 code.
+#[wasm_bindgen(js_name = encodeU64)]
+pub fn encode_u64(n: u64) -> WasmVDString {
+    WasmVDString(VDString::encode_u64(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Error paths construct JavaScript `Error`s and generation calls into
+    // `crypto`, so only the pure paths can run off wasm32.
+    #[test]
+    fn wraps_parsing_formatting_and_encoding() {
+        let code = WasmVDString::new(" ab29 xy ", Some(true)).unwrap();
+        assert_eq!((code.to_string(), code.length()), (String::from("AB29XY"), 6));
+        assert_eq!(code.grouped(3, None), "AB2-9XY");
+        assert_eq!(code.grouped(2, Some(String::from(" "))), "AB 29 XY");
+        assert!(code.equals(&WasmVDString::new("AB29XY", None).unwrap()));
+        assert_eq!(code.hamming_distance(&WasmVDString::new("AB29XZ", None).unwrap()), Some(1));
+        assert!(is_valid("AB29") && !is_valid("AB20"));
+
+        assert_eq!(encode_u64(31).to_string(), "BA");
+        let encoded = encode_u64(123_456_789);
+        assert_eq!(encoded.decode_u64().unwrap(), 123_456_789);
+
+        let gen = WasmVDGenerator::new(Some(10)).no_adjacent_repeats().prefix("VXP").unwrap();
+        assert!(gen.entropy_bits() > 0.0);
+    }
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn vds(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_vds"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn gen_prints_distinct_valid_codes() {
+    let output = vds(&["gen", "--length", "8", "--no-repeats", "--count", "200"], "");
+    assert!(output.status.success());
+    let mut codes: Vec<&str> = stdout(&output).lines().collect();
+    assert_eq!(codes.len(), 200);
+    for code in &codes {
+        let code: vds::VDString = code.parse().unwrap();
+        assert_eq!(code.len(), 8);
+        assert!(!code.has_repeats());
+    }
+    codes.sort_unstable();
+    codes.dedup();
+    assert_eq!(codes.len(), 200);
+}
+
+#[test]
+fn gen_reports_impossible_configurations() {
+    let output = vds(&["gen", "--length", "40", "--no-repeats"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn check_reads_arguments_or_stdin() {
+    let output = vds(&["check", "AB29", "AB20"], "");
+    assert_eq!(output.status.code(), Some(1));
+    let lines: Vec<&str> = stdout(&output).lines().collect();
+    assert_eq!(lines[0], "AB29: ok");
+    assert!(lines[1].starts_with("AB20: "), "{}", lines[1]);
+
+    let output = vds(&["check"], "XY7K\n\nK7M3\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "XY7K: ok\nK7M3: ok\n");
+
+    let output = vds(&["check", "--lenient", "xy7k"], "");
+    assert!(output.status.success());
+}
+
+#[test]
+fn fmt_groups_lenient_input() {
+    let output = vds(&["fmt", "--group", "4"], "ab29 xy7k\nK7M3PQ\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "AB29-XY7K\nK7M3-PQ\n");
+
+    let output = vds(&["fmt", "--group", "3", "--sep", " ", "AB29XY"], "");
+    assert_eq!(stdout(&output), "AB2 9XY\n");
+
+    let output = vds(&["fmt", "AB0"], "");
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn usage_errors_exit_with_status_2() {
+    assert_eq!(vds(&[], "").status.code(), Some(2));
+    assert_eq!(vds(&["frobnicate"], "").status.code(), Some(2));
+    assert_eq!(vds(&["gen", "--length", "eight"], "").status.code(), Some(2));
+    assert_eq!(vds(&["fmt", "--bogus"], "").status.code(), Some(2));
+    assert!(vds(&["help"], "").status.success());
+}
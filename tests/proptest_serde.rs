@@ -1,4 +1,4 @@
-#![cfg(feature = "serde")]
+#![cfg(all(feature = "serde", feature = "alloc"))]
 
 use vds::{VDChar, VDString, VDS_ALLOWED};
 use proptest::{prelude::*, sample::select};
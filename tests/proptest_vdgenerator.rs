@@ -1,4 +1,4 @@
-#![cfg(feature = "generate")]
+#![cfg(all(feature = "generate", feature = "alloc"))]
 
 use vds::{VDGenerator, VDS_ALLOWED};
 use proptest::prelude::*;
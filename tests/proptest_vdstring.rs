@@ -1,3 +1,5 @@
+#![cfg(feature = "alloc")]
+
 use vds::{VDString, VDS_ALLOWED};
 use proptest::{prelude::*, sample::select};
 
@@ -17,3 +19,16 @@ proptest! {
         prop_assert_eq!(&*parsed, input);
     }
 }
+
+proptest! {
+    #[test]
+    fn vdstring_bytes_codec_roundtrip(bytes in proptest::collection::vec(any::<u8>(), 0..40)) {
+        let code = VDString::encode_bytes(&bytes);
+        prop_assert_eq!(code.decode_bytes(), bytes);
+    }
+
+    #[test]
+    fn vdstring_u64_codec_roundtrip(n in any::<u64>()) {
+        prop_assert_eq!(VDString::encode_u64(n).decode_u64(), Ok(n));
+    }
+}